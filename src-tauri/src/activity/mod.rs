@@ -0,0 +1,32 @@
+//! Work a restart must not cut short, such as deletes and background scans. Silent
+//! updates wait until none of it is running.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+
+/// Marks work as running until dropped
+#[must_use = "the work only counts as running while the guard is held"]
+pub struct BusyGuard(());
+
+impl BusyGuard {
+    pub fn hold() -> Self {
+        IN_PROGRESS.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        IN_PROGRESS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether any guarded work is running
+pub fn is_busy() -> bool {
+    IN_PROGRESS.load(Ordering::SeqCst) > 0
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,12 @@
+use super::*;
+
+#[test]
+fn test_busy_guard_counts_while_held() {
+    let outer = BusyGuard::hold();
+    let inner = BusyGuard::hold();
+    assert!(is_busy());
+
+    drop(inner);
+    assert!(is_busy());
+    drop(outer);
+}
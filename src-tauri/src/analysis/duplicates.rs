@@ -0,0 +1,243 @@
+//! Finds npm packages installed more than once in a node_modules tree, the usual
+//! reason a flat npm or Yarn install is much larger than the same project under pnpm
+
+use crate::commands::largest_files::validate_scanned_path;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InstalledPackage {
+    name: String,
+    version: String,
+    path: PathBuf,
+    size_bytes: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageManifest {
+    name: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageCopy {
+    pub version: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackage {
+    pub name: String,
+    /// Distinct versions installed, in ascending order
+    pub versions: Vec<String>,
+    pub copies: Vec<PackageCopy>,
+    /// Size of every copy beyond the first of each version, which a content-addressed
+    /// store such as pnpm's would not need
+    pub wasted_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackagesResult {
+    /// Most wasted bytes first
+    pub packages: Vec<DuplicatePackage>,
+    pub total_wasted_bytes: u64,
+    /// Every package copy found, duplicated or not
+    pub package_count: usize,
+    pub directory_path: String,
+}
+
+/// Size of a package's own files. Nested `node_modules` are left out since their
+/// packages are counted on their own, and symlinks aren't followed.
+fn package_size(directory: &Path) -> u64 {
+    let Ok(children) = fs::read_dir(directory) else {
+        return 0;
+    };
+
+    children
+        .flatten()
+        .map(|child| {
+            let Ok(metadata) = child.path().symlink_metadata() else {
+                return 0;
+            };
+            if metadata.is_file() {
+                metadata.len()
+            } else if metadata.is_dir() && child.file_name() != "node_modules" {
+                package_size(&child.path())
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn read_package(directory: &Path) -> Option<InstalledPackage> {
+    let content = fs::read_to_string(directory.join("package.json")).ok()?;
+    let manifest: PackageManifest = serde_json::from_str(&content).ok()?;
+
+    Some(InstalledPackage {
+        name: manifest.name?,
+        version: manifest.version.unwrap_or_default(),
+        path: directory.to_path_buf(),
+        size_bytes: package_size(directory),
+    })
+}
+
+fn is_real_directory(path: &Path) -> bool {
+    path.symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// Collects every package under a node_modules directory, including `@scope/name`
+/// packages and packages nested in other packages' own node_modules. Dot directories
+/// such as `.bin` and pnpm's `.pnpm` store, and symlinked packages, are skipped so
+/// linked installs aren't counted as copies.
+fn collect_packages(node_modules: &Path, packages: &mut Vec<InstalledPackage>) {
+    let Ok(children) = fs::read_dir(node_modules) else {
+        return;
+    };
+
+    for child in children.flatten() {
+        let name = child.file_name().to_string_lossy().to_string();
+        let path = child.path();
+        if name.starts_with('.') || !is_real_directory(&path) {
+            continue;
+        }
+
+        if name.starts_with('@') {
+            let Ok(scoped) = fs::read_dir(&path) else {
+                continue;
+            };
+            for scoped_child in scoped.flatten() {
+                collect_package(&scoped_child.path(), packages);
+            }
+        } else {
+            collect_package(&path, packages);
+        }
+    }
+}
+
+fn collect_package(directory: &Path, packages: &mut Vec<InstalledPackage>) {
+    if !is_real_directory(directory) {
+        return;
+    }
+    if let Some(package) = read_package(directory) {
+        packages.push(package);
+    }
+
+    let nested = directory.join("node_modules");
+    if is_real_directory(&nested) {
+        collect_packages(&nested, packages);
+    }
+}
+
+/// Groups packages by name, keeping those installed more than once
+fn find_duplicates(packages: Vec<InstalledPackage>) -> Vec<DuplicatePackage> {
+    let mut by_name: HashMap<String, Vec<InstalledPackage>> = HashMap::new();
+    for package in packages {
+        by_name
+            .entry(package.name.clone())
+            .or_default()
+            .push(package);
+    }
+
+    let mut duplicates: Vec<DuplicatePackage> = by_name
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(name, mut copies)| {
+            // Largest first, so the copy kept for each version is its biggest
+            copies.sort_by(|a, b| {
+                b.size_bytes
+                    .cmp(&a.size_bytes)
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+
+            let mut versions = BTreeSet::new();
+            let mut wasted_bytes = 0;
+            for copy in &copies {
+                if !versions.insert(copy.version.clone()) {
+                    wasted_bytes += copy.size_bytes;
+                }
+            }
+
+            DuplicatePackage {
+                name,
+                versions: versions.into_iter().collect(),
+                copies: copies
+                    .into_iter()
+                    .map(|copy| PackageCopy {
+                        version: copy.version,
+                        path: copy.path.to_string_lossy().to_string(),
+                        size_bytes: copy.size_bytes,
+                    })
+                    .collect(),
+                wasted_bytes,
+            }
+        })
+        .collect();
+
+    duplicates.sort_by(|a, b| {
+        b.wasted_bytes
+            .cmp(&a.wasted_bytes)
+            .then_with(|| b.copies.len().cmp(&a.copies.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    duplicates
+}
+
+fn find_duplicate_packages(path: String) -> Result<DuplicatePackagesResult, String> {
+    let start = Instant::now();
+    let node_modules = Path::new(&path);
+
+    if !node_modules.is_dir() {
+        warn!("Path is not a directory");
+        return Err("Path is not a directory".to_string());
+    }
+
+    let mut packages = Vec::new();
+    collect_packages(node_modules, &mut packages);
+    let package_count = packages.len();
+
+    let packages = find_duplicates(packages);
+    let total_wasted_bytes = packages.iter().map(|package| package.wasted_bytes).sum();
+
+    debug!(
+        package_count,
+        duplicated = packages.len(),
+        total_wasted_bytes,
+        duration_ms = start.elapsed().as_millis() as u64,
+        "Found duplicate packages"
+    );
+
+    Ok(DuplicatePackagesResult {
+        packages,
+        total_wasted_bytes,
+        package_count,
+        directory_path: path,
+    })
+}
+
+/// Packages installed more than once in a scanned node_modules directory
+#[tauri::command]
+#[instrument(skip_all, fields(path = %path))]
+pub async fn get_duplicate_packages(path: String) -> Result<DuplicatePackagesResult, String> {
+    if let Err(error) = validate_scanned_path(Path::new(&path)) {
+        warn!(%error, "Rejected duplicate packages request");
+        return Err(error.to_string());
+    }
+
+    tokio::task::spawn_blocking(move || find_duplicate_packages(path))
+        .await
+        .map_err(|error| format!("Duplicate packages task failed: {error}"))?
+}
+
+#[cfg(test)]
+#[path = "duplicates.test.rs"]
+mod tests;
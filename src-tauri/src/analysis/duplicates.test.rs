@@ -0,0 +1,122 @@
+use super::*;
+use tempfile::TempDir;
+
+fn install(directory: &Path, name: &str, version: &str, size: usize) -> PathBuf {
+    let package = directory.join(name);
+    fs::create_dir_all(&package).unwrap();
+    fs::write(
+        package.join("package.json"),
+        format!(r#"{{"name":"{name}","version":"{version}"}}"#),
+    )
+    .unwrap();
+    fs::write(package.join("index.js"), "x".repeat(size)).unwrap();
+    package
+}
+
+fn manifest_size(name: &str, version: &str) -> u64 {
+    format!(r#"{{"name":"{name}","version":"{version}"}}"#).len() as u64
+}
+
+fn result(node_modules: &Path) -> DuplicatePackagesResult {
+    find_duplicate_packages(node_modules.to_string_lossy().to_string()).unwrap()
+}
+
+#[test]
+fn test_finds_nested_copies_of_different_versions() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    install(&node_modules, "lodash", "4.17.21", 500);
+    let webpack = install(&node_modules, "webpack", "5.0.0", 10);
+    install(&webpack.join("node_modules"), "lodash", "3.10.1", 400);
+
+    let result = result(&node_modules);
+
+    assert_eq!(result.package_count, 3);
+    assert_eq!(result.packages.len(), 1);
+    let lodash = &result.packages[0];
+    assert_eq!(lodash.name, "lodash");
+    assert_eq!(lodash.versions, vec!["3.10.1", "4.17.21"]);
+    assert_eq!(lodash.copies.len(), 2);
+    assert_eq!(lodash.wasted_bytes, 0);
+}
+
+#[test]
+fn test_counts_repeated_versions_as_wasted() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let first = install(&node_modules, "a", "1.0.0", 10);
+    let second = install(&node_modules, "b", "1.0.0", 10);
+    install(&first.join("node_modules"), "semver", "7.5.0", 300);
+    install(&second.join("node_modules"), "semver", "7.5.0", 300);
+
+    let result = result(&node_modules);
+
+    assert_eq!(result.packages.len(), 1);
+    assert_eq!(result.packages[0].versions, vec!["7.5.0"]);
+    assert_eq!(
+        result.packages[0].wasted_bytes,
+        300 + manifest_size("semver", "7.5.0")
+    );
+    assert_eq!(result.total_wasted_bytes, result.packages[0].wasted_bytes);
+}
+
+#[test]
+fn test_reads_scoped_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let jest = install(&node_modules, "jest", "29.0.0", 10);
+    install(&node_modules, "@babel/core", "7.22.0", 100);
+    install(&jest.join("node_modules"), "@babel/core", "7.20.0", 100);
+
+    let result = result(&node_modules);
+
+    assert_eq!(result.packages.len(), 1);
+    assert_eq!(result.packages[0].name, "@babel/core");
+}
+
+#[test]
+fn test_package_size_excludes_nested_node_modules() {
+    let temp_dir = TempDir::new().unwrap();
+    let package = install(temp_dir.path(), "webpack", "5.0.0", 100);
+    install(&package.join("node_modules"), "tapable", "2.0.0", 1_000);
+
+    assert_eq!(
+        package_size(&package),
+        100 + manifest_size("webpack", "5.0.0")
+    );
+}
+
+#[test]
+fn test_skips_dot_directories_and_symlinks() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let store = node_modules.join(".pnpm").join("node_modules");
+    let stored = install(&store, "react", "18.2.0", 100);
+    fs::create_dir_all(&node_modules).unwrap();
+    std::os::unix::fs::symlink(&stored, node_modules.join("react")).unwrap();
+
+    let result = result(&node_modules);
+
+    assert_eq!(result.package_count, 0);
+    assert!(result.packages.is_empty());
+}
+
+#[test]
+fn test_sorts_by_wasted_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let first = install(&node_modules, "a", "1.0.0", 10);
+    let second = install(&node_modules, "b", "1.0.0", 10);
+    for parent in [&first, &second] {
+        install(&parent.join("node_modules"), "small", "1.0.0", 10);
+        install(&parent.join("node_modules"), "large", "1.0.0", 1_000);
+    }
+
+    let names: Vec<String> = result(&node_modules)
+        .packages
+        .into_iter()
+        .map(|package| package.name)
+        .collect();
+
+    assert_eq!(names, vec!["large", "small"]);
+}
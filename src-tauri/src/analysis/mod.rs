@@ -0,0 +1,3 @@
+//! Looks inside dependency directories rather than just sizing them
+
+pub mod duplicates;
@@ -0,0 +1,164 @@
+//! Dependency directories kept in iCloud Drive or another synced folder. Files there can
+//! be dataless placeholders whose contents only exist in the cloud, which the trash
+//! refuses and which would be downloaded just to be thrown away.
+
+use crate::config;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Where a directory's contents live, which decides how it can be removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CloudStatus {
+    /// Not in a synced folder
+    Local,
+    /// In a synced folder with everything downloaded, so it can go to the trash
+    Synced,
+    /// Some contents are only in the cloud. The trash refuses these and a delete would
+    /// remove them from every device, so their local copy is evicted instead.
+    CloudOnly,
+}
+
+/// Folders that sync clients fill with placeholders
+pub fn synced_folders(home: &Path) -> [PathBuf; 3] {
+    [
+        icloud_drive(home),
+        home.join("Library").join("CloudStorage"),
+        home.join("Dropbox"),
+    ]
+}
+
+fn icloud_drive(home: &Path) -> PathBuf {
+    home.join("Library").join("Mobile Documents")
+}
+
+/// Whether the path is inside iCloud Drive, which `brctl` can evict from
+pub fn is_icloud_drive_path(path: &Path) -> bool {
+    dirs::home_dir().is_some_and(|home| path.starts_with(icloud_drive(&home)))
+}
+
+fn is_in_synced_folder(path: &Path) -> bool {
+    dirs::home_dir().is_some_and(|home| {
+        synced_folders(&home)
+            .iter()
+            .any(|folder| path.starts_with(folder))
+    })
+}
+
+/// Whether a file or directory is a placeholder whose contents are fetched when read
+#[cfg(target_os = "macos")]
+pub fn is_dataless(path: &Path) -> bool {
+    use std::os::macos::fs::MetadataExt;
+
+    const SF_DATALESS: u32 = 0x4000_0000;
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.st_flags() & SF_DATALESS != 0)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn is_dataless(_path: &Path) -> bool {
+    false
+}
+
+/// Looks for a dataless entry without reading inside dataless directories, which would
+/// download them. Gives up after `MAX_PROBE_ENTRIES` and reports what it found so far.
+fn has_dataless_contents(directory: &Path) -> bool {
+    let mut pending = vec![directory.to_path_buf()];
+    let mut probed = 0;
+
+    while let Some(current) = pending.pop() {
+        if is_dataless(&current) {
+            return true;
+        }
+        let Ok(children) = fs::read_dir(&current) else {
+            continue;
+        };
+        for child in children.flatten() {
+            probed += 1;
+            if probed > config::cloud::MAX_PROBE_ENTRIES {
+                debug!(path = %directory.display(), "Stopped probing for dataless files");
+                return false;
+            }
+            match child.file_type() {
+                Ok(file_type) if file_type.is_dir() => pending.push(child.path()),
+                Ok(_) if is_dataless(&child.path()) => return true,
+                _ => {}
+            }
+        }
+    }
+
+    false
+}
+
+/// Works out where a directory's contents live. Only synced folders are probed, since
+/// placeholders can't exist anywhere else.
+pub fn cloud_status(path: &Path) -> CloudStatus {
+    if !is_in_synced_folder(path) {
+        return CloudStatus::Local;
+    }
+
+    if has_dataless_contents(path) {
+        CloudStatus::CloudOnly
+    } else {
+        CloudStatus::Synced
+    }
+}
+
+/// Runs `cloud_status` on the blocking pool, since looking for placeholders can read
+/// many directories. A check that fails to run counts as `Synced`, so a directory is
+/// still moved to the trash and never evicted without asking.
+pub async fn probe_cloud_status(path: &Path) -> CloudStatus {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || cloud_status(&path))
+        .await
+        .unwrap_or_else(|error| {
+            warn!(%error, "Cloud status check failed");
+            CloudStatus::Synced
+        })
+}
+
+/// Removes the downloaded copy of an iCloud Drive directory, freeing the space while
+/// leaving the directory in iCloud
+pub async fn evict(path: &Path) -> Result<(), String> {
+    if !is_icloud_drive_path(path) {
+        return Err(
+            "Only iCloud Drive folders can be evicted; free up space from the sync app instead"
+                .to_string(),
+        );
+    }
+
+    brctl_evict(path).await
+}
+
+#[cfg(target_os = "macos")]
+async fn brctl_evict(path: &Path) -> Result<(), String> {
+    let output = tokio::process::Command::new("brctl")
+        .arg("evict")
+        .arg(path)
+        .kill_on_drop(true)
+        .output();
+
+    match tokio::time::timeout(config::cloud::EVICT_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => {
+            tracing::info!(path = %path.display(), "Evicted directory from iCloud Drive");
+            Ok(())
+        }
+        Ok(Ok(output)) => Err(format!(
+            "Failed to evict from iCloud: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Ok(Err(error)) => Err(format!("Failed to run brctl: {error}")),
+        Err(_) => Err("Timed out evicting from iCloud".to_string()),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn brctl_evict(_path: &Path) -> Result<(), String> {
+    Err("Evicting iCloud files is only supported on macOS".to_string())
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
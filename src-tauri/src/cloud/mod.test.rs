@@ -0,0 +1,53 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_is_icloud_drive_path() {
+    let home = dirs::home_dir().unwrap();
+    let icloud = home
+        .join("Library")
+        .join("Mobile Documents")
+        .join("com~apple~CloudDocs")
+        .join("app")
+        .join("node_modules");
+
+    assert!(is_icloud_drive_path(&icloud));
+    assert!(!is_icloud_drive_path(
+        &home.join("code").join("node_modules")
+    ));
+}
+
+#[test]
+fn test_synced_folders_under_home() {
+    let folders = synced_folders(Path::new("/Users/dev"));
+
+    assert!(folders.contains(&PathBuf::from("/Users/dev/Library/CloudStorage")));
+    assert!(folders.contains(&PathBuf::from("/Users/dev/Dropbox")));
+}
+
+#[test]
+fn test_cloud_status_outside_synced_folders_is_local() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir(&node_modules).unwrap();
+
+    assert_eq!(cloud_status(&node_modules), CloudStatus::Local);
+}
+
+#[test]
+fn test_downloaded_directory_has_no_dataless_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    let package = temp_dir.path().join("node_modules").join("react");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join("index.js"), "module.exports = {}").unwrap();
+
+    assert!(!has_dataless_contents(temp_dir.path()));
+}
+
+#[test]
+fn test_cloud_status_serialization() {
+    assert_eq!(
+        serde_json::to_string(&CloudStatus::CloudOnly).unwrap(),
+        "\"CLOUD_ONLY\""
+    );
+}
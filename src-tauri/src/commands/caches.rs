@@ -0,0 +1,215 @@
+use super::stats::record_cleanup;
+use crate::i18n;
+use crate::persistence;
+use crate::scanner::{calculate_dir_size_full, PackageManagerCache};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+#[cfg(unix)]
+use std::sync::LazyLock;
+use std::time::Instant;
+use tracing::{debug, error, info, instrument, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheClearResult {
+    pub cache: PackageManagerCache,
+    pub path: String,
+    pub size_freed: u64,
+}
+
+async fn measure_cache_size(path: &Path) -> u64 {
+    let measure_path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || calculate_dir_size_full(&measure_path).total_size)
+        .await
+        .unwrap_or_else(|error| {
+            warn!(%error, "Failed to measure package manager cache");
+            0
+        })
+}
+
+/// Printed by the login shell just before its PATH, so anything its startup files
+/// print can be told apart from it
+#[cfg(any(unix, test))]
+const LOGIN_PATH_MARKER: &str = "__DEPTOX_LOGIN_PATH__";
+
+/// The PATH printed after `LOGIN_PATH_MARKER`
+#[cfg(any(unix, test))]
+fn parse_login_shell_path(stdout: &str) -> Option<String> {
+    let (_, after_marker) = stdout.rsplit_once(LOGIN_PATH_MARKER)?;
+    after_marker
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// PATH as the user's login shell sets it up. Apps opened from the Finder or Dock
+/// get a minimal PATH without Homebrew, nvm or pyenv, where package managers live.
+#[cfg(unix)]
+static LOGIN_SHELL_PATH: LazyLock<Option<String>> = LazyLock::new(|| {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    // `printenv` reads the exported PATH, which fish joins with colons like other shells
+    let script = format!("echo {LOGIN_PATH_MARKER}; printenv PATH");
+    let output = Command::new(&shell)
+        .args(["-l", "-c", &script])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_login_shell_path(&String::from_utf8_lossy(&output.stdout))
+        }
+        Ok(output) => {
+            warn!(%shell, status = ?output.status.code(), "Login shell failed, keeping PATH");
+            None
+        }
+        Err(error) => {
+            warn!(%shell, %error, "Failed to run login shell, keeping PATH");
+            None
+        }
+    }
+});
+
+#[cfg(unix)]
+fn login_shell_path() -> Option<&'static str> {
+    LOGIN_SHELL_PATH.as_deref()
+}
+
+/// Windows apps inherit the PATH set in the system settings, so there's nothing to resolve
+#[cfg(not(unix))]
+fn login_shell_path() -> Option<&'static str> {
+    None
+}
+
+/// Runs a package manager with the login shell's PATH, returning its output
+fn run_package_manager(
+    cache: PackageManagerCache,
+    program: &str,
+    args: &[&str],
+) -> Result<String, String> {
+    let command_line = std::iter::once(program)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::null());
+    if let Some(path) = login_shell_path() {
+        command.env("PATH", path);
+    }
+
+    let output = command.output().map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => {
+            format!(
+                "Cannot clear {}: `{program}` was not found",
+                cache.translated_label(i18n::current_language())
+            )
+        }
+        _ => format!("Failed to run `{command_line}`: {error}"),
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!(
+            %command_line,
+            status = ?output.status.code(),
+            %stderr,
+            "Package manager command failed"
+        );
+        return Err(format!("`{command_line}` failed: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The store directory `pnpm store path` printed, refusing anything that isn't an
+/// absolute path strictly inside a directory other than home, such as home or `/`
+fn parse_pnpm_store_path(stdout: &str, home: &Path) -> Option<PathBuf> {
+    let line = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())?;
+    let store = PathBuf::from(line);
+    (store.is_absolute() && store.parent().is_some() && !home.starts_with(&store)).then_some(store)
+}
+
+/// Removes the store pnpm reports, see `PackageManagerCache::clear_command`
+fn clear_pnpm_store(home: &Path) -> Result<(), String> {
+    let stdout = run_package_manager(PackageManagerCache::Pnpm, "pnpm", &["store", "path"])?;
+    let store = parse_pnpm_store_path(&stdout, home).ok_or_else(|| {
+        format!(
+            "`pnpm store path` gave an unexpected path: {}",
+            stdout.trim()
+        )
+    })?;
+    if !store.is_dir() {
+        debug!(path = %store.display(), "pnpm store doesn't exist, nothing to clear");
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&store)
+        .map_err(|error| format!("Failed to remove {}: {error}", store.display()))
+}
+
+/// Runs the package manager's own clear command rather than deleting the directory,
+/// so a cache that is in use or shared between tools is never left half-removed.
+/// pnpm is the exception, see `PackageManagerCache::clear_command`.
+fn run_clear_command(cache: PackageManagerCache, home: &Path) -> Result<(), String> {
+    match cache.clear_command() {
+        Some((program, args)) => run_package_manager(cache, program, args).map(|_| ()),
+        None => clear_pnpm_store(home),
+    }
+}
+
+#[tauri::command]
+#[instrument(skip_all, fields(cache = ?cache))]
+pub async fn clear_package_manager_cache(
+    cache: PackageManagerCache,
+) -> Result<CacheClearResult, String> {
+    let start = Instant::now();
+    info!("Clearing package manager cache");
+
+    let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+    let cache_path = cache.locate(&home).ok_or_else(|| {
+        format!(
+            "No {} found",
+            cache.translated_label(i18n::current_language())
+        )
+    })?;
+
+    let size_before = measure_cache_size(&cache_path).await;
+
+    let command_home = home.clone();
+    tokio::task::spawn_blocking(move || run_clear_command(cache, &command_home))
+        .await
+        .map_err(|error| format!("Failed to clear cache: {error}"))??;
+
+    let size_after = if cache_path.exists() {
+        measure_cache_size(&cache_path).await
+    } else {
+        0
+    };
+    let size_freed = size_before.saturating_sub(size_after);
+
+    record_cleanup(None, size_freed);
+    persistence::flush_all();
+
+    info!(
+        duration_ms = start.elapsed().as_millis() as u64,
+        size_mb = size_freed as f64 / 1024.0 / 1024.0,
+        "Cleared package manager cache"
+    );
+
+    Ok(CacheClearResult {
+        cache,
+        path: cache_path.to_string_lossy().to_string(),
+        size_freed,
+    })
+}
+
+#[cfg(test)]
+#[path = "caches.test.rs"]
+mod tests;
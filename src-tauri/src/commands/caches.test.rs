@@ -0,0 +1,42 @@
+use super::*;
+
+#[test]
+fn test_parse_login_shell_path_skips_startup_output() {
+    let stdout = format!("Welcome back!\n{LOGIN_PATH_MARKER}\n/opt/homebrew/bin:/usr/bin\n");
+    assert_eq!(
+        parse_login_shell_path(&stdout).as_deref(),
+        Some("/opt/homebrew/bin:/usr/bin")
+    );
+}
+
+#[test]
+fn test_parse_login_shell_path_without_marker() {
+    assert_eq!(parse_login_shell_path("/usr/bin\n"), None);
+    assert_eq!(
+        parse_login_shell_path(&format!("{LOGIN_PATH_MARKER}\n")),
+        None
+    );
+}
+
+#[test]
+fn test_parse_pnpm_store_path() {
+    let home = Path::new("/Users/dev");
+    assert_eq!(
+        parse_pnpm_store_path("/Users/dev/Library/pnpm/store/v3\n", home),
+        Some(PathBuf::from("/Users/dev/Library/pnpm/store/v3"))
+    );
+    assert_eq!(
+        parse_pnpm_store_path("/Volumes/Work/.pnpm-store/v10\n", home),
+        Some(PathBuf::from("/Volumes/Work/.pnpm-store/v10"))
+    );
+}
+
+#[test]
+fn test_parse_pnpm_store_path_refuses_home_and_its_ancestors() {
+    let home = Path::new("/Users/dev");
+    assert_eq!(parse_pnpm_store_path("/Users/dev\n", home), None);
+    assert_eq!(parse_pnpm_store_path("/Users\n", home), None);
+    assert_eq!(parse_pnpm_store_path("/\n", home), None);
+    assert_eq!(parse_pnpm_store_path("store/v3\n", home), None);
+    assert_eq!(parse_pnpm_store_path("\n", home), None);
+}
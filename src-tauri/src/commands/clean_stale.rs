@@ -0,0 +1,124 @@
+use super::delete::{trash_all, BatchDeleteResult};
+use super::ignored::ignored_path_set;
+use super::scan_cache::get_cached_scan;
+use super::settings::get_settings_sync;
+use crate::config;
+use crate::notifications;
+use crate::scanner::{compute_age_ms, DirectoryEntry};
+use crate::telemetry;
+use crate::time;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::Emitter;
+use tracing::{error, info, instrument};
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+/// Emitted with the number of directories about to be deleted. Progress then arrives
+/// as `delete_progress`, like any batch delete.
+pub const STARTED_EVENT: &str = "clean_stale_started";
+pub const COMPLETE_EVENT: &str = "clean_stale_complete";
+
+fn stale_cutoff_ms(after_days: u32, now_ms: u64) -> u64 {
+    now_ms.saturating_sub(u64::from(after_days) * MILLISECONDS_PER_DAY)
+}
+
+/// Entries without a modification time are never stale
+fn is_stale(last_modified_ms: u64, cutoff_ms: u64) -> bool {
+    last_modified_ms > 0 && last_modified_ms <= cutoff_ms
+}
+
+/// Entries untouched for at least `after_days` as of the scan. Ones the user chose to
+/// ignore are never picked.
+fn stale_entries<'a>(
+    entries: &'a [DirectoryEntry],
+    ignored: &HashSet<String>,
+    after_days: u32,
+    now_ms: u64,
+) -> Vec<&'a DirectoryEntry> {
+    let cutoff_ms = stale_cutoff_ms(after_days, now_ms);
+
+    entries
+        .iter()
+        .filter(|entry| is_stale(entry.last_modified_ms, cutoff_ms))
+        .filter(|entry| !ignored.contains(&entry.path))
+        .collect()
+}
+
+/// Paths of entries that are still stale going by a fresh look at each directory, since
+/// the scan may be old and a project used since then must survive the cleanup
+fn still_stale_paths(entries: &[DirectoryEntry], cutoff_ms: u64) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|entry| {
+            compute_age_ms(Path::new(&entry.path), entry.age_source)
+                .is_some_and(|last_modified_ms| is_stale(last_modified_ms, cutoff_ms))
+        })
+        .map(|entry| entry.path.clone())
+        .collect()
+}
+
+/// Moves every dependency directory in the last scan untouched for `clean_stale_after_days`
+/// to the trash, then sends a notification with the space freed
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn clean_stale(app: tauri::AppHandle) -> Result<BatchDeleteResult, String> {
+    let after_days = get_settings_sync()
+        .map(|settings| settings.clean_stale_after_days)
+        .unwrap_or(config::delete::DEFAULT_CLEAN_STALE_AFTER_DAYS);
+    let cached = get_cached_scan()
+        .await?
+        .ok_or_else(|| "No scan results yet, run a scan first".to_string())?;
+
+    let now_ms = time::now_ms();
+    let candidates: Vec<DirectoryEntry> = stale_entries(
+        &cached.result.entries,
+        &ignored_path_set(),
+        after_days,
+        now_ms,
+    )
+    .into_iter()
+    .cloned()
+    .collect();
+    let cutoff_ms = stale_cutoff_ms(after_days, now_ms);
+    let paths = tokio::task::spawn_blocking(move || still_stale_paths(&candidates, cutoff_ms))
+        .await
+        .map_err(|error| format!("Stale check task failed: {error}"))?;
+    info!(
+        count = paths.len(),
+        after_days, "Cleaning stale directories"
+    );
+    let _ = app.emit(STARTED_EVENT, paths.len());
+
+    let batch = if paths.is_empty() {
+        BatchDeleteResult {
+            results: Vec::new(),
+            total_size_freed: 0,
+            successful_count: 0,
+            failed_count: 0,
+        }
+    } else {
+        trash_all(app.clone(), paths)
+            .await
+            .map_err(|error| error.to_string())?
+    };
+
+    let _ = app.emit(COMPLETE_EVENT, &batch);
+    notifications::notify_stale_cleaned(&app, &batch, after_days);
+
+    Ok(batch)
+}
+
+/// Runs `clean_stale` in the background for the tray menu, where there's no caller to
+/// hand an error back to
+pub fn spawn_clean_stale(app: tauri::AppHandle) {
+    telemetry::crash::spawn_monitored("clean_stale", async move {
+        if let Err(error) = clean_stale(app).await {
+            error!(%error, "Failed to clean stale directories");
+        }
+    });
+}
+
+#[cfg(test)]
+#[path = "clean_stale.test.rs"]
+mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+use crate::scanner::{AgeStrategy, DependencyCategory};
+
+const NOW_MS: u64 = 1_700_000_000_000;
+
+fn entry(path: &str, age_days: u64) -> DirectoryEntry {
+    DirectoryEntry {
+        last_modified_ms: NOW_MS - age_days * MILLISECONDS_PER_DAY,
+        scanned_at_ms: NOW_MS,
+        ..DirectoryEntry::for_test(path, DependencyCategory::NodeModules, 1_024)
+    }
+}
+
+fn paths(entries: Vec<&DirectoryEntry>) -> Vec<&str> {
+    entries.iter().map(|entry| entry.path.as_str()).collect()
+}
+
+#[test]
+fn test_stale_entries_uses_age_threshold() {
+    let entries = vec![
+        entry("/code/old/node_modules", 120),
+        entry("/code/edge/node_modules", 90),
+        entry("/code/recent/node_modules", 10),
+    ];
+
+    assert_eq!(
+        paths(stale_entries(&entries, &HashSet::new(), 90, NOW_MS)),
+        vec!["/code/old/node_modules", "/code/edge/node_modules"]
+    );
+}
+
+#[test]
+fn test_stale_entries_skips_unknown_age() {
+    let mut unknown = entry("/code/unknown/node_modules", 0);
+    unknown.last_modified_ms = 0;
+
+    assert!(stale_entries(&[unknown], &HashSet::new(), 90, NOW_MS).is_empty());
+}
+
+#[test]
+fn test_stale_entries_skips_ignored() {
+    let entries = vec![
+        entry("/code/old/node_modules", 120),
+        entry("/code/kept/node_modules", 120),
+    ];
+    let ignored = HashSet::from(["/code/kept/node_modules".to_string()]);
+
+    assert_eq!(
+        paths(stale_entries(&entries, &ignored, 90, NOW_MS)),
+        vec!["/code/old/node_modules"]
+    );
+}
+
+#[test]
+fn test_still_stale_paths_drops_directories_used_since_the_scan() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let used = temp_dir.path().join("node_modules");
+    std::fs::create_dir(&used).unwrap();
+    std::fs::write(used.join("index.js"), "").unwrap();
+
+    let mut entry = entry(used.to_str().unwrap(), 120);
+    entry.age_source = AgeStrategy::NewestFile;
+
+    let cutoff_ms = stale_cutoff_ms(90, time::now_ms());
+    assert!(still_stale_paths(&[entry], cutoff_ms).is_empty());
+}
+
+#[test]
+fn test_still_stale_paths_drops_missing_directories() {
+    let mut entry = entry("/nonexistent/deptox/node_modules", 120);
+    entry.age_source = AgeStrategy::DirectoryMtime;
+
+    assert!(still_stale_paths(&[entry], NOW_MS).is_empty());
+}
@@ -1,14 +1,39 @@
+use crate::activity::BusyGuard;
 use crate::config;
-use crate::scanner::DependencyCategory;
+use crate::error::DeptoxError;
+use crate::fixture;
+use crate::scanner::{
+    calculate_dir_size_full, disk_usage, expand_tilde, find_exceeded_categories,
+    DependencyCategory, DependencySizes, ScanStats,
+};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeSet, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
+use tauri::Emitter;
 use thiserror::Error;
 use tokio::sync::Semaphore;
-use tracing::{error, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 
-use super::settings::get_settings_sync;
+use super::deletion_journal::{move_to_trash, record_deletion, DeletionRecord};
+use super::license::trial;
+use super::scan_cache::remove_cached_entries;
+use super::settings::{get_settings_sync, AppSettings, InstallCheck};
+use super::stats::record_cleanup;
+use crate::cloud::{self, CloudStatus};
+use crate::integrations;
+use crate::persistence;
+use crate::processes::{
+    describe_processes, find_processes_holding_files, find_processes_in_project, OpenProcess,
+};
+use crate::scanner::size_index::remove_indexed_sizes;
+use crate::sounds;
+use crate::telemetry;
+use crate::tray;
 
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum DeleteValidationError {
@@ -20,6 +45,52 @@ pub enum DeleteValidationError {
     NotDependencyDirectory,
     #[error("Invalid path: {0}")]
     InvalidPath(String),
+    #[error("Project is open in {0}; confirm to delete anyway")]
+    ProjectOpen(String),
+    #[error("Files are in use by {0}; close them and try again")]
+    FilesInUse(String),
+    #[error("Files are in use by {0}; confirm to delete anyway")]
+    InUse(String),
+    #[error("Couldn't check in time whether files are in use; confirm to delete anyway")]
+    OpenFilesUnknown,
+    #[error("Cannot delete the scan root or a directory containing it")]
+    ScanRoot,
+    #[error("A package install appears to be running here; confirm to delete anyway")]
+    InstallInProgress,
+    #[error("A package install appears to be running here; try again once it finishes")]
+    InstallInProgressBlocked,
+    #[error("Some files are only in the cloud, so this will be evicted from this device instead of moved to the trash; confirm to continue")]
+    CloudOnly,
+}
+
+impl DeleteValidationError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DoesNotExist => "DELETE_DOES_NOT_EXIST",
+            Self::NotADirectory => "DELETE_NOT_A_DIRECTORY",
+            Self::NotDependencyDirectory => "DELETE_NOT_DEPENDENCY_DIRECTORY",
+            Self::InvalidPath(_) => "DELETE_INVALID_PATH",
+            Self::ProjectOpen(_) => "DELETE_PROJECT_OPEN",
+            Self::FilesInUse(_) => "DELETE_FILES_IN_USE",
+            Self::InUse(_) => "DELETE_IN_USE",
+            Self::OpenFilesUnknown => "DELETE_OPEN_FILES_UNKNOWN",
+            Self::ScanRoot => "DELETE_SCAN_ROOT",
+            Self::InstallInProgress => "DELETE_INSTALL_IN_PROGRESS",
+            Self::InstallInProgressBlocked => "DELETE_INSTALL_IN_PROGRESS_BLOCKED",
+            Self::CloudOnly => "DELETE_CLOUD_ONLY",
+        }
+    }
+
+    /// The editor or processes in the way, or why the path couldn't be resolved
+    pub fn context(&self) -> Option<String> {
+        match self {
+            Self::InvalidPath(detail)
+            | Self::ProjectOpen(detail)
+            | Self::FilesInUse(detail)
+            | Self::InUse(detail) => Some(detail.clone()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -58,8 +129,13 @@ fn validate_delete_path(path: &Path) -> Result<std::path::PathBuf, DeleteValidat
                 || name == "vendor"
                 || name == "deps"
                 || name == "pkg"
+                || (matches!(
+                    name,
+                    "bin" | "obj" | "packages" | "dist" | "cache" | "target"
+                ) && DependencyCategory::from_path(&canonical_path).is_some())
         })
-        .unwrap_or(false);
+        .unwrap_or(false)
+        || DependencyCategory::from_environment_directory(&canonical_path).is_some();
 
     if !is_dependency_dir {
         return Err(DeleteValidationError::NotDependencyDirectory);
@@ -68,7 +144,140 @@ fn validate_delete_path(path: &Path) -> Result<std::path::PathBuf, DeleteValidat
     Ok(canonical_path)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Directories scans start from: the configured root, or the fixture root in fixture mode
+fn configured_scan_roots(settings: &AppSettings) -> Vec<PathBuf> {
+    let mut roots = vec![PathBuf::from(expand_tilde(&settings.root_directory))];
+    roots.extend(fixture::active_root().map(PathBuf::from));
+    roots
+}
+
+/// Fails when the directory is a scan root or contains one. A root that is itself named
+/// like a dependency directory would otherwise pass validation and be deleted whole.
+fn ensure_not_scan_root(
+    canonical_path: &Path,
+    roots: &[PathBuf],
+) -> Result<(), DeleteValidationError> {
+    let contains_root = roots.iter().any(|root| {
+        let root = root.canonicalize().unwrap_or_else(|_| root.clone());
+        root.starts_with(canonical_path)
+    });
+
+    if contains_root {
+        return Err(DeleteValidationError::ScanRoot);
+    }
+    Ok(())
+}
+
+/// Whether a package manager looks to be installing into the directory: it holds a
+/// marker only present mid-install, or it or one of its direct children was modified
+/// within `config::delete::INSTALL_ACTIVITY_WINDOW`
+pub fn is_install_in_progress(path: &Path) -> bool {
+    is_install_in_progress_at(path, SystemTime::now())
+}
+
+fn is_install_in_progress_at(path: &Path, now: SystemTime) -> bool {
+    // A modification time in the future counts as recent
+    let modified_recently = |metadata: std::fs::Metadata| {
+        metadata.modified().is_ok_and(|modified| {
+            now.duration_since(modified)
+                .map_or(true, |age| age < config::delete::INSTALL_ACTIVITY_WINDOW)
+        })
+    };
+
+    if std::fs::metadata(path).is_ok_and(modified_recently) {
+        return true;
+    }
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| config::delete::INSTALL_MARKERS.contains(&name))
+            || entry.metadata().is_ok_and(modified_recently)
+    })
+}
+
+/// Fails when a package install looks to be running in the directory. `Warn` can be
+/// confirmed past like an open project; `Refuse` can't.
+fn ensure_no_install_in_progress(
+    canonical_path: &Path,
+    check: InstallCheck,
+    confirmed: bool,
+) -> Result<(), DeleteValidationError> {
+    match check {
+        InstallCheck::Off => Ok(()),
+        InstallCheck::Warn if confirmed => Ok(()),
+        _ if !is_install_in_progress(canonical_path) => Ok(()),
+        InstallCheck::Warn => Err(DeleteValidationError::InstallInProgress),
+        InstallCheck::Refuse => Err(DeleteValidationError::InstallInProgressBlocked),
+    }
+}
+
+/// Fails when a shell, editor or dev server is working inside the project that owns
+/// the directory, so dependencies aren't deleted out from under it
+async fn ensure_project_not_open(canonical_path: &Path) -> Result<(), DeleteValidationError> {
+    let Some(project_dir) = canonical_path.parent() else {
+        return Ok(());
+    };
+
+    let processes = find_processes_in_project(project_dir).await;
+    if processes.is_empty() {
+        return Ok(());
+    }
+
+    let commands: BTreeSet<&str> = processes
+        .iter()
+        .map(|process| process.command.as_str())
+        .collect();
+    Err(DeleteValidationError::ProjectOpen(
+        commands.into_iter().collect::<Vec<_>>().join(", "),
+    ))
+}
+
+/// Judges what `find_processes_holding_files` found before a permanent delete. Open
+/// files are refused outright, while a check that timed out only needs confirming.
+fn check_open_files(
+    processes: Option<Vec<OpenProcess>>,
+    confirmed: bool,
+) -> Result<(), DeleteValidationError> {
+    match processes {
+        Some(processes) if processes.is_empty() => Ok(()),
+        Some(processes) => Err(DeleteValidationError::FilesInUse(describe_processes(
+            &processes,
+        ))),
+        None if confirmed => Ok(()),
+        None => Err(DeleteValidationError::OpenFilesUnknown),
+    }
+}
+
+/// Fails when a process holds a file open inside the directory. Permanent deletes of
+/// open files fail partway with errors like EBUSY, so the holders are named up front.
+async fn ensure_no_open_files(
+    canonical_path: &Path,
+    confirmed: bool,
+) -> Result<(), DeleteValidationError> {
+    check_open_files(
+        find_processes_holding_files(canonical_path).await,
+        confirmed,
+    )
+}
+
+/// Fails when a process holds a file open inside a directory headed for the trash,
+/// or when that couldn't be checked in time. The move itself succeeds, but a dev
+/// server or build still using the files breaks, so like an open project this can be
+/// confirmed past.
+async fn ensure_not_in_use(canonical_path: &Path) -> Result<(), DeleteValidationError> {
+    match find_processes_holding_files(canonical_path).await {
+        Some(processes) if processes.is_empty() => Ok(()),
+        Some(processes) => Err(DeleteValidationError::InUse(describe_processes(&processes))),
+        None => Err(DeleteValidationError::OpenFilesUnknown),
+    }
+}
+
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteResult {
     pub success: bool,
@@ -76,79 +285,539 @@ pub struct DeleteResult {
     pub size_freed: u64,
 }
 
+/// Emitted as `delete_progress` each time an item in a batch delete finishes
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteProgress {
+    pub path: String,
+    /// Position of the path in the requested batch
+    pub index: usize,
+    /// Number of items finished so far, including this one
+    pub completed: usize,
+    pub total: usize,
+    pub success: bool,
+    pub size_freed: u64,
+}
+
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDeleteResult {
+    pub results: Vec<DeleteResult>,
+    pub total_size_freed: u64,
+    pub successful_count: usize,
+    pub failed_count: usize,
+}
+
+impl BatchDeleteResult {
+    fn from_results(results: Vec<DeleteResult>) -> Self {
+        let successful: Vec<&DeleteResult> =
+            results.iter().filter(|result| result.success).collect();
+        let total_size_freed = successful.iter().map(|result| result.size_freed).sum();
+        let successful_count = successful.len();
+
+        Self {
+            failed_count: results.len() - successful_count,
+            results,
+            total_size_freed,
+            successful_count,
+        }
+    }
+}
+
+/// Emitted as `secure_delete_progress` while a secure delete overwrites files
+#[derive(Debug, Clone, Default, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecureDeleteProgress {
+    pub path: String,
+    pub files_overwritten: usize,
+    pub total_files: usize,
+    pub bytes_overwritten: u64,
+    pub total_bytes: u64,
+    /// Files removed without being overwritten: hard links, files that couldn't be
+    /// opened or written, and the contents of folders that couldn't be read
+    pub files_skipped: usize,
+}
+
+/// What a secure delete will overwrite, and how much it has to leave alone
+#[derive(Debug, Default)]
+struct OverwriteTargets {
+    files: Vec<(PathBuf, u64)>,
+    skipped: usize,
+}
+
+/// Regular files under the directory that are safe to overwrite, with their sizes.
+/// Symlinks are ignored and hard-linked files are skipped: overwriting them would also
+/// destroy the copy they share, such as the global store pnpm links node_modules into.
+/// Entries that can't be read are skipped too, so one bad file doesn't stop the rest.
+fn collect_overwrite_targets(directory: &Path) -> std::io::Result<OverwriteTargets> {
+    let mut targets = OverwriteTargets::default();
+    let mut pending = vec![directory.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(error) if current == directory => return Err(error),
+            Err(error) => {
+                warn!(%error, path = ?current, "Failed to read folder to overwrite");
+                targets.skipped += 1;
+                continue;
+            }
+        };
+
+        for entry in entries {
+            let (path, metadata) =
+                match entry.and_then(|entry| Ok((entry.path(), entry.metadata()?))) {
+                    Ok(found) => found,
+                    Err(error) => {
+                        warn!(%error, "Failed to read file to overwrite");
+                        targets.skipped += 1;
+                        continue;
+                    }
+                };
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_file() && is_hard_linked(&metadata) {
+                targets.skipped += 1;
+            } else if metadata.is_file() {
+                targets.files.push((path, metadata.len()));
+            }
+        }
+    }
+
+    Ok(targets)
+}
+
+#[cfg(unix)]
+fn is_hard_linked(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+#[cfg(not(unix))]
+fn is_hard_linked(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// Replaces a file's contents with zeros in place and syncs them to disk
+fn overwrite_file(path: &Path, length: u64, zeros: &[u8]) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let mut remaining = length;
+    while remaining > 0 {
+        let chunk = remaining.min(zeros.len() as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()
+}
+
+/// Overwrites every file in the directory before it is removed, reporting progress
+/// at most once per emit interval and once more when finished. A file that can't be
+/// overwritten is counted as skipped rather than stopping the rest.
+fn overwrite_directory_contents(
+    directory: &Path,
+    mut on_progress: impl FnMut(&SecureDeleteProgress),
+) -> std::io::Result<SecureDeleteProgress> {
+    let targets = collect_overwrite_targets(directory)?;
+    let mut progress = SecureDeleteProgress {
+        path: directory.to_string_lossy().to_string(),
+        total_files: targets.files.len(),
+        total_bytes: targets.files.iter().map(|(_, length)| length).sum(),
+        files_skipped: targets.skipped,
+        ..SecureDeleteProgress::default()
+    };
+
+    let zeros = vec![0u8; config::delete::SECURE_DELETE_CHUNK_BYTES];
+    let mut last_emit_time = Instant::now();
+    for (path, length) in targets.files {
+        match overwrite_file(&path, length, &zeros) {
+            Ok(()) => {
+                progress.files_overwritten += 1;
+                progress.bytes_overwritten += length;
+            }
+            Err(error) => {
+                warn!(%error, ?path, "Failed to overwrite file");
+                progress.files_skipped += 1;
+            }
+        }
+
+        if last_emit_time.elapsed() >= config::scanner::EMIT_THROTTLE {
+            on_progress(&progress);
+            last_emit_time = Instant::now();
+        }
+    }
+
+    on_progress(&progress);
+    Ok(progress)
+}
+
+/// Measures the directory right before deletion so the reported size reflects what is
+/// actually freed, falling back to the size the frontend saw during the scan
+async fn measure_size_before_delete(path: &Path, expected_size_bytes: Option<u64>) -> u64 {
+    let measure_path = path.to_path_buf();
+    let measured =
+        tokio::task::spawn_blocking(move || calculate_dir_size_full(&measure_path).total_size)
+            .await;
+
+    match measured {
+        Ok(size_bytes) => {
+            if let Some(expected) = expected_size_bytes.filter(|expected| *expected != size_bytes) {
+                debug!(
+                    expected,
+                    measured = size_bytes,
+                    "Directory size changed since scan"
+                );
+            }
+            size_bytes
+        }
+        Err(error) => {
+            warn!(%error, "Failed to measure directory before delete");
+            expected_size_bytes.unwrap_or(0)
+        }
+    }
+}
+
+/// What the user has already confirmed for a delete, and how far it may go
+#[derive(Debug, Clone, Copy, Default)]
+struct DeleteOptions {
+    /// Skips the open project check when `protect_open_projects` is on, the install
+    /// check when `install_check` is `Warn`, and an open-file check that timed out
+    confirm_open_project: bool,
+    /// Allows evicting the local copy of a directory the trash can't take because
+    /// some of it is only in the cloud. Nothing is left to restore afterwards.
+    confirm_cloud_eviction: bool,
+    /// Moves the directory to the trash whatever the permanent and secure delete
+    /// settings say, and refuses rather than evicting anything the trash won't take
+    trash_only: bool,
+}
+
 #[tauri::command]
 #[instrument(skip_all, fields(path = %path))]
-pub async fn delete_to_trash(path: String) -> Result<DeleteResult, String> {
+pub async fn delete_to_trash(
+    app: tauri::AppHandle,
+    path: String,
+    expected_size_bytes: Option<u64>,
+    confirm_open_project: Option<bool>,
+    confirm_cloud_eviction: Option<bool>,
+) -> Result<DeleteResult, DeptoxError> {
+    let _busy = BusyGuard::hold();
+    trial::ensure_entitled()?;
+
+    let options = DeleteOptions {
+        confirm_open_project: confirm_open_project.unwrap_or(false),
+        confirm_cloud_eviction: confirm_cloud_eviction.unwrap_or(false),
+        trash_only: false,
+    };
+    let result = delete_directory(path, expected_size_bytes, options, Some(&app)).await;
+    persistence::flush_all();
+
+    if let Ok(deleted) = &result {
+        let batch = BatchDeleteResult::from_results(vec![deleted.clone()]);
+        refresh_cached_totals(&app, &batch).await;
+        report_cleanup(&batch);
+    }
+    result
+}
+
+/// Takes deleted directories out of the cached scan and the size index, then sends the
+/// reduced totals to the UI as `scan_stats` and to the tray, so neither waits on a rescan
+async fn refresh_cached_totals(app: &tauri::AppHandle, batch: &BatchDeleteResult) {
+    let deleted: HashSet<String> = batch
+        .results
+        .iter()
+        .filter(|result| result.success)
+        .map(|result| result.path.clone())
+        .collect();
+    if deleted.is_empty() {
+        return;
+    }
+
+    remove_indexed_sizes(&deleted);
+    let Some(cached) = remove_cached_entries(&deleted) else {
+        return;
+    };
+
+    let sizes = DependencySizes::from_entries(&cached.result.entries);
+    // Not tagged with a scan ID, since it corrects whichever result the UI is showing
+    let _ = app.emit(
+        "scan_stats",
+        ScanStats {
+            total_size: sizes.total_size,
+            directory_count: cached.result.entries.len(),
+            current_path: None,
+        },
+    );
+
+    let settings = get_settings_sync().unwrap_or_default();
+    let threshold =
+        settings.effective_threshold_bytes(disk_usage(Path::new(&cached.root_directory)).as_ref());
+    let exceeded_categories =
+        find_exceeded_categories(&sizes.by_category, &settings.category_thresholds);
+
+    if let Err(error) = tray::set_tray_category_sizes(app, &sizes.by_category) {
+        warn!(%error, "Failed to update tray category breakdown");
+    }
+    if let Err(error) = tray::set_tray_icon(
+        app.clone(),
+        sizes.total_size,
+        threshold,
+        Some(exceeded_categories),
+    )
+    .await
+    {
+        warn!(%error, "Failed to update tray icon");
+    }
+}
+
+/// Plays the reclaim cue and posts the cleanup to the webhook, if either is configured
+fn report_cleanup(batch: &BatchDeleteResult) {
+    let settings = get_settings_sync().unwrap_or_default();
+    if let Some(cue) = sounds::reclaim_cue(&settings, batch.total_size_freed) {
+        sounds::play(cue);
+    }
+    if let Some(webhook_url) = &settings.webhook_url {
+        integrations::post_cleanup_completed(webhook_url, batch);
+    }
+    telemetry::metrics::record_cleanup(batch);
+}
+
+/// Deletes a single dependency directory and records it in the history stores.
+/// Callers are responsible for checking the trial entitlement first and for
+/// flushing those stores once they are done.
+/// Secure delete progress is emitted when an AppHandle is given.
+async fn delete_directory(
+    path: String,
+    expected_size_bytes: Option<u64>,
+    options: DeleteOptions,
+    app: Option<&tauri::AppHandle>,
+) -> Result<DeleteResult, DeptoxError> {
     let start = Instant::now();
     info!("Starting delete operation");
 
     let path_buf = Path::new(&path);
-    let canonical_path = validate_delete_path(path_buf).map_err(|error| {
-        warn!(%error, "Validation failed");
-        error.to_string()
-    })?;
+    let canonical_path =
+        validate_delete_path(path_buf).inspect_err(|error| warn!(%error, "Validation failed"))?;
+
+    let settings = get_settings_sync().unwrap_or_default();
+    ensure_not_scan_root(&canonical_path, &configured_scan_roots(&settings))
+        .inspect_err(|error| warn!(%error, "Refusing to delete scan root"))?;
 
-    let size_freed = 0;
+    ensure_no_install_in_progress(
+        &canonical_path,
+        settings.install_check,
+        options.confirm_open_project,
+    )
+    .inspect_err(|error| warn!(%error, "Install in progress"))?;
 
-    let permanent_delete = get_settings_sync()
-        .map(|settings| settings.permanent_delete)
-        .unwrap_or(false);
+    let permanent_delete =
+        !options.trash_only && (settings.permanent_delete || settings.secure_delete);
+    let cloud_status = cloud::probe_cloud_status(&canonical_path).await;
+    let may_evict = options.confirm_cloud_eviction && !options.trash_only;
+    // Placeholders are only ever evicted, even with permanent or secure delete on
+    if cloud_status == CloudStatus::CloudOnly && !may_evict {
+        warn!("Directory is only partly downloaded from the cloud");
+        return Err(DeleteValidationError::CloudOnly.into());
+    }
+
+    if settings.protect_open_projects && !options.confirm_open_project {
+        ensure_project_not_open(&canonical_path)
+            .await
+            .inspect_err(|error| warn!(%error, "Project is open"))?;
+
+        // Permanent deletes check open files unconditionally below
+        if !permanent_delete {
+            ensure_not_in_use(&canonical_path)
+                .await
+                .inspect_err(|error| warn!(%error, "Files are in use"))?;
+        }
+    }
+
+    let category = DependencyCategory::from_path(&canonical_path);
+    let size_freed = measure_size_before_delete(&canonical_path, expected_size_bytes).await;
 
     if permanent_delete {
-        std::fs::remove_dir_all(&canonical_path).map_err(|error| {
-            error!(%error, "Failed to permanently delete");
-            format!("Failed to permanently delete: {error}")
-        })?;
+        ensure_no_open_files(&canonical_path, options.confirm_open_project)
+            .await
+            .inspect_err(|error| warn!(%error, "Files are in use"))?;
+    }
+
+    let mut permanently_removed = permanent_delete;
+    let mut trashed_item = None;
 
+    if cloud_status == CloudStatus::CloudOnly {
+        // The trash refuses placeholders, and removing or overwriting them would change the
+        // files on every device, so only the local copy goes. Nothing is left to restore.
+        cloud::evict(&canonical_path).await.map_err(|error| {
+            error!(%error, "Failed to evict cloud directory");
+            DeptoxError::delete_failed(&canonical_path, error)
+        })?;
+        permanently_removed = true;
         info!(
             duration_ms = start.elapsed().as_millis() as u64,
             size_mb = size_freed as f64 / 1024.0 / 1024.0,
-            "Successfully permanently deleted"
+            "Evicted local copy of cloud directory"
         );
-    } else if let Err(error) = trash::delete(&canonical_path) {
-        error!(%error, "Failed to move to trash");
-        let error_message = error.to_string();
-
-        if error_message.contains("needs to be downloaded") {
-            warn!("iCloud directory detected, attempting force delete");
-            std::fs::remove_dir_all(&canonical_path).map_err(|remove_error| {
-                error!(%remove_error, "Force delete also failed");
-                format!("Cannot delete: This directory is stored in iCloud. Attempted force delete but failed: {remove_error}")
+    } else if permanent_delete {
+        if settings.secure_delete {
+            let overwrite_path = canonical_path.clone();
+            let progress_app = app.cloned();
+            let overwritten = tokio::task::spawn_blocking(move || {
+                overwrite_directory_contents(&overwrite_path, |progress| {
+                    if let Some(app) = &progress_app {
+                        let _ = app.emit("secure_delete_progress", progress);
+                    }
+                })
+            })
+            .await
+            .map_err(|error| {
+                DeptoxError::delete_failed(
+                    &canonical_path,
+                    format!("Secure delete failed: {error}"),
+                )
+            })?
+            .map_err(|error| {
+                error!(%error, "Failed to overwrite files");
+                DeptoxError::delete_failed(
+                    &canonical_path,
+                    format!("Failed to overwrite files: {error}"),
+                )
             })?;
-            info!("Successfully force-deleted iCloud directory");
-        } else {
-            return Err(format!("Failed to move to trash: {error}"));
+            info!(
+                files = overwritten.files_overwritten,
+                bytes = overwritten.bytes_overwritten,
+                "Overwrote files before removal"
+            );
+            if overwritten.files_skipped > 0 {
+                warn!(
+                    skipped = overwritten.files_skipped,
+                    "Some files were removed without being overwritten"
+                );
+            }
         }
-    } else {
+
+        std::fs::remove_dir_all(&canonical_path).map_err(|error| {
+            error!(%error, "Failed to permanently delete");
+            DeptoxError::delete_failed(
+                &canonical_path,
+                format!("Failed to permanently delete: {error}"),
+            )
+        })?;
+
         info!(
             duration_ms = start.elapsed().as_millis() as u64,
             size_mb = size_freed as f64 / 1024.0 / 1024.0,
-            "Successfully moved to trash"
+            "Successfully permanently deleted"
         );
+    } else {
+        match move_to_trash(&canonical_path) {
+            Ok(trashed) => {
+                trashed_item = trashed;
+                info!(
+                    duration_ms = start.elapsed().as_millis() as u64,
+                    size_mb = size_freed as f64 / 1024.0 / 1024.0,
+                    "Successfully moved to trash"
+                );
+            }
+            Err(error) => {
+                error!(%error, "Failed to move to trash");
+
+                // Files can be evicted between the check and the move, so a synced directory
+                // the trash refuses gets the same treatment as one that was cloud-only
+                if cloud_status == CloudStatus::Local || options.trash_only {
+                    return Err(DeptoxError::delete_failed(
+                        &canonical_path,
+                        format!("Failed to move to trash: {error}"),
+                    ));
+                }
+                if !options.confirm_cloud_eviction {
+                    warn!("Trash refused a synced directory, eviction needs confirming");
+                    return Err(DeleteValidationError::CloudOnly.into());
+                }
+                cloud::evict(&canonical_path).await.map_err(|evict_error| {
+                    error!(%evict_error, "Eviction also failed");
+                    DeptoxError::delete_failed(
+                        &canonical_path,
+                        format!(
+                            "Failed to move to trash: {error}. Eviction also failed: {evict_error}"
+                        ),
+                    )
+                })?;
+                permanently_removed = true;
+                info!("Evicted cloud directory the trash refused");
+            }
+        }
     }
 
+    record_cleanup(category, size_freed);
+
+    let deleted_path = canonical_path.to_string_lossy().to_string();
+    let mut record = DeletionRecord::new(deleted_path.clone(), size_freed, permanently_removed);
+    if let Some(trashed) = trashed_item {
+        record.trash_location = Some(trashed.location);
+        record.trash_identity = Some(trashed.identity);
+    }
+    record_deletion(record);
+
     Ok(DeleteResult {
         success: true,
-        path: canonical_path.to_string_lossy().to_string(),
+        path: deleted_path,
         size_freed,
     })
 }
 
 #[tauri::command]
 #[instrument(skip_all, fields(count = paths.len()))]
-pub async fn delete_all_to_trash(paths: Vec<String>) -> Result<Vec<DeleteResult>, String> {
+pub async fn delete_all_to_trash(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    confirm_open_projects: Option<bool>,
+    confirm_cloud_eviction: Option<bool>,
+) -> Result<BatchDeleteResult, DeptoxError> {
+    let options = DeleteOptions {
+        confirm_open_project: confirm_open_projects.unwrap_or(false),
+        confirm_cloud_eviction: confirm_cloud_eviction.unwrap_or(false),
+        trash_only: false,
+    };
+    delete_batch(app, paths, options).await
+}
+
+/// Moves every path to the trash even when permanent or secure delete is on, for cleanups
+/// that run without the user reviewing each item, so they can always be undone
+pub async fn trash_all(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+) -> Result<BatchDeleteResult, DeptoxError> {
+    let options = DeleteOptions {
+        trash_only: true,
+        ..DeleteOptions::default()
+    };
+    delete_batch(app, paths, options).await
+}
+
+async fn delete_batch(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    options: DeleteOptions,
+) -> Result<BatchDeleteResult, DeptoxError> {
+    let _busy = BusyGuard::hold();
     let start = Instant::now();
     info!("Starting batch delete operation");
 
+    trial::ensure_entitled()?;
+
     let semaphore = Arc::new(Semaphore::new(config::delete::MAX_CONCURRENT_DELETES));
+    let completed = Arc::new(AtomicUsize::new(0));
+    let total = paths.len();
 
     let handles: Vec<_> = paths
         .into_iter()
-        .map(|path| {
+        .enumerate()
+        .map(|(index, path)| {
             let semaphore = semaphore.clone();
+            let completed = completed.clone();
+            let app = app.clone();
             tokio::spawn(async move {
                 let _permit = semaphore.acquire().await;
-                match delete_to_trash(path.clone()).await {
+                let result = match delete_directory(path.clone(), None, options, Some(&app)).await {
                     Ok(result) => result,
                     Err(error) => {
                         error!(%path, %error, "Failed to delete");
@@ -158,7 +827,19 @@ pub async fn delete_all_to_trash(paths: Vec<String>) -> Result<Vec<DeleteResult>
                             size_freed: 0,
                         }
                     }
-                }
+                };
+
+                let progress = DeleteProgress {
+                    path: result.path.clone(),
+                    index,
+                    completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+                    total,
+                    success: result.success,
+                    size_freed: result.size_freed,
+                };
+                let _ = app.emit("delete_progress", &progress);
+
+                result
             })
         })
         .collect();
@@ -178,15 +859,196 @@ pub async fn delete_all_to_trash(paths: Vec<String>) -> Result<Vec<DeleteResult>
         }
     }
 
-    let successful = results.iter().filter(|result| result.success).count();
+    persistence::flush_all();
+
+    let batch = BatchDeleteResult::from_results(results);
     info!(
-        successful,
-        total = results.len(),
+        successful = batch.successful_count,
+        failed = batch.failed_count,
+        total_size_freed = batch.total_size_freed,
         duration_ms = start.elapsed().as_millis() as u64,
         "Batch delete complete"
     );
+    refresh_cached_totals(&app, &batch).await;
+    report_cleanup(&batch);
+
+    Ok(batch)
+}
+
+/// What deleting one path would do, from `preview_delete_all`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePreviewItem {
+    /// The path as requested
+    pub path: String,
+    /// What would actually be deleted, None when the path doesn't resolve
+    pub canonical_path: Option<String>,
+    pub size_bytes: u64,
+    /// Why the delete would be refused, None when it would go ahead
+    pub error: Option<String>,
+    /// Processes working in the project, or holding its files open when moving to the trash,
+    /// which need confirming when `protect_open_projects` is on
+    pub open_in: Option<String>,
+    /// Stored in iCloud Drive or another synced folder
+    pub icloud: bool,
+    /// `CloudOnly` items are evicted rather than trashed, which needs confirming
+    pub cloud_status: CloudStatus,
+    /// The directory or its parent is read-only, so removal would fail
+    pub permission_denied: bool,
+    /// A package install looks to be running, which needs confirming when `install_check` is `Warn`
+    pub install_in_progress: bool,
+    /// Checking for open files timed out, so deleting needs confirming
+    pub open_files_unknown: bool,
+}
+
+impl DeletePreviewItem {
+    fn is_deletable(&self) -> bool {
+        self.error.is_none() && !self.permission_denied
+    }
+}
+
+/// Dry run of a batch delete for the confirmation dialog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePreview {
+    pub items: Vec<DeletePreviewItem>,
+    /// Size of every item that would be deleted
+    pub total_reclaimable_bytes: u64,
+    pub deletable_count: usize,
+    pub blocked_count: usize,
+    /// Whether items would be removed permanently rather than moved to the trash
+    pub permanent: bool,
+}
+
+impl DeletePreview {
+    fn from_items(items: Vec<DeletePreviewItem>, permanent: bool) -> Self {
+        let deletable: Vec<&DeletePreviewItem> =
+            items.iter().filter(|item| item.is_deletable()).collect();
+        let total_reclaimable_bytes = deletable.iter().map(|item| item.size_bytes).sum();
+        let deletable_count = deletable.len();
+
+        Self {
+            blocked_count: items.len() - deletable_count,
+            items,
+            total_reclaimable_bytes,
+            deletable_count,
+            permanent,
+        }
+    }
+}
+
+fn is_read_only(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.permissions().readonly())
+}
+
+/// Runs every check `delete_directory` would, without deleting anything
+async fn preview_directory(path: String, settings: &AppSettings) -> DeletePreviewItem {
+    let mut item = DeletePreviewItem {
+        path,
+        canonical_path: None,
+        size_bytes: 0,
+        error: None,
+        open_in: None,
+        icloud: false,
+        cloud_status: CloudStatus::Local,
+        permission_denied: false,
+        install_in_progress: false,
+        open_files_unknown: false,
+    };
+
+    let canonical_path = match validate_delete_path(Path::new(&item.path)) {
+        Ok(canonical_path) => canonical_path,
+        Err(error) => {
+            item.error = Some(error.to_string());
+            return item;
+        }
+    };
+    item.canonical_path = Some(canonical_path.to_string_lossy().to_string());
+    item.cloud_status = cloud::probe_cloud_status(&canonical_path).await;
+    item.icloud = item.cloud_status != CloudStatus::Local;
+    item.permission_denied =
+        is_read_only(&canonical_path) || canonical_path.parent().is_some_and(is_read_only);
+
+    if let Err(error) = ensure_not_scan_root(&canonical_path, &configured_scan_roots(settings)) {
+        item.error = Some(error.to_string());
+        return item;
+    }
+
+    match ensure_no_install_in_progress(&canonical_path, settings.install_check, false) {
+        Err(DeleteValidationError::InstallInProgress) => item.install_in_progress = true,
+        Err(error) => {
+            item.error = Some(error.to_string());
+            return item;
+        }
+        Ok(()) => {}
+    }
+
+    let permanent_delete = settings.permanent_delete || settings.secure_delete;
+    if settings.protect_open_projects {
+        if let Err(DeleteValidationError::ProjectOpen(commands)) =
+            ensure_project_not_open(&canonical_path).await
+        {
+            item.open_in = Some(commands);
+        } else if !permanent_delete {
+            match ensure_not_in_use(&canonical_path).await {
+                Err(DeleteValidationError::InUse(processes)) => item.open_in = Some(processes),
+                Err(DeleteValidationError::OpenFilesUnknown) => item.open_files_unknown = true,
+                _ => {}
+            }
+        }
+    }
+
+    if permanent_delete {
+        match ensure_no_open_files(&canonical_path, false).await {
+            Err(DeleteValidationError::OpenFilesUnknown) => item.open_files_unknown = true,
+            Err(error) => item.error = Some(error.to_string()),
+            Ok(()) => {}
+        }
+    }
+
+    item.size_bytes = measure_size_before_delete(&canonical_path, None).await;
+    item
+}
+
+#[tauri::command]
+#[instrument(skip_all, fields(count = paths.len()))]
+pub async fn preview_delete_all(paths: Vec<String>) -> Result<DeletePreview, DeptoxError> {
+    let start = Instant::now();
+    let settings = Arc::new(get_settings_sync().unwrap_or_default());
+    let semaphore = Arc::new(Semaphore::new(config::delete::MAX_CONCURRENT_DELETES));
+
+    let handles: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let semaphore = semaphore.clone();
+            let settings = settings.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire().await;
+                preview_directory(path, &settings).await
+            })
+        })
+        .collect();
+
+    let mut items = Vec::with_capacity(handles.len());
+    for handle in handles {
+        items.push(
+            handle
+                .await
+                .map_err(|error| DeptoxError::Internal(format!("Preview failed: {error}")))?,
+        );
+    }
+
+    let preview =
+        DeletePreview::from_items(items, settings.permanent_delete || settings.secure_delete);
+    info!(
+        deletable = preview.deletable_count,
+        blocked = preview.blocked_count,
+        total_reclaimable_bytes = preview.total_reclaimable_bytes,
+        duration_ms = start.elapsed().as_millis() as u64,
+        "Delete preview complete"
+    );
 
-    Ok(results)
+    Ok(preview)
 }
 
 #[cfg(test)]
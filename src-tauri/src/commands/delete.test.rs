@@ -64,6 +64,41 @@ fn test_validate_delete_path_valid_venv() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_validate_delete_path_valid_named_virtualenv() {
+    let temp_dir = TempDir::new().unwrap();
+    let venv = temp_dir.path().join("project").join("env-py311");
+    fs::create_dir_all(&venv).unwrap();
+    fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin").unwrap();
+
+    let result = validate_delete_path(&venv);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_validate_delete_path_valid_dotnet_obj() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("Api");
+    fs::create_dir_all(project.join("obj")).unwrap();
+    fs::write(project.join("Api.csproj"), "<Project />").unwrap();
+
+    let result = validate_delete_path(&project.join("obj"));
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_validate_delete_path_rejects_plain_bin() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = temp_dir.path().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+
+    let result = validate_delete_path(&bin);
+    assert!(matches!(
+        result,
+        Err(DeleteValidationError::NotDependencyDirectory)
+    ));
+}
+
 #[test]
 fn test_validation_error_display() {
     assert_eq!(
@@ -81,6 +116,78 @@ fn test_validation_error_display() {
     assert!(DeleteValidationError::InvalidPath("test error".to_string())
         .to_string()
         .contains("Invalid path"));
+    assert_eq!(
+        DeleteValidationError::ProjectOpen("code, zsh".to_string()).to_string(),
+        "Project is open in code, zsh; confirm to delete anyway"
+    );
+    assert_eq!(
+        DeleteValidationError::FilesInUse("node (4242)".to_string()).to_string(),
+        "Files are in use by node (4242); close them and try again"
+    );
+    assert_eq!(
+        DeleteValidationError::OpenFilesUnknown.to_string(),
+        "Couldn't check in time whether files are in use; confirm to delete anyway"
+    );
+    assert_eq!(
+        DeleteValidationError::InUse("node (4242)".to_string()).to_string(),
+        "Files are in use by node (4242); confirm to delete anyway"
+    );
+    assert_eq!(
+        DeleteValidationError::ScanRoot.to_string(),
+        "Cannot delete the scan root or a directory containing it"
+    );
+    assert_eq!(
+        DeleteValidationError::InstallInProgress.to_string(),
+        "A package install appears to be running here; confirm to delete anyway"
+    );
+}
+
+#[test]
+fn test_ensure_not_scan_root_rejects_root_named_like_dependencies() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("node_modules");
+    fs::create_dir(&root).unwrap();
+
+    let canonical_path = validate_delete_path(&root).unwrap();
+
+    assert_eq!(
+        ensure_not_scan_root(&canonical_path, std::slice::from_ref(&root)),
+        Err(DeleteValidationError::ScanRoot)
+    );
+}
+
+#[test]
+fn test_ensure_not_scan_root_rejects_ancestor_of_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let vendor = temp_dir.path().join("vendor");
+    let root = vendor.join("code");
+    fs::create_dir_all(&root).unwrap();
+
+    let canonical_path = vendor.canonicalize().unwrap();
+
+    assert_eq!(
+        ensure_not_scan_root(&canonical_path, &[root]),
+        Err(DeleteValidationError::ScanRoot)
+    );
+}
+
+#[test]
+fn test_ensure_not_scan_root_allows_directories_inside_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("app").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+    let sibling_root = temp_dir.path().join("node_modules_backup");
+    fs::create_dir(&sibling_root).unwrap();
+
+    let canonical_path = node_modules.canonicalize().unwrap();
+
+    assert_eq!(
+        ensure_not_scan_root(
+            &canonical_path,
+            &[temp_dir.path().to_path_buf(), sibling_root]
+        ),
+        Ok(())
+    );
 }
 
 #[test]
@@ -245,3 +352,287 @@ fn test_canonicalize_path_resolves_dot_segments() {
     assert!(!path_str.contains("/../"));
     assert!(!path_str.contains("/./"));
 }
+
+fn delete_result(path: &str, success: bool, size_freed: u64) -> DeleteResult {
+    DeleteResult {
+        success,
+        path: path.to_string(),
+        size_freed,
+    }
+}
+
+#[test]
+fn test_batch_delete_result_aggregates_successful_deletes() {
+    let batch = BatchDeleteResult::from_results(vec![
+        delete_result("/a/node_modules", true, 1000),
+        delete_result("/b/node_modules", false, 0),
+        delete_result("/c/vendor", true, 250),
+    ]);
+
+    assert_eq!(batch.total_size_freed, 1250);
+    assert_eq!(batch.successful_count, 2);
+    assert_eq!(batch.failed_count, 1);
+    assert_eq!(batch.results.len(), 3);
+}
+
+#[test]
+fn test_batch_delete_result_serialization() {
+    let batch = BatchDeleteResult::from_results(vec![delete_result("/a/Pods", true, 64)]);
+
+    let json = serde_json::to_string(&batch).unwrap();
+    assert!(json.contains("\"totalSizeFreed\":64"));
+    assert!(json.contains("\"successfulCount\":1"));
+    assert!(json.contains("\"failedCount\":0"));
+}
+
+#[tokio::test]
+async fn test_measure_size_before_delete_uses_actual_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir(&node_modules).unwrap();
+    fs::write(node_modules.join("index.js"), "123456").unwrap();
+
+    assert_eq!(measure_size_before_delete(&node_modules, None).await, 6);
+    assert_eq!(
+        measure_size_before_delete(&node_modules, Some(999)).await,
+        6
+    );
+}
+
+#[test]
+fn test_delete_progress_serialization() {
+    let progress = DeleteProgress {
+        path: "/project/node_modules".to_string(),
+        index: 2,
+        completed: 3,
+        total: 10,
+        success: true,
+        size_freed: 2048,
+    };
+
+    let json = serde_json::to_string(&progress).unwrap();
+    assert!(json.contains("\"index\":2"));
+    assert!(json.contains("\"completed\":3"));
+    assert!(json.contains("\"total\":10"));
+    assert!(json.contains("\"sizeFreed\":2048"));
+}
+
+fn preview_item(path: &str, size_bytes: u64, error: Option<&str>) -> DeletePreviewItem {
+    DeletePreviewItem {
+        path: path.to_string(),
+        canonical_path: Some(path.to_string()),
+        size_bytes,
+        error: error.map(str::to_string),
+        open_in: None,
+        icloud: false,
+        cloud_status: CloudStatus::Local,
+        permission_denied: false,
+        install_in_progress: false,
+        open_files_unknown: false,
+    }
+}
+
+#[test]
+fn test_delete_preview_counts_only_deletable_items() {
+    let mut read_only = preview_item("/c/vendor", 300, None);
+    read_only.permission_denied = true;
+
+    let preview = DeletePreview::from_items(
+        vec![
+            preview_item("/a/node_modules", 1000, None),
+            preview_item("/b/src", 0, Some("Can only delete dependency directories")),
+            read_only,
+        ],
+        false,
+    );
+
+    assert_eq!(preview.total_reclaimable_bytes, 1000);
+    assert_eq!(preview.deletable_count, 1);
+    assert_eq!(preview.blocked_count, 2);
+    assert!(!preview.permanent);
+}
+
+#[tokio::test]
+async fn test_preview_directory_sizes_without_deleting() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("app").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+    fs::write(node_modules.join("index.js"), "x".repeat(64)).unwrap();
+
+    let item = preview_directory(
+        node_modules.to_string_lossy().to_string(),
+        &AppSettings::default(),
+    )
+    .await;
+
+    assert_eq!(item.error, None);
+    assert_eq!(item.size_bytes, 64);
+    assert!(!item.icloud);
+    assert_eq!(item.cloud_status, CloudStatus::Local);
+    // Just written, so it looks like an install that hasn't settled yet
+    assert!(item.install_in_progress);
+    assert_eq!(
+        item.canonical_path.as_deref(),
+        Some(
+            node_modules
+                .canonicalize()
+                .unwrap()
+                .to_string_lossy()
+                .as_ref()
+        )
+    );
+    assert!(node_modules.exists());
+}
+
+#[tokio::test]
+async fn test_preview_directory_reports_validation_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("src");
+    fs::create_dir(&source).unwrap();
+
+    let item = preview_directory(
+        source.to_string_lossy().to_string(),
+        &AppSettings::default(),
+    )
+    .await;
+
+    assert_eq!(
+        item.error.as_deref(),
+        Some("Can only delete dependency directories")
+    );
+    assert!(item.canonical_path.is_none());
+    assert_eq!(item.size_bytes, 0);
+}
+
+#[test]
+fn test_overwrite_directory_contents_zeroes_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let package = node_modules.join("leaky");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join(".env"), "API_KEY=secret").unwrap();
+    fs::write(node_modules.join("index.js"), "module.exports = 1").unwrap();
+
+    let mut reports = Vec::new();
+    let progress =
+        overwrite_directory_contents(&node_modules, |progress| reports.push(progress.clone()))
+            .unwrap();
+
+    assert_eq!(progress.total_files, 2);
+    assert_eq!(progress.files_overwritten, 2);
+    assert_eq!(progress.bytes_overwritten, 32);
+    assert_eq!(progress.files_skipped, 0);
+    assert_eq!(reports.last(), Some(&progress));
+    assert_eq!(fs::read(package.join(".env")).unwrap(), vec![0u8; 14]);
+    assert_eq!(
+        fs::read(node_modules.join("index.js")).unwrap(),
+        vec![0u8; 18]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_collect_overwrite_targets_skips_links() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = temp_dir.path().join("store");
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir_all(&store).unwrap();
+    fs::create_dir_all(&node_modules).unwrap();
+    fs::write(store.join("shared.js"), "shared").unwrap();
+    fs::write(store.join("linked.js"), "linked").unwrap();
+    fs::write(node_modules.join("own.js"), "own").unwrap();
+    fs::hard_link(store.join("shared.js"), node_modules.join("shared.js")).unwrap();
+    std::os::unix::fs::symlink(store.join("linked.js"), node_modules.join("linked.js")).unwrap();
+
+    let targets = collect_overwrite_targets(&node_modules).unwrap();
+
+    assert_eq!(targets.files, vec![(node_modules.join("own.js"), 3)]);
+    assert_eq!(targets.skipped, 1);
+    let progress = overwrite_directory_contents(&node_modules, |_| {}).unwrap();
+    assert_eq!(progress.files_overwritten, 1);
+    assert_eq!(progress.files_skipped, 1);
+    assert_eq!(
+        fs::read_to_string(store.join("shared.js")).unwrap(),
+        "shared"
+    );
+    assert_eq!(
+        fs::read_to_string(store.join("linked.js")).unwrap(),
+        "linked"
+    );
+}
+
+const AFTER_INSTALL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+#[test]
+fn test_is_install_in_progress_for_recently_modified_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir_all(node_modules.join("react")).unwrap();
+
+    assert!(is_install_in_progress_at(&node_modules, SystemTime::now()));
+    assert!(!is_install_in_progress_at(
+        &node_modules,
+        SystemTime::now() + AFTER_INSTALL
+    ));
+}
+
+#[test]
+fn test_is_install_in_progress_with_marker() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir_all(node_modules.join(".staging")).unwrap();
+
+    assert!(is_install_in_progress_at(
+        &node_modules,
+        SystemTime::now() + AFTER_INSTALL
+    ));
+}
+
+#[test]
+fn test_is_install_in_progress_missing_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(!is_install_in_progress(&temp_dir.path().join("missing")));
+}
+
+#[test]
+fn test_check_open_files_needs_confirming_when_unknown() {
+    let node = OpenProcess {
+        pid: 4242,
+        command: "node".to_string(),
+    };
+
+    assert_eq!(check_open_files(Some(Vec::new()), false), Ok(()));
+    assert_eq!(
+        check_open_files(Some(vec![node.clone()]), true),
+        Err(DeleteValidationError::FilesInUse("node (4242)".to_string()))
+    );
+    assert_eq!(
+        check_open_files(None, false),
+        Err(DeleteValidationError::OpenFilesUnknown)
+    );
+    assert_eq!(check_open_files(None, true), Ok(()));
+}
+
+#[test]
+fn test_ensure_no_install_in_progress_respects_strictness() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir_all(node_modules.join(".staging")).unwrap();
+
+    assert_eq!(
+        ensure_no_install_in_progress(&node_modules, InstallCheck::Off, false),
+        Ok(())
+    );
+    assert_eq!(
+        ensure_no_install_in_progress(&node_modules, InstallCheck::Warn, false),
+        Err(DeleteValidationError::InstallInProgress)
+    );
+    assert_eq!(
+        ensure_no_install_in_progress(&node_modules, InstallCheck::Warn, true),
+        Ok(())
+    );
+    assert_eq!(
+        ensure_no_install_in_progress(&node_modules, InstallCheck::Refuse, true),
+        Err(DeleteValidationError::InstallInProgressBlocked)
+    );
+}
@@ -0,0 +1,548 @@
+use super::settings::get_settings_sync;
+use crate::config;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, instrument, warn};
+
+/// Batch deletes record concurrently, so the journal is kept in memory and written on flush
+static JOURNAL: BufferedJsonFile<Vec<DeletionRecord>> =
+    BufferedJsonFile::new(get_journal_path, load_journal_from, |path, records| {
+        save_journal_to(path, records)
+    });
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionRecord {
+    pub path: String,
+    pub size_bytes: u64,
+    pub deleted_at_ms: u64,
+    /// Where the directory ended up in the trash: the entry's path on macOS, the system
+    /// trash's item id elsewhere. None for permanent deletes or when it couldn't be found.
+    pub trash_location: Option<String>,
+    /// What the trash entry was when deptox created it. Restores and purges only act on
+    /// an entry that still matches, never on something trashed later under the same name.
+    #[serde(default)]
+    pub trash_identity: Option<TrashIdentity>,
+    #[serde(default)]
+    pub permanent: bool,
+    #[serde(default)]
+    pub restored: bool,
+    /// Permanently removed from the trash by the expired trash cleanup
+    #[serde(default)]
+    pub purged: bool,
+}
+
+impl DeletionRecord {
+    pub fn new(path: String, size_bytes: u64, permanent: bool) -> Self {
+        Self {
+            path,
+            size_bytes,
+            deleted_at_ms: time::now_ms(),
+            trash_location: None,
+            trash_identity: None,
+            permanent,
+            restored: false,
+            purged: false,
+        }
+    }
+
+    fn is_restorable(&self) -> bool {
+        !self.permanent && !self.restored && !self.purged
+    }
+
+    /// Whether the trash entry at `location` is the one this record's deletion created
+    fn owns_trash_entry(&self, location: &str, identity: TrashIdentity) -> bool {
+        self.trash_location.as_deref() == Some(location) && self.trash_identity == Some(identity)
+    }
+}
+
+/// Identifies a trash entry independently of its name, which the user can reuse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum TrashIdentity {
+    /// Device and inode of the trashed directory, which the move into `~/.Trash` keeps
+    File { device: u64, inode: u64 },
+    /// Deletion time the system trash recorded for the item, in seconds
+    #[serde(rename_all = "camelCase")]
+    TrashItem { time_deleted: i64 },
+}
+
+/// The trash entry a directory became
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedItem {
+    pub location: String,
+    pub identity: TrashIdentity,
+}
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+/// What a trash cleanup removed, for the activity view
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashPurgeSummary {
+    pub purged_count: usize,
+    pub bytes_reclaimed: u64,
+}
+
+/// Space taken up in the trash by directories deptox moved there
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashUsage {
+    pub item_count: usize,
+    pub size_bytes: u64,
+}
+
+fn get_journal_path() -> Result<PathBuf, String> {
+    config_path(config::app::JOURNAL_FILENAME)
+}
+
+fn load_journal_from(path: &Path) -> Vec<DeletionRecord> {
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            warn!(%error, "Failed to read deletion journal");
+            return Vec::new();
+        }
+    };
+
+    serde_json::from_str(&content).unwrap_or_else(|error| {
+        warn!(%error, "Failed to parse deletion journal, starting fresh");
+        Vec::new()
+    })
+}
+
+fn save_journal_to(path: &Path, records: &[DeletionRecord]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(records)
+        .map_err(|error| format!("Failed to serialize deletion journal: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write deletion journal: {error}"))
+}
+
+/// Appends a record, dropping the oldest entries once the journal exceeds its cap
+fn append_record(records: &mut Vec<DeletionRecord>, record: DeletionRecord) {
+    records.push(record);
+    if records.len() > config::journal::MAX_RECORDS {
+        let overflow = records.len() - config::journal::MAX_RECORDS;
+        records.drain(..overflow);
+    }
+}
+
+/// Finds the most recent restorable record for a path
+fn find_restorable_index(records: &[DeletionRecord], path: &str) -> Option<usize> {
+    records
+        .iter()
+        .rposition(|record| record.path == path && record.is_restorable())
+}
+
+/// Records still in the trash that were deleted more than `purge_after_days` ago.
+/// Zero disables the cleanup; shorter periods are raised to the minimum undo window.
+fn expired_records(
+    records: &[DeletionRecord],
+    now_ms: u64,
+    purge_after_days: u32,
+) -> Vec<DeletionRecord> {
+    if purge_after_days == 0 {
+        return Vec::new();
+    }
+
+    let days = purge_after_days.max(config::journal::MIN_TRASH_PURGE_DAYS);
+    let cutoff_ms = now_ms.saturating_sub(u64::from(days) * MILLISECONDS_PER_DAY);
+
+    records
+        .iter()
+        .filter(|record| record.is_restorable() && record.deleted_at_ms < cutoff_ms)
+        .cloned()
+        .collect()
+}
+
+/// Totals the restorable records for which `is_present` confirms the trash entry still exists
+fn trash_usage(
+    records: &[DeletionRecord],
+    is_present: impl Fn(&DeletionRecord) -> bool,
+) -> TrashUsage {
+    records
+        .iter()
+        .filter(|record| record.is_restorable() && is_present(record))
+        .fold(TrashUsage::default(), |usage, record| TrashUsage {
+            item_count: usage.item_count + 1,
+            size_bytes: usage.size_bytes + record.size_bytes,
+        })
+}
+
+/// Records a completed deletion in the journal; written to disk on the next flush.
+pub fn record_deletion(record: DeletionRecord) {
+    if let Err(error) = JOURNAL.update(|records| append_record(records, record)) {
+        warn!(%error, "Failed to record deletion");
+    }
+}
+
+/// Writes pending journal records to disk
+pub fn flush_journal() -> Result<bool, String> {
+    JOURNAL.flush()
+}
+
+/// Device and inode of `path`, without following a symlink in its place
+#[cfg(any(target_os = "macos", all(unix, test)))]
+fn file_identity(path: &Path) -> Option<TrashIdentity> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs::symlink_metadata(path).ok()?;
+    Some(TrashIdentity::File {
+        device: metadata.dev(),
+        inode: metadata.ino(),
+    })
+}
+
+/// Finds the entry of `dir` that is the same file as `identity`. Finder renames an item
+/// whose name is taken (e.g. "node_modules 2"), so names alone can't tell entries apart.
+#[cfg(any(target_os = "macos", all(unix, test)))]
+fn find_entry_with_identity(dir: &Path, identity: TrashIdentity) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| file_identity(path) == Some(identity))
+}
+
+#[cfg(target_os = "macos")]
+fn trash_dir() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".Trash"))
+        .ok_or_else(|| "Could not determine home directory".to_string())
+}
+
+/// Moves a directory to the trash and finds the entry it became, so the journal can
+/// later restore or purge exactly that entry. The entry is None if it can't be found,
+/// e.g. when the directory went to another volume's trash.
+#[cfg(target_os = "macos")]
+pub fn move_to_trash(path: &Path) -> Result<Option<TrashedItem>, trash::Error> {
+    let identity = file_identity(path);
+    trash::delete(path)?;
+
+    let trashed = identity.and_then(|identity| {
+        let location = find_entry_with_identity(&trash_dir().ok()?, identity)?;
+        Some(TrashedItem {
+            location: location.to_string_lossy().to_string(),
+            identity,
+        })
+    });
+    if trashed.is_none() {
+        warn!(path = %path.display(), "Could not locate trashed directory");
+    }
+
+    Ok(trashed)
+}
+
+/// Moves a directory to the trash and finds the entry it became, so the journal can
+/// later restore or purge exactly that entry. The entry is None if it can't be found.
+#[cfg(not(target_os = "macos"))]
+pub fn move_to_trash(path: &Path) -> Result<Option<TrashedItem>, trash::Error> {
+    let started_at_seconds = (time::now_ms() / 1000) as i64;
+    trash::delete(path)?;
+
+    // Older deletions of the same path belong to other records
+    let trashed = trash::os_limited::list()
+        .ok()
+        .and_then(|items| {
+            items
+                .into_iter()
+                .filter(|item| item.original_path() == path)
+                .filter(|item| item.time_deleted >= started_at_seconds - 1)
+                .max_by_key(|item| item.time_deleted)
+        })
+        .map(|item| TrashedItem {
+            location: item.id.to_string_lossy().to_string(),
+            identity: TrashIdentity::TrashItem {
+                time_deleted: item.time_deleted,
+            },
+        });
+    if trashed.is_none() {
+        warn!(path = %path.display(), "Could not locate trashed directory");
+    }
+
+    Ok(trashed)
+}
+
+/// The record's trash entry, if it is still the one its deletion created
+#[cfg(target_os = "macos")]
+fn verified_trash_entry(record: &DeletionRecord) -> Option<PathBuf> {
+    let trash_path = PathBuf::from(record.trash_location.as_ref()?);
+    if trash_path.parent() != Some(trash_dir().ok()?.as_path()) {
+        return None;
+    }
+
+    let identity = file_identity(&trash_path)?;
+    record
+        .owns_trash_entry(&trash_path.to_string_lossy(), identity)
+        .then_some(trash_path)
+}
+
+/// Whether `item` is the trash entry the record's deletion created
+#[cfg(not(target_os = "macos"))]
+fn is_record_trash_item(record: &DeletionRecord, item: &trash::TrashItem) -> bool {
+    item.original_path() == Path::new(&record.path)
+        && record.owns_trash_entry(
+            &item.id.to_string_lossy(),
+            TrashIdentity::TrashItem {
+                time_deleted: item.time_deleted,
+            },
+        )
+}
+
+/// The record's trash entry, if it is still the one its deletion created
+#[cfg(not(target_os = "macos"))]
+fn verified_trash_entry(record: &DeletionRecord) -> Result<Option<trash::TrashItem>, String> {
+    Ok(trash::os_limited::list()
+        .map_err(|error| format!("Failed to list trash: {error}"))?
+        .into_iter()
+        .find(|item| is_record_trash_item(record, item)))
+}
+
+/// Checks whether a trashed directory is still there, so items the user already
+/// emptied from the trash aren't reported as taking up space
+#[cfg(target_os = "macos")]
+fn trash_presence_check() -> impl Fn(&DeletionRecord) -> bool {
+    |record: &DeletionRecord| verified_trash_entry(record).is_some()
+}
+
+/// Checks whether a trashed directory is still there, so items the user already
+/// emptied from the trash aren't reported as taking up space
+#[cfg(not(target_os = "macos"))]
+fn trash_presence_check() -> impl Fn(&DeletionRecord) -> bool {
+    let trashed = trash::os_limited::list().unwrap_or_else(|error| {
+        warn!(%error, "Failed to list trash");
+        Vec::new()
+    });
+
+    move |record: &DeletionRecord| {
+        trashed
+            .iter()
+            .any(|item| is_record_trash_item(record, item))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn restore_from_trash(record: &DeletionRecord) -> Result<(), String> {
+    let trash_path = verified_trash_entry(record)
+        .ok_or_else(|| "Directory is no longer in the trash".to_string())?;
+
+    fs::rename(trash_path, &record.path)
+        .map_err(|error| format!("Failed to restore from trash: {error}"))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn restore_from_trash(record: &DeletionRecord) -> Result<(), String> {
+    let item = verified_trash_entry(record)?
+        .ok_or_else(|| "Directory is no longer in the trash".to_string())?;
+
+    trash::os_limited::restore_all([item])
+        .map_err(|error| format!("Failed to restore from trash: {error}"))
+}
+
+/// Permanently removes the trashed directory. One that is already gone (e.g. the user
+/// emptied the trash) or was replaced by a different entry counts as purged, and only
+/// the entry the deletion created is ever removed.
+#[cfg(target_os = "macos")]
+fn purge_from_trash(record: &DeletionRecord) -> Result<(), String> {
+    let Some(trash_path) = verified_trash_entry(record) else {
+        return Ok(());
+    };
+
+    fs::remove_dir_all(trash_path).map_err(|error| format!("Failed to purge from trash: {error}"))
+}
+
+/// Permanently removes the trashed directory. One that is already gone (e.g. the user
+/// emptied the trash) or was replaced by a different entry counts as purged, and only
+/// the entry the deletion created is ever removed.
+#[cfg(not(target_os = "macos"))]
+fn purge_from_trash(record: &DeletionRecord) -> Result<(), String> {
+    let Some(item) = verified_trash_entry(record)? else {
+        return Ok(());
+    };
+
+    trash::os_limited::purge_all([item])
+        .map_err(|error| format!("Failed to purge from trash: {error}"))
+}
+
+/// Whether two records describe the same deletion, for finding a record again after
+/// working on a copy outside the journal lock
+fn is_same_deletion(record: &DeletionRecord, other: &DeletionRecord) -> bool {
+    record.path == other.path && record.deleted_at_ms == other.deleted_at_ms
+}
+
+/// Marks the journal entries for the given deletions as purged
+fn mark_purged(records: &mut [DeletionRecord], purged: &[DeletionRecord]) {
+    for record in records.iter_mut() {
+        if purged
+            .iter()
+            .any(|purged_record| is_same_deletion(purged_record, record))
+        {
+            record.purged = true;
+        }
+    }
+}
+
+/// Marks the journal entry for the given deletion as restored
+fn mark_restored(records: &mut [DeletionRecord], restored: &DeletionRecord) {
+    for record in records
+        .iter_mut()
+        .filter(|record| is_same_deletion(restored, record))
+    {
+        record.restored = true;
+    }
+}
+
+/// Permanently removes the given records' trash entries and marks them purged in the
+/// journal. Items that fail to purge are left in place.
+fn purge_records(records: Vec<DeletionRecord>) -> Result<TrashPurgeSummary, String> {
+    // Purge outside the journal lock so deletes can keep recording meanwhile
+    let purged: Vec<DeletionRecord> = records
+        .into_iter()
+        .filter(|record| match purge_from_trash(record) {
+            Ok(()) => true,
+            Err(error) => {
+                warn!(path = %record.path, %error, "Failed to purge trash item");
+                false
+            }
+        })
+        .collect();
+
+    let summary = TrashPurgeSummary {
+        purged_count: purged.len(),
+        bytes_reclaimed: purged.iter().map(|record| record.size_bytes).sum(),
+    };
+
+    if summary.purged_count > 0 {
+        JOURNAL.update(|records| mark_purged(records, &purged))?;
+        flush_journal()?;
+    }
+
+    Ok(summary)
+}
+
+/// Empties deptox-created trash items older than the retention period and marks
+/// them purged in the journal. Items that fail to purge are left for the next run.
+pub fn purge_expired_trash_items(purge_after_days: u32) -> Result<TrashPurgeSummary, String> {
+    let expired =
+        JOURNAL.read(|records| expired_records(records, time::now_ms(), purge_after_days))?;
+
+    let summary = purge_records(expired)?;
+    if summary.purged_count > 0 {
+        info!(
+            purged = summary.purged_count,
+            bytes_reclaimed = summary.bytes_reclaimed,
+            "Purged expired trash items"
+        );
+    }
+
+    Ok(summary)
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn purge_expired_trash() -> Result<TrashPurgeSummary, String> {
+    let purge_after_days = get_settings_sync()?.trash_purge_after_days;
+
+    tokio::task::spawn_blocking(move || purge_expired_trash_items(purge_after_days))
+        .await
+        .map_err(|error| format!("Trash cleanup task failed: {error}"))?
+}
+
+/// Reports how much space directories deptox moved to the trash are still taking up.
+/// Other items in the trash are not counted.
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_trash_usage() -> Result<TrashUsage, String> {
+    let records: Vec<DeletionRecord> = JOURNAL.read(|records| records.clone())?;
+
+    let usage = tokio::task::spawn_blocking(move || trash_usage(&records, trash_presence_check()))
+        .await
+        .map_err(|error| format!("Trash usage task failed: {error}"))?;
+
+    debug!(
+        items = usage.item_count,
+        size_bytes = usage.size_bytes,
+        "Measured trash usage"
+    );
+    Ok(usage)
+}
+
+/// Permanently removes everything deptox moved to the trash, leaving the rest of the
+/// trash untouched. Those directories can no longer be restored afterwards.
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn empty_deptox_trash() -> Result<TrashPurgeSummary, String> {
+    let restorable: Vec<DeletionRecord> = JOURNAL.read(|records| {
+        records
+            .iter()
+            .filter(|record| record.is_restorable())
+            .cloned()
+            .collect()
+    })?;
+
+    let summary = tokio::task::spawn_blocking(move || purge_records(restorable))
+        .await
+        .map_err(|error| format!("Empty trash task failed: {error}"))??;
+
+    info!(
+        purged = summary.purged_count,
+        bytes_reclaimed = summary.bytes_reclaimed,
+        "Emptied deptox items from trash"
+    );
+    Ok(summary)
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_deletion_history() -> Result<Vec<DeletionRecord>, String> {
+    let records: Vec<DeletionRecord> =
+        JOURNAL.read(|records| records.iter().rev().cloned().collect())?;
+
+    debug!(count = records.len(), "Loaded deletion history");
+    Ok(records)
+}
+
+#[tauri::command]
+#[instrument(skip_all, fields(path = %path))]
+pub async fn restore_deleted(path: String) -> Result<DeletionRecord, String> {
+    info!("Restoring directory from trash");
+
+    let record = JOURNAL
+        .read(|records| find_restorable_index(records, &path).map(|index| records[index].clone()))?
+        .ok_or_else(|| {
+            warn!("No restorable deletion found");
+            "No restorable deletion found for this path".to_string()
+        })?;
+
+    if Path::new(&path).exists() {
+        warn!("Original location is occupied");
+        return Err("A directory already exists at the original location".to_string());
+    }
+
+    // Restore outside the journal lock so deletes can keep recording meanwhile
+    let restoring = record.clone();
+    tokio::task::spawn_blocking(move || restore_from_trash(&restoring))
+        .await
+        .map_err(|error| format!("Restore task failed: {error}"))??;
+
+    let restored = DeletionRecord {
+        restored: true,
+        ..record
+    };
+    JOURNAL.update(|records| mark_restored(records, &restored))?;
+    flush_journal()?;
+
+    info!("Directory restored from trash");
+    Ok(restored)
+}
+
+#[cfg(test)]
+#[path = "deletion_journal.test.rs"]
+mod tests;
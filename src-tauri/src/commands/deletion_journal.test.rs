@@ -0,0 +1,284 @@
+use super::*;
+use tempfile::TempDir;
+
+fn record(path: &str, permanent: bool) -> DeletionRecord {
+    DeletionRecord::new(path.to_string(), 1024, permanent)
+}
+
+#[test]
+fn test_deletion_record_new_sets_timestamp() {
+    let record = record("/project/node_modules", false);
+    assert!(record.deleted_at_ms > 0);
+    assert!(record.trash_location.is_none());
+    assert!(!record.restored);
+}
+
+#[test]
+fn test_deletion_record_serialization_camel_case() {
+    let mut record = record("/project/node_modules", false);
+    record.trash_location = Some("/Users/test/.Trash/node_modules".to_string());
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert!(json.contains("\"sizeBytes\":1024"));
+    assert!(json.contains("\"deletedAtMs\""));
+    assert!(json.contains("\"trashLocation\":\"/Users/test/.Trash/node_modules\""));
+    assert!(json.contains("\"permanent\":false"));
+    assert!(json.contains("\"restored\":false"));
+}
+
+#[test]
+fn test_deletion_record_deserialization_without_optional_fields() {
+    let json = r#"{"path":"/project/vendor","sizeBytes":10,"deletedAtMs":5,"trashLocation":null}"#;
+    let record: DeletionRecord = serde_json::from_str(json).unwrap();
+    assert!(!record.permanent);
+    assert!(!record.restored);
+}
+
+#[test]
+fn test_load_journal_from_nonexistent_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let records = load_journal_from(&temp_dir.path().join("missing.json"));
+    assert!(records.is_empty());
+}
+
+#[test]
+fn test_load_journal_from_corrupt_file_returns_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let journal_path = temp_dir.path().join("journal.json");
+    fs::write(&journal_path, "not json").unwrap();
+
+    assert!(load_journal_from(&journal_path).is_empty());
+}
+
+#[test]
+fn test_save_and_load_journal_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let journal_path = temp_dir.path().join("journal.json");
+    let records = vec![record("/a/node_modules", false), record("/b/Pods", true)];
+
+    save_journal_to(&journal_path, &records).unwrap();
+    let loaded = load_journal_from(&journal_path);
+
+    assert_eq!(loaded, records);
+}
+
+#[test]
+fn test_append_record_caps_journal_size() {
+    let mut records = Vec::new();
+    for index in 0..config::journal::MAX_RECORDS + 5 {
+        append_record(
+            &mut records,
+            record(&format!("/p{index}/node_modules"), false),
+        );
+    }
+
+    assert_eq!(records.len(), config::journal::MAX_RECORDS);
+    assert_eq!(records[0].path, "/p5/node_modules");
+}
+
+#[test]
+fn test_find_restorable_index_prefers_most_recent() {
+    let records = vec![
+        record("/a/node_modules", false),
+        record("/b/node_modules", false),
+        record("/a/node_modules", false),
+    ];
+
+    assert_eq!(find_restorable_index(&records, "/a/node_modules"), Some(2));
+}
+
+#[test]
+fn test_find_restorable_index_skips_permanent_and_restored() {
+    let mut restored = record("/a/node_modules", false);
+    restored.restored = true;
+    let records = vec![record("/a/node_modules", true), restored];
+
+    assert_eq!(find_restorable_index(&records, "/a/node_modules"), None);
+}
+
+#[test]
+fn test_find_restorable_index_unknown_path() {
+    let records = vec![record("/a/node_modules", false)];
+    assert_eq!(find_restorable_index(&records, "/b/node_modules"), None);
+}
+
+const DAY_MS: u64 = 86_400_000;
+
+fn record_deleted_at(path: &str, deleted_at_ms: u64) -> DeletionRecord {
+    DeletionRecord {
+        deleted_at_ms,
+        ..record(path, false)
+    }
+}
+
+#[test]
+fn test_expired_records_disabled_when_zero() {
+    let records = vec![record_deleted_at("/a/node_modules", 0)];
+    assert!(expired_records(&records, 100 * DAY_MS, 0).is_empty());
+}
+
+#[test]
+fn test_expired_records_only_restorable_items_past_cutoff() {
+    let now_ms = 100 * DAY_MS;
+    let mut restored = record_deleted_at("/restored/node_modules", DAY_MS);
+    restored.restored = true;
+    let mut purged = record_deleted_at("/purged/node_modules", DAY_MS);
+    purged.purged = true;
+    let mut permanent = record_deleted_at("/permanent/node_modules", DAY_MS);
+    permanent.permanent = true;
+
+    let records = vec![
+        record_deleted_at("/old/node_modules", now_ms - 31 * DAY_MS),
+        record_deleted_at("/recent/node_modules", now_ms - 5 * DAY_MS),
+        restored,
+        purged,
+        permanent,
+    ];
+
+    let expired = expired_records(&records, now_ms, 30);
+    let paths: Vec<&str> = expired.iter().map(|record| record.path.as_str()).collect();
+    assert_eq!(paths, vec!["/old/node_modules"]);
+}
+
+#[test]
+fn test_expired_records_keeps_minimum_undo_window() {
+    let now_ms = 100 * DAY_MS;
+    let records = vec![record_deleted_at("/a/node_modules", now_ms - 2 * DAY_MS)];
+
+    assert!(expired_records(&records, now_ms, 1).is_empty());
+}
+
+#[test]
+fn test_mark_purged_matches_path_and_time() {
+    let mut records = vec![
+        record_deleted_at("/a/node_modules", 10),
+        record_deleted_at("/a/node_modules", 20),
+    ];
+    let purged = vec![record_deleted_at("/a/node_modules", 10)];
+
+    mark_purged(&mut records, &purged);
+
+    assert!(records[0].purged);
+    assert!(!records[1].purged);
+    assert_eq!(find_restorable_index(&records, "/a/node_modules"), Some(1));
+}
+
+#[test]
+fn test_mark_restored_matches_path_and_time() {
+    let mut records = vec![
+        record_deleted_at("/a/node_modules", 10),
+        record_deleted_at("/a/node_modules", 20),
+    ];
+
+    mark_restored(&mut records, &record_deleted_at("/a/node_modules", 20));
+
+    assert!(!records[0].restored);
+    assert!(records[1].restored);
+    assert_eq!(find_restorable_index(&records, "/a/node_modules"), Some(0));
+}
+
+#[test]
+fn test_deletion_record_deserialization_defaults_purged() {
+    let json = r#"{"path":"/project/vendor","sizeBytes":10,"deletedAtMs":5,"trashLocation":null}"#;
+    let record: DeletionRecord = serde_json::from_str(json).unwrap();
+    assert!(!record.purged);
+}
+
+#[test]
+fn test_trash_usage_counts_restorable_items_still_in_trash() {
+    let mut restored = record("/restored/node_modules", false);
+    restored.restored = true;
+    let mut purged = record("/purged/node_modules", false);
+    purged.purged = true;
+
+    let records = vec![
+        record("/a/node_modules", false),
+        record("/b/node_modules", false),
+        record("/emptied/node_modules", false),
+        record("/permanent/node_modules", true),
+        restored,
+        purged,
+    ];
+
+    let usage = trash_usage(&records, |record| record.path != "/emptied/node_modules");
+
+    assert_eq!(
+        usage,
+        TrashUsage {
+            item_count: 2,
+            size_bytes: 2048,
+        }
+    );
+}
+
+#[test]
+fn test_trash_usage_empty_journal() {
+    assert_eq!(trash_usage(&[], |_| true), TrashUsage::default());
+}
+
+#[test]
+fn test_trash_usage_serialization_camel_case() {
+    let json = serde_json::to_string(&TrashUsage {
+        item_count: 3,
+        size_bytes: 42,
+    })
+    .unwrap();
+
+    assert_eq!(json, r#"{"itemCount":3,"sizeBytes":42}"#);
+}
+
+#[test]
+fn test_owns_trash_entry_requires_location_and_identity() {
+    let identity = TrashIdentity::File {
+        device: 1,
+        inode: 42,
+    };
+    let mut record = record("/a/node_modules", false);
+    assert!(!record.owns_trash_entry("/Users/test/.Trash/node_modules", identity));
+
+    record.trash_location = Some("/Users/test/.Trash/node_modules".to_string());
+    record.trash_identity = Some(identity);
+
+    assert!(record.owns_trash_entry("/Users/test/.Trash/node_modules", identity));
+    assert!(!record.owns_trash_entry("/Users/test/.Trash/node_modules 2", identity));
+    assert!(!record.owns_trash_entry(
+        "/Users/test/.Trash/node_modules",
+        TrashIdentity::File {
+            device: 1,
+            inode: 43,
+        }
+    ));
+}
+
+#[test]
+fn test_deletion_record_deserialization_defaults_trash_identity() {
+    let json =
+        r#"{"path":"/project/vendor","sizeBytes":10,"deletedAtMs":5,"trashLocation":"/t/vendor"}"#;
+    let record: DeletionRecord = serde_json::from_str(json).unwrap();
+    assert!(record.trash_identity.is_none());
+}
+
+#[test]
+fn test_trash_identity_serialization() {
+    let json = serde_json::to_string(&TrashIdentity::TrashItem { time_deleted: 7 }).unwrap();
+    assert_eq!(json, r#"{"kind":"trashItem","timeDeleted":7}"#);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_find_entry_with_identity_ignores_same_named_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let original = temp_dir.path().join("node_modules");
+    fs::create_dir(&original).unwrap();
+    let identity = file_identity(&original).unwrap();
+
+    let trash = temp_dir.path().join("Trash");
+    fs::create_dir(&trash).unwrap();
+    fs::create_dir(trash.join("node_modules")).unwrap();
+    fs::rename(&original, trash.join("node_modules 2")).unwrap();
+
+    assert_eq!(
+        find_entry_with_identity(&trash, identity),
+        Some(trash.join("node_modules 2"))
+    );
+}
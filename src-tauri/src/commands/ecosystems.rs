@@ -0,0 +1,90 @@
+use super::settings::{default_enabled_categories, get_settings_sync};
+use crate::config;
+use crate::fixture;
+use crate::scanner::{expand_tilde, sweep_ecosystem_markers, DependencyCategory, EcosystemSweep};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+use tracing::{info, instrument, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EcosystemDetection {
+    pub root_directory: String,
+    /// Categories with project markers under the scan root
+    pub detected_categories: Vec<DependencyCategory>,
+    /// Categories to preselect, falling back to the defaults when nothing was found
+    pub suggested_categories: Vec<DependencyCategory>,
+    /// False when the sweep stopped at its directory limit, so more ecosystems may exist
+    pub complete: bool,
+}
+
+/// Orders detected categories like the settings list and picks what onboarding preselects
+fn detection_from_sweep(root_directory: String, sweep: &EcosystemSweep) -> EcosystemDetection {
+    let detected_categories: Vec<DependencyCategory> = DependencyCategory::all()
+        .into_iter()
+        .filter(|category| sweep.detected.contains(category))
+        .collect();
+
+    let suggested_categories = if detected_categories.is_empty() {
+        let defaults = default_enabled_categories();
+        DependencyCategory::all()
+            .into_iter()
+            .filter(|category| defaults.contains(category))
+            .collect()
+    } else {
+        detected_categories.clone()
+    };
+
+    EcosystemDetection {
+        root_directory,
+        detected_categories,
+        suggested_categories,
+        complete: sweep.complete,
+    }
+}
+
+/// Shallow sweep for project markers under `root` (the scan root when omitted),
+/// reporting which categories are worth scanning for. Used for onboarding defaults.
+#[tauri::command]
+#[instrument(skip_all, fields(root = ?root))]
+pub async fn detect_ecosystems(root: Option<String>) -> Result<EcosystemDetection, String> {
+    let start = Instant::now();
+    let root_directory = match root {
+        Some(root) => expand_tilde(&root),
+        None => fixture::active_root().unwrap_or_else(|| {
+            expand_tilde(&get_settings_sync().unwrap_or_default().root_directory)
+        }),
+    };
+
+    if !Path::new(&root_directory).is_dir() {
+        warn!(%root_directory, "Ecosystem detection root is not a directory");
+        return Err(format!("Not a directory: {root_directory}"));
+    }
+
+    let sweep_root = root_directory.clone();
+    let sweep = tokio::task::spawn_blocking(move || {
+        sweep_ecosystem_markers(
+            Path::new(&sweep_root),
+            config::ecosystems::DETECTION_MAX_DEPTH,
+            config::ecosystems::DETECTION_MAX_DIRECTORIES,
+        )
+    })
+    .await
+    .map_err(|error| format!("Failed to detect ecosystems: {error}"))?;
+
+    let detection = detection_from_sweep(root_directory, &sweep);
+
+    info!(
+        detected = ?detection.detected_categories,
+        directories = sweep.directories_checked,
+        complete = sweep.complete,
+        duration_ms = start.elapsed().as_millis() as u64,
+        "Detected project ecosystems"
+    );
+    Ok(detection)
+}
+
+#[cfg(test)]
+#[path = "ecosystems.test.rs"]
+mod tests;
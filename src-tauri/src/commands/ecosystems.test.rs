@@ -0,0 +1,82 @@
+use super::*;
+
+fn sweep(detected: &[DependencyCategory], complete: bool) -> EcosystemSweep {
+    EcosystemSweep {
+        detected: detected.iter().copied().collect(),
+        directories_checked: 12,
+        complete,
+    }
+}
+
+#[test]
+fn test_detection_orders_categories_like_settings() {
+    let detection = detection_from_sweep(
+        "/home/user".to_string(),
+        &sweep(
+            &[DependencyCategory::GoMod, DependencyCategory::NodeModules],
+            true,
+        ),
+    );
+
+    assert_eq!(
+        detection.detected_categories,
+        vec![DependencyCategory::NodeModules, DependencyCategory::GoMod]
+    );
+    assert_eq!(
+        detection.suggested_categories,
+        detection.detected_categories
+    );
+    assert!(detection.complete);
+}
+
+#[test]
+fn test_detection_suggests_default_categories_when_nothing_found() {
+    let detection = detection_from_sweep("/home/user".to_string(), &sweep(&[], false));
+
+    assert!(detection.detected_categories.is_empty());
+    assert_eq!(
+        detection.suggested_categories.len(),
+        default_enabled_categories().len()
+    );
+    assert!(!detection
+        .suggested_categories
+        .contains(&DependencyCategory::BuildArtifacts));
+    assert!(!detection.complete);
+}
+
+#[test]
+fn test_ecosystem_detection_serialization() {
+    let detection = detection_from_sweep(
+        "/home/user".to_string(),
+        &sweep(&[DependencyCategory::DotNet], true),
+    );
+    let json = serde_json::to_string(&detection).unwrap();
+
+    assert!(json.contains("\"rootDirectory\":\"/home/user\""));
+    assert!(json.contains("\"detectedCategories\":[\"DOT_NET\"]"));
+    assert!(json.contains("\"suggestedCategories\":[\"DOT_NET\"]"));
+}
+
+#[tokio::test]
+async fn test_detect_ecosystems_with_explicit_root() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("site");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(project.join("pubspec.yaml"), "").unwrap();
+
+    let detection = detect_ecosystems(Some(temp_dir.path().to_string_lossy().to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        detection.detected_categories,
+        vec![DependencyCategory::DartTool]
+    );
+    assert!(detection.complete);
+}
+
+#[tokio::test]
+async fn test_detect_ecosystems_rejects_missing_root() {
+    let result = detect_ecosystems(Some("/nonexistent/deptox/root".to_string())).await;
+    assert!(result.is_err());
+}
@@ -1,15 +1,16 @@
-#[cfg(target_os = "macos")]
-use std::path::Path;
-#[cfg(target_os = "macos")]
+use super::settings::get_settings_sync;
+use crate::fixture;
+use crate::scanner::{disk_usage, expand_tilde, DiskUsage};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-#[cfg(target_os = "macos")]
 use std::time::Instant;
-#[cfg(target_os = "macos")]
-use tracing::{debug, error, instrument, warn};
-#[cfg(not(target_os = "macos"))]
-use tracing::{instrument, warn};
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, error, info, instrument, warn};
 
-#[cfg(target_os = "macos")]
 fn validate_path_exists(path: &str) -> Result<(), String> {
     let path_buf = Path::new(path);
     if !path_buf.exists() {
@@ -19,7 +20,6 @@ fn validate_path_exists(path: &str) -> Result<(), String> {
     Ok(())
 }
 
-#[cfg(target_os = "macos")]
 fn validate_path_within_home(path: &str) -> Result<(), String> {
     let home_dir = dirs::home_dir().ok_or_else(|| {
         warn!("Could not determine home directory");
@@ -44,34 +44,189 @@ fn validate_path_within_home(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Builds the command that shows a path in the platform file manager.
+/// Directories are opened; files are selected in their folder where the platform allows it.
+#[cfg(target_os = "macos")]
+fn reveal_command(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    if path.is_file() {
+        command.arg("-R");
+    }
+    command.arg(path);
+    command
+}
+
+/// Builds the command that shows a path in the platform file manager.
+/// Directories are opened; files are selected in their folder where the platform allows it.
+#[cfg(target_os = "windows")]
+fn reveal_command(path: &Path) -> Command {
+    let mut command = Command::new("explorer");
+    if path.is_file() {
+        // Explorer expects `/select,` and the path as a single argument
+        let mut select_argument = std::ffi::OsString::from("/select,");
+        select_argument.push(path);
+        command.arg(select_argument);
+    } else {
+        command.arg(path);
+    }
+    command
+}
+
+/// Builds the command that shows a path in the platform file manager.
+/// `xdg-open` can't select a file, so a file's containing folder is opened instead.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn reveal_command(path: &Path) -> Command {
+    let target = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+
+    let mut command = Command::new("xdg-open");
+    command.arg(target);
+    command
+}
+
 #[tauri::command]
 #[instrument(skip_all, fields(path = %path))]
-pub fn open_in_finder(path: String) -> Result<(), String> {
+pub fn reveal_in_file_manager(path: String) -> Result<(), String> {
+    let start = Instant::now();
+    debug!("Revealing path in file manager");
+
+    validate_path_exists(&path)?;
+    validate_path_within_home(&path)?;
+
+    reveal_command(Path::new(&path)).spawn().map_err(|error| {
+        error!(%error, "Failed to spawn file manager command");
+        format!("Failed to open file manager: {error}")
+    })?;
+    debug!(
+        duration_ms = start.elapsed().as_millis() as u64,
+        "Revealed in file manager"
+    );
+    Ok(())
+}
+
+/// Total and free space on the volume holding the scan root
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_disk_usage() -> Result<DiskUsage, String> {
+    let root_directory = fixture::active_root()
+        .unwrap_or_else(|| expand_tilde(&get_settings_sync().unwrap_or_default().root_directory));
+
+    let usage = disk_usage(Path::new(&root_directory)).ok_or_else(|| {
+        warn!(%root_directory, "Failed to read disk usage");
+        format!("Failed to read disk usage for {root_directory}")
+    })?;
+    debug!(?usage, "Read disk usage");
+
+    Ok(usage)
+}
+
+/// Privacy & Security pane listing apps with Full Disk Access
+#[cfg(target_os = "macos")]
+const FULL_DISK_ACCESS_PANE: &str =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles";
+
+/// Opens System Settings where the user can grant Full Disk Access, so protected
+/// folders listed in a scan's `skipped_entries` become readable on the next scan.
+#[tauri::command]
+#[instrument(skip_all)]
+pub fn request_full_disk_access() -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        let start = Instant::now();
-        debug!("Opening path in Finder");
-
-        validate_path_exists(&path)?;
-        validate_path_within_home(&path)?;
-
-        Command::new("open").arg(&path).spawn().map_err(|error| {
-            error!(%error, "Failed to spawn open command");
-            format!("Failed to open Finder: {error}")
-        })?;
-        debug!(
-            duration_ms = start.elapsed().as_millis() as u64,
-            "Opened in Finder"
-        );
+        Command::new("open")
+            .arg(FULL_DISK_ACCESS_PANE)
+            .spawn()
+            .map_err(|error| {
+                error!(%error, "Failed to open Full Disk Access settings");
+                format!("Failed to open Full Disk Access settings: {error}")
+            })?;
+        debug!("Opened Full Disk Access settings");
         Ok(())
     }
 
     #[cfg(not(target_os = "macos"))]
     {
-        let _ = path;
-        warn!("open_in_finder is only supported on macOS");
-        Err("open_in_finder is only supported on macOS".to_string())
+        Err("Full Disk Access is only available on macOS".to_string())
+    }
+}
+
+/// Whether the app can read macOS privacy-protected folders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FullDiskAccessStatus {
+    Granted,
+    Denied,
+    /// None of the probed locations exist, so there was nothing to test against
+    Unknown,
+    /// Only macOS gates folders behind Full Disk Access
+    NotRequired,
+}
+
+/// Home-relative locations that only open with Full Disk Access granted
+#[cfg(target_os = "macos")]
+const FULL_DISK_ACCESS_PROBES: &[&str] = &[
+    "Library/Application Support/com.apple.TCC",
+    "Library/Safari",
+    "Library/Mail",
+    "Library/Messages",
+];
+
+/// Tries each location in turn and reports the first that answers either way.
+/// Metadata stays readable without permission, so the probe has to list or open.
+fn probe_full_disk_access(paths: &[PathBuf]) -> FullDiskAccessStatus {
+    for path in paths {
+        let attempt = if path.is_dir() {
+            fs::read_dir(path).map(|_| ())
+        } else {
+            fs::File::open(path).map(|_| ())
+        };
+
+        match attempt {
+            Ok(()) => return FullDiskAccessStatus::Granted,
+            Err(error) if error.kind() == ErrorKind::PermissionDenied => {
+                return FullDiskAccessStatus::Denied
+            }
+            Err(error) => debug!(path = %path.display(), %error, "Skipping access probe"),
+        }
     }
+    FullDiskAccessStatus::Unknown
+}
+
+pub fn full_disk_access_status() -> FullDiskAccessStatus {
+    #[cfg(target_os = "macos")]
+    {
+        let Some(home) = dirs::home_dir() else {
+            return FullDiskAccessStatus::Unknown;
+        };
+        let paths: Vec<PathBuf> = FULL_DISK_ACCESS_PROBES
+            .iter()
+            .map(|relative| home.join(relative))
+            .collect();
+        probe_full_disk_access(&paths)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        FullDiskAccessStatus::NotRequired
+    }
+}
+
+/// Emits `full_disk_access_missing` when protected folders can't be read, so the UI
+/// can walk the user through granting access before a scan comes back incomplete
+pub fn notify_if_full_disk_access_missing(app: &AppHandle) {
+    let status = full_disk_access_status();
+    info!(?status, "Checked Full Disk Access");
+    if status == FullDiskAccessStatus::Denied {
+        let _ = app.emit("full_disk_access_missing", status);
+    }
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub fn check_full_disk_access() -> Result<FullDiskAccessStatus, String> {
+    Ok(full_disk_access_status())
 }
 
 #[cfg(test)]
@@ -1,11 +1,8 @@
 use super::*;
-#[cfg(target_os = "macos")]
 use std::fs;
-#[cfg(target_os = "macos")]
 use tempfile::TempDir;
 
 #[test]
-#[cfg(target_os = "macos")]
 fn test_validate_path_exists_with_existing_path() {
     let temp_dir = TempDir::new().unwrap();
     let result = validate_path_exists(temp_dir.path().to_str().unwrap());
@@ -13,7 +10,6 @@ fn test_validate_path_exists_with_existing_path() {
 }
 
 #[test]
-#[cfg(target_os = "macos")]
 fn test_validate_path_exists_with_existing_file() {
     let temp_dir = TempDir::new().unwrap();
     let file_path = temp_dir.path().join("test_file.txt");
@@ -24,7 +20,6 @@ fn test_validate_path_exists_with_existing_file() {
 }
 
 #[test]
-#[cfg(target_os = "macos")]
 fn test_validate_path_exists_with_nonexistent_path() {
     let result = validate_path_exists("/nonexistent/path/that/does/not/exist");
     assert!(result.is_err());
@@ -32,43 +27,59 @@ fn test_validate_path_exists_with_nonexistent_path() {
 }
 
 #[test]
-#[cfg(target_os = "macos")]
 fn test_validate_path_exists_with_empty_path() {
     let result = validate_path_exists("");
     assert!(result.is_err());
 }
 
 #[test]
-#[cfg(target_os = "macos")]
-fn test_open_in_finder_with_nonexistent_path() {
-    let result = open_in_finder("/nonexistent/path/that/does/not/exist".to_string());
+fn test_validate_path_within_home_rejects_outside_path() {
+    let outside = TempDir::new().unwrap();
+    let Some(home_dir) = dirs::home_dir().and_then(|home| home.canonicalize().ok()) else {
+        return;
+    };
+    if outside
+        .path()
+        .canonicalize()
+        .unwrap()
+        .starts_with(&home_dir)
+    {
+        return;
+    }
+
+    let result = validate_path_within_home(outside.path().to_str().unwrap());
+    assert_eq!(result.unwrap_err(), "Path must be within home directory");
+}
+
+#[test]
+fn test_reveal_in_file_manager_with_nonexistent_path() {
+    let result = reveal_in_file_manager("/nonexistent/path/that/does/not/exist".to_string());
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Path does not exist");
 }
 
 #[test]
-#[cfg(target_os = "macos")]
-fn test_open_in_finder_with_empty_path() {
-    let result = open_in_finder("".to_string());
+fn test_reveal_in_file_manager_with_empty_path() {
+    let result = reveal_in_file_manager("".to_string());
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), "Path does not exist");
 }
 
 #[test]
 #[cfg(target_os = "macos")]
-fn test_open_in_finder_with_existing_directory() {
+fn test_reveal_in_file_manager_with_existing_directory() {
     let home_dir = dirs::home_dir().unwrap();
     let temp_dir = tempfile::Builder::new()
         .prefix("deptox_test_")
         .tempdir_in(&home_dir)
         .unwrap();
-    let result = open_in_finder(temp_dir.path().to_str().unwrap().to_string());
+    let result = reveal_in_file_manager(temp_dir.path().to_str().unwrap().to_string());
     assert!(result.is_ok());
 }
 
 #[test]
 #[cfg(target_os = "macos")]
-fn test_open_in_finder_with_existing_file() {
+fn test_reveal_in_file_manager_with_existing_file() {
     let home_dir = dirs::home_dir().unwrap();
     let temp_dir = tempfile::Builder::new()
         .prefix("deptox_test_")
@@ -77,12 +88,61 @@ fn test_open_in_finder_with_existing_file() {
     let file_path = temp_dir.path().join("test_file.txt");
     fs::write(&file_path, "test content").unwrap();
 
-    let result = open_in_finder(file_path.to_str().unwrap().to_string());
+    let result = reveal_in_file_manager(file_path.to_str().unwrap().to_string());
     assert!(result.is_ok());
 }
 
 #[test]
 #[cfg(target_os = "macos")]
+fn test_reveal_command_selects_file_in_finder() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test_file.txt");
+    fs::write(&file_path, "test content").unwrap();
+
+    let command = reveal_command(&file_path);
+    let arguments: Vec<_> = command.get_args().collect();
+    assert_eq!(command.get_program(), "open");
+    assert_eq!(arguments, vec!["-R".as_ref(), file_path.as_os_str()]);
+}
+
+#[test]
+#[cfg(target_os = "windows")]
+fn test_reveal_command_selects_file_in_explorer() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test_file.txt");
+    fs::write(&file_path, "test content").unwrap();
+
+    let command = reveal_command(&file_path);
+    let arguments: Vec<_> = command.get_args().collect();
+    let mut expected = std::ffi::OsString::from("/select,");
+    expected.push(&file_path);
+    assert_eq!(command.get_program(), "explorer");
+    assert_eq!(arguments, vec![expected.as_os_str()]);
+}
+
+#[test]
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn test_reveal_command_opens_parent_of_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test_file.txt");
+    fs::write(&file_path, "test content").unwrap();
+
+    let command = reveal_command(&file_path);
+    let arguments: Vec<_> = command.get_args().collect();
+    assert_eq!(command.get_program(), "xdg-open");
+    assert_eq!(arguments, vec![temp_dir.path().as_os_str()]);
+}
+
+#[test]
+fn test_reveal_command_opens_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let command = reveal_command(temp_dir.path());
+    let arguments: Vec<_> = command.get_args().collect();
+    assert_eq!(arguments, vec![temp_dir.path().as_os_str()]);
+}
+
+#[test]
 fn test_validate_path_exists_with_special_characters() {
     let temp_dir = TempDir::new().unwrap();
     let special_path = temp_dir.path().join("test file with spaces");
@@ -93,7 +153,6 @@ fn test_validate_path_exists_with_special_characters() {
 }
 
 #[test]
-#[cfg(target_os = "macos")]
 fn test_validate_path_exists_with_unicode() {
     let temp_dir = TempDir::new().unwrap();
     let unicode_path = temp_dir.path().join("test_日本語_文件夹");
@@ -103,13 +162,40 @@ fn test_validate_path_exists_with_unicode() {
     assert!(result.is_ok());
 }
 
+#[cfg(not(target_os = "macos"))]
 #[test]
+fn test_request_full_disk_access_unsupported_off_macos() {
+    assert!(request_full_disk_access().is_err());
+}
+
+#[test]
+fn test_probe_full_disk_access_readable_directory_is_granted() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let status = probe_full_disk_access(&[temp_dir.path().to_path_buf()]);
+    assert_eq!(status, FullDiskAccessStatus::Granted);
+}
+
+#[test]
+fn test_probe_full_disk_access_skips_missing_locations() {
+    let temp_dir = TempDir::new().unwrap();
+    let readable_file = temp_dir.path().join("TCC.db");
+    fs::write(&readable_file, "x").unwrap();
+
+    let status = probe_full_disk_access(&[temp_dir.path().join("missing"), readable_file]);
+    assert_eq!(status, FullDiskAccessStatus::Granted);
+}
+
+#[test]
+fn test_probe_full_disk_access_nothing_to_probe_is_unknown() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let status = probe_full_disk_access(&[temp_dir.path().join("missing")]);
+    assert_eq!(status, FullDiskAccessStatus::Unknown);
+}
+
 #[cfg(not(target_os = "macos"))]
-fn test_open_in_finder_unsupported_platform() {
-    let result = open_in_finder("/some/path".to_string());
-    assert!(result.is_err());
-    assert_eq!(
-        result.unwrap_err(),
-        "open_in_finder is only supported on macOS"
-    );
+#[test]
+fn test_full_disk_access_not_required_off_macos() {
+    assert_eq!(full_disk_access_status(), FullDiskAccessStatus::NotRequired);
 }
@@ -0,0 +1,134 @@
+use crate::config;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, instrument, warn};
+
+/// Directories the user chose to hide from results, matched by exact path.
+/// Kept apart from exclude patterns so hiding one entry never touches the user's patterns.
+static IGNORED: BufferedJsonFile<Vec<IgnoredDirectory>> =
+    BufferedJsonFile::new(get_ignored_path, load_ignored_from, |path, ignored| {
+        save_ignored_to(path, ignored)
+    });
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoredDirectory {
+    pub path: String,
+    pub ignored_at_ms: u64,
+}
+
+fn get_ignored_path() -> Result<PathBuf, String> {
+    config_path(config::app::IGNORED_FILENAME)
+}
+
+fn load_ignored_from(path: &Path) -> Vec<IgnoredDirectory> {
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| {
+            warn!("Failed to load ignored directories, starting fresh");
+            Vec::new()
+        })
+}
+
+fn save_ignored_to(path: &Path, ignored: &[IgnoredDirectory]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(ignored)
+        .map_err(|error| format!("Failed to serialize ignored directories: {error}"))?;
+
+    fs::write(path, content)
+        .map_err(|error| format!("Failed to write ignored directories: {error}"))
+}
+
+/// Drops trailing separators so `/a/node_modules/` and `/a/node_modules` are one entry
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim().trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path.trim().to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Adds a path unless it is already ignored, returning whether it was added
+fn add_ignored(ignored: &mut Vec<IgnoredDirectory>, path: String, now_ms: u64) -> bool {
+    if ignored.iter().any(|directory| directory.path == path) {
+        return false;
+    }
+
+    ignored.push(IgnoredDirectory {
+        path,
+        ignored_at_ms: now_ms,
+    });
+    true
+}
+
+/// Removes a path, returning whether it was ignored
+fn remove_ignored(ignored: &mut Vec<IgnoredDirectory>, path: &str) -> bool {
+    let before = ignored.len();
+    ignored.retain(|directory| directory.path != path);
+    ignored.len() != before
+}
+
+/// Paths to leave out of scan results. Empty if the store can't be read,
+/// so a broken file shows too much rather than failing the scan.
+pub fn ignored_path_set() -> HashSet<String> {
+    IGNORED
+        .read(|ignored| {
+            ignored
+                .iter()
+                .map(|directory| directory.path.clone())
+                .collect()
+        })
+        .unwrap_or_else(|error| {
+            warn!(%error, "Failed to read ignored directories");
+            HashSet::new()
+        })
+}
+
+#[tauri::command]
+#[instrument]
+pub async fn ignore_directory(path: String) -> Result<(), String> {
+    let path = normalize_path(&path);
+    if path.is_empty() {
+        return Err("Path is empty".to_string());
+    }
+
+    if IGNORED.update(|ignored| add_ignored(ignored, path, time::now_ms()))? {
+        IGNORED.flush()?;
+        info!("Directory ignored");
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[instrument]
+pub async fn unignore_directory(path: String) -> Result<(), String> {
+    let path = normalize_path(&path);
+
+    if IGNORED.update(|ignored| remove_ignored(ignored, &path))? {
+        IGNORED.flush()?;
+        info!("Directory no longer ignored");
+    }
+    Ok(())
+}
+
+/// Ignored directories, most recently ignored first
+#[tauri::command]
+#[instrument]
+pub async fn list_ignored() -> Result<Vec<IgnoredDirectory>, String> {
+    let mut ignored = IGNORED.read(Vec::clone)?;
+    ignored.sort_by(|a, b| b.ignored_at_ms.cmp(&a.ignored_at_ms));
+    Ok(ignored)
+}
+
+#[cfg(test)]
+#[path = "ignored.test.rs"]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_normalize_path_trims_trailing_separators() {
+    assert_eq!(normalize_path("/a/node_modules/"), "/a/node_modules");
+    assert_eq!(normalize_path(r"C:\a\node_modules\"), r"C:\a\node_modules");
+    assert_eq!(normalize_path(" /a/vendor "), "/a/vendor");
+    assert_eq!(normalize_path("/"), "/");
+}
+
+#[test]
+fn test_add_ignored_skips_duplicates() {
+    let mut ignored = Vec::new();
+
+    assert!(add_ignored(&mut ignored, "/a/node_modules".to_string(), 1));
+    assert!(!add_ignored(&mut ignored, "/a/node_modules".to_string(), 2));
+
+    assert_eq!(ignored.len(), 1);
+    assert_eq!(ignored[0].ignored_at_ms, 1);
+}
+
+#[test]
+fn test_remove_ignored_reports_whether_path_was_ignored() {
+    let mut ignored = Vec::new();
+    add_ignored(&mut ignored, "/a/node_modules".to_string(), 1);
+    add_ignored(&mut ignored, "/b/Pods".to_string(), 2);
+
+    assert!(remove_ignored(&mut ignored, "/a/node_modules"));
+    assert!(!remove_ignored(&mut ignored, "/a/node_modules"));
+    assert_eq!(ignored.len(), 1);
+    assert_eq!(ignored[0].path, "/b/Pods");
+}
+
+#[test]
+fn test_save_and_load_ignored_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let ignored_path = temp_dir.path().join("ignored_paths.json");
+    let mut ignored = Vec::new();
+    add_ignored(&mut ignored, "/a/node_modules".to_string(), 5);
+
+    save_ignored_to(&ignored_path, &ignored).unwrap();
+
+    assert_eq!(load_ignored_from(&ignored_path), ignored);
+}
+
+#[test]
+fn test_load_ignored_from_missing_or_corrupt_file() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(load_ignored_from(&temp_dir.path().join("missing.json")).is_empty());
+
+    let corrupt_path = temp_dir.path().join("ignored_paths.json");
+    fs::write(&corrupt_path, "not json").unwrap();
+    assert!(load_ignored_from(&corrupt_path).is_empty());
+}
+
+#[test]
+fn test_ignored_directory_serialization_camel_case() {
+    let directory = IgnoredDirectory {
+        path: "/a/node_modules".to_string(),
+        ignored_at_ms: 42,
+    };
+
+    let json = serde_json::to_string(&directory).unwrap();
+    assert_eq!(json, r#"{"path":"/a/node_modules","ignoredAtMs":42}"#);
+}
@@ -1,9 +1,23 @@
+use super::scan_cache::cached_entry_paths;
+use super::settings::get_settings_sync;
 use crate::config;
+use crate::fixture;
+use crate::scanner::{expand_tilde, DependencyCategory};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
+use thiserror::Error;
 use tracing::{debug, instrument, warn};
 
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DrillDownValidationError {
+    #[error("Can only inspect scanned dependency directories")]
+    OutsideScannedEntries,
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FileEntry {
@@ -18,9 +32,163 @@ pub struct LargestFilesResult {
     pub directory_path: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildDirectoryEntry {
+    pub path: String,
+    pub size_bytes: u64,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestChildrenResult {
+    pub children: Vec<ChildDirectoryEntry>,
+    pub directory_path: String,
+    pub depth: usize,
+}
+
+/// Accepts a path inside a dependency directory from the last scan, or inside any
+/// dependency directory under the scan root so freshly rescanned entries still work.
+/// Mirrors `validate_delete_path` so the frontend can't use this to list arbitrary files.
+fn validate_drill_down_path(
+    path: &Path,
+    entry_paths: &[String],
+    root_directory: &Path,
+) -> Result<PathBuf, DrillDownValidationError> {
+    let canonical_path = path.canonicalize().map_err(|error| {
+        DrillDownValidationError::InvalidPath(format!("Failed to resolve path: {error}"))
+    })?;
+
+    let inside_scanned_entry = entry_paths
+        .iter()
+        .any(|entry_path| canonical_path.starts_with(entry_path));
+    if inside_scanned_entry {
+        return Ok(canonical_path);
+    }
+
+    let Ok(canonical_root) = root_directory.canonicalize() else {
+        return Err(DrillDownValidationError::OutsideScannedEntries);
+    };
+
+    let inside_dependency_directory = canonical_path
+        .ancestors()
+        .take_while(|ancestor| ancestor.starts_with(&canonical_root) && *ancestor != canonical_root)
+        .any(|ancestor| DependencyCategory::from_path(ancestor).is_some());
+    if !inside_dependency_directory {
+        return Err(DrillDownValidationError::OutsideScannedEntries);
+    }
+
+    Ok(canonical_path)
+}
+
+pub(crate) fn validate_scanned_path(path: &Path) -> Result<PathBuf, DrillDownValidationError> {
+    let root_directory = fixture::active_root()
+        .unwrap_or_else(|| expand_tilde(&get_settings_sync().unwrap_or_default().root_directory));
+
+    validate_drill_down_path(path, &cached_entry_paths(), Path::new(&root_directory))
+}
+
 #[tauri::command]
 #[instrument(skip_all, fields(path = %path))]
 pub async fn get_largest_files(path: String) -> Result<LargestFilesResult, String> {
+    if let Err(error) = validate_scanned_path(Path::new(&path)) {
+        warn!(%error, "Rejected largest files request");
+        return Err(error.to_string());
+    }
+
+    find_largest_files(path)
+}
+
+/// The biggest subdirectories `depth` levels below `path` (1 for immediate children),
+/// e.g. which packages inside a node_modules take the most space
+#[tauri::command]
+#[instrument(skip_all, fields(path = %path, depth))]
+pub async fn get_largest_children(
+    path: String,
+    depth: Option<usize>,
+) -> Result<LargestChildrenResult, String> {
+    if let Err(error) = validate_scanned_path(Path::new(&path)) {
+        warn!(%error, "Rejected largest children request");
+        return Err(error.to_string());
+    }
+
+    let depth = depth
+        .unwrap_or(1)
+        .clamp(1, config::largest_files::MAX_CHILD_DEPTH);
+    tokio::task::spawn_blocking(move || find_largest_children(path, depth))
+        .await
+        .map_err(|error| format!("Largest children task failed: {error}"))?
+}
+
+fn find_largest_children(path: String, depth: usize) -> Result<LargestChildrenResult, String> {
+    let start = Instant::now();
+    let root = Path::new(&path);
+
+    if !root.is_dir() {
+        warn!("Path is not a directory");
+        return Err("Path is not a directory".to_string());
+    }
+
+    let mut totals: HashMap<PathBuf, (u64, usize)> = HashMap::new();
+
+    let walker = jwalk::WalkDir::new(&path)
+        .skip_hidden(false)
+        .follow_links(false)
+        .parallelism(jwalk::Parallelism::Serial);
+
+    for entry in walker.into_iter().flatten() {
+        // Files directly above the requested depth don't belong to any child
+        if entry.depth() <= depth {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let entry_path = entry.path();
+        let Ok(relative) = entry_path.strip_prefix(root) else {
+            continue;
+        };
+        let child = root.join(relative.components().take(depth).collect::<PathBuf>());
+
+        let (size_bytes, file_count) = totals.entry(child).or_default();
+        *size_bytes += metadata.len();
+        *file_count += 1;
+    }
+
+    let mut children: Vec<ChildDirectoryEntry> = totals
+        .into_iter()
+        .map(|(child, (size_bytes, file_count))| ChildDirectoryEntry {
+            path: child.to_string_lossy().to_string(),
+            size_bytes,
+            file_count,
+        })
+        .collect();
+    children.sort_by(|a, b| {
+        b.size_bytes
+            .cmp(&a.size_bytes)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    children.truncate(config::largest_files::MAX_CHILDREN);
+
+    debug!(
+        child_count = children.len(),
+        duration_ms = start.elapsed().as_millis() as u64,
+        "Found largest children"
+    );
+
+    Ok(LargestChildrenResult {
+        children,
+        directory_path: path,
+        depth,
+    })
+}
+
+fn find_largest_files(path: String) -> Result<LargestFilesResult, String> {
     let start = Instant::now();
     debug!("Finding largest files in directory");
 
@@ -2,17 +2,15 @@ use super::*;
 use std::fs;
 use tempfile::TempDir;
 
-#[tokio::test]
-async fn test_get_largest_files_finds_files() {
+#[test]
+fn test_find_largest_files_finds_files() {
     let temp_dir = TempDir::new().unwrap();
 
     fs::write(temp_dir.path().join("small.txt"), "hello").unwrap();
     fs::write(temp_dir.path().join("medium.txt"), "a".repeat(1000)).unwrap();
     fs::write(temp_dir.path().join("large.txt"), "b".repeat(5000)).unwrap();
 
-    let result = get_largest_files(temp_dir.path().to_string_lossy().to_string())
-        .await
-        .unwrap();
+    let result = find_largest_files(temp_dir.path().to_string_lossy().to_string()).unwrap();
 
     assert_eq!(result.files.len(), 3);
     assert_eq!(result.files[0].size_bytes, 5000);
@@ -20,8 +18,8 @@ async fn test_get_largest_files_finds_files() {
     assert_eq!(result.files[2].size_bytes, 5);
 }
 
-#[tokio::test]
-async fn test_get_largest_files_limits_to_eight() {
+#[test]
+fn test_find_largest_files_limits_to_eight() {
     let temp_dir = TempDir::new().unwrap();
 
     for index in 0..12 {
@@ -29,87 +27,217 @@ async fn test_get_largest_files_limits_to_eight() {
         fs::write(temp_dir.path().join(format!("file_{}.txt", index)), content).unwrap();
     }
 
-    let result = get_largest_files(temp_dir.path().to_string_lossy().to_string())
-        .await
-        .unwrap();
+    let result = find_largest_files(temp_dir.path().to_string_lossy().to_string()).unwrap();
 
     assert_eq!(result.files.len(), 8);
     assert_eq!(result.files[0].size_bytes, 1200);
     assert_eq!(result.files[7].size_bytes, 500);
 }
 
-#[tokio::test]
-async fn test_get_largest_files_nested_directories() {
+#[test]
+fn test_find_largest_files_nested_directories() {
     let temp_dir = TempDir::new().unwrap();
 
     fs::create_dir(temp_dir.path().join("subdir")).unwrap();
     fs::write(temp_dir.path().join("root.txt"), "root").unwrap();
     fs::write(temp_dir.path().join("subdir/nested.txt"), "a".repeat(1000)).unwrap();
 
-    let result = get_largest_files(temp_dir.path().to_string_lossy().to_string())
-        .await
-        .unwrap();
+    let result = find_largest_files(temp_dir.path().to_string_lossy().to_string()).unwrap();
 
     assert_eq!(result.files.len(), 2);
     assert!(result.files[0].path.contains("nested.txt"));
     assert_eq!(result.files[0].size_bytes, 1000);
 }
 
-#[tokio::test]
-async fn test_get_largest_files_empty_directory() {
+#[test]
+fn test_find_largest_files_empty_directory() {
     let temp_dir = TempDir::new().unwrap();
 
-    let result = get_largest_files(temp_dir.path().to_string_lossy().to_string())
-        .await
-        .unwrap();
+    let result = find_largest_files(temp_dir.path().to_string_lossy().to_string()).unwrap();
 
     assert_eq!(result.files.len(), 0);
 }
 
-#[tokio::test]
-async fn test_get_largest_files_nonexistent_directory() {
-    let result = get_largest_files("/nonexistent/path/that/does/not/exist".to_string()).await;
+#[test]
+fn test_find_largest_files_nonexistent_directory() {
+    let result = find_largest_files("/nonexistent/path/that/does/not/exist".to_string());
 
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("does not exist"));
 }
 
-#[tokio::test]
-async fn test_get_largest_files_on_file_not_directory() {
+#[test]
+fn test_find_largest_files_on_file_not_directory() {
     let temp_dir = TempDir::new().unwrap();
     let file_path = temp_dir.path().join("file.txt");
     fs::write(&file_path, "content").unwrap();
 
-    let result = get_largest_files(file_path.to_string_lossy().to_string()).await;
+    let result = find_largest_files(file_path.to_string_lossy().to_string());
 
     assert!(result.is_err());
     assert!(result.unwrap_err().contains("not a directory"));
 }
 
-#[tokio::test]
-async fn test_get_largest_files_returns_sorted() {
+#[test]
+fn test_find_largest_files_returns_sorted() {
     let temp_dir = TempDir::new().unwrap();
 
     fs::write(temp_dir.path().join("a.txt"), "a".repeat(100)).unwrap();
     fs::write(temp_dir.path().join("b.txt"), "b".repeat(500)).unwrap();
     fs::write(temp_dir.path().join("c.txt"), "c".repeat(300)).unwrap();
 
-    let result = get_largest_files(temp_dir.path().to_string_lossy().to_string())
-        .await
-        .unwrap();
+    let result = find_largest_files(temp_dir.path().to_string_lossy().to_string()).unwrap();
 
     assert_eq!(result.files[0].size_bytes, 500);
     assert_eq!(result.files[1].size_bytes, 300);
     assert_eq!(result.files[2].size_bytes, 100);
 }
 
-#[tokio::test]
-async fn test_get_largest_files_includes_directory_path() {
+#[test]
+fn test_find_largest_files_includes_directory_path() {
     let temp_dir = TempDir::new().unwrap();
     fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
 
     let path = temp_dir.path().to_string_lossy().to_string();
-    let result = get_largest_files(path.clone()).await.unwrap();
+    let result = find_largest_files(path.clone()).unwrap();
 
     assert_eq!(result.directory_path, path);
 }
+
+#[test]
+fn test_validate_drill_down_path_inside_scanned_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let entry = temp_dir.path().join("app").join("node_modules");
+    let nested = entry.join("lodash");
+    fs::create_dir_all(&nested).unwrap();
+
+    let entry_paths = vec![entry.canonicalize().unwrap().to_string_lossy().to_string()];
+    let root = temp_dir.path().join("elsewhere");
+
+    assert!(validate_drill_down_path(&nested, &entry_paths, &root).is_ok());
+}
+
+#[test]
+fn test_validate_drill_down_path_dependency_directory_under_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("app").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+
+    let result = validate_drill_down_path(&node_modules, &[], temp_dir.path());
+    assert_eq!(result, Ok(node_modules.canonicalize().unwrap()));
+}
+
+#[test]
+fn test_validate_drill_down_path_rejects_plain_directory_under_root() {
+    let temp_dir = TempDir::new().unwrap();
+    let documents = temp_dir.path().join("Documents");
+    fs::create_dir_all(&documents).unwrap();
+
+    let result = validate_drill_down_path(&documents, &[], temp_dir.path());
+    assert_eq!(result, Err(DrillDownValidationError::OutsideScannedEntries));
+}
+
+#[test]
+fn test_validate_drill_down_path_rejects_dependency_directory_outside_root() {
+    let root = TempDir::new().unwrap();
+    let other = TempDir::new().unwrap();
+    let node_modules = other.path().join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+
+    let result = validate_drill_down_path(&node_modules, &[], root.path());
+    assert_eq!(result, Err(DrillDownValidationError::OutsideScannedEntries));
+}
+
+#[test]
+fn test_validate_drill_down_path_nonexistent() {
+    let result = validate_drill_down_path(
+        Path::new("/nonexistent/path/node_modules"),
+        &[],
+        Path::new("/"),
+    );
+    assert!(matches!(
+        result,
+        Err(DrillDownValidationError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn test_find_largest_children_sums_immediate_children() {
+    let temp_dir = TempDir::new().unwrap();
+    let react = temp_dir.path().join("react");
+    let lodash = temp_dir.path().join("lodash").join("fp");
+    fs::create_dir_all(&react).unwrap();
+    fs::create_dir_all(&lodash).unwrap();
+    fs::write(react.join("index.js"), "r".repeat(100)).unwrap();
+    fs::write(lodash.join("map.js"), "l".repeat(300)).unwrap();
+    fs::write(lodash.join("filter.js"), "l".repeat(200)).unwrap();
+    fs::write(temp_dir.path().join(".package-lock.json"), "{}").unwrap();
+
+    let result = find_largest_children(temp_dir.path().to_string_lossy().to_string(), 1).unwrap();
+
+    assert_eq!(
+        result.children,
+        vec![
+            ChildDirectoryEntry {
+                path: temp_dir.path().join("lodash").to_string_lossy().to_string(),
+                size_bytes: 500,
+                file_count: 2,
+            },
+            ChildDirectoryEntry {
+                path: react.to_string_lossy().to_string(),
+                size_bytes: 100,
+                file_count: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_find_largest_children_groups_scoped_packages_at_depth_two() {
+    let temp_dir = TempDir::new().unwrap();
+    let core = temp_dir.path().join("@babel").join("core");
+    let parser = temp_dir.path().join("@babel").join("parser");
+    fs::create_dir_all(core.join("lib")).unwrap();
+    fs::create_dir_all(&parser).unwrap();
+    fs::write(core.join("lib").join("index.js"), "c".repeat(400)).unwrap();
+    fs::write(parser.join("index.js"), "p".repeat(50)).unwrap();
+
+    let result = find_largest_children(temp_dir.path().to_string_lossy().to_string(), 2).unwrap();
+
+    let paths: Vec<&str> = result
+        .children
+        .iter()
+        .map(|child| child.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            core.to_string_lossy().as_ref(),
+            parser.to_string_lossy().as_ref()
+        ]
+    );
+    assert_eq!(result.children[0].size_bytes, 400);
+}
+
+#[test]
+fn test_find_largest_children_limits_results() {
+    let temp_dir = TempDir::new().unwrap();
+    for index in 0..config::largest_files::MAX_CHILDREN + 5 {
+        let package = temp_dir.path().join(format!("package-{index}"));
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("index.js"), "x".repeat(index + 1)).unwrap();
+    }
+
+    let result = find_largest_children(temp_dir.path().to_string_lossy().to_string(), 1).unwrap();
+
+    assert_eq!(result.children.len(), config::largest_files::MAX_CHILDREN);
+}
+
+#[test]
+fn test_find_largest_children_rejects_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("index.js");
+    fs::write(&file, "x").unwrap();
+
+    assert!(find_largest_children(file.to_string_lossy().to_string(), 1).is_err());
+}
@@ -0,0 +1,956 @@
+use crate::config;
+use crate::error::DeptoxError;
+use crate::hash::{fnv1a, FNV_OFFSET_BASIS};
+use crate::persistence::config_path;
+use crate::secure_store;
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
+use tracing::{debug, error, info, instrument, warn};
+
+pub mod trial;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum LicenseError {
+    #[error("License key cannot be empty")]
+    EmptyKey,
+    #[error("Failed to verify license: {0}")]
+    Network(String),
+    #[error("Failed to parse license response: {0}")]
+    MalformedResponse(String),
+    /// Gumroad turned the key down, with its own explanation
+    #[error("{0}")]
+    Rejected(String),
+    /// The response didn't match this product or the key that was sent
+    #[error("{0}")]
+    InvalidResponse(String),
+    #[error("This license is already activated on {0} devices")]
+    DeviceLimit(u32),
+    #[error("Your trial has ended. Activate a license to keep cleaning up.")]
+    TrialExpired,
+    #[error("{0}")]
+    Storage(String),
+}
+
+impl LicenseError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::EmptyKey => "LICENSE_EMPTY_KEY",
+            Self::Network(_) => "LICENSE_NETWORK",
+            Self::MalformedResponse(_) => "LICENSE_MALFORMED_RESPONSE",
+            Self::Rejected(_) => "LICENSE_REJECTED",
+            Self::InvalidResponse(_) => "LICENSE_INVALID_RESPONSE",
+            Self::DeviceLimit(_) => "LICENSE_DEVICE_LIMIT",
+            Self::TrialExpired => "LICENSE_TRIAL_EXPIRED",
+            Self::Storage(_) => "LICENSE_STORAGE",
+        }
+    }
+
+    /// The device limit, so the UI can phrase it in its own words
+    pub fn context(&self) -> Option<String> {
+        match self {
+            Self::DeviceLimit(max_devices) => Some(max_devices.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseInfo {
+    pub is_licensed: bool,
+    pub license_key: Option<String>,
+    pub licensed_email: Option<String>,
+    /// Indicates whether the license status is from cache due to network failure.
+    /// When true, the is_licensed field reflects the last known state, not a fresh validation.
+    #[serde(default)]
+    pub is_cached: bool,
+    /// Devices the license has been activated on, as last reported by Gumroad
+    #[serde(default)]
+    pub activation_count: Option<u32>,
+    /// Devices the license may be activated on
+    #[serde(default)]
+    pub max_activations: u32,
+    /// Days left before a license that can't be revalidated stops counting as licensed.
+    /// Only set when `is_cached` is true.
+    #[serde(default)]
+    pub grace_days_remaining: Option<u32>,
+}
+
+impl LicenseInfo {
+    fn unlicensed() -> Self {
+        Self {
+            is_licensed: false,
+            license_key: None,
+            licensed_email: None,
+            is_cached: false,
+            activation_count: None,
+            max_activations: config::gumroad::MAX_DEVICES,
+            grace_days_remaining: None,
+        }
+    }
+
+    fn from_stored(stored: &StoredLicense, is_cached: bool) -> Self {
+        Self::from_stored_at(stored, is_cached, time::now_ms())
+    }
+
+    /// A license that hasn't been validated online within the grace period is reported
+    /// as unlicensed. Licenses saved before validation times were recorded get their
+    /// deadline at the next successful validation.
+    fn from_stored_at(stored: &StoredLicense, is_cached: bool, now_ms: u64) -> Self {
+        let grace_days_remaining = stored
+            .last_validated_ms
+            .map(|validated_ms| grace_days_remaining(validated_ms, now_ms));
+
+        Self {
+            is_licensed: stored.validated && grace_days_remaining != Some(0),
+            license_key: Some(mask_license_key(&stored.license_key)),
+            licensed_email: stored.licensed_email.clone(),
+            is_cached,
+            activation_count: stored.activation_count,
+            max_activations: config::gumroad::MAX_DEVICES,
+            grace_days_remaining: grace_days_remaining.filter(|_| is_cached),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredLicense {
+    /// Kept in the secure store where there is one, so empty in the file
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    license_key: String,
+    licensed_email: Option<String>,
+    validated: bool,
+    #[serde(default)]
+    activation_count: Option<u32>,
+    /// When Gumroad last confirmed the license, which starts the offline grace period
+    #[serde(default)]
+    last_validated_ms: Option<u64>,
+}
+
+/// The key this device last activated, kept apart from the license so deactivating
+/// and activating again here doesn't use up another seat
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceActivation {
+    /// Hashed, so the key itself isn't left behind once the license is removed
+    license_key_hash: String,
+    device_id: String,
+}
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+/// Whole days left in the offline grace period, rounded up so the last partial day counts
+fn grace_days_remaining(last_validated_ms: u64, now_ms: u64) -> u32 {
+    let deadline_ms =
+        last_validated_ms + config::gumroad::OFFLINE_GRACE_DAYS * MILLISECONDS_PER_DAY;
+    deadline_ms
+        .saturating_sub(now_ms)
+        .div_ceil(MILLISECONDS_PER_DAY) as u32
+}
+
+#[derive(Debug, Deserialize)]
+struct GumroadResponse {
+    success: bool,
+    message: Option<String>,
+    /// How many times the license has been activated with `increment_uses_count`
+    #[serde(default)]
+    uses: Option<u32>,
+    purchase: Option<GumroadPurchase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GumroadPurchase {
+    email: Option<String>,
+    product_id: Option<String>,
+    /// The license key that was validated - should match what we sent
+    license_key: Option<String>,
+}
+
+/// Validates that the Gumroad API response is authentic and matches our product.
+/// Returns an error message if validation fails, None if valid.
+fn validate_gumroad_response(
+    response: &GumroadResponse,
+    expected_license_key: &str,
+) -> Option<String> {
+    if !response.success {
+        return None;
+    }
+
+    let purchase = match &response.purchase {
+        Some(purchase) => purchase,
+        None => {
+            return Some("Invalid response: missing purchase data".to_string());
+        }
+    };
+
+    match &purchase.product_id {
+        Some(product_id) if product_id == config::gumroad::PRODUCT_ID => {}
+        Some(product_id) => {
+            warn!(
+                expected = config::gumroad::PRODUCT_ID,
+                received = %product_id,
+                "Product ID mismatch in license response"
+            );
+            return Some("Invalid license: product mismatch".to_string());
+        }
+        None => {
+            warn!("Missing product_id in license response");
+            return Some("Invalid response: missing product verification".to_string());
+        }
+    }
+
+    match &purchase.license_key {
+        Some(response_key) if response_key == expected_license_key => {}
+        Some(response_key) => {
+            warn!(
+                expected = %expected_license_key,
+                received = %response_key,
+                "License key mismatch in response"
+            );
+            return Some("Invalid response: license key mismatch".to_string());
+        }
+        None => {
+            warn!("Missing license_key in license response");
+            return Some("Invalid response: missing license verification".to_string());
+        }
+    }
+
+    None
+}
+
+fn get_license_path() -> Result<PathBuf, String> {
+    config_path(config::app::LICENSE_FILENAME)
+}
+
+const LICENSE_KEY_SECRET: &str = "license_key";
+
+/// Loads the license, reading the key from the secure store. A key still in the
+/// file from before the secure store was used is moved into it.
+fn load_stored_license() -> Option<StoredLicense> {
+    let license_path = get_license_path().ok()?;
+
+    if !license_path.exists() {
+        return None;
+    }
+
+    let content = fs::read_to_string(&license_path).ok()?;
+    let mut stored: StoredLicense = serde_json::from_str(&content).ok()?;
+
+    if stored.license_key.is_empty() {
+        let secret = secure_store::load_secret(LICENSE_KEY_SECRET)
+            .map_err(|error| warn!(%error, "Failed to load license key"))
+            .ok()??;
+        stored.license_key = String::from_utf8(secret).ok()?;
+    } else if secure_store::is_available() {
+        match save_stored_license(&stored) {
+            Ok(()) => info!("Moved license key to the secure store"),
+            Err(error) => warn!(%error, "Failed to move license key to the secure store"),
+        }
+    }
+
+    Some(stored)
+}
+
+fn save_stored_license(license: &StoredLicense) -> Result<(), String> {
+    let license_path = get_license_path()?;
+
+    let stored_securely =
+        secure_store::store_secret(LICENSE_KEY_SECRET, license.license_key.as_bytes())?;
+    let file_license = StoredLicense {
+        license_key: if stored_securely {
+            String::new()
+        } else {
+            license.license_key.clone()
+        },
+        ..license.clone()
+    };
+
+    let content = serde_json::to_string_pretty(&file_license)
+        .map_err(|error| format!("Failed to serialize license: {error}"))?;
+
+    fs::write(&license_path, content)
+        .map_err(|error| format!("Failed to write license file: {error}"))?;
+
+    debug!(?license_path, "License saved");
+    Ok(())
+}
+
+fn delete_stored_license() -> Result<(), String> {
+    let license_path = get_license_path()?;
+
+    secure_store::delete_secret(LICENSE_KEY_SECRET)?;
+
+    if license_path.exists() {
+        fs::remove_file(&license_path)
+            .map_err(|error| format!("Failed to delete license file: {error}"))?;
+        info!(?license_path, "License file deleted");
+    }
+
+    Ok(())
+}
+
+fn get_device_activation_path() -> Result<PathBuf, String> {
+    config_path(config::app::DEVICE_ACTIVATION_FILENAME)
+}
+
+fn load_device_activation() -> Option<DeviceActivation> {
+    let content = fs::read_to_string(get_device_activation_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_device_activation(activation: &DeviceActivation) -> Result<(), String> {
+    let activation_path = get_device_activation_path()?;
+
+    let content = serde_json::to_string_pretty(activation)
+        .map_err(|error| format!("Failed to serialize device activation: {error}"))?;
+
+    fs::write(&activation_path, content)
+        .map_err(|error| format!("Failed to write device activation: {error}"))
+}
+
+/// Reads the hardware UUID from `ioreg -rd1 -c IOPlatformExpertDevice` output
+#[cfg(any(target_os = "macos", test))]
+fn parse_platform_uuid(ioreg_output: &str) -> Option<String> {
+    ioreg_output
+        .lines()
+        .find(|line| line.contains("\"IOPlatformUUID\""))
+        .and_then(|line| line.split('"').nth(3))
+        .filter(|uuid| !uuid.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(target_os = "macos")]
+fn hardware_identifier() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    parse_platform_uuid(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Reads the machine GUID from `reg query` output for the Cryptography key
+#[cfg(any(target_os = "windows", test))]
+fn parse_machine_guid(reg_output: &str) -> Option<String> {
+    reg_output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("MachineGuid"))
+        .and_then(|line| line.split_whitespace().nth(2))
+        .map(str::to_string)
+}
+
+#[cfg(target_os = "windows")]
+fn hardware_identifier() -> Option<String> {
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SOFTWARE\Microsoft\Cryptography",
+            "/v",
+            "MachineGuid",
+        ])
+        .output()
+        .ok()?;
+    parse_machine_guid(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "linux")]
+fn hardware_identifier() -> Option<String> {
+    fs::read_to_string("/etc/machine-id")
+        .ok()
+        .map(|machine_id| machine_id.trim().to_string())
+        .filter(|machine_id| !machine_id.is_empty())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn hardware_identifier() -> Option<String> {
+    None
+}
+
+/// Hashes the hardware identifier so the raw UUID never leaves the machine
+fn hash_device_identifier(identifier: &str) -> String {
+    let hash = fnv1a(
+        FNV_OFFSET_BASIS,
+        format!("{}:{identifier}", config::app::APP_CONFIG_DIR).as_bytes(),
+    );
+    format!("{hash:016x}")
+}
+
+fn hash_license_key(license_key: &str) -> String {
+    format!("{:016x}", fnv1a(FNV_OFFSET_BASIS, license_key.as_bytes()))
+}
+
+/// Stable identifier for this device, None where the hardware can't be identified
+fn device_id() -> Option<String> {
+    hardware_identifier().map(|identifier| hash_device_identifier(&identifier))
+}
+
+/// Whether activating should count as a new device. Reactivating the same key on the
+/// device it's already active on doesn't, so reinstalling doesn't use up seats.
+fn is_new_activation(
+    activation: Option<&DeviceActivation>,
+    license_key: &str,
+    device_id: &str,
+) -> bool {
+    !activation.is_some_and(|activation| {
+        activation.license_key_hash == hash_license_key(license_key)
+            && activation.device_id == device_id
+    })
+}
+
+/// Seat limits are enforced client-side since Gumroad only counts activations. A refused
+/// activation has already been counted, and only the seller can decrement it.
+fn exceeds_device_limit(activation_count: Option<u32>) -> bool {
+    activation_count.is_some_and(|count| count > config::gumroad::MAX_DEVICES)
+}
+
+async fn verify_license(
+    license_key: &str,
+    increment_uses_count: bool,
+    device_id: Option<&str>,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let mut form = vec![
+        ("product_id", config::gumroad::PRODUCT_ID),
+        ("license_key", license_key),
+        (
+            "increment_uses_count",
+            if increment_uses_count {
+                "true"
+            } else {
+                "false"
+            },
+        ),
+    ];
+    form.extend(device_id.map(|device_id| ("device_id", device_id)));
+
+    reqwest::Client::new()
+        .post(config::gumroad::API_URL)
+        .form(&form)
+        .send()
+        .await
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_license_info() -> Result<LicenseInfo, DeptoxError> {
+    debug!("Getting license info");
+
+    match load_stored_license() {
+        Some(stored) if stored.validated => {
+            debug!(email = ?stored.licensed_email, "Found valid stored license");
+            Ok(LicenseInfo::from_stored(&stored, false))
+        }
+        Some(_) => {
+            debug!("Found stored license but not validated");
+            Ok(LicenseInfo::unlicensed())
+        }
+        None => {
+            debug!("No stored license found");
+            Ok(LicenseInfo::unlicensed())
+        }
+    }
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn activate_license(license_key: String) -> Result<LicenseInfo, DeptoxError> {
+    info!("Attempting to activate license");
+
+    let trimmed_key = license_key.trim().to_string();
+
+    if trimmed_key.is_empty() {
+        return Err(LicenseError::EmptyKey.into());
+    }
+
+    let device_id = device_id();
+    let activation = load_device_activation();
+    // Without a device identifier every activation would look new, so none are counted
+    let increment_uses_count = device_id
+        .as_deref()
+        .is_some_and(|device_id| is_new_activation(activation.as_ref(), &trimmed_key, device_id));
+
+    let response = verify_license(&trimmed_key, increment_uses_count, device_id.as_deref())
+        .await
+        .map_err(|error| {
+            error!(%error, "Failed to connect to Gumroad API");
+            LicenseError::Network(error.to_string())
+        })?;
+
+    let gumroad_response: GumroadResponse = response.json().await.map_err(|error| {
+        error!(%error, "Failed to parse Gumroad response");
+        LicenseError::MalformedResponse(error.to_string())
+    })?;
+
+    if !gumroad_response.success {
+        let message = gumroad_response
+            .message
+            .unwrap_or_else(|| "Invalid license key".to_string());
+        warn!(%message, "License validation failed");
+        return Err(LicenseError::Rejected(message).into());
+    }
+
+    if let Some(validation_error) = validate_gumroad_response(&gumroad_response, &trimmed_key) {
+        error!(%validation_error, "Gumroad response validation failed");
+        return Err(LicenseError::InvalidResponse(validation_error).into());
+    }
+
+    let activation_count = gumroad_response.uses;
+    if increment_uses_count && exceeds_device_limit(activation_count) {
+        warn!(?activation_count, "License device limit reached");
+        return Err(LicenseError::DeviceLimit(config::gumroad::MAX_DEVICES).into());
+    }
+
+    let email = gumroad_response
+        .purchase
+        .and_then(|purchase| purchase.email);
+
+    info!(email = ?email, ?activation_count, "License validated successfully");
+
+    let stored_license = StoredLicense {
+        license_key: trimmed_key,
+        licensed_email: email,
+        validated: true,
+        activation_count,
+        last_validated_ms: Some(time::now_ms()),
+    };
+
+    save_stored_license(&stored_license).map_err(LicenseError::Storage)?;
+
+    if let Some(device_id) = device_id {
+        let activation = DeviceActivation {
+            license_key_hash: hash_license_key(&stored_license.license_key),
+            device_id,
+        };
+        if let Err(error) = save_device_activation(&activation) {
+            warn!(%error, "Failed to save device activation");
+        }
+    }
+
+    Ok(LicenseInfo::from_stored(&stored_license, false))
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn revalidate_license() -> Result<LicenseInfo, DeptoxError> {
+    info!("Revalidating stored license");
+
+    let stored = match load_stored_license() {
+        Some(license) => license,
+        None => {
+            debug!("No stored license to revalidate");
+            return Ok(LicenseInfo::unlicensed());
+        }
+    };
+
+    let response = match verify_license(&stored.license_key, false, None).await {
+        Ok(response) => response,
+        Err(error) => {
+            warn!(%error, "Network error during revalidation, returning cached state");
+            return Ok(LicenseInfo::from_stored(&stored, true));
+        }
+    };
+
+    let gumroad_response: GumroadResponse = match response.json().await {
+        Ok(response) => response,
+        Err(error) => {
+            warn!(%error, "Failed to parse revalidation response, returning cached state");
+            return Ok(LicenseInfo::from_stored(&stored, true));
+        }
+    };
+
+    if gumroad_response.success {
+        if let Some(validation_error) =
+            validate_gumroad_response(&gumroad_response, &stored.license_key)
+        {
+            error!(%validation_error, "Gumroad revalidation response validation failed");
+            return Err(LicenseError::InvalidResponse(validation_error).into());
+        }
+
+        let revalidated = StoredLicense {
+            validated: true,
+            activation_count: gumroad_response.uses.or(stored.activation_count),
+            last_validated_ms: Some(time::now_ms()),
+            ..stored
+        };
+        if let Err(error) = save_stored_license(&revalidated) {
+            warn!(%error, "Failed to save activation count");
+        }
+
+        debug!(
+            activation_count = ?revalidated.activation_count,
+            "License revalidation successful"
+        );
+        Ok(LicenseInfo::from_stored(&revalidated, false))
+    } else {
+        let message = gumroad_response
+            .message
+            .unwrap_or_else(|| "License expired or invalid".to_string());
+        warn!(%message, "License revalidation failed");
+
+        let invalid_license = StoredLicense {
+            validated: false,
+            ..stored
+        };
+        let _ = save_stored_license(&invalid_license);
+
+        Err(LicenseError::Rejected(message).into())
+    }
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn deactivate_license() -> Result<(), DeptoxError> {
+    info!("Deactivating license");
+    delete_stored_license().map_err(LicenseError::Storage)?;
+    Ok(())
+}
+
+fn mask_license_key(key: &str) -> String {
+    let char_count = key.chars().count();
+
+    if char_count <= 8 {
+        return "*".repeat(char_count);
+    }
+
+    let first: String = key.chars().take(4).collect();
+    let last: String = key.chars().skip(char_count - 4).collect();
+    format!("{first}...{last}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_license_key() {
+        assert_eq!(mask_license_key("ABCD-1234-EFGH-5678"), "ABCD...5678");
+        assert_eq!(mask_license_key("SHORT"), "*****");
+        assert_eq!(mask_license_key("12345678"), "********");
+        assert_eq!(mask_license_key("123456789"), "1234...6789");
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_success_with_valid_data() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: Some(GumroadPurchase {
+                email: Some("user@example.com".to_string()),
+                product_id: Some(config::gumroad::PRODUCT_ID.to_string()),
+                license_key: Some("TEST-LICENSE-KEY".to_string()),
+            }),
+        };
+
+        let result = validate_gumroad_response(&response, "TEST-LICENSE-KEY");
+        assert!(result.is_none(), "Valid response should pass validation");
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_failed_response_skips_validation() {
+        let response = GumroadResponse {
+            success: false,
+            message: Some("Invalid license".to_string()),
+            uses: None,
+            purchase: None,
+        };
+
+        let result = validate_gumroad_response(&response, "ANY-KEY");
+        assert!(result.is_none(), "Failed responses should skip validation");
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_missing_purchase() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: None,
+        };
+
+        let result = validate_gumroad_response(&response, "TEST-KEY");
+        assert!(result.is_some(), "Missing purchase should fail validation");
+        assert!(result.unwrap().contains("missing purchase data"));
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_wrong_product_id() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: Some(GumroadPurchase {
+                email: Some("user@example.com".to_string()),
+                product_id: Some("WRONG-PRODUCT-ID".to_string()),
+                license_key: Some("TEST-KEY".to_string()),
+            }),
+        };
+
+        let result = validate_gumroad_response(&response, "TEST-KEY");
+        assert!(result.is_some(), "Wrong product ID should fail validation");
+        assert!(result.unwrap().contains("product mismatch"));
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_missing_product_id() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: Some(GumroadPurchase {
+                email: Some("user@example.com".to_string()),
+                product_id: None,
+                license_key: Some("TEST-KEY".to_string()),
+            }),
+        };
+
+        let result = validate_gumroad_response(&response, "TEST-KEY");
+        assert!(
+            result.is_some(),
+            "Missing product ID should fail validation"
+        );
+        assert!(result.unwrap().contains("missing product verification"));
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_wrong_license_key() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: Some(GumroadPurchase {
+                email: Some("user@example.com".to_string()),
+                product_id: Some(config::gumroad::PRODUCT_ID.to_string()),
+                license_key: Some("DIFFERENT-KEY".to_string()),
+            }),
+        };
+
+        let result = validate_gumroad_response(&response, "EXPECTED-KEY");
+        assert!(result.is_some(), "Wrong license key should fail validation");
+        assert!(result.unwrap().contains("license key mismatch"));
+    }
+
+    #[test]
+    fn test_validate_gumroad_response_missing_license_key() {
+        let response = GumroadResponse {
+            success: true,
+            message: None,
+            uses: None,
+            purchase: Some(GumroadPurchase {
+                email: Some("user@example.com".to_string()),
+                product_id: Some(config::gumroad::PRODUCT_ID.to_string()),
+                license_key: None,
+            }),
+        };
+
+        let result = validate_gumroad_response(&response, "TEST-KEY");
+        assert!(
+            result.is_some(),
+            "Missing license key should fail validation"
+        );
+        assert!(result.unwrap().contains("missing license verification"));
+    }
+
+    #[test]
+    fn test_gumroad_response_reads_uses() {
+        let json = r#"{"success":true,"uses":2,"purchase":null}"#;
+        let response: GumroadResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.uses, Some(2));
+
+        let json = r#"{"success":false,"message":"That license does not exist"}"#;
+        let response: GumroadResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.uses, None);
+    }
+
+    #[test]
+    fn test_parse_platform_uuid() {
+        let output = r#"+-o MacBookPro18,3  <class IOPlatformExpertDevice>
+    {
+      "IOPlatformSerialNumber" = "C02XXXXXXXXX"
+      "IOPlatformUUID" = "4C4C4544-0042-3510-8052-B7C04F4E4D32"
+    }"#;
+
+        assert_eq!(
+            parse_platform_uuid(output).as_deref(),
+            Some("4C4C4544-0042-3510-8052-B7C04F4E4D32")
+        );
+        assert_eq!(parse_platform_uuid("no uuid here"), None);
+    }
+
+    #[test]
+    fn test_hash_device_identifier_is_stable_and_opaque() {
+        let uuid = "4C4C4544-0042-3510-8052-B7C04F4E4D32";
+        let device_id = hash_device_identifier(uuid);
+
+        assert_eq!(device_id, hash_device_identifier(uuid));
+        assert_ne!(device_id, hash_device_identifier("another-device"));
+        assert_eq!(device_id.len(), 16);
+        assert!(!device_id.contains("4C4C4544"));
+    }
+
+    fn stored_license(validated: bool) -> StoredLicense {
+        StoredLicense {
+            license_key: "TEST-KEY".to_string(),
+            licensed_email: None,
+            validated,
+            activation_count: Some(1),
+            last_validated_ms: None,
+        }
+    }
+
+    fn device_activation(license_key: &str, device_id: &str) -> DeviceActivation {
+        DeviceActivation {
+            license_key_hash: hash_license_key(license_key),
+            device_id: device_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_new_activation() {
+        let active_here = device_activation("TEST-KEY", "device-a");
+
+        assert!(is_new_activation(None, "TEST-KEY", "device-a"));
+        assert!(!is_new_activation(
+            Some(&active_here),
+            "TEST-KEY",
+            "device-a"
+        ));
+        assert!(is_new_activation(
+            Some(&active_here),
+            "OTHER-KEY",
+            "device-a"
+        ));
+        assert!(is_new_activation(
+            Some(&active_here),
+            "TEST-KEY",
+            "device-b"
+        ));
+    }
+
+    #[test]
+    fn test_device_activation_does_not_store_the_key() {
+        let json = serde_json::to_string(&device_activation("TEST-KEY", "device-a")).unwrap();
+
+        assert!(json.contains("\"deviceId\":\"device-a\""));
+        assert!(!json.contains("TEST-KEY"));
+    }
+
+    #[test]
+    fn test_parse_machine_guid() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Cryptography\r\n    MachineGuid    REG_SZ    6f1c2d3e-4a5b-4c6d-8e9f-0a1b2c3d4e5f\r\n\r\n";
+
+        assert_eq!(
+            parse_machine_guid(output).as_deref(),
+            Some("6f1c2d3e-4a5b-4c6d-8e9f-0a1b2c3d4e5f")
+        );
+        assert_eq!(parse_machine_guid("ERROR: not found"), None);
+    }
+
+    #[test]
+    fn test_exceeds_device_limit() {
+        assert!(!exceeds_device_limit(None));
+        assert!(!exceeds_device_limit(Some(config::gumroad::MAX_DEVICES)));
+        assert!(exceeds_device_limit(Some(config::gumroad::MAX_DEVICES + 1)));
+    }
+
+    #[test]
+    fn test_stored_license_without_device_fields() {
+        let json = r#"{"licenseKey":"TEST-KEY","licensedEmail":null,"validated":true}"#;
+        let stored: StoredLicense = serde_json::from_str(json).unwrap();
+
+        assert!(stored.activation_count.is_none());
+    }
+
+    #[test]
+    fn test_license_info_reports_activations() {
+        let info = LicenseInfo::from_stored(&stored_license(true), false);
+        let json = serde_json::to_string(&info).unwrap();
+
+        assert!(json.contains("\"activationCount\":1"));
+        assert!(json.contains(&format!(
+            "\"maxActivations\":{}",
+            config::gumroad::MAX_DEVICES
+        )));
+    }
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+    const DAY_MS: u64 = MILLISECONDS_PER_DAY;
+
+    #[test]
+    fn test_grace_days_remaining() {
+        let grace_days = config::gumroad::OFFLINE_GRACE_DAYS as u32;
+
+        assert_eq!(grace_days_remaining(NOW_MS, NOW_MS), grace_days);
+        assert_eq!(
+            grace_days_remaining(NOW_MS - DAY_MS / 2, NOW_MS),
+            grace_days
+        );
+        assert_eq!(
+            grace_days_remaining(NOW_MS - 3 * DAY_MS, NOW_MS),
+            grace_days - 3
+        );
+        assert_eq!(
+            grace_days_remaining(
+                NOW_MS - config::gumroad::OFFLINE_GRACE_DAYS * DAY_MS,
+                NOW_MS
+            ),
+            0
+        );
+        assert_eq!(grace_days_remaining(0, NOW_MS), 0);
+    }
+
+    #[test]
+    fn test_cached_license_reports_grace_period() {
+        let stored = StoredLicense {
+            last_validated_ms: Some(NOW_MS - 3 * DAY_MS),
+            ..stored_license(true)
+        };
+
+        let cached = LicenseInfo::from_stored_at(&stored, true, NOW_MS);
+        assert!(cached.is_licensed);
+        assert_eq!(
+            cached.grace_days_remaining,
+            Some(config::gumroad::OFFLINE_GRACE_DAYS as u32 - 3)
+        );
+
+        let fresh = LicenseInfo::from_stored_at(&stored, false, NOW_MS);
+        assert!(fresh.is_licensed);
+        assert_eq!(fresh.grace_days_remaining, None);
+    }
+
+    #[test]
+    fn test_expired_grace_period_downgrades_to_unlicensed() {
+        let stored = StoredLicense {
+            last_validated_ms: Some(NOW_MS - (config::gumroad::OFFLINE_GRACE_DAYS + 1) * DAY_MS),
+            ..stored_license(true)
+        };
+
+        let info = LicenseInfo::from_stored_at(&stored, true, NOW_MS);
+        assert!(!info.is_licensed);
+        assert_eq!(info.grace_days_remaining, Some(0));
+    }
+
+    #[test]
+    fn test_license_without_validation_time_has_no_deadline() {
+        let info = LicenseInfo::from_stored_at(&stored_license(true), true, NOW_MS);
+
+        assert!(info.is_licensed);
+        assert_eq!(info.grace_days_remaining, None);
+    }
+
+    #[test]
+    fn test_stored_license_omits_empty_key() {
+        let stored = StoredLicense {
+            license_key: String::new(),
+            ..stored_license(true)
+        };
+        let json = serde_json::to_string(&stored).unwrap();
+
+        assert!(!json.contains("licenseKey"));
+        let loaded: StoredLicense = serde_json::from_str(&json).unwrap();
+        assert!(loaded.license_key.is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+//! Free trial before a license is required. The start time is signed with a key tied to
+//! this device, so editing or copying the trial file ends the trial instead of extending it.
+//! The record is mirrored in the secure store where there is one, so deleting the file
+//! doesn't start a new trial either.
+
+use super::{device_id, load_stored_license, LicenseError, LicenseInfo, MILLISECONDS_PER_DAY};
+use crate::config;
+use crate::error::DeptoxError;
+use crate::persistence::config_path;
+use crate::secure_store;
+use crate::time;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, instrument, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrialRecord {
+    started_at_ms: u64,
+    signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialStatus {
+    pub days_remaining: u32,
+    pub expired: bool,
+    /// Licensed, or still within the trial
+    pub entitled: bool,
+}
+
+const TRIAL_SECRET: &str = "trial_record";
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn sign(started_at_ms: u64, key: &[u8]) -> String {
+    to_hex(&hmac_sha256(key, &started_at_ms.to_le_bytes()))
+}
+
+fn signing_key(device_id: &str) -> Vec<u8> {
+    let mut key = config::trial::SIGNING_SALT.to_vec();
+    key.extend(device_id.as_bytes());
+    key
+}
+
+fn get_trial_path() -> Result<PathBuf, String> {
+    config_path(config::app::TRIAL_FILENAME)
+}
+
+fn read_trial_record(path: &Path) -> Option<TrialRecord> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_trial_record(path: &Path, record: &TrialRecord) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(record).map_err(|error| error.to_string())?;
+    fs::write(path, content).map_err(|error| error.to_string())
+}
+
+fn load_mirrored_record() -> Option<TrialRecord> {
+    let secret = secure_store::load_secret(TRIAL_SECRET)
+        .map_err(|error| warn!(%error, "Failed to load mirrored trial record"))
+        .ok()??;
+    serde_json::from_slice(&secret).ok()
+}
+
+fn mirror_record(record: &TrialRecord) {
+    let mirrored = serde_json::to_vec(record)
+        .map_err(|error| error.to_string())
+        .and_then(|secret| secure_store::store_secret(TRIAL_SECRET, &secret));
+    if let Err(error) = mirrored {
+        warn!(%error, "Failed to mirror trial record");
+    }
+}
+
+/// Returns when the trial started, starting it now on first launch. A missing file is
+/// restored from the mirrored record when there is one. A record that can't be read or
+/// whose signature doesn't match counts as a trial that started at the epoch, i.e. one
+/// that has long expired.
+fn load_or_start_trial(
+    path: &Path,
+    mirrored: Option<&TrialRecord>,
+    key: &[u8],
+    now_ms: u64,
+) -> u64 {
+    let record = if path.exists() {
+        read_trial_record(path)
+    } else if let Some(mirrored) = mirrored {
+        info!("Trial file is missing, restoring it from the mirrored record");
+        if let Err(error) = write_trial_record(path, mirrored) {
+            warn!(%error, "Failed to restore trial file");
+        }
+        Some(mirrored.clone())
+    } else {
+        let record = TrialRecord {
+            started_at_ms: now_ms,
+            signature: sign(now_ms, key),
+        };
+        match write_trial_record(path, &record) {
+            Ok(()) => info!("Started trial"),
+            Err(error) => warn!(%error, "Failed to save trial start"),
+        }
+        return now_ms;
+    };
+
+    match record {
+        Some(record) if record.signature == sign(record.started_at_ms, key) => record.started_at_ms,
+        _ => {
+            warn!("Trial record is invalid, treating the trial as expired");
+            0
+        }
+    }
+}
+
+/// Days left are rounded up, and capped so winding the clock back doesn't add any
+fn trial_status(started_at_ms: u64, now_ms: u64, licensed: bool) -> TrialStatus {
+    let trial_ms = config::trial::TRIAL_DAYS * MILLISECONDS_PER_DAY;
+    let elapsed_ms = now_ms.saturating_sub(started_at_ms);
+    let days_remaining = trial_ms
+        .saturating_sub(elapsed_ms)
+        .div_ceil(MILLISECONDS_PER_DAY) as u32;
+    let expired = days_remaining == 0;
+
+    TrialStatus {
+        days_remaining,
+        expired,
+        entitled: licensed || !expired,
+    }
+}
+
+fn current_trial_status() -> Result<TrialStatus, String> {
+    let licensed = load_stored_license()
+        .is_some_and(|stored| LicenseInfo::from_stored(&stored, false).is_licensed);
+    let now_ms = time::now_ms();
+
+    // Signing with a missing identifier would fail every check, so a device that
+    // can't be identified right now stays entitled until the next check
+    let Some(device_id) = device_id() else {
+        warn!("Device identifier unavailable, skipping the trial check");
+        return Ok(TrialStatus {
+            entitled: true,
+            ..trial_status(now_ms, now_ms, licensed)
+        });
+    };
+
+    let trial_path = get_trial_path()?;
+    let mirrored = load_mirrored_record();
+    let started_at_ms = load_or_start_trial(
+        &trial_path,
+        mirrored.as_ref(),
+        &signing_key(&device_id),
+        now_ms,
+    );
+
+    if mirrored.is_none() && secure_store::is_available() {
+        if let Some(record) = read_trial_record(&trial_path) {
+            mirror_record(&record);
+        }
+    }
+
+    Ok(trial_status(started_at_ms, now_ms, licensed))
+}
+
+/// Fails once the trial has expired without a license, for commands that need one
+pub fn ensure_entitled() -> Result<(), DeptoxError> {
+    if current_trial_status()
+        .map_err(LicenseError::Storage)?
+        .entitled
+    {
+        return Ok(());
+    }
+
+    warn!("Trial expired without a license");
+    Err(LicenseError::TrialExpired.into())
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_trial_status() -> Result<TrialStatus, DeptoxError> {
+    let status = current_trial_status().map_err(LicenseError::Storage)?;
+    debug!(
+        days_remaining = status.days_remaining,
+        entitled = status.entitled,
+        "Loaded trial status"
+    );
+    Ok(status)
+}
+
+#[cfg(test)]
+#[path = "trial.test.rs"]
+mod tests;
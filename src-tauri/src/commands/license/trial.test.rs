@@ -0,0 +1,139 @@
+use super::*;
+use tempfile::TempDir;
+
+const NOW_MS: u64 = 1_700_000_000_000;
+const KEY: &[u8] = b"test-key";
+
+#[test]
+fn test_hmac_sha256_matches_rfc_4231() {
+    assert_eq!(
+        to_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn test_hmac_sha256_hashes_long_keys() {
+    // RFC 4231 test case 6
+    assert_eq!(
+        to_hex(&hmac_sha256(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        )),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
+
+#[test]
+fn test_first_launch_starts_trial() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+
+    assert_eq!(load_or_start_trial(&path, None, KEY, NOW_MS), NOW_MS);
+    assert!(path.exists());
+    assert_eq!(
+        load_or_start_trial(&path, None, KEY, NOW_MS + MILLISECONDS_PER_DAY),
+        NOW_MS
+    );
+}
+
+#[test]
+fn test_edited_trial_start_expires_trial() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+    load_or_start_trial(&path, None, KEY, NOW_MS);
+
+    let content = fs::read_to_string(&path).unwrap();
+    fs::write(
+        &path,
+        content.replace(&NOW_MS.to_string(), &(NOW_MS + 1).to_string()),
+    )
+    .unwrap();
+
+    assert_eq!(load_or_start_trial(&path, None, KEY, NOW_MS), 0);
+}
+
+#[test]
+fn test_trial_from_another_device_expires_trial() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+    load_or_start_trial(&path, None, KEY, NOW_MS);
+
+    assert_eq!(load_or_start_trial(&path, None, b"other-device", NOW_MS), 0);
+}
+
+#[test]
+fn test_deleted_trial_file_is_restored_from_mirror() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+    load_or_start_trial(&path, None, KEY, NOW_MS);
+    let mirrored = read_trial_record(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let later_ms = NOW_MS + MILLISECONDS_PER_DAY;
+    assert_eq!(
+        load_or_start_trial(&path, Some(&mirrored), KEY, later_ms),
+        NOW_MS
+    );
+    assert_eq!(read_trial_record(&path), Some(mirrored));
+}
+
+#[test]
+fn test_tampered_mirror_expires_trial() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+    let mirrored = TrialRecord {
+        started_at_ms: NOW_MS,
+        signature: sign(NOW_MS - 1, KEY),
+    };
+
+    assert_eq!(load_or_start_trial(&path, Some(&mirrored), KEY, NOW_MS), 0);
+}
+
+#[test]
+fn test_corrupt_trial_record_expires_trial() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("trial.json");
+    fs::write(&path, "not json").unwrap();
+
+    assert_eq!(load_or_start_trial(&path, None, KEY, NOW_MS), 0);
+}
+
+#[test]
+fn test_trial_status_counts_down() {
+    let trial_days = config::trial::TRIAL_DAYS as u32;
+
+    assert_eq!(
+        trial_status(NOW_MS, NOW_MS, false),
+        TrialStatus {
+            days_remaining: trial_days,
+            expired: false,
+            entitled: true,
+        }
+    );
+    assert_eq!(
+        trial_status(NOW_MS, NOW_MS + 3 * MILLISECONDS_PER_DAY, false).days_remaining,
+        trial_days - 3
+    );
+}
+
+#[test]
+fn test_trial_status_expired() {
+    let ended_ms = NOW_MS + config::trial::TRIAL_DAYS * MILLISECONDS_PER_DAY;
+
+    assert_eq!(
+        trial_status(NOW_MS, ended_ms, false),
+        TrialStatus {
+            days_remaining: 0,
+            expired: true,
+            entitled: false,
+        }
+    );
+    assert!(trial_status(NOW_MS, ended_ms, true).entitled);
+}
+
+#[test]
+fn test_trial_status_ignores_clock_rollback() {
+    let status = trial_status(NOW_MS, NOW_MS - 30 * MILLISECONDS_PER_DAY, false);
+    assert_eq!(status.days_remaining, config::trial::TRIAL_DAYS as u32);
+}
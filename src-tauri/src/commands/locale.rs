@@ -1,19 +1,118 @@
+use crate::i18n;
+use crate::scanner::{CategoryLabel, DependencyCategory};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MeasurementSystem {
+    Metric,
+    Us,
+    /// Metric, but with miles and pints left over
+    Uk,
+}
+
+/// The user's locale, for formatting sizes, numbers and dates to match the system
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemLocale {
+    /// BCP 47 tag such as `en-GB`, ready for `Intl` formatters
+    pub identifier: String,
+    pub language: String,
+    pub region: Option<String>,
+    pub measurement_system: MeasurementSystem,
+}
+
+impl SystemLocale {
+    /// Builds the locale from a POSIX (`en_GB.UTF-8`) or Apple (`zh_Hans_CN`) identifier.
+    /// `uses_metric` comes from the system where it says so, otherwise from the region.
+    fn from_identifier(identifier: &str, uses_metric: Option<bool>) -> Self {
+        let base = identifier
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .trim();
+        let mut parts = base.split(['_', '-']).filter(|part| !part.is_empty());
+
+        let language = parts
+            .next()
+            .map(str::to_lowercase)
+            .filter(|language| language.chars().all(|c| c.is_ascii_alphabetic()))
+            .filter(|language| !matches!(language.as_str(), "c" | "posix"))
+            .unwrap_or_else(|| "en".to_string());
+        let region = parts
+            .find(|part| {
+                (part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (part.len() == 3 && part.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(str::to_uppercase);
+
+        let uses_metric =
+            uses_metric.unwrap_or_else(|| !matches!(region.as_deref(), Some("US" | "LR" | "MM")));
+        let measurement_system = match (uses_metric, region.as_deref()) {
+            (false, _) => MeasurementSystem::Us,
+            (true, Some("GB")) => MeasurementSystem::Uk,
+            (true, _) => MeasurementSystem::Metric,
+        };
+
+        let identifier = match &region {
+            Some(region) => format!("{language}-{region}"),
+            None => language.clone(),
+        };
+
+        Self {
+            identifier,
+            language,
+            region,
+            measurement_system,
+        }
+    }
+}
+
+/// GUI apps on macOS start without `LANG`, so the locale comes from the system preferences
+#[cfg(target_os = "macos")]
+pub(crate) fn detect_locale() -> SystemLocale {
+    use objc2_foundation::NSLocale;
+
+    let locale = NSLocale::currentLocale();
+    SystemLocale::from_identifier(
+        &locale.localeIdentifier().to_string(),
+        Some(locale.usesMetricSystem()),
+    )
+}
+
+/// The first locale variable set, in the order POSIX gives them precedence
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn detect_locale() -> SystemLocale {
+    let identifier = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_else(|| "en_US.UTF-8".to_string());
+
+    SystemLocale::from_identifier(&identifier, None)
+}
+
 #[tauri::command]
 #[instrument]
-pub fn get_system_locale() -> Result<String, String> {
+pub fn get_system_locale() -> Result<SystemLocale, String> {
     info!("Getting system locale");
 
-    let locale = std::env::var("LANG")
-        .or_else(|_| std::env::var("LC_ALL"))
-        .or_else(|_| std::env::var("LC_MESSAGES"))
-        .unwrap_or_else(|_| "en_US.UTF-8".to_string());
+    let locale = detect_locale();
 
-    let language_code = locale.split('_').next().unwrap_or("en").to_lowercase();
+    info!(locale = %locale.identifier, "System locale detected");
+    Ok(locale)
+}
 
-    info!(locale = %language_code, "System locale detected");
-    Ok(language_code)
+/// Category labels in the system language, with the locale keys the UI translates them under
+#[tauri::command]
+pub fn get_category_labels() -> Vec<CategoryLabel> {
+    let language = i18n::current_language();
+    DependencyCategory::all()
+        .iter()
+        .map(|category| category.localized_label(language))
+        .collect()
 }
 
 #[cfg(test)]
@@ -25,9 +124,66 @@ mod tests {
         let result = get_system_locale();
         assert!(result.is_ok());
         let locale = result.unwrap();
-        // Should return a non-empty locale string
-        assert!(!locale.is_empty());
         // Should be at least 2 characters (language code)
-        assert!(locale.len() >= 2);
+        assert!(locale.language.len() >= 2);
+        assert!(locale.identifier.starts_with(&locale.language));
+    }
+
+    #[test]
+    fn test_locale_from_posix_identifier() {
+        let locale = SystemLocale::from_identifier("en_GB.UTF-8", None);
+
+        assert_eq!(locale.identifier, "en-GB");
+        assert_eq!(locale.language, "en");
+        assert_eq!(locale.region.as_deref(), Some("GB"));
+        assert_eq!(locale.measurement_system, MeasurementSystem::Uk);
+    }
+
+    #[test]
+    fn test_locale_from_apple_identifier_with_script() {
+        let locale = SystemLocale::from_identifier("zh_Hans_CN", Some(true));
+
+        assert_eq!(locale.identifier, "zh-CN");
+        assert_eq!(locale.language, "zh");
+        assert_eq!(locale.measurement_system, MeasurementSystem::Metric);
+    }
+
+    #[test]
+    fn test_locale_measurement_system() {
+        assert_eq!(
+            SystemLocale::from_identifier("en_US", None).measurement_system,
+            MeasurementSystem::Us
+        );
+        assert_eq!(
+            SystemLocale::from_identifier("de_DE@euro", None).measurement_system,
+            MeasurementSystem::Metric
+        );
+        // The system setting wins over the region
+        assert_eq!(
+            SystemLocale::from_identifier("en_US", Some(true)).measurement_system,
+            MeasurementSystem::Metric
+        );
+    }
+
+    #[test]
+    fn test_locale_falls_back_to_english() {
+        let posix = SystemLocale::from_identifier("C", None);
+        assert_eq!(posix.identifier, "en");
+        assert_eq!(posix.region, None);
+
+        assert_eq!(SystemLocale::from_identifier("", None).language, "en");
+        assert_eq!(
+            SystemLocale::from_identifier("es_419", None).identifier,
+            "es-419"
+        );
+    }
+
+    #[test]
+    fn test_get_category_labels_covers_every_category() {
+        let labels = get_category_labels();
+        assert_eq!(labels.len(), DependencyCategory::all().len());
+        assert!(labels
+            .iter()
+            .all(|label| label.key.starts_with("categories.")));
     }
 }
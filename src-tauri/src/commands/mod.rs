@@ -1,8 +1,18 @@
 pub mod autostart;
+pub mod caches;
+pub mod clean_stale;
 pub mod delete;
+pub mod deletion_journal;
+pub mod ecosystems;
 pub mod filesystem;
+pub mod ignored;
 pub mod largest_files;
 pub mod license;
 pub mod locale;
+pub mod onboarding;
+pub mod reinstall;
 pub mod scan;
+pub mod scan_cache;
+pub mod schemas;
 pub mod settings;
+pub mod stats;
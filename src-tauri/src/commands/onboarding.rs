@@ -0,0 +1,24 @@
+use super::settings::update_settings;
+use crate::scanner::DependencyCategory;
+use std::collections::HashSet;
+use tracing::{info, instrument};
+
+/// Saves the categories chosen during onboarding and marks it as done.
+/// An empty selection keeps the current categories, which is how onboarding is skipped.
+#[tauri::command]
+#[instrument(skip_all, fields(categories = enabled_categories.len()))]
+pub async fn complete_onboarding(
+    enabled_categories: HashSet<DependencyCategory>,
+) -> Result<(), String> {
+    let mut changes = serde_json::Map::new();
+    if !enabled_categories.is_empty() {
+        let categories =
+            serde_json::to_value(enabled_categories).map_err(|error| error.to_string())?;
+        changes.insert("enabledCategories".to_string(), categories);
+    }
+    changes.insert("onboardingCompleted".to_string(), true.into());
+
+    update_settings(changes).map_err(|error| error.to_string())?;
+    info!("Onboarding completed");
+    Ok(())
+}
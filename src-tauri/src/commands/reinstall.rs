@@ -0,0 +1,164 @@
+use crate::scanner::DependencyCategory;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReinstallCommand {
+    /// Shell command that restores the dependency directory
+    pub command: String,
+    /// Project directory the command should be run from
+    pub working_directory: String,
+    /// Lockfile or manifest the suggestion was derived from
+    pub source_file: String,
+}
+
+/// Project files checked for each category, most specific first.
+/// Lockfiles come before manifests so the suggestion reproduces the exact dependency tree.
+/// A leading `*` matches any file with that suffix, for project files named after the project.
+fn reinstall_candidates(category: DependencyCategory) -> &'static [(&'static str, &'static str)] {
+    match category {
+        DependencyCategory::NodeModules => &[
+            ("pnpm-lock.yaml", "pnpm install"),
+            ("yarn.lock", "yarn install"),
+            ("bun.lock", "bun install"),
+            ("bun.lockb", "bun install"),
+            ("package-lock.json", "npm ci"),
+            ("npm-shrinkwrap.json", "npm ci"),
+            ("package.json", "npm install"),
+        ],
+        DependencyCategory::Composer => &[
+            ("composer.lock", "composer install"),
+            ("composer.json", "composer install"),
+        ],
+        DependencyCategory::Bundler => &[
+            ("Gemfile.lock", "bundle install"),
+            ("Gemfile", "bundle install"),
+        ],
+        DependencyCategory::Pods => &[("Podfile.lock", "pod install"), ("Podfile", "pod install")],
+        DependencyCategory::PythonVenv => &[
+            ("uv.lock", "uv sync"),
+            ("poetry.lock", "poetry install"),
+            ("Pipfile.lock", "pipenv install"),
+            ("requirements.txt", "pip install -r requirements.txt"),
+        ],
+        DependencyCategory::CondaEnv => &[
+            ("environment.yml", "conda env create -f environment.yml"),
+            ("environment.yaml", "conda env create -f environment.yaml"),
+        ],
+        DependencyCategory::ElixirDeps => {
+            &[("mix.lock", "mix deps.get"), ("mix.exs", "mix deps.get")]
+        }
+        DependencyCategory::DartTool => &[
+            ("pubspec.lock", "dart pub get"),
+            ("pubspec.yaml", "dart pub get"),
+        ],
+        DependencyCategory::GoMod => {
+            &[("go.sum", "go mod download"), ("go.mod", "go mod download")]
+        }
+        DependencyCategory::DotNet => &[
+            ("packages.lock.json", "dotnet restore --locked-mode"),
+            ("*.sln", "dotnet build"),
+            ("*.csproj", "dotnet build"),
+            ("*.fsproj", "dotnet build"),
+            ("*.vbproj", "dotnet build"),
+        ],
+        DependencyCategory::BuildArtifacts => &[
+            ("pnpm-lock.yaml", "pnpm run build"),
+            ("yarn.lock", "yarn build"),
+            ("bun.lock", "bun run build"),
+            ("package.json", "npm run build"),
+        ],
+    }
+}
+
+/// Resolves a candidate to the matching file name in the project directory
+fn find_project_file(project_directory: &Path, pattern: &str) -> Option<String> {
+    let Some(suffix) = pattern.strip_prefix('*') else {
+        return project_directory
+            .join(pattern)
+            .is_file()
+            .then(|| pattern.to_string());
+    };
+
+    let mut matches: Vec<String> = std::fs::read_dir(project_directory)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|file_name| file_name.ends_with(suffix))
+        .collect();
+    matches.sort();
+    matches.into_iter().next()
+}
+
+/// Picks the reinstall command for a project by looking for known lockfiles and manifests
+fn suggest_reinstall_command(
+    project_directory: &Path,
+    category: DependencyCategory,
+) -> Option<ReinstallCommand> {
+    reinstall_candidates(category)
+        .iter()
+        .find_map(|(pattern, command)| {
+            find_project_file(project_directory, pattern).map(|file_name| (file_name, command))
+        })
+        .map(|(file_name, command)| ReinstallCommand {
+            command: command.to_string(),
+            working_directory: project_directory.to_string_lossy().to_string(),
+            source_file: file_name,
+        })
+}
+
+/// Lists the categories a dependency directory could belong to, best guess first.
+/// The directory is usually already deleted, so content-based detection is only a hint
+/// and every category sharing the directory name stays in the running.
+fn candidate_categories(dependency_path: &Path) -> Vec<DependencyCategory> {
+    let Some(directory_name) = dependency_path.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+
+    let mut categories: Vec<DependencyCategory> = DependencyCategory::from_path(dependency_path)
+        .into_iter()
+        .collect();
+
+    for category in DependencyCategory::all() {
+        if category.directory_names().contains(&directory_name) && !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+
+    categories
+}
+
+#[tauri::command]
+#[instrument(skip_all, fields(path = %path))]
+pub fn get_reinstall_command(path: String) -> Result<Option<ReinstallCommand>, String> {
+    let dependency_path = Path::new(&path);
+
+    let categories = candidate_categories(dependency_path);
+    if categories.is_empty() {
+        warn!("Path is not a recognized dependency directory");
+        return Err("Path is not a recognized dependency directory".to_string());
+    }
+
+    let project_directory = dependency_path
+        .parent()
+        .ok_or_else(|| "Dependency directory has no parent".to_string())?;
+
+    let suggestion = categories
+        .into_iter()
+        .find_map(|category| suggest_reinstall_command(project_directory, category));
+
+    debug!(
+        command = suggestion
+            .as_ref()
+            .map(|suggestion| suggestion.command.as_str()),
+        "Resolved reinstall command"
+    );
+    Ok(suggestion)
+}
+
+#[cfg(test)]
+#[path = "reinstall.test.rs"]
+mod tests;
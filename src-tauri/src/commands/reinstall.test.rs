@@ -0,0 +1,131 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_project(files: &[&str]) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    for file_name in files {
+        fs::write(temp_dir.path().join(file_name), "").unwrap();
+    }
+    temp_dir
+}
+
+#[test]
+fn test_suggest_reinstall_command_prefers_lockfile() {
+    let project = create_project(&["package.json", "package-lock.json"]);
+
+    let suggestion =
+        suggest_reinstall_command(project.path(), DependencyCategory::NodeModules).unwrap();
+
+    assert_eq!(suggestion.command, "npm ci");
+    assert_eq!(suggestion.source_file, "package-lock.json");
+    assert_eq!(
+        suggestion.working_directory,
+        project.path().to_string_lossy()
+    );
+}
+
+#[test]
+fn test_suggest_reinstall_command_node_package_managers() {
+    let pnpm = create_project(&["package.json", "pnpm-lock.yaml"]);
+    let yarn = create_project(&["package.json", "yarn.lock"]);
+    let manifest_only = create_project(&["package.json"]);
+
+    let command_for = |project: &TempDir| {
+        suggest_reinstall_command(project.path(), DependencyCategory::NodeModules)
+            .map(|suggestion| suggestion.command)
+    };
+
+    assert_eq!(command_for(&pnpm).as_deref(), Some("pnpm install"));
+    assert_eq!(command_for(&yarn).as_deref(), Some("yarn install"));
+    assert_eq!(command_for(&manifest_only).as_deref(), Some("npm install"));
+}
+
+#[test]
+fn test_suggest_reinstall_command_other_ecosystems() {
+    let cases = [
+        (
+            "Gemfile.lock",
+            DependencyCategory::Bundler,
+            "bundle install",
+        ),
+        (
+            "composer.lock",
+            DependencyCategory::Composer,
+            "composer install",
+        ),
+        ("Podfile.lock", DependencyCategory::Pods, "pod install"),
+        (
+            "poetry.lock",
+            DependencyCategory::PythonVenv,
+            "poetry install",
+        ),
+        ("mix.lock", DependencyCategory::ElixirDeps, "mix deps.get"),
+        ("pubspec.lock", DependencyCategory::DartTool, "dart pub get"),
+        ("go.sum", DependencyCategory::GoMod, "go mod download"),
+    ];
+
+    for (lockfile, category, expected) in cases {
+        let project = create_project(&[lockfile]);
+        let suggestion = suggest_reinstall_command(project.path(), category).unwrap();
+        assert_eq!(suggestion.command, expected, "lockfile {lockfile}");
+    }
+}
+
+#[test]
+fn test_suggest_reinstall_command_dotnet_project_file() {
+    let project = create_project(&["Api.csproj", "Program.cs"]);
+
+    let suggestion = suggest_reinstall_command(project.path(), DependencyCategory::DotNet).unwrap();
+
+    assert_eq!(suggestion.command, "dotnet build");
+    assert_eq!(suggestion.source_file, "Api.csproj");
+}
+
+#[test]
+fn test_suggest_reinstall_command_dotnet_prefers_lockfile() {
+    let project = create_project(&["Api.csproj", "packages.lock.json"]);
+
+    let suggestion = suggest_reinstall_command(project.path(), DependencyCategory::DotNet).unwrap();
+
+    assert_eq!(suggestion.command, "dotnet restore --locked-mode");
+}
+
+#[test]
+fn test_suggest_reinstall_command_without_project_files() {
+    let project = create_project(&[]);
+    assert!(suggest_reinstall_command(project.path(), DependencyCategory::NodeModules).is_none());
+}
+
+#[test]
+fn test_get_reinstall_command_after_vendor_deleted() {
+    let project = create_project(&["Gemfile", "Gemfile.lock"]);
+    let vendor = project.path().join("vendor");
+
+    let suggestion = get_reinstall_command(vendor.to_string_lossy().to_string())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(suggestion.command, "bundle install");
+}
+
+#[test]
+fn test_get_reinstall_command_unknown_directory() {
+    let project = create_project(&["package.json"]);
+    let unknown = project.path().join("build");
+
+    assert!(get_reinstall_command(unknown.to_string_lossy().to_string()).is_err());
+}
+
+#[test]
+fn test_reinstall_command_serialization() {
+    let suggestion = ReinstallCommand {
+        command: "npm ci".to_string(),
+        working_directory: "/project".to_string(),
+        source_file: "package-lock.json".to_string(),
+    };
+
+    let json = serde_json::to_string(&suggestion).unwrap();
+    assert!(json.contains("\"workingDirectory\":\"/project\""));
+    assert!(json.contains("\"sourceFile\":\"package-lock.json\""));
+}
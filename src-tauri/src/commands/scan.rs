@@ -1,23 +1,36 @@
-use crate::commands::settings::get_settings_sync;
+use crate::commands::ignored::ignored_path_set;
+use crate::commands::scan_cache::store_scan_result;
+use crate::commands::settings::{
+    exclude_pattern_warnings, get_settings_sync, AppSettings, SettingsWarning,
+};
 use crate::config;
+use crate::error::DeptoxError;
+use crate::fixture::{self, simulate_entries, SizeDistribution};
+use crate::scanner::size_index::{directory_fingerprint, record_size, save_size_index};
 use crate::scanner::{
-    calculate_dir_size_full, expand_tilde, get_all_dependency_directory_names,
-    get_target_directory_names, is_inside_dependency_directory, parse_exclude_patterns,
-    should_exclude_path, should_skip_directory, DependencyCategory, DirectoryEntry,
-    DiscoveredDirectory, ScanResult, ScanStats, SizeCalculatorPool,
+    calculate_dir_size_limited, compute_age_ms, expand_tilde, get_all_dependency_directory_names,
+    get_build_artifact_directory_names, get_target_directory_names, parse_exclude_patterns,
+    parse_include_patterns, report_scan_stats, resolve_project_info, run_scan, score_entry,
+    AgeStrategy, DependencyCategory, DirectoryEntry, DiscoveredDirectory, Safety, ScanConfig,
+    ScanResult, ScanSink, ScanStats, ScanThrottle, SkippedVolumes,
 };
+use crate::sounds;
+use crate::time;
+use crate::tray;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::{Arc, LazyLock, Mutex};
-#[cfg(test)]
-use std::time::UNIX_EPOCH;
 use std::time::{Duration, Instant};
 use tauri::Emitter;
 use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, info, instrument, warn};
 
 #[cfg(test)]
 fn get_last_modified_ms(path: &Path) -> u64 {
+    use std::time::UNIX_EPOCH;
+
     path.metadata()
         .and_then(|metadata| metadata.modified())
         .map(|modified| {
@@ -29,312 +42,122 @@ fn get_last_modified_ms(path: &Path) -> u64 {
         .unwrap_or(0)
 }
 
-fn determine_category(
-    directory_name: &str,
-    path: &Path,
-    enabled_categories: &std::collections::HashSet<DependencyCategory>,
-) -> Option<DependencyCategory> {
-    match DependencyCategory::from_directory_name(directory_name) {
-        Some(matched_category) => Some(matched_category),
-        None if directory_name == "vendor" => {
-            let vendor_category = DependencyCategory::from_vendor_directory(path)?;
-            if enabled_categories.contains(&vendor_category) {
-                Some(vendor_category)
-            } else {
-                None
-            }
-        }
-        None if directory_name == "deps" => {
-            let deps_category = DependencyCategory::from_deps_directory(path)?;
-            if enabled_categories.contains(&deps_category) {
-                Some(deps_category)
-            } else {
-                None
-            }
-        }
-        None if directory_name == "pkg" => {
-            let pkg_category = DependencyCategory::from_pkg_directory(path)?;
-            if enabled_categories.contains(&pkg_category) {
-                Some(pkg_category)
-            } else {
-                None
-            }
-        }
-        None => None,
+/// The one place settings become a scan, shared by interactive and background scans so
+/// the tray total always agrees with the window. Built here rather than in the scanner,
+/// which doesn't depend on settings or the ignore list.
+pub fn build_scan_config(settings: &AppSettings, throttle: ScanThrottle) -> ScanConfig {
+    let root_directory =
+        fixture::active_root().unwrap_or_else(|| expand_tilde(&settings.root_directory));
+
+    ScanConfig {
+        skipped_volumes: SkippedVolumes::new(
+            &settings.skipped_volume_kinds,
+            Path::new(&root_directory),
+        ),
+        root_directory,
+        enabled_categories: settings.enabled_categories.clone(),
+        target_dir_names: target_directory_names(settings),
+        all_dependency_dirs: get_all_dependency_directory_names(),
+        exclude_patterns: parse_exclude_patterns(&settings.exclude_paths),
+        include_patterns: parse_include_patterns(&settings.include_paths),
+        ignored_paths: ignored_path_set(),
+        follow_symlinks: settings.follow_symlinks,
+        scan_package_manager_caches: settings.scan_package_manager_caches,
+        max_file_count: file_count_limit(settings),
+        incremental_sizing: settings.incremental_sizing,
+        min_size_bytes: settings.min_size_bytes,
+        deadline: scan_deadline(settings),
+        throttle,
     }
 }
 
-fn maybe_emit_scan_stats(
-    app: &tauri::AppHandle,
-    last_emit_time: &mut Instant,
-    running_total_size: u64,
-    entry_count: usize,
-    current_path: &str,
-) {
-    if last_emit_time.elapsed() >= config::scanner::EMIT_THROTTLE {
-        let _ = app.emit(
-            "scan_stats",
-            ScanStats {
-                total_size: running_total_size,
-                directory_count: entry_count,
-                current_path: Some(current_path.to_string()),
-            },
-        );
-        *last_emit_time = Instant::now();
+/// Directory names the walk matches, dropping build artifacts that aren't configured.
+/// Build artifact names are unique to their category, so removing them is safe.
+pub fn target_directory_names(settings: &AppSettings) -> std::collections::HashSet<&'static str> {
+    let mut names = get_target_directory_names(&settings.enabled_categories);
+    let configured = get_build_artifact_directory_names(&settings.build_artifact_directories);
+    for name in DependencyCategory::BuildArtifacts.directory_names() {
+        if !configured.contains(name) {
+            names.remove(name);
+        }
     }
+    names
 }
 
-struct ScanConfig {
-    root_directory: String,
-    enabled_categories: std::collections::HashSet<DependencyCategory>,
-    target_dir_names: std::collections::HashSet<&'static str>,
-    all_dependency_dirs: std::collections::HashSet<&'static str>,
-    exclude_patterns: Vec<String>,
+/// Resolves the overall scan deadline, where zero disables it
+fn scan_deadline(settings: &AppSettings) -> Option<Duration> {
+    (settings.scan_timeout_minutes > 0)
+        .then(|| Duration::from_secs(u64::from(settings.scan_timeout_minutes) * 60))
 }
 
-struct DiscoveryProgress {
-    discovered: Vec<DiscoveredDirectory>,
-    total_skipped: usize,
-    last_emit_time: Instant,
+/// Resolves the per-directory file-count ceiling, where zero disables the limit
+pub fn file_count_limit(settings: &AppSettings) -> Option<usize> {
+    (settings.max_files_per_directory > 0).then_some(settings.max_files_per_directory)
 }
 
-impl DiscoveryProgress {
-    fn new() -> Self {
-        Self {
-            discovered: Vec::new(),
-            total_skipped: 0,
-            last_emit_time: Instant::now()
-                .checked_sub(Duration::from_millis(100))
-                .unwrap_or_else(Instant::now),
-        }
-    }
+/// Payload of every scan event, tagged with the scan it came from so the UI can drop
+/// events from a scan it has already replaced
+#[derive(Debug, Clone, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanEvent<T> {
+    pub scan_id: String,
+    #[serde(flatten)]
+    pub payload: T,
 }
 
-fn discover_dependency_directory(
-    directory_entry: &jwalk::DirEntry<((), ())>,
-    config: &ScanConfig,
-    progress: &mut DiscoveryProgress,
-    app: &tauri::AppHandle,
-) -> Option<DiscoveredDirectory> {
-    if !directory_entry.file_type().is_dir() {
-        return None;
-    }
-
-    let path = directory_entry.path();
-    let path_string = path.to_string_lossy().to_string();
-
-    maybe_emit_scan_stats(
-        app,
-        &mut progress.last_emit_time,
-        0,
-        progress.discovered.len(),
-        &path_string,
-    );
-
-    let directory_name = directory_entry.file_name().to_str().unwrap_or("");
-
-    if !config.target_dir_names.contains(directory_name) {
-        return None;
-    }
-
-    let category = determine_category(directory_name, &path, &config.enabled_categories)?;
-
-    if is_inside_dependency_directory(&path_string, directory_name, &config.all_dependency_dirs) {
-        return None;
-    }
-
-    if should_exclude_path(&path_string, &config.exclude_patterns) {
-        debug!(path = %path_string, "Skipping excluded path");
-        return None;
-    }
-
-    debug!(path = %path_string, category = ?category, "Discovered dependency directory");
-
-    Some(DiscoveredDirectory {
-        path: path_string,
-        category,
-    })
+/// Payload of `scan_started` and `scan_cancelled`, which carry nothing but the scan
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanId {
+    pub scan_id: String,
 }
 
-fn execute_directory_walk(
-    config: &ScanConfig,
-    token: &CancellationToken,
+fn emit_scan_event<T: Serialize + Clone>(
     app: &tauri::AppHandle,
-) -> Option<ScanResult> {
-    let start = Instant::now();
-    let mut progress = DiscoveryProgress::new();
-
-    let num_threads = num_cpus::get().min(config::scanner::SIZE_POOL_THREADS);
-    debug!(
-        cpus = num_cpus::get(),
-        threads = num_threads,
-        "Starting discovery phase"
+    event: &str,
+    scan_id: &str,
+    payload: T,
+) {
+    let _ = app.emit(
+        event,
+        ScanEvent {
+            scan_id: scan_id.to_string(),
+            payload,
+        },
     );
+}
 
-    for entry in jwalk::WalkDir::new(&config.root_directory)
-        .max_depth(config::scanner::MAX_SCAN_DEPTH)
-        .skip_hidden(false)
-        .follow_links(false)
-        .parallelism(jwalk::Parallelism::RayonDefaultPool {
-            busy_timeout: config::scanner::JWALK_BUSY_TIMEOUT,
-        })
-        .process_read_dir(|_, _, _, children| {
-            children.retain(|directory_entry_result| {
-                if let Ok(ref directory_entry) = directory_entry_result {
-                    let name = directory_entry.file_name();
-                    if let Some(name_string) = name.to_str() {
-                        !should_skip_directory(name_string)
-                    } else {
-                        true
-                    }
-                } else {
-                    true
-                }
-            });
-        })
-    {
-        if token.is_cancelled() {
-            debug!(
-                discovered = progress.discovered.len(),
-                "Discovery cancelled"
-            );
-            return None;
-        }
-
-        match entry {
-            Ok(directory_entry) => {
-                if let Some(discovered) =
-                    discover_dependency_directory(&directory_entry, config, &mut progress, app)
-                {
-                    progress.discovered.push(discovered);
-                }
-            }
-            Err(_) => {
-                progress.total_skipped += 1;
-            }
-        }
-    }
-
-    let discovery_time = start.elapsed().as_millis();
-    let discovered_count = progress.discovered.len();
-
-    info!(
-        discovered = discovered_count,
-        skipped = progress.total_skipped,
-        discovery_time_ms = discovery_time,
-        "Discovery phase complete, starting size calculations"
+fn emit_scan_id(app: &tauri::AppHandle, event: &str, scan_id: &str) {
+    let _ = app.emit(
+        event,
+        ScanId {
+            scan_id: scan_id.to_string(),
+        },
     );
+}
 
-    if token.is_cancelled() {
-        debug!("Scan cancelled after discovery");
-        return None;
-    }
-
-    let mut pool = match SizeCalculatorPool::new(num_threads) {
-        Ok(pool) => pool,
-        Err(error) => {
-            error!(%error, "Failed to create size calculator pool");
-            return None;
-        }
-    };
+/// Streams scan progress to the window as `scan_stats`, `scan_discovered` and `scan_entry`
+struct EventSink<'a> {
+    app: &'a tauri::AppHandle,
+    scan_id: &'a str,
+}
 
-    for discovered in &progress.discovered {
-        if token.is_cancelled() {
-            break;
-        }
-        pool.submit(discovered.path.clone(), discovered.category);
+impl ScanSink for EventSink<'_> {
+    fn stats(&self, stats: &ScanStats) {
+        SCAN_STATE.lock().unwrap().record_stats(self.scan_id, stats);
+        emit_scan_event(self.app, "scan_stats", self.scan_id, stats);
     }
 
-    let mut all_entries: Vec<DirectoryEntry> = Vec::with_capacity(discovered_count);
-    let mut running_total_size: u64 = 0;
-    let results_receiver = pool.results();
-    let mut results_collected: usize = 0;
-    let mut timeouts: usize = 0;
-
-    while results_collected < discovered_count {
-        if token.is_cancelled() {
-            debug!(collected = all_entries.len(), "Size calculation cancelled");
-            pool.shutdown();
-            return None;
-        }
-
-        match results_receiver.recv_timeout(Duration::from_secs(30)) {
-            Ok(result) => {
-                results_collected += 1;
-                timeouts = 0;
-
-                let entry = DirectoryEntry {
-                    path: result.path.clone(),
-                    size_bytes: result.total_size,
-                    file_count: result.file_count,
-                    last_modified_ms: result.last_modified_ms,
-                    category: result.category,
-                    has_only_symlinks: result.has_only_symlinks,
-                };
-
-                debug!(
-                    path = %result.path,
-                    size_bytes = result.total_size,
-                    size_mb = result.total_size as f64 / 1024.0 / 1024.0,
-                    file_count = result.file_count,
-                    "Size calculation complete"
-                );
-
-                info!(
-                    path = %result.path,
-                    size_bytes = result.total_size,
-                    file_count = result.file_count,
-                    "Emitting scan_entry"
-                );
-
-                let _ = app.emit("scan_entry", &entry);
-                running_total_size += entry.size_bytes;
-                all_entries.push(entry);
-            }
-            Err(_) => {
-                timeouts += 1;
-                warn!(
-                    timeouts = timeouts,
-                    collected = results_collected,
-                    expected = discovered_count,
-                    "Timeout waiting for size calculation result"
-                );
-                if timeouts >= config::scanner::MAX_TIMEOUT_RETRIES {
-                    warn!("Too many timeouts, stopping size collection");
-                    break;
-                }
-            }
-        }
+    /// Announces a directory before it is sized, so the UI can show a placeholder row
+    /// that `scan_entry` later fills in
+    fn discovered(&self, discovered: &DiscoveredDirectory) {
+        emit_scan_event(self.app, "scan_discovered", self.scan_id, discovered);
     }
 
-    pool.shutdown();
-    drop(pool);
-
-    let scan_time_ms = start.elapsed().as_millis();
-
-    info!(
-        entries = all_entries.len(),
-        total_size_gb = running_total_size as f64 / 1024.0 / 1024.0 / 1024.0,
-        duration_ms = scan_time_ms,
-        discovery_ms = discovery_time,
-        sizing_ms = scan_time_ms - discovery_time,
-        skipped = progress.total_skipped,
-        "Scan complete"
-    );
-
-    let sort_start = Instant::now();
-    all_entries.sort_by(|first, second| second.size_bytes.cmp(&first.size_bytes));
-    debug!(
-        duration_ms = sort_start.elapsed().as_millis(),
-        "Sort completed"
-    );
-
-    Some(ScanResult {
-        entries: all_entries,
-        total_size: running_total_size,
-        scan_time_ms,
-        skipped_count: progress.total_skipped,
-    })
+    fn entry(&self, entry: &DirectoryEntry) {
+        debug!(path = %entry.path, size_bytes = entry.size_bytes, "Emitting scan_entry");
+        emit_scan_event(self.app, "scan_entry", self.scan_id, entry);
+    }
 }
 
 async fn cancel_previous_scan() -> Option<Arc<Notify>> {
@@ -358,27 +181,95 @@ async fn cancel_previous_scan() -> Option<Arc<Notify>> {
     None
 }
 
-fn register_new_scan(token: CancellationToken, completion_notify: Arc<Notify>) {
-    let mut state = SCAN_STATE.lock().unwrap();
-    state.token = Some(token);
-    state.completion_notify = Some(completion_notify);
+/// Registers a scan as the one in progress, returning its new ID
+fn register_new_scan(token: CancellationToken, completion_notify: Arc<Notify>) -> String {
+    SCAN_STATE
+        .lock()
+        .unwrap()
+        .start(token, completion_notify, time::now_ms())
+}
+
+fn finish_scan(scan_id: &str) {
+    SCAN_STATE.lock().unwrap().finish(scan_id);
+}
+
+/// The scan in progress, for a window that was closed while it ran
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveScan {
+    pub scan_id: String,
+    pub started_at_ms: u64,
+    /// The most recent `scan_stats`, None until the first is emitted
+    pub stats: Option<ScanStats>,
 }
 
 struct ScanState {
     token: Option<CancellationToken>,
     completion_notify: Option<Arc<Notify>>,
+    active: Option<ActiveScan>,
+}
+
+impl ScanState {
+    fn start(
+        &mut self,
+        token: CancellationToken,
+        completion_notify: Arc<Notify>,
+        now_ms: u64,
+    ) -> String {
+        let scan_id = uuid::Uuid::new_v4().to_string();
+        self.token = Some(token);
+        self.completion_notify = Some(completion_notify);
+        self.active = Some(ActiveScan {
+            scan_id: scan_id.clone(),
+            started_at_ms: now_ms,
+            stats: None,
+        });
+        scan_id
+    }
+
+    /// Only the scan that is still active may update or clear it, since a replaced
+    /// scan can finish after its successor has started
+    fn is_active(&self, scan_id: &str) -> bool {
+        self.active
+            .as_ref()
+            .is_some_and(|active| active.scan_id == scan_id)
+    }
+
+    fn record_stats(&mut self, scan_id: &str, stats: &ScanStats) {
+        if let Some(active) = self
+            .active
+            .as_mut()
+            .filter(|active| active.scan_id == scan_id)
+        {
+            active.stats = Some(stats.clone());
+        }
+    }
+
+    fn finish(&mut self, scan_id: &str) {
+        if self.is_active(scan_id) {
+            self.active = None;
+        }
+    }
 }
 
 static SCAN_STATE: LazyLock<Mutex<ScanState>> = LazyLock::new(|| {
     Mutex::new(ScanState {
         token: None,
         completion_notify: None,
+        active: None,
     })
 });
 
+/// Returns the scan in progress, so a reopened window can pick up its events again
 #[tauri::command]
 #[instrument(skip_all)]
-pub async fn start_scan(app: tauri::AppHandle) -> Result<(), String> {
+pub fn get_active_scan() -> Option<ActiveScan> {
+    SCAN_STATE.lock().unwrap().active.clone()
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn start_scan(app: tauri::AppHandle) -> Result<Vec<SettingsWarning>, DeptoxError> {
     let command_start = Instant::now();
     info!("Starting scan");
 
@@ -386,39 +277,61 @@ pub async fn start_scan(app: tauri::AppHandle) -> Result<(), String> {
 
     let token = CancellationToken::new();
     let completion_notify = Arc::new(Notify::new());
-    register_new_scan(token.clone(), completion_notify.clone());
+    let scan_id = register_new_scan(token.clone(), completion_notify.clone());
+    emit_scan_id(&app, "scan_started", &scan_id);
+    tray::set_state(&app, tray::TrayState::Scanning);
 
     let settings = get_settings_sync().unwrap_or_default();
-    let config = ScanConfig {
-        root_directory: expand_tilde(&settings.root_directory),
-        enabled_categories: settings.enabled_categories.clone(),
-        target_dir_names: get_target_directory_names(&settings.enabled_categories),
-        all_dependency_dirs: get_all_dependency_directory_names(),
-        exclude_patterns: parse_exclude_patterns(&settings.exclude_paths),
-    };
+    let warnings = exclude_pattern_warnings(&settings);
+    if !warnings.is_empty() {
+        warn!(?warnings, "Exclude patterns hide the scan root");
+    }
+
+    let config = build_scan_config(&settings, ScanThrottle::unthrottled());
 
     info!(
         root_directory = %config.root_directory,
         categories = ?config.enabled_categories,
         exclude_patterns = ?config.exclude_patterns,
+        include_patterns = ?config.include_patterns,
+        follow_symlinks = config.follow_symlinks,
+        max_file_count = ?config.max_file_count,
+        %scan_id,
         "Starting directory scan"
     );
 
     tokio::task::spawn(async move {
         let app_for_emit = app.clone();
-        let result =
-            tokio::task::spawn_blocking(move || execute_directory_walk(&config, &token, &app))
-                .await;
+        let root_directory = config.root_directory.clone();
+        let sink_scan_id = scan_id.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let sink = EventSink {
+                app: &app,
+                scan_id: &sink_scan_id,
+            };
+            run_scan(&config, &sink, &token)
+        })
+        .await;
+        finish_scan(&scan_id);
+        tray::finish_scanning(&app_for_emit);
 
         if let Ok(Some(scan_result)) = result {
+            let cached = store_scan_result(&root_directory, &scan_result);
+            if let Err(error) = tray::update_summary(&app_for_emit, &cached) {
+                warn!(%error, "Failed to update tray scan summary");
+            }
+            if let Some(cue) = sounds::scan_cue(&settings, scan_result.scan_time_ms) {
+                sounds::play(cue);
+            }
+
             info!(
                 entries = scan_result.entries.len(),
                 "Emitting scan_complete"
             );
-            let _ = app_for_emit.emit("scan_complete", scan_result);
+            emit_scan_event(&app_for_emit, "scan_complete", &scan_id, scan_result);
         } else if let Ok(None) = result {
             info!("Emitting scan_cancelled");
-            let _ = app_for_emit.emit("scan_cancelled", ());
+            emit_scan_id(&app_for_emit, "scan_cancelled", &scan_id);
         }
 
         completion_notify.notify_waiters();
@@ -429,6 +342,88 @@ pub async fn start_scan(app: tauri::AppHandle) -> Result<(), String> {
         duration_ms = command_start.elapsed().as_millis(),
         "start_scan returned"
     );
+    Ok(warnings)
+}
+
+/// Streams a fabricated scan through the same events as `start_scan`, for load testing
+/// the frontend with large results. Nothing is read from disk or written to the scan cache.
+#[tauri::command]
+#[instrument(skip(app))]
+pub async fn simulate_scan(
+    app: tauri::AppHandle,
+    entry_count: usize,
+    size_distribution: SizeDistribution,
+) -> Result<(), DeptoxError> {
+    if entry_count > config::fixture::MAX_SIMULATED_ENTRIES {
+        return Err(DeptoxError::InvalidArgument(format!(
+            "Cannot simulate more than {} entries",
+            config::fixture::MAX_SIMULATED_ENTRIES
+        )));
+    }
+
+    cancel_previous_scan().await;
+
+    let token = CancellationToken::new();
+    let completion_notify = Arc::new(Notify::new());
+    let scan_id = register_new_scan(token.clone(), completion_notify.clone());
+    emit_scan_id(&app, "scan_started", &scan_id);
+
+    tokio::task::spawn(async move {
+        let start = Instant::now();
+        let now_ms = time::now_ms();
+        let mut entries = simulate_entries(entry_count, size_distribution, now_ms, now_ms);
+        let mut last_emit_time = Instant::now()
+            .checked_sub(config::scanner::EMIT_THROTTLE)
+            .unwrap_or_else(Instant::now);
+        let mut running_total_size: u64 = 0;
+        let sink = EventSink {
+            app: &app,
+            scan_id: &scan_id,
+        };
+
+        for (index, entry) in entries.iter().enumerate() {
+            if token.is_cancelled() {
+                finish_scan(&scan_id);
+                info!("Emitting scan_cancelled");
+                emit_scan_id(&app, "scan_cancelled", &scan_id);
+                completion_notify.notify_waiters();
+                return;
+            }
+
+            running_total_size += entry.size_bytes;
+            report_scan_stats(
+                &sink,
+                &mut last_emit_time,
+                running_total_size,
+                index + 1,
+                &entry.path,
+            );
+            sink.discovered(&DiscoveredDirectory {
+                path: entry.path.clone(),
+                category: entry.category,
+            });
+            sink.entry(entry);
+
+            // Lets other tasks, including cancellation, run between bursts of events
+            if index % 100 == 99 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        entries.sort_by(|first, second| second.size_bytes.cmp(&first.size_bytes));
+        let mut result = ScanResult::from_entries(entries, start.elapsed().as_millis(), 0, now_ms);
+        // Simulated projects aren't repositories, so nothing waits on git
+        result.score_safety(now_ms, &|| false);
+
+        info!(
+            entries = result.entries.len(),
+            "Emitting simulated scan_complete"
+        );
+        finish_scan(&scan_id);
+        emit_scan_event(&app, "scan_complete", &scan_id, result);
+        completion_notify.notify_waiters();
+    });
+
     Ok(())
 }
 
@@ -454,7 +449,7 @@ pub struct RescanResult {
 
 #[tauri::command]
 #[instrument(skip_all, fields(path = %path))]
-pub async fn rescan_directory(path: String) -> Result<RescanResult, String> {
+pub async fn rescan_directory(path: String) -> Result<RescanResult, DeptoxError> {
     info!(%path, "Rescanning directory");
     let start = Instant::now();
 
@@ -479,38 +474,115 @@ pub async fn rescan_directory(path: String) -> Result<RescanResult, String> {
     let directory_name = path_ref
         .file_name()
         .and_then(|name| name.to_str())
-        .ok_or_else(|| "Invalid directory name".to_string())?;
+        .ok_or_else(|| DeptoxError::invalid_path(&path, "Invalid directory name"))?;
 
     let category = match directory_name {
-        "vendor" => DependencyCategory::from_vendor_directory(path_ref)
-            .ok_or_else(|| format!("Unknown vendor type for: {directory_name}"))?,
-        "deps" => DependencyCategory::from_deps_directory(path_ref)
-            .ok_or_else(|| format!("Not an Elixir deps directory: {directory_name}"))?,
-        "pkg" => DependencyCategory::from_pkg_directory(path_ref)
-            .ok_or_else(|| format!("Not a Go pkg directory: {directory_name}"))?,
+        "vendor" => DependencyCategory::from_vendor_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(&path, format!("Unknown vendor type for: {directory_name}"))
+        })?,
+        "deps" => DependencyCategory::from_deps_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(
+                &path,
+                format!("Not an Elixir deps directory: {directory_name}"),
+            )
+        })?,
+        "pkg" => DependencyCategory::from_pkg_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(&path, format!("Not a Go pkg directory: {directory_name}"))
+        })?,
+        "bin" | "obj" => {
+            DependencyCategory::from_dotnet_build_directory(path_ref).ok_or_else(|| {
+                DeptoxError::invalid_path(
+                    &path,
+                    format!("Not a .NET build directory: {directory_name}"),
+                )
+            })?
+        }
+        "packages" => {
+            DependencyCategory::from_nuget_packages_directory(path_ref).ok_or_else(|| {
+                DeptoxError::invalid_path(
+                    &path,
+                    format!("Not a NuGet packages directory: {directory_name}"),
+                )
+            })?
+        }
+        "dist" => DependencyCategory::from_dist_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(
+                &path,
+                format!("Not a JavaScript dist directory: {directory_name}"),
+            )
+        })?,
+        "cache" => DependencyCategory::from_angular_cache_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(
+                &path,
+                format!("Not an Angular cache directory: {directory_name}"),
+            )
+        })?,
+        "target" => DependencyCategory::from_cargo_target_directory(path_ref).ok_or_else(|| {
+            DeptoxError::invalid_path(
+                &path,
+                format!("Not a Rust target directory: {directory_name}"),
+            )
+        })?,
         _ => DependencyCategory::from_directory_name(directory_name)
-            .ok_or_else(|| format!("Unknown dependency category for: {directory_name}"))?,
+            .or_else(|| DependencyCategory::from_environment_directory(path_ref))
+            .ok_or_else(|| {
+                DeptoxError::invalid_path(
+                    &path,
+                    format!("Unknown dependency category for: {directory_name}"),
+                )
+            })?,
     };
 
+    let settings = get_settings_sync().unwrap_or_default();
+    let max_file_count = file_count_limit(&settings);
+    let incremental_sizing = settings.incremental_sizing;
     let path_clone = path.clone();
-    let size_result =
-        tokio::task::spawn_blocking(move || calculate_dir_size_full(Path::new(&path_clone)))
-            .await
-            .map_err(|error| format!("Failed to calculate size: {error}"))?;
+    let (fingerprint, size_result, project_info) = tokio::task::spawn_blocking(move || {
+        let path_ref = Path::new(&path_clone);
+        (
+            incremental_sizing
+                .then(|| directory_fingerprint(path_ref))
+                .flatten(),
+            calculate_dir_size_limited(path_ref, max_file_count),
+            resolve_project_info(path_ref),
+        )
+    })
+    .await
+    .map_err(|error| DeptoxError::Internal(format!("Failed to calculate size: {error}")))?;
 
-    let entry = DirectoryEntry {
+    let mut entry = DirectoryEntry {
         path,
         size_bytes: size_result.total_size,
         file_count: size_result.file_count,
         last_modified_ms: size_result.last_modified_ms,
         category,
         has_only_symlinks: size_result.has_only_symlinks,
+        last_commit_ms: project_info.last_commit_ms,
+        git_branch: project_info.git_branch,
+        project_name: project_info.project_name,
+        workspace_root: project_info.workspace_root,
+        truncated: size_result.truncated,
+        scanned_at_ms: time::now_ms(),
+        stale: false,
+        age_source: AgeStrategy::NewestFile,
+        safety: Safety::Caution,
     };
+    let scored_entry = entry.clone();
+    entry.safety =
+        tokio::task::spawn_blocking(move || score_entry(&scored_entry, scored_entry.scanned_at_ms))
+            .await
+            .unwrap_or_default();
+
+    if let Some(fingerprint) = fingerprint {
+        record_size(&entry, fingerprint, entry.scanned_at_ms);
+        save_size_index(entry.scanned_at_ms);
+    }
 
     info!(
         path = %entry.path,
         size_bytes = size_result.total_size,
         file_count = size_result.file_count,
+        truncated = size_result.truncated,
         duration_ms = start.elapsed().as_millis(),
         "Rescan complete"
     );
@@ -521,6 +593,45 @@ pub async fn rescan_directory(path: String) -> Result<RescanResult, String> {
     })
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecomputedAge {
+    pub path: String,
+    pub age_source: AgeStrategy,
+    /// None when the strategy has nothing to go on, e.g. a project without git history
+    pub last_modified_ms: Option<u64>,
+}
+
+/// Recomputes a directory's age under another definition of "last used",
+/// so the UI can re-sort without rescanning
+#[tauri::command]
+#[instrument(skip_all, fields(path = %path, strategy = ?strategy))]
+pub async fn recompute_age(
+    path: String,
+    strategy: AgeStrategy,
+) -> Result<RecomputedAge, DeptoxError> {
+    if !Path::new(&path).is_dir() {
+        warn!("Path is not a directory");
+        return Err(DeptoxError::invalid_path(
+            &path,
+            format!("Not a directory: {path}"),
+        ));
+    }
+
+    let age_path = path.clone();
+    let last_modified_ms =
+        tokio::task::spawn_blocking(move || compute_age_ms(Path::new(&age_path), strategy))
+            .await
+            .map_err(|error| DeptoxError::Internal(format!("Failed to compute age: {error}")))?;
+
+    debug!(?last_modified_ms, "Recomputed age");
+    Ok(RecomputedAge {
+        path,
+        age_source: strategy,
+        last_modified_ms,
+    })
+}
+
 #[cfg(test)]
 #[path = "scan.test.rs"]
 mod tests;
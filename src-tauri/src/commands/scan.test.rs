@@ -1,4 +1,5 @@
 use super::*;
+use crate::scanner::{get_all_dependency_directory_names, is_inside_dependency_directory};
 use std::fs;
 use tempfile::TempDir;
 
@@ -320,3 +321,181 @@ async fn test_rescan_monorepo_packages_have_correct_sizes() {
         schema_entry.size_bytes
     );
 }
+
+#[tokio::test]
+async fn test_rescan_directory_dist_with_package_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir(&dist).unwrap();
+    fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+    fs::write(dist.join("main.js"), "console.log(1);").unwrap();
+
+    let result = rescan_directory(dist.to_string_lossy().to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.entry.unwrap().category,
+        DependencyCategory::BuildArtifacts
+    );
+}
+
+#[tokio::test]
+async fn test_recompute_age_reports_strategy() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("project").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+    fs::write(node_modules.join("index.js"), "module.exports = {};").unwrap();
+
+    let recomputed = recompute_age(
+        node_modules.to_string_lossy().to_string(),
+        AgeStrategy::DirectoryMtime,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(recomputed.age_source, AgeStrategy::DirectoryMtime);
+    assert!(recomputed.last_modified_ms.is_some());
+
+    let recomputed = recompute_age(
+        node_modules.to_string_lossy().to_string(),
+        AgeStrategy::GitCommit,
+    )
+    .await
+    .unwrap();
+    assert_eq!(recomputed.last_modified_ms, None);
+}
+
+#[tokio::test]
+async fn test_recompute_age_rejects_missing_directory() {
+    let result = recompute_age(
+        "/nonexistent/project/node_modules".to_string(),
+        AgeStrategy::NewestFile,
+    )
+    .await;
+
+    assert!(result.is_err());
+}
+
+fn scan_state() -> ScanState {
+    ScanState {
+        token: None,
+        completion_notify: None,
+        active: None,
+    }
+}
+
+fn stats(total_size: u64) -> ScanStats {
+    ScanStats {
+        total_size,
+        directory_count: 1,
+        current_path: None,
+    }
+}
+
+#[test]
+fn test_scan_state_tracks_active_scan() {
+    let mut state = scan_state();
+    let scan_id = state.start(CancellationToken::new(), Arc::new(Notify::new()), 1_000);
+
+    state.record_stats(&scan_id, &stats(2048));
+
+    let active = state.active.clone().unwrap();
+    assert_eq!(active.scan_id, scan_id);
+    assert_eq!(active.started_at_ms, 1_000);
+    assert_eq!(active.stats.unwrap().total_size, 2048);
+
+    state.finish(&scan_id);
+    assert!(state.active.is_none());
+}
+
+#[test]
+fn test_scan_state_ignores_replaced_scan() {
+    let mut state = scan_state();
+    let first = state.start(CancellationToken::new(), Arc::new(Notify::new()), 1_000);
+    let second = state.start(CancellationToken::new(), Arc::new(Notify::new()), 2_000);
+    assert_ne!(first, second);
+
+    state.record_stats(&first, &stats(4096));
+    state.finish(&first);
+
+    let active = state.active.clone().unwrap();
+    assert_eq!(active.scan_id, second);
+    assert!(active.stats.is_none());
+}
+
+#[test]
+fn test_scan_event_flattens_payload() {
+    let event = ScanEvent {
+        scan_id: "abc".to_string(),
+        payload: stats(10),
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(json["scanId"], "abc");
+    assert_eq!(json["totalSize"], 10);
+}
+
+#[test]
+fn test_file_count_limit_zero_disables_ceiling() {
+    let mut settings = AppSettings::default();
+    assert_eq!(
+        file_count_limit(&settings),
+        Some(config::scanner::DEFAULT_MAX_FILES_PER_DIRECTORY)
+    );
+
+    settings.max_files_per_directory = 0;
+    assert_eq!(file_count_limit(&settings), None);
+}
+
+#[test]
+fn test_scan_config_expands_include_patterns() {
+    let settings = AppSettings {
+        include_paths: "~/code, */work/*".to_string(),
+        ..AppSettings::default()
+    };
+    let config = build_scan_config(&settings, ScanThrottle::unthrottled());
+
+    assert_eq!(
+        config.include_patterns,
+        vec![expand_tilde("~/code"), "*/work/*".to_string()]
+    );
+    assert!(
+        build_scan_config(&AppSettings::default(), ScanThrottle::unthrottled())
+            .include_patterns
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_scan_deadline_zero_disables_timeout() {
+    let settings = AppSettings {
+        scan_timeout_minutes: 0,
+        ..AppSettings::default()
+    };
+    assert_eq!(scan_deadline(&settings), None);
+
+    let settings = AppSettings {
+        scan_timeout_minutes: 5,
+        ..AppSettings::default()
+    };
+    assert_eq!(scan_deadline(&settings), Some(Duration::from_secs(300)));
+}
+
+#[test]
+fn test_target_directory_names_respects_build_artifact_list() {
+    let mut settings = AppSettings::default();
+    assert!(!target_directory_names(&settings).contains("dist"));
+
+    settings
+        .enabled_categories
+        .insert(DependencyCategory::BuildArtifacts);
+    settings.build_artifact_directories = vec![".next".to_string()];
+    let names = target_directory_names(&settings);
+
+    assert!(names.contains(".next"));
+    assert!(names.contains("node_modules"));
+    assert!(!names.contains("dist"));
+    assert!(!names.contains(".turbo"));
+}
@@ -0,0 +1,163 @@
+use crate::config;
+use crate::history;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::scanner::{find_category_outliers, group_workspaces, DirectoryEntry, ScanResult};
+use crate::telemetry;
+use crate::time;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+
+/// Most recent completed scan, shared by interactive and background scans so the
+/// window can show fresh results on open without rescanning
+static SCAN_CACHE: BufferedJsonFile<Option<CachedScan>> = BufferedJsonFile::new(
+    get_scan_cache_path,
+    load_scan_cache_from,
+    save_scan_cache_to,
+);
+
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedScan {
+    pub root_directory: String,
+    pub completed_at_ms: u64,
+    pub result: ScanResult,
+}
+
+fn get_scan_cache_path() -> Result<PathBuf, String> {
+    config_path(config::app::SCAN_CACHE_FILENAME)
+}
+
+fn load_scan_cache_from(path: &Path) -> Option<CachedScan> {
+    if !path.exists() {
+        return None;
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .or_else(|| {
+            warn!("Failed to load scan cache, ignoring it");
+            None
+        })
+}
+
+fn save_scan_cache_to(path: &Path, cached: &Option<CachedScan>) -> Result<(), String> {
+    let content = serde_json::to_string(cached)
+        .map_err(|error| format!("Failed to serialize scan cache: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write scan cache: {error}"))
+}
+
+/// Replaces the cached scan with a completed result and writes it straight to disk.
+pub fn store_scan_result(root_directory: &str, result: &ScanResult) -> CachedScan {
+    let cached = CachedScan {
+        root_directory: root_directory.to_string(),
+        completed_at_ms: time::now_ms(),
+        result: result.clone(),
+    };
+
+    let stored = SCAN_CACHE
+        .update(|slot| *slot = Some(cached.clone()))
+        .and_then(|_| SCAN_CACHE.flush());
+    if let Err(error) = stored {
+        warn!(%error, "Failed to store scan cache");
+    }
+    history::record_scan(result);
+    telemetry::metrics::record_scan(result);
+
+    cached
+}
+
+/// Paths of the dependency directories in the most recent completed scan
+pub fn cached_entry_paths() -> Vec<String> {
+    SCAN_CACHE
+        .read(|cached| {
+            cached
+                .iter()
+                .flat_map(|cached| cached.result.entries.iter())
+                .map(|entry| entry.path.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn remove_entries(result: &mut ScanResult, paths: &HashSet<String>) {
+    result.entries.retain(|entry| !paths.contains(&entry.path));
+    result.total_size = result.entries.iter().map(|entry| entry.size_bytes).sum();
+    result.total_file_count = result
+        .entries
+        .iter()
+        .map(|entry| entry.file_count as u64)
+        .sum();
+    result.workspaces = group_workspaces(&result.entries);
+}
+
+/// Drops deleted directories from the cached scan, and from its totals, so it doesn't
+/// offer them again. Returns the updated scan, if there is one.
+pub fn remove_cached_entries(paths: &HashSet<String>) -> Option<CachedScan> {
+    let removed = SCAN_CACHE.update(|cached| {
+        let cached = cached.as_mut()?;
+        remove_entries(&mut cached.result, paths);
+        Some(cached.clone())
+    });
+    removed.unwrap_or_else(|error| {
+        warn!(%error, "Failed to update scan cache");
+        None
+    })
+}
+
+/// Writes a pending scan cache to disk
+pub fn flush_scan_cache() -> Result<bool, String> {
+    SCAN_CACHE.flush()
+}
+
+/// Returns the most recent completed scan, from either a manual or background scan,
+/// with entries older than the rescan interval flagged as stale
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_cached_scan() -> Result<Option<CachedScan>, String> {
+    let mut cached = SCAN_CACHE.read(Option::clone)?;
+    if let Some(cached) = cached.as_mut() {
+        cached
+            .result
+            .mark_stale_entries(time::now_ms(), config::background::STALE_AFTER_MS);
+    }
+
+    debug!(
+        cached = cached.is_some(),
+        entries = cached
+            .as_ref()
+            .map_or(0, |cached| cached.result.entries.len()),
+        "Loaded scan cache"
+    );
+    Ok(cached)
+}
+
+/// Entries from the most recent scan that are larger than their category's median,
+/// largest first, for surfacing unusually bloated installs. Empty when nothing is cached.
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_outlier_entries() -> Result<Vec<DirectoryEntry>, String> {
+    let mut outliers = SCAN_CACHE.read(|cached| {
+        cached
+            .as_ref()
+            .map(|cached| find_category_outliers(&cached.result.entries))
+            .unwrap_or_default()
+    })?;
+
+    let now_ms = time::now_ms();
+    for entry in &mut outliers {
+        entry.stale = entry.is_stale(now_ms, config::background::STALE_AFTER_MS);
+    }
+
+    debug!(outliers = outliers.len(), "Found category outliers");
+    Ok(outliers)
+}
+
+#[cfg(test)]
+#[path = "scan_cache.test.rs"]
+mod tests;
@@ -0,0 +1,83 @@
+use super::*;
+use crate::scanner::{DependencyCategory, DirectoryEntry};
+use tempfile::TempDir;
+
+fn cached_scan() -> CachedScan {
+    let entry = DirectoryEntry {
+        file_count: 12,
+        last_modified_ms: 1_700_000_000_000,
+        git_branch: Some("main".to_string()),
+        ..DirectoryEntry::for_test(
+            "/projects/app/node_modules",
+            DependencyCategory::NodeModules,
+            2048,
+        )
+    };
+
+    CachedScan {
+        root_directory: "/projects".to_string(),
+        completed_at_ms: 1_700_000_100_000,
+        result: ScanResult::from_entries(vec![entry], 42, 1, 1_700_000_100_000),
+    }
+}
+
+#[test]
+fn test_load_scan_cache_from_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(load_scan_cache_from(&temp_dir.path().join("scan_cache.json")).is_none());
+}
+
+#[test]
+fn test_save_and_load_scan_cache_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("scan_cache.json");
+
+    save_scan_cache_to(&path, &Some(cached_scan())).unwrap();
+    let loaded = load_scan_cache_from(&path).unwrap();
+
+    assert_eq!(loaded.root_directory, "/projects");
+    assert_eq!(loaded.completed_at_ms, 1_700_000_100_000);
+    assert_eq!(loaded.result.entries.len(), 1);
+    assert_eq!(loaded.result.total_size, 2048);
+    assert_eq!(loaded.result.skipped_count, 1);
+    assert_eq!(loaded.result.entries[0].git_branch.as_deref(), Some("main"));
+}
+
+#[test]
+fn test_load_scan_cache_from_invalid_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("scan_cache.json");
+    fs::write(&path, "{not json").unwrap();
+
+    assert!(load_scan_cache_from(&path).is_none());
+}
+
+#[test]
+fn test_remove_entries_updates_totals() {
+    let mut result = cached_scan().result;
+    let mut second = result.entries[0].clone();
+    second.path = "/projects/api/node_modules".to_string();
+    second.size_bytes = 1024;
+    second.file_count = 3;
+    result.entries.push(second);
+
+    remove_entries(
+        &mut result,
+        &HashSet::from(["/projects/app/node_modules".to_string()]),
+    );
+
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.entries[0].path, "/projects/api/node_modules");
+    assert_eq!(result.total_size, 1024);
+    assert_eq!(result.total_file_count, 3);
+    assert!(result.workspaces.is_empty());
+}
+
+#[test]
+fn test_cached_scan_serialization_uses_camel_case() {
+    let json = serde_json::to_string(&cached_scan()).unwrap();
+
+    assert!(json.contains("\"rootDirectory\""));
+    assert!(json.contains("\"completedAtMs\""));
+    assert!(json.contains("\"scanTimeMs\""));
+}
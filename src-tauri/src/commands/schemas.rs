@@ -0,0 +1,90 @@
+use super::clean_stale;
+use super::delete::{BatchDeleteResult, DeleteProgress, SecureDeleteProgress};
+use super::filesystem::FullDiskAccessStatus;
+use super::locale::SystemLocale;
+use super::scan::{ActiveScan, ScanEvent, ScanId};
+use super::scan_cache::CachedScan;
+use super::settings::AppSettings;
+use crate::deep_link;
+use crate::error::ErrorPayload;
+use crate::notifications::REVIEW_EVENT;
+use crate::scanner::{
+    DependencyCategory, DirectoryEntry, DiscoveredDirectory, ScanResult, ScanStats,
+};
+use crate::tray;
+use crate::updates::PendingUpdate;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+/// JSON schemas for what the backend exchanges with the frontend, generated from
+/// the Rust types so the CLI and automations can validate against the same shapes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schemas {
+    /// Keyed by Rust type name
+    pub types: BTreeMap<&'static str, RootSchema>,
+    /// Payload of each event the backend emits, keyed by event name
+    pub events: BTreeMap<&'static str, RootSchema>,
+}
+
+fn build_schemas() -> Schemas {
+    let types = BTreeMap::from([
+        ("AppSettings", schema_for!(AppSettings)),
+        ("DirectoryEntry", schema_for!(DirectoryEntry)),
+        ("ScanResult", schema_for!(ScanResult)),
+        ("CachedScan", schema_for!(CachedScan)),
+        ("ActiveScan", schema_for!(ActiveScan)),
+        ("ErrorPayload", schema_for!(ErrorPayload)),
+        ("SystemLocale", schema_for!(SystemLocale)),
+        ("PendingUpdate", schema_for!(PendingUpdate)),
+    ]);
+
+    let events = BTreeMap::from([
+        ("scan_started", schema_for!(ScanId)),
+        ("scan_stats", schema_for!(ScanEvent<ScanStats>)),
+        (
+            "scan_discovered",
+            schema_for!(ScanEvent<DiscoveredDirectory>),
+        ),
+        ("scan_entry", schema_for!(ScanEvent<DirectoryEntry>)),
+        ("scan_complete", schema_for!(ScanEvent<ScanResult>)),
+        ("scan_cancelled", schema_for!(ScanId)),
+        ("background_scan_complete", schema_for!(CachedScan)),
+        ("delete_progress", schema_for!(DeleteProgress)),
+        ("secure_delete_progress", schema_for!(SecureDeleteProgress)),
+        (clean_stale::STARTED_EVENT, schema_for!(usize)),
+        (clean_stale::COMPLETE_EVENT, schema_for!(BatchDeleteResult)),
+        (
+            "full_disk_access_missing",
+            schema_for!(FullDiskAccessStatus),
+        ),
+        (REVIEW_EVENT, schema_for!(())),
+        ("tray-scan-requested", schema_for!(())),
+        ("tray-settings-requested", schema_for!(())),
+        ("tray-about-requested", schema_for!(())),
+        ("tray-update-requested", schema_for!(())),
+        (
+            tray::CATEGORY_REQUESTED_EVENT,
+            schema_for!(DependencyCategory),
+        ),
+        (deep_link::CLEAN_EVENT, schema_for!(String)),
+        ("second-instance-launched", schema_for!(Vec<String>)),
+        ("window-detached-changed", schema_for!(bool)),
+        ("update-available", schema_for!(PendingUpdate)),
+    ]);
+
+    Schemas { types, events }
+}
+
+#[tauri::command]
+#[instrument]
+pub fn get_schemas() -> Schemas {
+    build_schemas()
+}
+
+#[cfg(test)]
+#[path = "schemas.test.rs"]
+mod tests;
@@ -0,0 +1,66 @@
+use super::*;
+
+fn property_names(schema: &RootSchema) -> Vec<&str> {
+    schema
+        .schema
+        .object
+        .as_ref()
+        .map(|object| object.properties.keys().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_type_schemas_use_serialized_field_names() {
+    let schemas = build_schemas();
+
+    let settings = property_names(&schemas.types["AppSettings"]);
+    assert!(settings.contains(&"thresholdBytes"));
+    assert!(settings.contains(&"excludePaths"));
+    assert!(!settings.contains(&"threshold_bytes"));
+
+    let result = property_names(&schemas.types["ScanResult"]);
+    assert!(result.contains(&"entries"));
+    assert!(result.contains(&"coverage"));
+
+    let error = property_names(&schemas.types["ErrorPayload"]);
+    assert!(error.contains(&"code"));
+    assert!(error.contains(&"context"));
+}
+
+#[test]
+fn test_event_schemas_match_payload_types() {
+    let schemas = build_schemas();
+
+    let entry = property_names(&schemas.events["scan_entry"]);
+    assert!(entry.contains(&"scanId"));
+    assert!(entry.contains(&"sizeBytes"));
+    let complete = property_names(&schemas.events["scan_complete"]);
+    assert!(complete.contains(&"scanId"));
+    assert!(complete.contains(&"entries"));
+    assert_eq!(
+        property_names(&schemas.events["scan_cancelled"]),
+        vec!["scanId"]
+    );
+    assert!(property_names(&schemas.events["delete_progress"]).contains(&"sizeFreed"));
+    assert!(
+        property_names(&schemas.events[clean_stale::COMPLETE_EVENT]).contains(&"totalSizeFreed")
+    );
+    assert!(property_names(&schemas.events["scan_discovered"]).contains(&"category"));
+    assert!(schemas.events.contains_key(REVIEW_EVENT));
+    assert!(schemas.events[tray::CATEGORY_REQUESTED_EVENT]
+        .schema
+        .enum_values
+        .is_some());
+    assert!(schemas.events.contains_key(deep_link::CLEAN_EVENT));
+}
+
+#[test]
+fn test_schemas_serialize_as_json_schema() {
+    let json = serde_json::to_value(build_schemas()).unwrap();
+
+    assert_eq!(
+        json["types"]["AppSettings"]["$schema"],
+        "http://json-schema.org/draft-07/schema#"
+    );
+    assert!(json["types"]["DirectoryEntry"]["definitions"]["DependencyCategory"].is_object());
+}
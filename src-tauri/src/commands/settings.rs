@@ -1,59 +1,160 @@
 use crate::config;
-use crate::scanner::DependencyCategory;
+use crate::error::DeptoxError;
+use crate::format;
+use crate::scanner::{
+    expand_tilde, migrate_legacy_patterns, parse_exclude_patterns, should_exclude_path,
+    DependencyCategory, DiskUsage, ScanPriority, VolumeKind, BUILD_ARTIFACT_DIRECTORIES,
+};
+use crate::secure_store;
+use crate::telemetry;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use thiserror::Error;
 use tracing::{debug, info, instrument, warn};
 
 /// Validates exclude patterns for length and complexity limits
 fn validate_exclude_patterns(exclude_paths: &str) -> Result<(), SettingsError> {
-    if exclude_paths.len() > config::exclude_patterns::MAX_TOTAL_LENGTH {
-        return Err(SettingsError::InvalidExcludePatterns(format!(
-            "Total exclude patterns length exceeds {} characters",
+    validate_path_patterns(exclude_paths, "exclude").map_err(SettingsError::InvalidExcludePatterns)
+}
+
+/// Validates include patterns against the same limits as exclude patterns
+fn validate_include_patterns(include_paths: &str) -> Result<(), SettingsError> {
+    validate_path_patterns(include_paths, "include").map_err(SettingsError::InvalidIncludePatterns)
+}
+
+/// Only http and https endpoints can receive webhooks
+fn validate_webhook_url(webhook_url: Option<&str>) -> Result<(), SettingsError> {
+    check_http_url(webhook_url).map_err(SettingsError::InvalidWebhookUrl)
+}
+
+fn validate_crash_report_url(crash_report_url: Option<&str>) -> Result<(), SettingsError> {
+    check_http_url(crash_report_url).map_err(SettingsError::InvalidCrashReportUrl)
+}
+
+/// Returns why an optional endpoint isn't an http(s) URL
+fn check_http_url(url: Option<&str>) -> Result<(), String> {
+    let Some(url) = url else {
+        return Ok(());
+    };
+
+    let parsed = reqwest::Url::parse(url).map_err(|error| error.to_string())?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("must start with http:// or https://".to_string());
+    }
+    Ok(())
+}
+
+/// A percentage threshold must leave something to compare against and can't exceed the disk
+fn validate_threshold_percent(threshold_percent: u8) -> Result<(), SettingsError> {
+    if !(1..=100).contains(&threshold_percent) {
+        return Err(SettingsError::InvalidThresholdPercent(threshold_percent));
+    }
+    Ok(())
+}
+
+/// Checks comma-separated wildcard patterns for length and complexity limits,
+/// naming the kind of pattern ("exclude", "include") in the message
+fn validate_path_patterns(paths: &str, kind: &str) -> Result<(), String> {
+    if paths.len() > config::exclude_patterns::MAX_TOTAL_LENGTH {
+        return Err(format!(
+            "Total {kind} patterns length exceeds {} characters",
             config::exclude_patterns::MAX_TOTAL_LENGTH
-        )));
+        ));
     }
 
-    let patterns: Vec<&str> = exclude_paths
+    let patterns: Vec<&str> = paths
         .split(',')
         .map(|pattern| pattern.trim())
         .filter(|pattern| !pattern.is_empty())
         .collect();
 
     if patterns.len() > config::exclude_patterns::MAX_PATTERN_COUNT {
-        return Err(SettingsError::InvalidExcludePatterns(format!(
-            "Too many exclude patterns (max {})",
+        return Err(format!(
+            "Too many {kind} patterns (max {})",
             config::exclude_patterns::MAX_PATTERN_COUNT
-        )));
+        ));
     }
 
     for pattern in patterns {
         if pattern.len() > config::exclude_patterns::MAX_PATTERN_LENGTH {
-            return Err(SettingsError::InvalidExcludePatterns(format!(
+            return Err(format!(
                 "Pattern exceeds {} characters: {}...",
                 config::exclude_patterns::MAX_PATTERN_LENGTH,
                 &pattern[..50.min(pattern.len())]
-            )));
+            ));
         }
 
+        // A run like `**` is one wildcard that also crosses separators
         let wildcard_count = pattern
-            .chars()
-            .filter(|character| *character == '*')
+            .match_indices('*')
+            .filter(|(index, _)| !pattern[..*index].ends_with('*'))
             .count();
         if wildcard_count > config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN {
-            return Err(SettingsError::InvalidExcludePatterns(format!(
+            return Err(format!(
                 "Pattern has too many wildcards (max {}): {}...",
                 config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN,
                 &pattern[..50.min(pattern.len())]
-            )));
+            ));
         }
     }
 
     Ok(())
 }
 
+/// Non-fatal problems with saved settings that the UI should surface to the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SettingsWarning {
+    /// The pattern matches every path under the scan root, so scans find nothing
+    #[serde(rename_all = "camelCase")]
+    RootExcluded {
+        pattern: String,
+        root_directory: String,
+    },
+    /// The pattern matches any path at all, regardless of the scan root
+    #[serde(rename_all = "camelCase")]
+    ExcludesEverything { pattern: String },
+}
+
+/// Paths that no sensible exclude pattern should match, used to detect over-broad patterns
+const EXCLUDE_PROBE_NAMES: [&str; 2] = ["deptox-probe-a", "deptox-probe-b"];
+
+/// Checks whether an exclude pattern matches every probe path beneath `base`
+fn pattern_excludes_all_under(base: &str, pattern: &str) -> bool {
+    let patterns = [pattern.to_string()];
+    let base = base.trim_end_matches(std::path::MAIN_SEPARATOR);
+
+    EXCLUDE_PROBE_NAMES.iter().all(|probe| {
+        let probe_path = format!("{base}{}{probe}", std::path::MAIN_SEPARATOR);
+        should_exclude_path(&probe_path, &patterns)
+    })
+}
+
+/// Detects exclude patterns that would silently hide the whole scan root
+pub fn exclude_pattern_warnings(settings: &AppSettings) -> Vec<SettingsWarning> {
+    let root_directory = expand_tilde(&settings.root_directory);
+
+    parse_exclude_patterns(&settings.exclude_paths)
+        .into_iter()
+        .filter_map(|pattern| {
+            if pattern_excludes_all_under("", &pattern) {
+                Some(SettingsWarning::ExcludesEverything { pattern })
+            } else if pattern_excludes_all_under(&root_directory, &pattern) {
+                Some(SettingsWarning::RootExcluded {
+                    pattern,
+                    root_directory: root_directory.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Error)]
 pub enum SettingsError {
     #[error("Failed to determine config directory")]
@@ -70,10 +171,58 @@ pub enum SettingsError {
     Serialize(#[source] serde_json::Error),
     #[error("Invalid exclude patterns: {0}")]
     InvalidExcludePatterns(String),
+    #[error("Invalid include patterns: {0}")]
+    InvalidIncludePatterns(String),
+    #[error("Invalid webhook URL: {0}")]
+    InvalidWebhookUrl(String),
+    #[error("Invalid crash report URL: {0}")]
+    InvalidCrashReportUrl(String),
+    #[error("Threshold percentage must be between 1 and 100, got {0}")]
+    InvalidThresholdPercent(u8),
+    #[error("Failed to delete settings: {0}")]
+    Delete(#[source] std::io::Error),
+    #[error("Failed to save webhook URL to the secure store: {0}")]
+    SecureStore(String),
+}
+
+impl SettingsError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoConfigDir => "SETTINGS_NO_CONFIG_DIR",
+            Self::CreateDir(_) => "SETTINGS_CREATE_DIR",
+            Self::Read(_) => "SETTINGS_READ",
+            Self::Write(_) => "SETTINGS_WRITE",
+            Self::Parse(_) => "SETTINGS_PARSE",
+            Self::Serialize(_) => "SETTINGS_SERIALIZE",
+            Self::InvalidExcludePatterns(_) => "SETTINGS_INVALID_EXCLUDE_PATTERNS",
+            Self::InvalidIncludePatterns(_) => "SETTINGS_INVALID_INCLUDE_PATTERNS",
+            Self::InvalidWebhookUrl(_) => "SETTINGS_INVALID_WEBHOOK_URL",
+            Self::InvalidCrashReportUrl(_) => "SETTINGS_INVALID_CRASH_REPORT_URL",
+            Self::InvalidThresholdPercent(_) => "SETTINGS_INVALID_THRESHOLD_PERCENT",
+            Self::Delete(_) => "SETTINGS_DELETE",
+            Self::SecureStore(_) => "SETTINGS_SECURE_STORE",
+        }
+    }
+
+    /// The rejected value for validation failures
+    pub fn context(&self) -> Option<String> {
+        match self {
+            Self::InvalidExcludePatterns(detail)
+            | Self::InvalidIncludePatterns(detail)
+            | Self::InvalidWebhookUrl(detail)
+            | Self::InvalidCrashReportUrl(detail) => Some(detail.clone()),
+            Self::InvalidThresholdPercent(percent) => Some(percent.to_string()),
+            _ => None,
+        }
+    }
 }
 
-fn default_enabled_categories() -> HashSet<DependencyCategory> {
-    DependencyCategory::all().into_iter().collect()
+/// Build output is opt-in, since removing it means a rebuild rather than a reinstall
+pub(crate) fn default_enabled_categories() -> HashSet<DependencyCategory> {
+    DependencyCategory::all()
+        .into_iter()
+        .filter(|category| *category != DependencyCategory::BuildArtifacts)
+        .collect()
 }
 
 fn default_min_size_bytes() -> u64 {
@@ -104,7 +253,144 @@ fn default_font_size() -> FontSize {
     FontSize::Default
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+fn default_follow_symlinks() -> bool {
+    false
+}
+
+fn default_max_files_per_directory() -> usize {
+    config::scanner::DEFAULT_MAX_FILES_PER_DIRECTORY
+}
+
+fn default_category_thresholds() -> HashMap<DependencyCategory, u64> {
+    HashMap::new()
+}
+
+fn default_scan_package_manager_caches() -> bool {
+    false
+}
+
+/// Settings saved before onboarding existed belong to users who are already set up
+fn default_onboarding_completed() -> bool {
+    true
+}
+
+fn default_build_artifact_directories() -> Vec<String> {
+    BUILD_ARTIFACT_DIRECTORIES
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn default_history_retention_days() -> u32 {
+    config::history::DEFAULT_RETENTION_DAYS
+}
+
+fn default_protect_open_projects() -> bool {
+    false
+}
+
+fn default_include_paths() -> String {
+    String::new()
+}
+
+fn default_trash_purge_after_days() -> u32 {
+    0
+}
+
+/// Settings saved before glob patterns have no syntax marker, so they are migrated on load
+fn default_pattern_syntax() -> PatternSyntax {
+    PatternSyntax::Legacy
+}
+
+fn default_notification_contents() -> NotificationContents {
+    NotificationContents::Total
+}
+
+fn default_completion_sounds() -> bool {
+    false
+}
+
+fn default_reclaim_sound_threshold_bytes() -> u64 {
+    config::sounds::DEFAULT_RECLAIM_THRESHOLD_BYTES
+}
+
+fn default_secure_delete() -> bool {
+    false
+}
+
+fn default_incremental_sizing() -> bool {
+    true
+}
+
+fn default_scan_priority() -> ScanPriority {
+    ScanPriority::Low
+}
+
+fn default_install_check() -> InstallCheck {
+    InstallCheck::Warn
+}
+
+fn default_webhook_url() -> Option<String> {
+    None
+}
+
+fn default_local_api_enabled() -> bool {
+    false
+}
+
+fn default_clean_stale_after_days() -> u32 {
+    config::delete::DEFAULT_CLEAN_STALE_AFTER_DAYS
+}
+
+fn default_threshold_mode() -> ThresholdMode {
+    ThresholdMode::Absolute
+}
+
+fn default_threshold_percent() -> u8 {
+    config::defaults::THRESHOLD_PERCENT_OF_FREE
+}
+
+fn default_scan_timeout_minutes() -> u32 {
+    config::scanner::DEFAULT_SCAN_TIMEOUT_MINUTES
+}
+
+fn default_skipped_volume_kinds() -> HashSet<VolumeKind> {
+    HashSet::from([VolumeKind::Network, VolumeKind::CloudStorage])
+}
+
+fn default_crash_report_url() -> Option<String> {
+    None
+}
+
+fn default_usage_analytics() -> bool {
+    false
+}
+
+fn default_size_unit_base() -> SizeUnitBase {
+    SizeUnitBase::Binary
+}
+
+fn default_tray_display_mode() -> TrayDisplayMode {
+    TrayDisplayMode::ExcessOnly
+}
+
+fn default_pin_window() -> bool {
+    false
+}
+
+fn default_scan_on_launch() -> bool {
+    false
+}
+
+fn default_show_window_on_launch() -> bool {
+    false
+}
+
+fn default_auto_update() -> AutoUpdate {
+    AutoUpdate::Prompt
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum FontSize {
     Default,
@@ -112,7 +398,28 @@ pub enum FontSize {
     ExtraLarge,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PatternSyntax {
+    /// The original wildcard syntax, where `*` crossed path separators
+    Legacy,
+    /// Standard globs, see `scanner::patterns`
+    Glob,
+}
+
+/// What the threshold notification lists below the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationContents {
+    /// Only the total size
+    Total,
+    /// The largest dependency folders
+    TopOffenders,
+    /// Size per dependency category
+    CategoryBreakdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RescanInterval {
     OneHour,
@@ -122,7 +429,84 @@ pub enum RescanInterval {
     Never,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How deletes treat a directory a package manager appears to be installing into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InstallCheck {
+    /// Delete without checking
+    Off,
+    /// Ask for confirmation first, like an open project
+    Warn,
+    /// Refuse the delete until the install has finished
+    Refuse,
+}
+
+/// How the background scanner decides the total is too large
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ThresholdMode {
+    /// Compare against `threshold_bytes`
+    Absolute,
+    /// Compare against `threshold_percent` of the free space on the scanned disk
+    PercentOfDisk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TrayDisplayMode {
+    /// Only the icon
+    Off,
+    /// The amount over the threshold, and nothing while within it
+    ExcessOnly,
+    /// The total dependency size, updated as scans and deletes change it
+    AlwaysTotal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AutoUpdate {
+    /// Offer new versions in the tray and let the user choose when to install
+    Prompt,
+    /// Install new versions while no scan is running, then relaunch
+    Silent,
+    /// Never check on a schedule
+    Off,
+}
+
+/// The base sizes are counted in. Both use KB, MB and GB labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SizeUnitBase {
+    /// 1 KB is 1024 bytes
+    Binary,
+    /// 1 KB is 1000 bytes, as macOS shows sizes
+    Decimal,
+}
+
+impl SizeUnitBase {
+    pub fn base(self) -> f64 {
+        match self {
+            Self::Binary => 1024.0,
+            Self::Decimal => 1000.0,
+        }
+    }
+
+    /// Re-expresses a whole number of KB, MB, GB or TB counted in this base as the same
+    /// number counted in `target`, so a 5 GB threshold still reads 5 GB after switching.
+    /// Sizes that aren't a whole number of any unit are returned unchanged.
+    pub fn rebase(self, bytes: u64, target: SizeUnitBase) -> u64 {
+        let from = self.base() as u64;
+        let to = target.base() as u64;
+
+        (1..=4)
+            .rev()
+            .find(|&power| bytes > 0 && bytes % from.pow(power) == 0)
+            .and_then(|power| (bytes / from.pow(power)).checked_mul(to.pow(power)))
+            .unwrap_or(bytes)
+    }
+}
+
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppSettings {
     pub threshold_bytes: u64,
@@ -143,6 +527,113 @@ pub struct AppSettings {
     pub notify_on_threshold_exceeded: bool,
     #[serde(default = "default_font_size")]
     pub font_size: FontSize,
+    #[serde(default = "default_follow_symlinks")]
+    pub follow_symlinks: bool,
+    #[serde(default = "default_max_files_per_directory")]
+    pub max_files_per_directory: usize,
+    /// Optional per-category limits; categories without one only count toward `threshold_bytes`
+    #[serde(default = "default_category_thresholds")]
+    pub category_thresholds: HashMap<DependencyCategory, u64>,
+    /// Also size global package manager caches such as `~/.npm/_cacache`
+    #[serde(default = "default_scan_package_manager_caches")]
+    pub scan_package_manager_caches: bool,
+    /// False until the first-launch category detection has been accepted or skipped
+    #[serde(default = "default_onboarding_completed")]
+    pub onboarding_completed: bool,
+    /// Which `BuildArtifacts` directories to match, e.g. `.next` or `.angular/cache`
+    #[serde(default = "default_build_artifact_directories")]
+    pub build_artifact_directories: Vec<String>,
+    /// Scan history older than this is pruned, zero keeps it forever
+    #[serde(default = "default_history_retention_days")]
+    pub history_retention_days: u32,
+    /// Refuse to delete from a project that an editor, shell or dev server has open
+    /// unless the delete is explicitly confirmed
+    #[serde(default = "default_protect_open_projects")]
+    pub protect_open_projects: bool,
+    /// Comma-separated wildcard patterns like `exclude_paths`; when set, only
+    /// dependency directories matching one of them are scanned
+    #[serde(default = "default_include_paths")]
+    pub include_paths: String,
+    /// Empty deptox's own trash items after this many days, zero keeps them until
+    /// the trash is emptied
+    #[serde(default = "default_trash_purge_after_days")]
+    pub trash_purge_after_days: u32,
+    /// Syntax `exclude_paths` and `include_paths` were written in
+    #[serde(default = "default_pattern_syntax")]
+    pub pattern_syntax: PatternSyntax,
+    #[serde(default = "default_notification_contents")]
+    pub notification_contents: NotificationContents,
+    /// Play a sound when a long scan finishes or a cleanup frees at least
+    /// `reclaim_sound_threshold_bytes`, macOS only
+    #[serde(default = "default_completion_sounds")]
+    pub completion_sounds: bool,
+    /// Zero plays no sound for cleanups
+    #[serde(default = "default_reclaim_sound_threshold_bytes")]
+    pub reclaim_sound_threshold_bytes: u64,
+    /// Overwrite file contents before removing them. Implies `permanent_delete`,
+    /// since overwritten files can't be restored from the trash anyway. On APFS and
+    /// other copy-on-write filesystems the zeros land in new blocks, so the old
+    /// contents can survive on disk until reused, as they can in snapshots.
+    /// Hard-linked files are removed without being overwritten.
+    #[serde(default = "default_secure_delete")]
+    pub secure_delete: bool,
+    /// Reuse sizes from the size index for directories whose fingerprint is unchanged
+    #[serde(default = "default_incremental_sizing")]
+    pub incremental_sizing: bool,
+    /// How hard background scans may work the machine; interactive scans always run at full speed
+    #[serde(default = "default_scan_priority")]
+    pub scan_priority: ScanPriority,
+    /// Whether to check for a package install running in a directory before deleting it
+    #[serde(default = "default_install_check")]
+    pub install_check: InstallCheck,
+    /// Receives a JSON POST after a background scan crosses the threshold or a cleanup
+    /// finishes, e.g. a Slack incoming webhook. None disables it.
+    #[serde(default = "default_webhook_url")]
+    pub webhook_url: Option<String>,
+    /// Serve the localhost API for scripts and launcher extensions, from the next launch
+    #[serde(default = "default_local_api_enabled")]
+    pub local_api_enabled: bool,
+    /// The tray's Clean Stale action deletes dependency directories untouched for this many days
+    #[serde(default = "default_clean_stale_after_days")]
+    pub clean_stale_after_days: u32,
+    /// Whether `threshold_bytes` or `threshold_percent` decides when the total is too large
+    #[serde(default = "default_threshold_mode")]
+    pub threshold_mode: ThresholdMode,
+    /// Percentage of free disk space used in `ThresholdMode::PercentOfDisk`
+    #[serde(default = "default_threshold_percent")]
+    pub threshold_percent: u8,
+    /// Scans stop early and report partial results after this many minutes, zero waits forever
+    #[serde(default = "default_scan_timeout_minutes")]
+    pub scan_timeout_minutes: u32,
+    /// Kinds of storage scans don't descend into, unless the scan root is on one
+    #[serde(default = "default_skipped_volume_kinds")]
+    pub skipped_volume_kinds: HashSet<VolumeKind>,
+    /// Anonymized panic reports are posted here on the next launch. None keeps them off,
+    /// which is the default; panics are still written to the log file either way.
+    #[serde(default = "default_crash_report_url")]
+    pub crash_report_url: Option<String>,
+    /// Share scan durations, entry counts and bytes freed, never paths, to guide performance work
+    #[serde(default = "default_usage_analytics")]
+    pub usage_analytics: bool,
+    /// Whether a kilobyte is 1024 bytes or, as Finder counts it, 1000. Saving a change
+    /// keeps whole-unit thresholds at the same number in the new unit.
+    #[serde(default = "default_size_unit_base")]
+    pub size_unit_base: SizeUnitBase,
+    /// What the menu bar title, or the tray badge off macOS, shows next to the icon
+    #[serde(default = "default_tray_display_mode")]
+    pub tray_display_mode: TrayDisplayMode,
+    /// Keep the window open when it loses focus, so results stay visible beside an editor
+    #[serde(default = "default_pin_window")]
+    pub pin_window: bool,
+    /// Start a scan as soon as the app launches rather than waiting for the background interval
+    #[serde(default = "default_scan_on_launch")]
+    pub scan_on_launch: bool,
+    /// Open the window when launched by hand. Launches at login always start hidden.
+    #[serde(default = "default_show_window_on_launch")]
+    pub show_window_on_launch: bool,
+    /// How scheduled update checks handle a new version
+    #[serde(default = "default_auto_update")]
+    pub auto_update: AutoUpdate,
 }
 
 impl Default for AppSettings {
@@ -162,10 +653,68 @@ impl Default for AppSettings {
             confirm_before_delete: default_confirm_before_delete(),
             notify_on_threshold_exceeded: default_notify_on_threshold_exceeded(),
             font_size: default_font_size(),
+            follow_symlinks: default_follow_symlinks(),
+            max_files_per_directory: default_max_files_per_directory(),
+            category_thresholds: default_category_thresholds(),
+            scan_package_manager_caches: default_scan_package_manager_caches(),
+            onboarding_completed: false,
+            build_artifact_directories: default_build_artifact_directories(),
+            history_retention_days: default_history_retention_days(),
+            protect_open_projects: default_protect_open_projects(),
+            include_paths: default_include_paths(),
+            trash_purge_after_days: default_trash_purge_after_days(),
+            pattern_syntax: PatternSyntax::Glob,
+            notification_contents: default_notification_contents(),
+            completion_sounds: default_completion_sounds(),
+            reclaim_sound_threshold_bytes: default_reclaim_sound_threshold_bytes(),
+            secure_delete: default_secure_delete(),
+            incremental_sizing: default_incremental_sizing(),
+            scan_priority: default_scan_priority(),
+            install_check: default_install_check(),
+            webhook_url: default_webhook_url(),
+            local_api_enabled: default_local_api_enabled(),
+            clean_stale_after_days: default_clean_stale_after_days(),
+            threshold_mode: default_threshold_mode(),
+            threshold_percent: default_threshold_percent(),
+            scan_timeout_minutes: default_scan_timeout_minutes(),
+            skipped_volume_kinds: default_skipped_volume_kinds(),
+            crash_report_url: default_crash_report_url(),
+            usage_analytics: default_usage_analytics(),
+            size_unit_base: default_size_unit_base(),
+            tray_display_mode: default_tray_display_mode(),
+            pin_window: default_pin_window(),
+            scan_on_launch: default_scan_on_launch(),
+            show_window_on_launch: default_show_window_on_launch(),
+            auto_update: default_auto_update(),
         }
     }
 }
 
+impl AppSettings {
+    /// The threshold to compare scan totals against. In `PercentOfDisk` mode this follows the
+    /// free space measured now, falling back to `threshold_bytes` when it can't be measured.
+    pub fn effective_threshold_bytes(&self, disk_usage: Option<&DiskUsage>) -> u64 {
+        match (self.threshold_mode, disk_usage) {
+            (ThresholdMode::PercentOfDisk, Some(usage)) => {
+                usage.free_bytes / 100 * u64::from(self.threshold_percent)
+            }
+            _ => self.threshold_bytes,
+        }
+    }
+
+    /// Carries the size thresholds over from `previous` to `size_unit_base`, keeping the
+    /// numbers the user entered rather than the bytes they came to
+    fn rebase_thresholds(&mut self, previous: SizeUnitBase) {
+        let target = self.size_unit_base;
+        let rebase = |bytes: &mut u64| *bytes = previous.rebase(*bytes, target);
+
+        rebase(&mut self.threshold_bytes);
+        rebase(&mut self.min_size_bytes);
+        rebase(&mut self.reclaim_sound_threshold_bytes);
+        self.category_thresholds.values_mut().for_each(rebase);
+    }
+}
+
 fn get_settings_path() -> Result<PathBuf, SettingsError> {
     let config_dir = dirs::config_dir()
         .ok_or(SettingsError::NoConfigDir)?
@@ -176,59 +725,293 @@ fn get_settings_path() -> Result<PathBuf, SettingsError> {
     Ok(config_dir.join(config::app::SETTINGS_FILENAME))
 }
 
+const WEBHOOK_URL_SECRET: &str = "webhook_url";
+
+/// The webhook URL as last read from or written to the secure store, so settings reads
+/// don't each go to the Keychain. None until it has been read.
+static WEBHOOK_URL: Mutex<Option<Option<String>>> = Mutex::new(None);
+
+fn load_webhook_url() -> Option<String> {
+    let mut cached = WEBHOOK_URL
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    if let Some(webhook_url) = cached.as_ref() {
+        return webhook_url.clone();
+    }
+
+    let webhook_url = secure_store::load_secret(WEBHOOK_URL_SECRET)
+        .map_err(|error| warn!(%error, "Failed to load webhook URL"))
+        .ok()
+        .flatten()
+        .and_then(|secret| String::from_utf8(secret).ok());
+    *cached = Some(webhook_url.clone());
+    webhook_url
+}
+
+/// Saves the webhook URL to the secure store, or removes it for None. Returns false
+/// when the platform has no secure store, so the URL has to stay in the settings file.
+fn store_webhook_url(webhook_url: Option<&str>) -> Result<bool, SettingsError> {
+    if !secure_store::is_available() {
+        return Ok(false);
+    }
+
+    match webhook_url {
+        Some(webhook_url) => {
+            secure_store::store_secret(WEBHOOK_URL_SECRET, webhook_url.as_bytes())
+                .map_err(SettingsError::SecureStore)?;
+        }
+        None => {
+            secure_store::delete_secret(WEBHOOK_URL_SECRET).map_err(SettingsError::SecureStore)?;
+        }
+    }
+
+    *WEBHOOK_URL
+        .lock()
+        .unwrap_or_else(|error| error.into_inner()) = Some(webhook_url.map(str::to_string));
+    Ok(true)
+}
+
+/// What goes in the settings file: everything but a webhook URL kept in the secure store
+fn settings_for_file(settings: &AppSettings, webhook_url_stored: bool) -> AppSettings {
+    AppSettings {
+        webhook_url: settings.webhook_url.clone().filter(|_| !webhook_url_stored),
+        ..settings.clone()
+    }
+}
+
+/// What goes in an export: the webhook and crash report URLs can carry tokens, and
+/// belong to this machine rather than every machine the file is imported on
+fn settings_for_export(settings: &AppSettings) -> AppSettings {
+    AppSettings {
+        webhook_url: None,
+        crash_report_url: None,
+        ..settings.clone()
+    }
+}
+
 #[instrument(skip_all)]
 pub fn get_settings_sync() -> Result<AppSettings, String> {
-    let settings_path = get_settings_path().map_err(|error| error.to_string())?;
+    load_settings().map_err(|error| error.to_string())
+}
+
+fn load_settings() -> Result<AppSettings, SettingsError> {
+    let settings_path = get_settings_path()?;
 
     if !settings_path.exists() {
         debug!("Settings file not found, using defaults");
         return Ok(AppSettings::default());
     }
 
-    let content = fs::read_to_string(&settings_path)
-        .map_err(|error| SettingsError::Read(error).to_string())?;
+    let content = fs::read_to_string(&settings_path).map_err(SettingsError::Read)?;
 
-    serde_json::from_str(&content).map_err(|error| {
+    let mut settings: AppSettings = serde_json::from_str(&content).map_err(|error| {
         warn!(%error, "Failed to parse settings, using defaults");
-        SettingsError::Parse(error).to_string()
-    })
+        SettingsError::Parse(error)
+    })?;
+
+    if migrate_pattern_syntax(&mut settings) {
+        info!("Migrated exclude and include patterns to glob syntax");
+        if let Err(error) = write_settings_file(&settings, &settings_path) {
+            warn!(%error, "Failed to save migrated patterns");
+        }
+    }
+
+    if !secure_store::is_available() {
+        return Ok(settings);
+    }
+
+    // A URL still in the file was saved before the secure store was used for it
+    match settings.webhook_url.as_deref() {
+        None => settings.webhook_url = load_webhook_url(),
+        Some(webhook_url) => {
+            let moved = store_webhook_url(Some(webhook_url)).and_then(|_| {
+                write_settings_file(&settings_for_file(&settings, true), &settings_path)
+            });
+            match moved {
+                Ok(()) => info!("Moved webhook URL to the secure store"),
+                Err(error) => warn!(%error, "Failed to move webhook URL to the secure store"),
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Writes settings as pretty JSON, the format shared by the settings file and exports
+fn write_settings_file(settings: &AppSettings, path: &Path) -> Result<(), SettingsError> {
+    let content = serde_json::to_string_pretty(settings).map_err(SettingsError::Serialize)?;
+    fs::write(path, content).map_err(SettingsError::Write)
+}
+
+/// Reads a settings file exported from this or another machine. Legacy patterns are
+/// migrated and all patterns validated before anything is applied.
+fn read_settings_export(path: &Path) -> Result<AppSettings, SettingsError> {
+    let content = fs::read_to_string(path).map_err(SettingsError::Read)?;
+    let mut settings: AppSettings = serde_json::from_str(&content).map_err(SettingsError::Parse)?;
+
+    migrate_pattern_syntax(&mut settings);
+    validate_exclude_patterns(&settings.exclude_paths)?;
+    validate_include_patterns(&settings.include_paths)?;
+    validate_webhook_url(settings.webhook_url.as_deref())?;
+    validate_crash_report_url(settings.crash_report_url.as_deref())?;
+    validate_threshold_percent(settings.threshold_percent)?;
+
+    Ok(settings)
+}
+
+/// Rewrites patterns saved in the legacy wildcard syntax as equivalent globs.
+/// Returns whether anything changed, so the caller knows to save.
+fn migrate_pattern_syntax(settings: &mut AppSettings) -> bool {
+    if settings.pattern_syntax == PatternSyntax::Glob {
+        return false;
+    }
+
+    settings.exclude_paths = migrate_legacy_patterns(&settings.exclude_paths);
+    settings.include_paths = migrate_legacy_patterns(&settings.include_paths);
+    settings.pattern_syntax = PatternSyntax::Glob;
+    true
 }
 
 #[tauri::command]
-pub async fn get_settings() -> Result<AppSettings, String> {
-    get_settings_sync()
+pub async fn get_settings() -> Result<AppSettings, DeptoxError> {
+    Ok(load_settings()?)
+}
+
+/// Applies the fields the UI sent on top of `current`. The UI only sends the settings it
+/// edits, so everything else keeps its stored value rather than falling back to defaults.
+fn merge_settings(
+    current: &AppSettings,
+    changes: serde_json::Map<String, serde_json::Value>,
+) -> Result<AppSettings, SettingsError> {
+    let mut merged = serde_json::to_value(current).map_err(SettingsError::Serialize)?;
+    if let Some(fields) = merged.as_object_mut() {
+        fields.extend(changes);
+    }
+
+    serde_json::from_value(merged).map_err(SettingsError::Parse)
 }
 
+/// Merges the UI's changes into `previous`, rebasing the thresholds only when the
+/// changes themselves switch `size_unit_base`
+fn apply_settings_changes(
+    previous: &AppSettings,
+    changes: serde_json::Map<String, serde_json::Value>,
+) -> Result<AppSettings, SettingsError> {
+    let sets_unit_base = changes.contains_key("sizeUnitBase");
+    let mut settings = merge_settings(previous, changes)?;
+
+    if sets_unit_base && previous.size_unit_base != settings.size_unit_base {
+        settings.rebase_thresholds(previous.size_unit_base);
+        info!(size_unit_base = ?settings.size_unit_base, "Rebased size thresholds");
+    }
+
+    Ok(settings)
+}
+
+/// Saves the settings the UI changed, leaving the ones it didn't send untouched
 #[tauri::command]
-#[instrument(skip_all)]
-pub async fn save_settings(settings: AppSettings) -> Result<(), String> {
-    validate_exclude_patterns(&settings.exclude_paths).map_err(|error| error.to_string())?;
+#[instrument(skip_all, fields(fields = settings.len()))]
+pub async fn save_settings(
+    settings: serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<SettingsWarning>, DeptoxError> {
+    let previous = load_settings().unwrap_or_default();
+    store_settings(apply_settings_changes(&previous, settings)?)
+}
 
-    let settings_path = get_settings_path().map_err(|error| error.to_string())?;
+/// Changes a few settings from the backend. Unlike `save_settings`, a settings file that
+/// can't be read is an error rather than defaults, so one field never resets the rest.
+pub fn update_settings(
+    changes: serde_json::Map<String, serde_json::Value>,
+) -> Result<Vec<SettingsWarning>, DeptoxError> {
+    let previous = load_settings()?;
+    store_settings(apply_settings_changes(&previous, changes)?)
+}
+
+/// Validates and writes settings. Thresholds are taken as they are, already in the
+/// settings' own `size_unit_base`.
+pub fn store_settings(mut settings: AppSettings) -> Result<Vec<SettingsWarning>, DeptoxError> {
+    // The UI only ever edits patterns loaded through `get_settings_sync`, which are already globs
+    settings.pattern_syntax = PatternSyntax::Glob;
+
+    validate_exclude_patterns(&settings.exclude_paths)?;
+    validate_include_patterns(&settings.include_paths)?;
+    validate_webhook_url(settings.webhook_url.as_deref())?;
+    validate_crash_report_url(settings.crash_report_url.as_deref())?;
+    validate_threshold_percent(settings.threshold_percent)?;
+
+    let warnings = exclude_pattern_warnings(&settings);
+    if !warnings.is_empty() {
+        warn!(?warnings, "Exclude patterns hide the scan root");
+    }
 
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|error| SettingsError::Serialize(error).to_string())?;
+    let settings_path = get_settings_path()?;
 
-    fs::write(&settings_path, content).map_err(|error| SettingsError::Write(error).to_string())?;
+    let webhook_url_stored = store_webhook_url(settings.webhook_url.as_deref())?;
+    write_settings_file(
+        &settings_for_file(&settings, webhook_url_stored),
+        &settings_path,
+    )?;
+    format::set_size_unit_base(settings.size_unit_base);
+    telemetry::crash::set_crash_reporting(settings.crash_report_url.is_some());
 
     debug!(?settings_path, "Settings saved");
+    Ok(warnings)
+}
+
+/// Settings applied from an imported file, for the UI to load in place of its own
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedSettings {
+    pub settings: AppSettings,
+    pub warnings: Vec<SettingsWarning>,
+}
+
+#[tauri::command]
+#[instrument]
+pub async fn export_settings(path: String) -> Result<(), DeptoxError> {
+    let settings = load_settings()?;
+    write_settings_file(&settings_for_export(&settings), Path::new(&path))?;
+
+    info!("Settings exported");
     Ok(())
 }
 
+#[tauri::command]
+#[instrument]
+pub async fn import_settings(path: String) -> Result<ImportedSettings, DeptoxError> {
+    let mut settings = read_settings_export(Path::new(&path))?;
+
+    // Onboarding and the URLs left out of exports belong to this machine rather than
+    // the one the file came from
+    let current = load_settings().unwrap_or_default();
+    settings.onboarding_completed = current.onboarding_completed;
+    settings.webhook_url = current.webhook_url;
+    settings.crash_report_url = current.crash_report_url;
+
+    // The file's thresholds are in its own unit base, so they aren't rebased
+    let warnings = store_settings(settings.clone())?;
+
+    info!("Settings imported");
+    Ok(ImportedSettings { settings, warnings })
+}
+
 #[tauri::command]
 #[instrument(skip_all)]
-pub async fn reset_settings() -> Result<(), String> {
+pub async fn reset_settings() -> Result<(), DeptoxError> {
     info!("Resetting settings to defaults");
 
-    let settings_path = get_settings_path().map_err(|error| error.to_string())?;
+    let settings_path = get_settings_path()?;
 
     if settings_path.exists() {
         fs::remove_file(&settings_path).map_err(|error| {
             warn!(%error, "Failed to delete settings file");
-            format!("Failed to delete settings: {error}")
+            SettingsError::Delete(error)
         })?;
         info!(?settings_path, "Settings file deleted");
     }
+    store_webhook_url(None)?;
+    format::set_size_unit_base(default_size_unit_base());
+    telemetry::crash::set_crash_reporting(default_crash_report_url().is_some());
 
     Ok(())
 }
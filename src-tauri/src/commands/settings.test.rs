@@ -42,7 +42,7 @@ fn test_app_settings_default() {
     assert!(settings.notify_on_threshold_exceeded);
     assert_eq!(settings.font_size, FontSize::Default);
     // All categories enabled by default
-    assert_eq!(settings.enabled_categories.len(), 8);
+    assert_eq!(settings.enabled_categories.len(), 10);
     assert!(settings
         .enabled_categories
         .contains(&DependencyCategory::NodeModules));
@@ -67,6 +67,18 @@ fn test_app_settings_default() {
     assert!(settings
         .enabled_categories
         .contains(&DependencyCategory::GoMod));
+    assert!(settings
+        .enabled_categories
+        .contains(&DependencyCategory::CondaEnv));
+    assert!(settings
+        .enabled_categories
+        .contains(&DependencyCategory::DotNet));
+    // Build output is opt-in
+    assert!(!settings
+        .enabled_categories
+        .contains(&DependencyCategory::BuildArtifacts));
+    // Fresh installs go through onboarding
+    assert!(!settings.onboarding_completed);
 }
 
 #[test]
@@ -86,6 +98,39 @@ fn test_app_settings_serialization_camel_case() {
         confirm_before_delete: true,
         notify_on_threshold_exceeded: false,
         font_size: FontSize::Large,
+        follow_symlinks: true,
+        max_files_per_directory: 500_000,
+        category_thresholds: HashMap::from([(DependencyCategory::Pods, 2_147_483_648)]),
+        scan_package_manager_caches: true,
+        onboarding_completed: false,
+        build_artifact_directories: vec!["dist".to_string()],
+        history_retention_days: 30,
+        protect_open_projects: true,
+        include_paths: "~/code, ~/work".to_string(),
+        trash_purge_after_days: 14,
+        pattern_syntax: PatternSyntax::Glob,
+        notification_contents: NotificationContents::TopOffenders,
+        completion_sounds: true,
+        reclaim_sound_threshold_bytes: 10 * 1024 * 1024 * 1024,
+        secure_delete: true,
+        incremental_sizing: false,
+        scan_priority: ScanPriority::Normal,
+        install_check: InstallCheck::Refuse,
+        webhook_url: Some("https://hooks.slack.com/services/T000/B000/XXXX".to_string()),
+        local_api_enabled: true,
+        clean_stale_after_days: 30,
+        threshold_mode: ThresholdMode::PercentOfDisk,
+        threshold_percent: 25,
+        scan_timeout_minutes: 0,
+        skipped_volume_kinds: HashSet::new(),
+        crash_report_url: Some("https://crashes.example.com/deptox".to_string()),
+        usage_analytics: true,
+        size_unit_base: SizeUnitBase::Decimal,
+        tray_display_mode: TrayDisplayMode::AlwaysTotal,
+        pin_window: true,
+        scan_on_launch: true,
+        show_window_on_launch: true,
+        auto_update: AutoUpdate::Silent,
     };
 
     let json = serde_json::to_string(&settings).unwrap();
@@ -99,6 +144,13 @@ fn test_app_settings_serialization_camel_case() {
     assert!(json.contains("\"confirmBeforeDelete\":true"));
     assert!(json.contains("\"notifyOnThresholdExceeded\":false"));
     assert!(json.contains("\"fontSize\":\"LARGE\""));
+    assert!(json.contains("\"scanPackageManagerCaches\":true"));
+    assert!(json.contains("\"onboardingCompleted\":false"));
+    assert!(json.contains("\"buildArtifactDirectories\":[\"dist\"]"));
+    assert!(json.contains("\"historyRetentionDays\":30"));
+    assert!(json.contains("\"categoryThresholds\":{\"PODS\":2147483648}"));
+    assert!(json.contains("\"maxFilesPerDirectory\":500000"));
+    assert!(json.contains("\"followSymlinks\":true"));
 }
 
 #[test]
@@ -132,7 +184,7 @@ fn test_app_settings_deserialization_without_optional_fields_uses_defaults() {
     assert_eq!(settings.threshold_bytes, 5_368_709_120);
     assert_eq!(settings.root_directory, "/home/user");
     // Should default to all categories
-    assert_eq!(settings.enabled_categories.len(), 8);
+    assert_eq!(settings.enabled_categories.len(), 10);
     // Should default to 0 for min_size_bytes
     assert_eq!(settings.min_size_bytes, 0);
     // Should default to false for permanent_delete
@@ -147,6 +199,8 @@ fn test_app_settings_deserialization_without_optional_fields_uses_defaults() {
     assert!(settings.notify_on_threshold_exceeded);
     // Should default to Default for font_size
     assert_eq!(settings.font_size, FontSize::Default);
+    // Existing users should not be sent through onboarding again
+    assert!(settings.onboarding_completed);
 }
 
 #[test]
@@ -181,6 +235,39 @@ fn test_save_and_load_settings() {
         confirm_before_delete: true,
         notify_on_threshold_exceeded: false,
         font_size: FontSize::ExtraLarge,
+        follow_symlinks: true,
+        max_files_per_directory: 500_000,
+        category_thresholds: HashMap::from([(DependencyCategory::Pods, 2_147_483_648)]),
+        scan_package_manager_caches: true,
+        onboarding_completed: false,
+        build_artifact_directories: vec!["dist".to_string()],
+        history_retention_days: 30,
+        protect_open_projects: true,
+        include_paths: "~/code, ~/work".to_string(),
+        trash_purge_after_days: 14,
+        pattern_syntax: PatternSyntax::Glob,
+        notification_contents: NotificationContents::TopOffenders,
+        completion_sounds: true,
+        reclaim_sound_threshold_bytes: 10 * 1024 * 1024 * 1024,
+        secure_delete: true,
+        incremental_sizing: false,
+        scan_priority: ScanPriority::Normal,
+        install_check: InstallCheck::Refuse,
+        webhook_url: Some("https://hooks.slack.com/services/T000/B000/XXXX".to_string()),
+        local_api_enabled: true,
+        clean_stale_after_days: 30,
+        threshold_mode: ThresholdMode::PercentOfDisk,
+        threshold_percent: 25,
+        scan_timeout_minutes: 0,
+        skipped_volume_kinds: HashSet::new(),
+        crash_report_url: Some("https://crashes.example.com/deptox".to_string()),
+        usage_analytics: true,
+        size_unit_base: SizeUnitBase::Decimal,
+        tray_display_mode: TrayDisplayMode::AlwaysTotal,
+        pin_window: true,
+        scan_on_launch: true,
+        show_window_on_launch: true,
+        auto_update: AutoUpdate::Silent,
     };
 
     save_settings_to_path(&original, &settings_path).unwrap();
@@ -199,6 +286,7 @@ fn test_save_and_load_settings() {
         original.notify_on_threshold_exceeded
     );
     assert_eq!(loaded.font_size, original.font_size);
+    assert_eq!(loaded.notification_contents, original.notification_contents);
 }
 
 #[test]
@@ -254,6 +342,39 @@ fn test_settings_roundtrip_preserves_values() {
             confirm_before_delete: default_confirm_before_delete(),
             notify_on_threshold_exceeded: default_notify_on_threshold_exceeded(),
             font_size: default_font_size(),
+            follow_symlinks: default_follow_symlinks(),
+            max_files_per_directory: default_max_files_per_directory(),
+            category_thresholds: default_category_thresholds(),
+            scan_package_manager_caches: default_scan_package_manager_caches(),
+            onboarding_completed: default_onboarding_completed(),
+            build_artifact_directories: default_build_artifact_directories(),
+            history_retention_days: default_history_retention_days(),
+            protect_open_projects: default_protect_open_projects(),
+            include_paths: default_include_paths(),
+            trash_purge_after_days: default_trash_purge_after_days(),
+            pattern_syntax: default_pattern_syntax(),
+            notification_contents: default_notification_contents(),
+            completion_sounds: default_completion_sounds(),
+            reclaim_sound_threshold_bytes: default_reclaim_sound_threshold_bytes(),
+            secure_delete: default_secure_delete(),
+            incremental_sizing: default_incremental_sizing(),
+            scan_priority: default_scan_priority(),
+            install_check: default_install_check(),
+            webhook_url: default_webhook_url(),
+            local_api_enabled: default_local_api_enabled(),
+            clean_stale_after_days: default_clean_stale_after_days(),
+            threshold_mode: default_threshold_mode(),
+            threshold_percent: default_threshold_percent(),
+            scan_timeout_minutes: default_scan_timeout_minutes(),
+            skipped_volume_kinds: default_skipped_volume_kinds(),
+            crash_report_url: default_crash_report_url(),
+            usage_analytics: default_usage_analytics(),
+            size_unit_base: default_size_unit_base(),
+            tray_display_mode: default_tray_display_mode(),
+            pin_window: default_pin_window(),
+            scan_on_launch: default_scan_on_launch(),
+            show_window_on_launch: default_show_window_on_launch(),
+            auto_update: default_auto_update(),
         };
 
         save_settings_to_path(&original, &settings_path).unwrap();
@@ -299,6 +420,18 @@ fn test_settings_error_display_all_variants() {
     assert!(write_error.to_string().contains("write"));
 }
 
+#[test]
+fn test_notification_contents_serialization() {
+    assert_eq!(
+        serde_json::to_string(&NotificationContents::TopOffenders).unwrap(),
+        "\"TOP_OFFENDERS\""
+    );
+    assert_eq!(
+        serde_json::from_str::<NotificationContents>("\"CATEGORY_BREAKDOWN\"").unwrap(),
+        NotificationContents::CategoryBreakdown
+    );
+}
+
 #[test]
 fn test_rescan_interval_serialization() {
     assert_eq!(
@@ -349,7 +482,7 @@ fn test_rescan_interval_deserialization() {
 
 #[test]
 fn test_default_functions() {
-    assert_eq!(default_enabled_categories().len(), 8);
+    assert_eq!(default_enabled_categories().len(), 10);
     assert_eq!(default_min_size_bytes(), 0);
     assert!(!default_permanent_delete());
     assert!(default_exclude_paths().is_empty());
@@ -357,6 +490,42 @@ fn test_default_functions() {
     assert!(default_confirm_before_delete());
     assert!(default_notify_on_threshold_exceeded());
     assert_eq!(default_font_size(), FontSize::Default);
+    assert!(!default_follow_symlinks());
+    assert_eq!(default_max_files_per_directory(), 2_000_000);
+    assert!(default_category_thresholds().is_empty());
+    assert!(!default_scan_package_manager_caches());
+    assert!(default_onboarding_completed());
+    assert_eq!(default_build_artifact_directories().len(), 6);
+    assert_eq!(default_history_retention_days(), 365);
+    assert!(!default_protect_open_projects());
+    assert!(default_include_paths().is_empty());
+    assert_eq!(default_trash_purge_after_days(), 0);
+    assert_eq!(default_pattern_syntax(), PatternSyntax::Legacy);
+    assert_eq!(default_notification_contents(), NotificationContents::Total);
+    assert!(!default_completion_sounds());
+    assert_eq!(
+        default_reclaim_sound_threshold_bytes(),
+        config::sounds::DEFAULT_RECLAIM_THRESHOLD_BYTES
+    );
+    assert!(!default_secure_delete());
+    assert!(default_incremental_sizing());
+    assert_eq!(default_scan_priority(), ScanPriority::Low);
+    assert_eq!(default_install_check(), InstallCheck::Warn);
+    assert_eq!(default_webhook_url(), None);
+    assert!(!default_local_api_enabled());
+    assert_eq!(default_clean_stale_after_days(), 90);
+    assert_eq!(default_threshold_mode(), ThresholdMode::Absolute);
+    assert_eq!(default_threshold_percent(), 10);
+    assert_eq!(default_scan_timeout_minutes(), 20);
+    assert!(!default_skipped_volume_kinds().contains(&VolumeKind::External));
+    assert_eq!(default_crash_report_url(), None);
+    assert!(!default_usage_analytics());
+    assert_eq!(default_size_unit_base(), SizeUnitBase::Binary);
+    assert_eq!(default_tray_display_mode(), TrayDisplayMode::ExcessOnly);
+    assert!(!default_pin_window());
+    assert!(!default_scan_on_launch());
+    assert!(!default_show_window_on_launch());
+    assert_eq!(default_auto_update(), AutoUpdate::Prompt);
 }
 
 #[test]
@@ -418,12 +587,12 @@ async fn test_save_and_reset_settings_async() {
     let original_settings = get_settings().await.unwrap_or_default();
     let test_threshold = original_settings.threshold_bytes + 1000;
 
-    let new_settings = AppSettings {
-        threshold_bytes: test_threshold,
-        ..original_settings.clone()
-    };
+    let changes = serde_json::Map::from_iter([(
+        "thresholdBytes".to_string(),
+        serde_json::json!(test_threshold),
+    )]);
 
-    let save_result = save_settings(new_settings.clone()).await;
+    let save_result = save_settings(changes).await;
     assert!(save_result.is_ok(), "save_settings should succeed");
 
     let loaded = get_settings().await.unwrap();
@@ -443,6 +612,169 @@ async fn test_save_and_reset_settings_async() {
     );
 }
 
+#[test]
+fn test_merge_settings_keeps_fields_not_sent() {
+    let current = AppSettings {
+        secure_delete: true,
+        scan_on_launch: true,
+        webhook_url: Some("https://hooks.example.com/deptox".to_string()),
+        ..AppSettings::default()
+    };
+    let changes = serde_json::Map::from_iter([
+        ("thresholdBytes".to_string(), serde_json::json!(42)),
+        ("permanentDelete".to_string(), serde_json::json!(true)),
+    ]);
+
+    let merged = merge_settings(&current, changes).unwrap();
+
+    assert_eq!(merged.threshold_bytes, 42);
+    assert!(merged.permanent_delete);
+    assert!(merged.secure_delete);
+    assert!(merged.scan_on_launch);
+    assert_eq!(merged.webhook_url, current.webhook_url);
+}
+
+#[test]
+fn test_apply_settings_changes_rebases_when_unit_base_sent() {
+    let previous = AppSettings {
+        threshold_bytes: 5 * 1024 * 1024 * 1024,
+        ..AppSettings::default()
+    };
+    let changes =
+        serde_json::Map::from_iter([("sizeUnitBase".to_string(), serde_json::json!("DECIMAL"))]);
+
+    let settings = apply_settings_changes(&previous, changes).unwrap();
+
+    assert_eq!(settings.size_unit_base, SizeUnitBase::Decimal);
+    assert_eq!(settings.threshold_bytes, 5_000_000_000);
+}
+
+#[test]
+fn test_apply_settings_changes_keeps_thresholds_when_unit_base_not_sent() {
+    let previous = AppSettings {
+        size_unit_base: SizeUnitBase::Decimal,
+        ..AppSettings::default()
+    };
+    let changes = serde_json::Map::from_iter([(
+        "thresholdBytes".to_string(),
+        serde_json::json!(5u64 * 1024 * 1024 * 1024),
+    )]);
+
+    let settings = apply_settings_changes(&previous, changes).unwrap();
+
+    assert_eq!(settings.size_unit_base, SizeUnitBase::Decimal);
+    assert_eq!(settings.threshold_bytes, 5 * 1024 * 1024 * 1024);
+}
+
+#[test]
+fn test_merge_settings_rejects_invalid_values() {
+    let changes =
+        serde_json::Map::from_iter([("thresholdBytes".to_string(), serde_json::json!("lots"))]);
+
+    assert!(matches!(
+        merge_settings(&AppSettings::default(), changes),
+        Err(SettingsError::Parse(_))
+    ));
+}
+
+#[test]
+fn test_exported_settings_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("deptox-settings.json");
+
+    let original = AppSettings {
+        threshold_bytes: 42 * 1024 * 1024 * 1024,
+        exclude_paths: "archive, ~/work/keep/**".to_string(),
+        notification_contents: NotificationContents::CategoryBreakdown,
+        ..AppSettings::default()
+    };
+
+    write_settings_file(&original, &export_path).unwrap();
+    let imported = read_settings_export(&export_path).unwrap();
+
+    assert_eq!(imported.threshold_bytes, original.threshold_bytes);
+    assert_eq!(imported.exclude_paths, original.exclude_paths);
+    assert_eq!(
+        imported.notification_contents,
+        NotificationContents::CategoryBreakdown
+    );
+    assert_eq!(imported.pattern_syntax, PatternSyntax::Glob);
+}
+
+#[test]
+fn test_exports_leave_out_machine_urls() {
+    let settings = AppSettings {
+        webhook_url: Some("https://hooks.slack.com/services/T0/B0/secret".to_string()),
+        crash_report_url: Some("https://crashes.example.com/report?token=secret".to_string()),
+        threshold_bytes: 42,
+        ..AppSettings::default()
+    };
+
+    let exported = settings_for_export(&settings);
+
+    assert_eq!(exported.webhook_url, None);
+    assert_eq!(exported.crash_report_url, None);
+    assert_eq!(exported.threshold_bytes, 42);
+}
+
+#[test]
+fn test_settings_file_leaves_out_stored_webhook_url() {
+    let settings = AppSettings {
+        webhook_url: Some("https://hooks.example.com/secret".to_string()),
+        ..AppSettings::default()
+    };
+
+    assert_eq!(settings_for_file(&settings, true).webhook_url, None);
+    assert_eq!(
+        settings_for_file(&settings, false).webhook_url,
+        settings.webhook_url
+    );
+}
+
+#[test]
+fn test_import_migrates_legacy_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("deptox-settings.json");
+    fs::write(
+        &export_path,
+        r#"{"thresholdBytes": 1024, "rootDirectory": "/", "excludePaths": "*/archive/*"}"#,
+    )
+    .unwrap();
+
+    let imported = read_settings_export(&export_path).unwrap();
+
+    assert_eq!(imported.exclude_paths, "**/archive/**");
+    assert_eq!(imported.pattern_syntax, PatternSyntax::Glob);
+}
+
+#[test]
+fn test_import_rejects_invalid_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let export_path = temp_dir.path().join("deptox-settings.json");
+    let settings = AppSettings {
+        include_paths: "*/".repeat(config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN + 1),
+        ..AppSettings::default()
+    };
+    write_settings_file(&settings, &export_path).unwrap();
+
+    let error = read_settings_export(&export_path).unwrap_err();
+
+    assert!(matches!(error, SettingsError::InvalidIncludePatterns(_)));
+}
+
+#[test]
+fn test_import_reports_unreadable_files() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let missing = read_settings_export(&temp_dir.path().join("missing.json")).unwrap_err();
+    assert!(matches!(missing, SettingsError::Read(_)));
+
+    let malformed_path = temp_dir.path().join("malformed.json");
+    fs::write(&malformed_path, "not json").unwrap();
+    let malformed = read_settings_export(&malformed_path).unwrap_err();
+    assert!(matches!(malformed, SettingsError::Parse(_)));
+}
+
 #[test]
 fn test_settings_error_parse() {
     let error = SettingsError::Parse(serde_json::from_str::<AppSettings>("invalid").unwrap_err());
@@ -501,3 +833,281 @@ fn test_font_size_copy() {
     let copied = original;
     assert_eq!(original, copied);
 }
+
+#[test]
+fn test_validate_include_patterns_accepts_typical_patterns() {
+    assert!(validate_include_patterns("").is_ok());
+    assert!(validate_include_patterns("~/code, ~/work, */clients/*").is_ok());
+}
+
+#[test]
+fn test_validate_path_patterns_counts_double_star_once() {
+    let limit = config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN;
+
+    assert!(validate_exclude_patterns(&"**/".repeat(limit)).is_ok());
+    assert!(validate_exclude_patterns(&"**/".repeat(limit + 1)).is_err());
+}
+
+#[test]
+fn test_migrated_legacy_patterns_stay_valid() {
+    let legacy = "*/".repeat(config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN);
+
+    assert!(validate_exclude_patterns(&legacy).is_ok());
+    assert!(validate_exclude_patterns(&migrate_legacy_patterns(&legacy)).is_ok());
+}
+
+#[test]
+fn test_validate_include_patterns_mirrors_exclude_limits() {
+    let too_many = vec!["~/code"; config::exclude_patterns::MAX_PATTERN_COUNT + 1].join(",");
+    let error = validate_include_patterns(&too_many).unwrap_err();
+    assert!(matches!(error, SettingsError::InvalidIncludePatterns(_)));
+    assert!(error.to_string().contains("Too many include patterns"));
+
+    let wildcards = "*/".repeat(config::exclude_patterns::MAX_WILDCARDS_PER_PATTERN + 1);
+    assert!(validate_include_patterns(&wildcards).is_err());
+    assert!(validate_exclude_patterns(&wildcards).is_err());
+
+    let too_long = "a".repeat(config::exclude_patterns::MAX_TOTAL_LENGTH + 1);
+    assert!(validate_include_patterns(&too_long)
+        .unwrap_err()
+        .to_string()
+        .contains("Total include patterns length"));
+}
+
+#[test]
+fn test_validate_webhook_url() {
+    assert!(validate_webhook_url(None).is_ok());
+    assert!(validate_webhook_url(Some("https://hooks.slack.com/services/T000/B000/XXXX")).is_ok());
+    assert!(validate_webhook_url(Some("http://localhost:8080/hooks/deptox")).is_ok());
+
+    assert!(validate_webhook_url(Some("not a url"))
+        .unwrap_err()
+        .to_string()
+        .starts_with("Invalid webhook URL"));
+    assert!(validate_webhook_url(Some("file:///etc/passwd")).is_err());
+}
+
+#[test]
+fn test_validate_crash_report_url() {
+    assert!(validate_crash_report_url(None).is_ok());
+    assert!(validate_crash_report_url(Some("https://crashes.example.com/deptox")).is_ok());
+    assert!(validate_crash_report_url(Some("file:///tmp/crash"))
+        .unwrap_err()
+        .to_string()
+        .starts_with("Invalid crash report URL"));
+}
+
+#[test]
+fn test_validate_threshold_percent() {
+    assert!(validate_threshold_percent(1).is_ok());
+    assert!(validate_threshold_percent(100).is_ok());
+    assert!(matches!(
+        validate_threshold_percent(0),
+        Err(SettingsError::InvalidThresholdPercent(0))
+    ));
+    assert!(validate_threshold_percent(101).is_err());
+}
+
+#[test]
+fn test_effective_threshold_absolute_ignores_disk() {
+    let settings = AppSettings {
+        threshold_bytes: 5_000,
+        ..AppSettings::default()
+    };
+    let usage = DiskUsage {
+        total_bytes: 1_000_000,
+        free_bytes: 200_000,
+    };
+
+    assert_eq!(settings.effective_threshold_bytes(Some(&usage)), 5_000);
+}
+
+#[test]
+fn test_effective_threshold_percent_of_free_space() {
+    let settings = AppSettings {
+        threshold_bytes: 5_000,
+        threshold_mode: ThresholdMode::PercentOfDisk,
+        threshold_percent: 25,
+        ..AppSettings::default()
+    };
+    let usage = DiskUsage {
+        total_bytes: 1_000_000,
+        free_bytes: 200_000,
+    };
+
+    assert_eq!(settings.effective_threshold_bytes(Some(&usage)), 50_000);
+    assert_eq!(settings.effective_threshold_bytes(None), 5_000);
+}
+
+#[test]
+fn test_threshold_mode_serialization() {
+    assert_eq!(
+        serde_json::to_string(&ThresholdMode::PercentOfDisk).unwrap(),
+        "\"PERCENT_OF_DISK\""
+    );
+}
+
+#[test]
+fn test_size_unit_base_rebase_keeps_whole_units() {
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    assert_eq!(
+        SizeUnitBase::Binary.rebase(5 * GIB, SizeUnitBase::Decimal),
+        5_000_000_000
+    );
+    assert_eq!(
+        SizeUnitBase::Decimal.rebase(5_000_000_000, SizeUnitBase::Binary),
+        5 * GIB
+    );
+    // 1.5 GiB is a whole 1536 MiB
+    assert_eq!(
+        SizeUnitBase::Binary.rebase(3 * GIB / 2, SizeUnitBase::Decimal),
+        1_536_000_000
+    );
+}
+
+#[test]
+fn test_size_unit_base_rebase_leaves_other_sizes() {
+    assert_eq!(SizeUnitBase::Binary.rebase(0, SizeUnitBase::Decimal), 0);
+    assert_eq!(
+        SizeUnitBase::Binary.rebase(1_500, SizeUnitBase::Decimal),
+        1_500
+    );
+    // Too large to express in the other base, so kept as it was
+    let largest_whole_terabytes = u64::MAX / 1_000_000_000_000 * 1_000_000_000_000;
+    assert_eq!(
+        SizeUnitBase::Decimal.rebase(largest_whole_terabytes, SizeUnitBase::Binary),
+        largest_whole_terabytes
+    );
+    assert_eq!(
+        SizeUnitBase::Binary.rebase(2048, SizeUnitBase::Binary),
+        2048
+    );
+}
+
+#[test]
+fn test_rebase_thresholds_after_switching_to_decimal() {
+    let mut settings = AppSettings {
+        size_unit_base: SizeUnitBase::Decimal,
+        category_thresholds: HashMap::from([(
+            DependencyCategory::NodeModules,
+            2 * 1024 * 1024 * 1024,
+        )]),
+        ..AppSettings::default()
+    };
+
+    settings.rebase_thresholds(SizeUnitBase::Binary);
+
+    assert_eq!(settings.threshold_bytes, 5_000_000_000);
+    assert_eq!(
+        settings.category_thresholds[&DependencyCategory::NodeModules],
+        2_000_000_000
+    );
+    assert_eq!(settings.min_size_bytes, 0);
+}
+
+#[test]
+fn test_size_unit_base_serialization() {
+    assert_eq!(
+        serde_json::to_string(&SizeUnitBase::Decimal).unwrap(),
+        "\"DECIMAL\""
+    );
+}
+
+#[test]
+fn test_migrate_pattern_syntax_rewrites_legacy_patterns() {
+    let mut settings = AppSettings {
+        exclude_paths: "*/active-*, legacy-project".to_string(),
+        include_paths: "~/code".to_string(),
+        pattern_syntax: PatternSyntax::Legacy,
+        ..AppSettings::default()
+    };
+
+    assert!(migrate_pattern_syntax(&mut settings));
+    assert_eq!(settings.exclude_paths, "**/active-**, **legacy-project**");
+    assert_eq!(settings.include_paths, "~/code**");
+    assert_eq!(settings.pattern_syntax, PatternSyntax::Glob);
+
+    // Already migrated, so a second pass leaves the patterns alone
+    assert!(!migrate_pattern_syntax(&mut settings));
+    assert_eq!(settings.exclude_paths, "**/active-**, **legacy-project**");
+}
+
+#[test]
+fn test_settings_without_pattern_syntax_are_legacy() {
+    let json = r#"{"thresholdBytes": 1024, "rootDirectory": "/test", "excludePaths": "*/skip/*"}"#;
+    let settings: AppSettings = serde_json::from_str(json).unwrap();
+    assert_eq!(settings.pattern_syntax, PatternSyntax::Legacy);
+    assert_eq!(AppSettings::default().pattern_syntax, PatternSyntax::Glob);
+}
+
+fn settings_with_excludes(root_directory: &str, exclude_paths: &str) -> AppSettings {
+    AppSettings {
+        root_directory: root_directory.to_string(),
+        exclude_paths: exclude_paths.to_string(),
+        ..AppSettings::default()
+    }
+}
+
+#[test]
+fn test_exclude_pattern_warnings_none_for_targeted_patterns() {
+    let settings = settings_with_excludes("/Users/test", "*/archive/*, legacy-project");
+    assert!(exclude_pattern_warnings(&settings).is_empty());
+}
+
+#[test]
+fn test_exclude_pattern_warnings_detects_root_match() {
+    let settings = settings_with_excludes("/Users/test", "test, */old/*");
+
+    assert_eq!(
+        exclude_pattern_warnings(&settings),
+        vec![SettingsWarning::RootExcluded {
+            pattern: "test".to_string(),
+            root_directory: "/Users/test".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_exclude_pattern_warnings_detects_pattern_matching_root_directory() {
+    // A pattern matching a directory excludes everything inside it
+    let settings = settings_with_excludes("/Users/test", "*/test");
+
+    assert_eq!(
+        exclude_pattern_warnings(&settings),
+        vec![SettingsWarning::RootExcluded {
+            pattern: "*/test".to_string(),
+            root_directory: "/Users/test".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_exclude_pattern_warnings_ignores_pattern_below_root() {
+    let settings = settings_with_excludes("/Users/test", "/Users/test/archive/**");
+    assert!(exclude_pattern_warnings(&settings).is_empty());
+}
+
+#[test]
+fn test_exclude_pattern_warnings_detects_match_everything() {
+    let settings = settings_with_excludes("/Users/test", "*");
+
+    assert_eq!(
+        exclude_pattern_warnings(&settings),
+        vec![SettingsWarning::ExcludesEverything {
+            pattern: "*".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_settings_warning_serialization() {
+    let warning = SettingsWarning::RootExcluded {
+        pattern: "test".to_string(),
+        root_directory: "/Users/test".to_string(),
+    };
+
+    let json = serde_json::to_string(&warning).unwrap();
+    assert!(json.contains("\"kind\":\"ROOT_EXCLUDED\""));
+    assert!(json.contains("\"rootDirectory\":\"/Users/test\""));
+}
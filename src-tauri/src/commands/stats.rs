@@ -0,0 +1,156 @@
+use crate::config;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::scanner::DependencyCategory;
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+
+/// Batch deletes record concurrently, so stats are kept in memory and written on flush
+static STATS: BufferedJsonFile<CleanupStats> =
+    BufferedJsonFile::new(get_stats_path, load_stats_from, save_stats_to);
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryCleanup {
+    pub deletions: u64,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyCleanup {
+    /// Calendar month in `YYYY-MM` form (UTC)
+    pub month: String,
+    pub deletions: u64,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupStats {
+    pub total_bytes_freed: u64,
+    pub total_deletions: u64,
+    #[serde(default)]
+    pub by_category: HashMap<DependencyCategory, CategoryCleanup>,
+    /// Oldest month first
+    #[serde(default)]
+    pub monthly: Vec<MonthlyCleanup>,
+    pub first_cleanup_ms: Option<u64>,
+}
+
+impl CleanupStats {
+    fn record(
+        &mut self,
+        category: Option<DependencyCategory>,
+        bytes_freed: u64,
+        timestamp_ms: u64,
+    ) {
+        self.total_bytes_freed += bytes_freed;
+        self.total_deletions += 1;
+        if self.first_cleanup_ms.is_none() {
+            self.first_cleanup_ms = Some(timestamp_ms);
+        }
+
+        if let Some(category) = category {
+            let category_stats = self.by_category.entry(category).or_default();
+            category_stats.deletions += 1;
+            category_stats.bytes_freed += bytes_freed;
+        }
+
+        let month = month_key(timestamp_ms);
+        match self.monthly.iter_mut().find(|entry| entry.month == month) {
+            Some(entry) => {
+                entry.deletions += 1;
+                entry.bytes_freed += bytes_freed;
+            }
+            None => {
+                self.monthly.push(MonthlyCleanup {
+                    month,
+                    deletions: 1,
+                    bytes_freed,
+                });
+                self.monthly
+                    .sort_by(|first, second| first.month.cmp(&second.month));
+            }
+        }
+    }
+}
+
+/// Formats a Unix timestamp as a `YYYY-MM` month key using the civil-from-days algorithm
+fn month_key(timestamp_ms: u64) -> String {
+    let days = (timestamp_ms / MILLISECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}")
+}
+
+fn get_stats_path() -> Result<PathBuf, String> {
+    config_path(config::app::STATS_FILENAME)
+}
+
+fn load_stats_from(path: &Path) -> CleanupStats {
+    if !path.exists() {
+        return CleanupStats::default();
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| {
+            warn!("Failed to load cleanup stats, starting fresh");
+            CleanupStats::default()
+        })
+}
+
+fn save_stats_to(path: &Path, stats: &CleanupStats) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(stats)
+        .map_err(|error| format!("Failed to serialize cleanup stats: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write cleanup stats: {error}"))
+}
+
+/// Adds a completed deletion to the cleanup statistics; written to disk on the next flush.
+pub fn record_cleanup(category: Option<DependencyCategory>, bytes_freed: u64) {
+    let timestamp_ms = time::now_ms();
+    if let Err(error) = STATS.update(|stats| stats.record(category, bytes_freed, timestamp_ms)) {
+        warn!(%error, "Failed to record cleanup stats");
+    }
+}
+
+/// Writes pending cleanup stats to disk
+pub fn flush_stats() -> Result<bool, String> {
+    STATS.flush()
+}
+
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_cleanup_stats() -> Result<CleanupStats, String> {
+    let stats = STATS.read(CleanupStats::clone)?;
+
+    debug!(
+        total_bytes_freed = stats.total_bytes_freed,
+        total_deletions = stats.total_deletions,
+        "Loaded cleanup stats"
+    );
+    Ok(stats)
+}
+
+#[cfg(test)]
+#[path = "stats.test.rs"]
+mod tests;
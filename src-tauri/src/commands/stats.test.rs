@@ -0,0 +1,99 @@
+use super::*;
+use tempfile::TempDir;
+
+// 2026-01-14T12:00:00Z
+const JANUARY_2026_MS: u64 = 1_768_392_000_000;
+// 2026-02-01T00:00:00Z
+const FEBRUARY_2026_MS: u64 = 1_769_904_000_000;
+
+#[test]
+fn test_month_key_epoch() {
+    assert_eq!(month_key(0), "1970-01");
+}
+
+#[test]
+fn test_month_key_known_dates() {
+    assert_eq!(month_key(JANUARY_2026_MS), "2026-01");
+    assert_eq!(month_key(FEBRUARY_2026_MS), "2026-02");
+    // 2024-02-29T23:59:59Z (leap day)
+    assert_eq!(month_key(1_709_251_199_000), "2024-02");
+    // 2024-03-01T00:00:00Z
+    assert_eq!(month_key(1_709_251_200_000), "2024-03");
+    // 1999-12-31T23:59:59Z
+    assert_eq!(month_key(946_684_799_000), "1999-12");
+}
+
+#[test]
+fn test_cleanup_stats_record_accumulates_totals() {
+    let mut stats = CleanupStats::default();
+    stats.record(Some(DependencyCategory::NodeModules), 1000, JANUARY_2026_MS);
+    stats.record(Some(DependencyCategory::NodeModules), 500, JANUARY_2026_MS);
+    stats.record(Some(DependencyCategory::Pods), 250, FEBRUARY_2026_MS);
+
+    assert_eq!(stats.total_bytes_freed, 1750);
+    assert_eq!(stats.total_deletions, 3);
+    assert_eq!(stats.first_cleanup_ms, Some(JANUARY_2026_MS));
+
+    let node_modules = &stats.by_category[&DependencyCategory::NodeModules];
+    assert_eq!(node_modules.deletions, 2);
+    assert_eq!(node_modules.bytes_freed, 1500);
+    assert_eq!(stats.by_category[&DependencyCategory::Pods].deletions, 1);
+}
+
+#[test]
+fn test_cleanup_stats_record_groups_by_month() {
+    let mut stats = CleanupStats::default();
+    stats.record(None, 300, FEBRUARY_2026_MS);
+    stats.record(None, 100, JANUARY_2026_MS);
+    stats.record(None, 200, JANUARY_2026_MS);
+
+    assert_eq!(stats.monthly.len(), 2);
+    assert_eq!(stats.monthly[0].month, "2026-01");
+    assert_eq!(stats.monthly[0].bytes_freed, 300);
+    assert_eq!(stats.monthly[0].deletions, 2);
+    assert_eq!(stats.monthly[1].month, "2026-02");
+    assert_eq!(stats.monthly[1].bytes_freed, 300);
+}
+
+#[test]
+fn test_cleanup_stats_record_without_category() {
+    let mut stats = CleanupStats::default();
+    stats.record(None, 42, JANUARY_2026_MS);
+
+    assert_eq!(stats.total_deletions, 1);
+    assert!(stats.by_category.is_empty());
+}
+
+#[test]
+fn test_cleanup_stats_serialization_camel_case() {
+    let mut stats = CleanupStats::default();
+    stats.record(Some(DependencyCategory::GoMod), 64, JANUARY_2026_MS);
+
+    let json = serde_json::to_string(&stats).unwrap();
+    assert!(json.contains("\"totalBytesFreed\":64"));
+    assert!(json.contains("\"byCategory\":{\"GO_MOD\""));
+    assert!(json.contains("\"month\":\"2026-01\""));
+    assert!(json.contains("\"firstCleanupMs\""));
+}
+
+#[test]
+fn test_save_and_load_stats_roundtrip() {
+    let temp_dir = TempDir::new().unwrap();
+    let stats_path = temp_dir.path().join("stats.json");
+
+    let mut stats = CleanupStats::default();
+    stats.record(Some(DependencyCategory::Composer), 2048, JANUARY_2026_MS);
+
+    save_stats_to(&stats_path, &stats).unwrap();
+    assert_eq!(load_stats_from(&stats_path), stats);
+}
+
+#[test]
+fn test_load_stats_from_missing_or_corrupt_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let stats_path = temp_dir.path().join("stats.json");
+    assert_eq!(load_stats_from(&stats_path), CleanupStats::default());
+
+    fs::write(&stats_path, "{broken").unwrap();
+    assert_eq!(load_stats_from(&stats_path), CleanupStats::default());
+}
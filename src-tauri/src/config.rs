@@ -1,17 +1,83 @@
 pub mod app {
+    use std::time::Duration;
+
     pub const APP_CONFIG_DIR: &str = "deptox";
     pub const SETTINGS_FILENAME: &str = "settings.json";
     pub const LICENSE_FILENAME: &str = "license.json";
+    pub const DEVICE_ACTIVATION_FILENAME: &str = "device_activation.json";
+    pub const JOURNAL_FILENAME: &str = "deletion_journal.json";
+    pub const STATS_FILENAME: &str = "cleanup_stats.json";
+    pub const OVERRIDES_FILENAME: &str = "overrides.json";
+    pub const SCAN_CACHE_FILENAME: &str = "scan_cache.json";
+    pub const HISTORY_FILENAME: &str = "scan_history.json";
+    pub const IGNORED_FILENAME: &str = "ignored_paths.json";
+    pub const TRIAL_FILENAME: &str = "trial.json";
+    pub const API_TOKEN_FILENAME: &str = "api_token";
+    /// Keychain service secrets are saved under, matching the bundle identifier
+    pub const KEYCHAIN_SERVICE: &str = "com.deptox.menubar";
+    pub const SIZE_INDEX_FILENAME: &str = "size_index.json";
+    pub const METRICS_FILENAME: &str = "usage_metrics.json";
+    pub const INSTALLED_UPDATE_FILENAME: &str = "installed_update";
+    pub const EXIT_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+}
+
+pub mod fixture {
+    pub const DEFAULT_PROJECT_COUNT: usize = 200;
+    pub const PROJECTS_PER_GROUP: usize = 100;
+    pub const FILES_PER_DIRECTORY: usize = 8;
+    pub const BYTES_PER_FILE: usize = 4096;
+    /// Upper bound for `simulate_scan`, well past the largest real scans seen
+    pub const MAX_SIMULATED_ENTRIES: usize = 100_000;
+    /// Average file size used to derive file counts for simulated entries
+    pub const SIMULATED_BYTES_PER_FILE: u64 = 16 * 1024;
+}
+
+pub mod telemetry {
+    use std::time::Duration;
+
+    pub const SERVICE_NAME: &str = "deptox";
+    /// Crash reports wait in this folder inside the config directory until they're sent
+    pub const CRASH_REPORTS_DIRECTORY: &str = "crash_reports";
+    pub const MAX_CRASH_MESSAGE_CHARS: usize = 500;
+    pub const CRASH_REPORT_TIMEOUT: Duration = Duration::from_secs(10);
+    /// Set at build time, so builds without it collect nothing to upload
+    pub const METRICS_URL: Option<&str> = option_env!("DEPTOX_METRICS_URL");
+    /// Samples are sent together at most this often
+    pub const METRICS_UPLOAD_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+    /// Oldest samples are dropped past this, so a machine that never uploads stays small
+    pub const MAX_PENDING_METRICS: usize = 500;
+    pub const METRICS_UPLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+}
+
+pub mod logs {
+    /// Log files live in this folder inside the config directory
+    pub const DIRECTORY: &str = "logs";
+    pub const FILENAME_PREFIX: &str = "deptox";
+    pub const FILENAME_SUFFIX: &str = "log";
+    /// Files are rotated daily, so this is also how many days of logs are kept
+    pub const MAX_FILES: usize = 7;
+    pub const MAX_RECENT_LINES: usize = 5_000;
 }
 
 pub mod gumroad {
     pub const PRODUCT_ID: &str = "-I6OpIuv1ULHDdhOvkCs5g==";
     pub const API_URL: &str = "https://api.gumroad.com/v2/licenses/verify";
+    /// Devices one license can be activated on
+    pub const MAX_DEVICES: u32 = 3;
+    /// How long a license stays valid without reaching Gumroad to revalidate it
+    pub const OFFLINE_GRACE_DAYS: u64 = 14;
+}
+
+pub mod trial {
+    pub const TRIAL_DAYS: u64 = 14;
+    /// Mixed into the trial signing key along with the device identifier
+    pub const SIGNING_SALT: &[u8] = b"deptox-trial-v1";
 }
 
 pub mod defaults {
     pub const THRESHOLD_BYTES: u64 = 5_368_709_120;
     pub const BACKGROUND_THRESHOLD_BYTES: u64 = 1_073_741_824;
+    pub const THRESHOLD_PERCENT_OF_FREE: u8 = 10;
 }
 
 pub mod scanner {
@@ -19,22 +85,175 @@ pub mod scanner {
 
     pub const MAX_SCAN_DEPTH: usize = 15;
     pub const SIZE_POOL_THREADS: usize = 8;
+    /// Thread cap for low-priority background scans
+    pub const LOW_PRIORITY_THREADS: usize = 2;
+    /// Thread cap for background scans while on battery power
+    pub const BATTERY_SCAN_THREADS: usize = 2;
+    /// Threads shared by every size walk for directory reads and file stats
+    pub const SIZE_WALK_THREADS: usize = 8;
+    /// Entries returned per getattrlistbulk call are bounded by this buffer
+    pub const BULK_ATTRIBUTES_BUFFER_BYTES: usize = 256 * 1024;
     pub const EMIT_THROTTLE: Duration = Duration::from_millis(50);
     pub const JWALK_BUSY_TIMEOUT: Duration = Duration::from_millis(100);
     pub const PREVIOUS_SCAN_TIMEOUT: Duration = Duration::from_secs(2);
     pub const MAX_TIMEOUT_RETRIES: usize = 3;
+    /// How long sizing waits for any result before counting a timeout
+    pub const RESULT_TIMEOUT: Duration = Duration::from_secs(30);
+    /// How often discovery and sizing wake to check for cancellation and the deadline
+    pub const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+    pub const MAX_PROJECT_SEARCH_DEPTH: usize = 8;
+    pub const DEFAULT_MAX_FILES_PER_DIRECTORY: usize = 2_000_000;
+    /// Uncategorized directories smaller than this aren't worth suggesting
+    pub const SUGGESTION_MIN_SIZE_BYTES: u64 = 100 * 1024 * 1024;
+    pub const MAX_SUGGESTION_CANDIDATES: usize = 50;
+    /// Scans running longer than this finish early with what they have, zero disables it
+    pub const DEFAULT_SCAN_TIMEOUT_MINUTES: u32 = 20;
+    /// Unreadable paths kept on a scan result, the rest only count towards `skipped_count`
+    pub const MAX_SKIPPED_ENTRIES: usize = 500;
+}
+
+pub mod ecosystems {
+    /// The marker sweep only looks near the top of the scan root
+    pub const DETECTION_MAX_DEPTH: usize = 4;
+    pub const DETECTION_MAX_DIRECTORIES: usize = 5_000;
 }
 
 pub mod background {
     pub const SCAN_INTERVAL_MINUTES: u64 = 30;
+    /// Entries sized longer ago than one background interval are reported as stale
+    pub const STALE_AFTER_MS: u64 = SCAN_INTERVAL_MINUTES * 60 * 1000;
+}
+
+pub mod safety {
+    use std::time::Duration;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    /// A project touched this recently is likely in use right now
+    pub const RISKY_WITHIN_MS: u64 = DAY_MS;
+    /// A project untouched this long can be reinstalled at leisure if it's ever needed
+    pub const SAFE_AFTER_MS: u64 = 180 * DAY_MS;
+    /// How long `git status` gets before a project is left at caution, e.g. on a network drive
+    pub const GIT_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+    /// How often a running `git status` is checked for completion, cancellation and the deadline
+    pub const GIT_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(10);
+    /// Projects checked with git at once when scoring a scan
+    pub const GIT_STATUS_THREADS: usize = 4;
+}
+
+pub mod updates {
+    use std::time::Duration;
+
+    /// Gives the first check room to stay out of the way of launch work
+    pub const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(60);
+    pub const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+    /// How soon a silent install deferred by a running scan is tried again
+    pub const IDLE_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
 }
 
 pub mod delete {
+    use std::time::Duration;
+
     pub const MAX_CONCURRENT_DELETES: usize = 4;
+    /// A dependency directory modified more recently than this may still be installing
+    pub const INSTALL_ACTIVITY_WINDOW: Duration = Duration::from_secs(2 * 60);
+    /// Entries package managers only keep around while an install is running,
+    /// such as the scratch directory older npm versions extract into
+    pub const INSTALL_MARKERS: &[&str] = &[".staging"];
+    /// Files are overwritten this many bytes at a time by secure delete
+    pub const SECURE_DELETE_CHUNK_BYTES: usize = 1024 * 1024;
+    pub const DEFAULT_CLEAN_STALE_AFTER_DAYS: u32 = 90;
+}
+
+pub mod cloud {
+    use std::time::Duration;
+
+    /// Entries checked for cloud-only placeholders before a directory counts as downloaded
+    pub const MAX_PROBE_ENTRIES: usize = 20_000;
+    pub const EVICT_TIMEOUT: Duration = Duration::from_secs(60);
+}
+
+pub mod processes {
+    use std::time::Duration;
+
+    /// `lsof` can stall on unresponsive network mounts, so checks give up after this
+    pub const LSOF_TIMEOUT: Duration = Duration::from_secs(3);
+}
+
+pub mod history {
+    /// Scans closer together than this keep only the latest point
+    pub const MIN_POINT_INTERVAL_MS: u64 = 6 * 60 * 60 * 1000;
+    pub const MAX_POINTS: usize = 2_000;
+    /// Only the most recent points keep per-directory sizes for `compare_scans`
+    pub const MAX_SNAPSHOTS: usize = 60;
+    pub const DEFAULT_RETENTION_DAYS: u32 = 365;
+}
+
+pub mod size_index {
+    /// Indexed sizes older than this are recomputed, catching edits the fingerprint misses
+    pub const MAX_AGE_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+}
+
+pub mod journal {
+    pub const MAX_RECORDS: usize = 500;
+    /// Trash cleanup never purges items deleted more recently than this, so deletes stay undoable
+    pub const MIN_TRASH_PURGE_DAYS: u32 = 3;
+}
+
+pub mod notifications {
+    /// Folders or categories listed when the notification shows more than the total
+    pub const MAX_DETAIL_LINES: usize = 3;
+}
+
+pub mod local_api {
+    pub const PORT: u16 = 47_613;
+    pub const TOKEN_BYTES: usize = 32;
+    /// Largest request body read, well above a delete of a few thousand paths
+    pub const MAX_BODY_BYTES: u64 = 1024 * 1024;
+}
+
+pub mod integrations {
+    use std::time::Duration;
+
+    /// Largest folders included in a threshold webhook
+    pub const MAX_WEBHOOK_ENTRIES: usize = 10;
+    pub const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+}
+
+pub mod sounds {
+    use crate::config::bytes::GB;
+
+    /// Scans shorter than this finish while the user is still watching, so they get no cue
+    pub const LONG_SCAN_MS: u128 = 20_000;
+    pub const DEFAULT_RECLAIM_THRESHOLD_BYTES: u64 = 5 * GB as u64;
 }
 
 pub mod largest_files {
     pub const MAX_FILES: usize = 8;
+    pub const MAX_CHILDREN: usize = 20;
+    /// Deepest level `get_largest_children` groups by, enough for `node_modules/@scope/package`
+    pub const MAX_CHILD_DEPTH: usize = 3;
+}
+
+pub mod tray {
+    use std::time::Duration;
+
+    /// Tray updates arriving within this window are coalesced into one
+    pub const UPDATE_DEBOUNCE: Duration = Duration::from_millis(250);
+
+    /// Badge drawn onto the tray icon where the platform has no tray title
+    pub const BADGE_BACKGROUND: [u8; 4] = [220, 38, 38, 255];
+    pub const BADGE_FOREGROUND: [u8; 4] = [255, 255, 255, 255];
+    /// Each font pixel is drawn as a square of this many icon pixels
+    pub const BADGE_GLYPH_SCALE: u32 = 2;
+    pub const BADGE_PADDING: u32 = 2;
+
+    /// Dot drawn in the icon's corner for the warning and error states
+    pub const WARNING_COLOR: [u8; 4] = [245, 158, 11, 255];
+    pub const ERROR_COLOR: [u8; 4] = [220, 38, 38, 255];
+    /// The scanning spinner is a ring of dots with a fading tail, advanced once per frame
+    pub const SPINNER_DOTS: usize = 8;
+    pub const SPINNER_FRAME_INTERVAL: Duration = Duration::from_millis(125);
 }
 
 pub mod window {
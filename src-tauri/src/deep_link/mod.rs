@@ -0,0 +1,80 @@
+//! `deptox://` links, so notifications, docs and other tools can open the app on the right
+//! screen. Each link maps onto the same window events as the tray menu:
+//!
+//! - `deptox://scan` starts a scan
+//! - `deptox://settings` opens settings
+//! - `deptox://clean?path=/code/app/node_modules` opens the app to clean one directory
+
+use tauri::Url;
+use tracing::{info, warn};
+
+pub const SCHEME: &str = "deptox";
+
+/// Emitted to the window with the path from a `deptox://clean` link
+pub const CLEAN_EVENT: &str = "deep-link-clean-requested";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Scan,
+    Settings,
+    Clean { path: String },
+}
+
+impl DeepLink {
+    pub fn parse(url: &Url) -> Option<Self> {
+        if url.scheme() != SCHEME {
+            return None;
+        }
+
+        match url.host_str()? {
+            "scan" => Some(Self::Scan),
+            "settings" => Some(Self::Settings),
+            "clean" => url
+                .query_pairs()
+                .find(|(key, _)| key == "path")
+                .map(|(_, path)| path.into_owned())
+                .filter(|path| !path.is_empty())
+                .map(|path| Self::Clean { path }),
+            _ => None,
+        }
+    }
+
+    /// The window event the frontend already listens for
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::Scan => "tray-scan-requested",
+            Self::Settings => "tray-settings-requested",
+            Self::Clean { .. } => CLEAN_EVENT,
+        }
+    }
+}
+
+/// Links passed on the command line, which is how Windows and Linux hand them to a
+/// second instance
+pub fn links_in_args(args: &[String]) -> Vec<Url> {
+    args.iter()
+        .filter(|arg| arg.starts_with(&format!("{SCHEME}://")))
+        .filter_map(|arg| Url::parse(arg).ok())
+        .collect()
+}
+
+pub fn handle_urls(app_handle: &tauri::AppHandle, urls: &[Url]) {
+    for url in urls {
+        match DeepLink::parse(url) {
+            Some(link) => {
+                info!(?link, "Opening from deep link");
+                match &link {
+                    DeepLink::Clean { path } => {
+                        crate::show_window_with_event(app_handle, link.event_name(), path.clone())
+                    }
+                    _ => crate::show_window_with_event(app_handle, link.event_name(), ()),
+                }
+            }
+            None => warn!(%url, "Ignoring unrecognised deep link"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,48 @@
+use super::*;
+
+fn parse(url: &str) -> Option<DeepLink> {
+    DeepLink::parse(&Url::parse(url).unwrap())
+}
+
+#[test]
+fn test_parse_screens() {
+    assert_eq!(parse("deptox://scan"), Some(DeepLink::Scan));
+    assert_eq!(parse("deptox://settings"), Some(DeepLink::Settings));
+    assert_eq!(parse("deptox://settings/"), Some(DeepLink::Settings));
+}
+
+#[test]
+fn test_parse_clean_decodes_path() {
+    assert_eq!(
+        parse("deptox://clean?path=%2FUsers%2Fdev%2Fmy%20app%2Fnode_modules"),
+        Some(DeepLink::Clean {
+            path: "/Users/dev/my app/node_modules".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_parse_clean_requires_path() {
+    assert_eq!(parse("deptox://clean"), None);
+    assert_eq!(parse("deptox://clean?path="), None);
+}
+
+#[test]
+fn test_parse_rejects_unknown_links() {
+    assert_eq!(parse("deptox://delete?path=/"), None);
+    assert_eq!(parse("https://scan"), None);
+}
+
+#[test]
+fn test_links_in_args() {
+    let args = vec![
+        "/Applications/deptox.app/Contents/MacOS/deptox".to_string(),
+        "--autostart".to_string(),
+        "deptox://scan".to_string(),
+    ];
+
+    let links = links_in_args(&args);
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(DeepLink::parse(&links[0]), Some(DeepLink::Scan));
+}
@@ -0,0 +1,98 @@
+use crate::commands::delete::DeleteValidationError;
+use crate::commands::license::LicenseError;
+use crate::commands::settings::SettingsError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::Path;
+use thiserror::Error;
+
+/// Error returned by scan, delete, settings and license commands. It reaches the
+/// frontend as an `ErrorPayload`, so the UI can branch on `code` and localize the
+/// message rather than matching on English text.
+#[derive(Debug, Error)]
+pub enum DeptoxError {
+    #[error(transparent)]
+    Settings(#[from] SettingsError),
+    #[error(transparent)]
+    DeleteRejected(#[from] DeleteValidationError),
+    #[error("{message}")]
+    DeleteFailed { path: String, message: String },
+    #[error(transparent)]
+    License(#[from] LicenseError),
+    #[error("{message}")]
+    InvalidPath { path: String, message: String },
+    #[error("{0}")]
+    InvalidArgument(String),
+    /// Failures from helpers that still report plain strings
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl DeptoxError {
+    pub fn delete_failed(path: &Path, message: impl Into<String>) -> Self {
+        Self::DeleteFailed {
+            path: path.to_string_lossy().to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_path(path: &str, message: impl Into<String>) -> Self {
+        Self::InvalidPath {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Stable identifier for the failure, independent of the message wording
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Settings(error) => error.code(),
+            Self::DeleteRejected(error) => error.code(),
+            Self::DeleteFailed { .. } => "DELETE_FAILED",
+            Self::License(error) => error.code(),
+            Self::InvalidPath { .. } => "INVALID_PATH",
+            Self::InvalidArgument(_) => "INVALID_ARGUMENT",
+            Self::Internal(_) => "INTERNAL",
+        }
+    }
+
+    /// The value a localized message needs besides the code, such as the path involved
+    pub fn context(&self) -> Option<String> {
+        match self {
+            Self::Settings(error) => error.context(),
+            Self::DeleteRejected(error) => error.context(),
+            Self::DeleteFailed { path, .. } | Self::InvalidPath { path, .. } => Some(path.clone()),
+            Self::License(error) => error.context(),
+            Self::InvalidArgument(_) | Self::Internal(_) => None,
+        }
+    }
+}
+
+/// How a `DeptoxError` is serialized for the frontend
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorPayload {
+    pub code: String,
+    pub message: String,
+    pub context: Option<String>,
+}
+
+impl From<&DeptoxError> for ErrorPayload {
+    fn from(error: &DeptoxError) -> Self {
+        Self {
+            code: error.code().to_string(),
+            message: error.to_string(),
+            context: error.context(),
+        }
+    }
+}
+
+impl Serialize for DeptoxError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorPayload::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+#[path = "error.test.rs"]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn test_validation_error_serializes_code_and_context() {
+    let error = DeptoxError::from(DeleteValidationError::ProjectOpen("Cursor".to_string()));
+
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(json["code"], "DELETE_PROJECT_OPEN");
+    assert_eq!(
+        json["message"],
+        "Project is open in Cursor; confirm to delete anyway"
+    );
+    assert_eq!(json["context"], "Cursor");
+}
+
+#[test]
+fn test_settings_error_keeps_message() {
+    let error = DeptoxError::from(SettingsError::InvalidThresholdPercent(120));
+
+    assert_eq!(error.code(), "SETTINGS_INVALID_THRESHOLD_PERCENT");
+    assert_eq!(
+        error.to_string(),
+        "Threshold percentage must be between 1 and 100, got 120"
+    );
+    assert_eq!(error.context().as_deref(), Some("120"));
+}
+
+#[test]
+fn test_license_error_code() {
+    let error = DeptoxError::from(LicenseError::DeviceLimit(3));
+
+    assert_eq!(error.code(), "LICENSE_DEVICE_LIMIT");
+    assert_eq!(error.context().as_deref(), Some("3"));
+    assert_eq!(
+        DeptoxError::from(LicenseError::TrialExpired).code(),
+        "LICENSE_TRIAL_EXPIRED"
+    );
+}
+
+#[test]
+fn test_delete_failure_carries_path() {
+    let error = DeptoxError::delete_failed(
+        Path::new("/projects/app/node_modules"),
+        "Failed to move to trash: denied",
+    );
+
+    let payload = ErrorPayload::from(&error);
+    assert_eq!(payload.code, "DELETE_FAILED");
+    assert_eq!(payload.message, "Failed to move to trash: denied");
+    assert_eq!(
+        payload.context.as_deref(),
+        Some("/projects/app/node_modules")
+    );
+}
+
+#[test]
+fn test_internal_errors_serialize_without_context() {
+    let error = DeptoxError::Internal("Failed to read cache".to_string());
+
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(json["code"], "INTERNAL");
+    assert_eq!(json["message"], "Failed to read cache");
+    assert!(json["context"].is_null());
+}
@@ -0,0 +1,175 @@
+use crate::config;
+use crate::scanner::DependencyCategory;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::info;
+
+mod simulate;
+
+pub use simulate::{simulate_entries, SizeDistribution};
+
+/// Root of the generated tree when launched with `--fixture`, replacing the configured scan root
+static FIXTURE_ROOT: OnceLock<String> = OnceLock::new();
+
+/// Marker written at the fixture root so an existing tree is reused and real
+/// directories are never mistaken for a fixture
+const FIXTURE_MARKER: &str = ".deptox-fixture";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureLayout {
+    pub project_count: usize,
+    pub files_per_directory: usize,
+    pub bytes_per_file: usize,
+}
+
+impl Default for FixtureLayout {
+    fn default() -> Self {
+        Self {
+            project_count: config::fixture::DEFAULT_PROJECT_COUNT,
+            files_per_directory: config::fixture::FILES_PER_DIRECTORY,
+            bytes_per_file: config::fixture::BYTES_PER_FILE,
+        }
+    }
+}
+
+/// Parses the hidden `--fixture <path> [--fixture-projects <count>]` launch arguments
+pub fn parse_fixture_args(
+    args: impl IntoIterator<Item = String>,
+) -> Option<(PathBuf, FixtureLayout)> {
+    let mut root = None;
+    let mut layout = FixtureLayout::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fixture" => root = args.next().map(PathBuf::from),
+            "--fixture-projects" => {
+                if let Some(count) = args.next().and_then(|value| value.parse().ok()) {
+                    layout.project_count = count;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    root.map(|root| (root, layout))
+}
+
+/// Project manifest plus the dependency directory layout each category is detected by
+fn project_files(
+    category: DependencyCategory,
+) -> (&'static str, &'static str, Option<&'static str>) {
+    match category {
+        DependencyCategory::NodeModules => ("package.json", "node_modules", None),
+        DependencyCategory::Composer => ("composer.json", "vendor", Some("composer")),
+        DependencyCategory::Bundler => ("Gemfile", "vendor", Some("bundle")),
+        DependencyCategory::Pods => ("Podfile", "Pods", None),
+        DependencyCategory::PythonVenv => ("pyproject.toml", ".venv", None),
+        DependencyCategory::CondaEnv => ("environment.yml", "env", Some("conda-meta")),
+        DependencyCategory::ElixirDeps => ("mix.exs", "deps", None),
+        DependencyCategory::DartTool => ("pubspec.yaml", ".dart_tool", None),
+        DependencyCategory::GoMod => ("go.mod", "pkg", Some("mod")),
+        DependencyCategory::DotNet => ("App.csproj", "obj", None),
+        DependencyCategory::BuildArtifacts => ("package.json", "dist", None),
+    }
+}
+
+fn create_project(
+    project_dir: &Path,
+    category: DependencyCategory,
+    file_count: usize,
+    bytes_per_file: usize,
+) -> io::Result<()> {
+    let (manifest, dependency_dir_name, marker_dir) = project_files(category);
+    let dependency_dir = project_dir.join(dependency_dir_name);
+    let content_dir = match marker_dir {
+        Some(marker) => dependency_dir.join(marker),
+        None => dependency_dir,
+    };
+
+    fs::create_dir_all(&content_dir)?;
+    fs::write(project_dir.join(manifest), "")?;
+
+    let content = "x".repeat(bytes_per_file);
+    for index in 0..file_count {
+        fs::write(content_dir.join(format!("file_{index}.txt")), &content)?;
+    }
+
+    Ok(())
+}
+
+/// Generates a synthetic tree of projects cycling through every category.
+/// Sizes vary per project so sorting and thresholds behave like a real disk.
+/// Returns the number of dependency directories created.
+pub fn generate_fixture(root: &Path, layout: &FixtureLayout) -> io::Result<usize> {
+    let categories = DependencyCategory::all();
+
+    for index in 0..layout.project_count {
+        let category = categories[index % categories.len()];
+        let project_dir = root
+            .join(format!(
+                "group-{:03}",
+                index / config::fixture::PROJECTS_PER_GROUP
+            ))
+            .join(format!("project-{index:05}"));
+        let size_multiplier = 1 + index % 7;
+
+        create_project(
+            &project_dir,
+            category,
+            layout.files_per_directory,
+            layout.bytes_per_file * size_multiplier,
+        )?;
+    }
+
+    fs::write(root.join(FIXTURE_MARKER), layout.project_count.to_string())?;
+
+    Ok(layout.project_count)
+}
+
+/// Generates the fixture tree (reusing one from a previous launch) and makes it the scan root.
+/// Refuses to write into a non-empty directory that isn't already a fixture.
+pub fn prepare_fixture(root: &Path, layout: &FixtureLayout) -> Result<(), String> {
+    let marker = root.join(FIXTURE_MARKER);
+    let reusable = fs::read_to_string(&marker)
+        .map(|content| content.trim() == layout.project_count.to_string())
+        .unwrap_or(false);
+
+    if !reusable {
+        let is_empty = fs::read_dir(root)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(true);
+
+        if !is_empty && !marker.exists() {
+            return Err(format!(
+                "Refusing to generate fixture in non-empty directory: {}",
+                root.display()
+            ));
+        }
+
+        if marker.exists() {
+            fs::remove_dir_all(root)
+                .map_err(|error| format!("Failed to clear previous fixture: {error}"))?;
+        }
+
+        fs::create_dir_all(root)
+            .map_err(|error| format!("Failed to create fixture directory: {error}"))?;
+        let created = generate_fixture(root, layout)
+            .map_err(|error| format!("Failed to generate fixture: {error}"))?;
+        info!(path = %root.display(), created, "Generated fixture tree");
+    }
+
+    let _ = FIXTURE_ROOT.set(root.to_string_lossy().to_string());
+    Ok(())
+}
+
+/// The fixture root when running in fixture mode
+pub fn active_root() -> Option<String> {
+    FIXTURE_ROOT.get().cloned()
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
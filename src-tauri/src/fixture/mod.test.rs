@@ -0,0 +1,102 @@
+use super::*;
+use crate::test_helpers::calculate_actual_directory_size;
+use tempfile::TempDir;
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn small_layout(project_count: usize) -> FixtureLayout {
+    FixtureLayout {
+        project_count,
+        files_per_directory: 2,
+        bytes_per_file: 10,
+    }
+}
+
+#[test]
+fn test_parse_fixture_args_without_flag() {
+    assert!(parse_fixture_args(args(&["deptox", "--autostart"])).is_none());
+}
+
+#[test]
+fn test_parse_fixture_args_with_path_and_count() {
+    let (root, layout) = parse_fixture_args(args(&[
+        "deptox",
+        "--fixture",
+        "/tmp/fixture",
+        "--fixture-projects",
+        "10000",
+    ]))
+    .unwrap();
+
+    assert_eq!(root, PathBuf::from("/tmp/fixture"));
+    assert_eq!(layout.project_count, 10000);
+    assert_eq!(
+        layout.files_per_directory,
+        config::fixture::FILES_PER_DIRECTORY
+    );
+}
+
+#[test]
+fn test_parse_fixture_args_ignores_invalid_count() {
+    let (_, layout) = parse_fixture_args(args(&[
+        "deptox",
+        "--fixture-projects",
+        "lots",
+        "--fixture",
+        "/tmp/fixture",
+    ]))
+    .unwrap();
+
+    assert_eq!(layout.project_count, config::fixture::DEFAULT_PROJECT_COUNT);
+}
+
+#[test]
+fn test_generate_fixture_creates_detectable_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let categories = DependencyCategory::all();
+
+    let created = generate_fixture(temp_dir.path(), &small_layout(categories.len())).unwrap();
+    assert_eq!(created, categories.len());
+
+    for (index, category) in categories.into_iter().enumerate() {
+        let (_, dependency_dir_name, _) = project_files(category);
+        let dependency_dir = temp_dir
+            .path()
+            .join("group-000")
+            .join(format!("project-{index:05}"))
+            .join(dependency_dir_name);
+
+        assert_eq!(
+            DependencyCategory::from_path(&dependency_dir),
+            Some(category),
+            "{}",
+            dependency_dir.display()
+        );
+        assert!(calculate_actual_directory_size(&dependency_dir) > 0);
+    }
+}
+
+#[test]
+fn test_prepare_fixture_refuses_non_empty_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("important.txt"), "keep me").unwrap();
+
+    assert!(prepare_fixture(temp_dir.path(), &small_layout(2)).is_err());
+    assert!(temp_dir.path().join("important.txt").exists());
+}
+
+#[test]
+fn test_prepare_fixture_regenerates_when_layout_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().join("fixture");
+
+    prepare_fixture(&root, &small_layout(2)).unwrap();
+    assert!(root.join("group-000").join("project-00001").exists());
+    assert!(!root.join("group-000").join("project-00002").exists());
+
+    prepare_fixture(&root, &small_layout(3)).unwrap();
+    assert!(root.join("group-000").join("project-00002").exists());
+    assert_eq!(fs::read_to_string(root.join(FIXTURE_MARKER)).unwrap(), "3");
+}
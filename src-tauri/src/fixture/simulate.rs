@@ -0,0 +1,98 @@
+use super::project_files;
+use crate::config;
+use crate::scanner::{AgeStrategy, DependencyCategory, DirectoryEntry, Safety};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+/// Simulated entries were last touched at most this long ago
+const MAX_AGE_DAYS: u64 = 730;
+
+/// How simulated entry sizes are spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SizeDistribution {
+    /// Every size between 1 MB and 1 GB is equally likely
+    Uniform,
+    /// Mostly small directories with a few very large ones, like a real disk
+    LongTail,
+}
+
+/// SplitMix64, so simulations are reproducible without a random number dependency
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl SizeDistribution {
+    fn sample(self, random: &mut SplitMix64) -> u64 {
+        let mb = config::bytes::MB;
+        match self {
+            SizeDistribution::Uniform => (mb + random.next_f64() * (1024.0 - 1.0) * mb) as u64,
+            // Exponent spread from 100 KB to about 10 GB, skewed so large sizes are rare
+            SizeDistribution::LongTail => {
+                let skewed = random.next_f64().powi(3);
+                (100.0 * config::bytes::KB * 100_000f64.powf(skewed)) as u64
+            }
+        }
+    }
+}
+
+/// Fabricates entries cycling through every category, with the same path layout as
+/// the on-disk fixture. The same seed always produces the same entries.
+pub fn simulate_entries(
+    count: usize,
+    distribution: SizeDistribution,
+    seed: u64,
+    now_ms: u64,
+) -> Vec<DirectoryEntry> {
+    let categories = DependencyCategory::all();
+    let mut random = SplitMix64(seed);
+
+    (0..count)
+        .map(|index| {
+            let category = categories[index % categories.len()];
+            let (_, dependency_dir_name, _) = project_files(category);
+            let size_bytes = distribution.sample(&mut random);
+            let age_ms = random.next_u64() % (MAX_AGE_DAYS * MILLISECONDS_PER_DAY);
+
+            DirectoryEntry {
+                path: format!(
+                    "/simulated/group-{:03}/project-{index:05}/{dependency_dir_name}",
+                    index / config::fixture::PROJECTS_PER_GROUP
+                ),
+                size_bytes,
+                file_count: (size_bytes / config::fixture::SIMULATED_BYTES_PER_FILE).max(1)
+                    as usize,
+                last_modified_ms: now_ms.saturating_sub(age_ms),
+                category,
+                has_only_symlinks: false,
+                last_commit_ms: None,
+                git_branch: None,
+                project_name: None,
+                workspace_root: None,
+                truncated: false,
+                scanned_at_ms: now_ms,
+                stale: false,
+                age_source: AgeStrategy::NewestFile,
+                safety: Safety::Caution,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "simulate.test.rs"]
+mod tests;
@@ -0,0 +1,62 @@
+use super::*;
+
+const NOW_MS: u64 = 1_750_000_000_000;
+
+#[test]
+fn test_simulate_entries_is_reproducible() {
+    let first = simulate_entries(50, SizeDistribution::LongTail, 7, NOW_MS);
+    let second = simulate_entries(50, SizeDistribution::LongTail, 7, NOW_MS);
+    let other_seed = simulate_entries(50, SizeDistribution::LongTail, 8, NOW_MS);
+
+    let sizes = |entries: &[DirectoryEntry]| -> Vec<u64> {
+        entries.iter().map(|entry| entry.size_bytes).collect()
+    };
+    assert_eq!(sizes(&first), sizes(&second));
+    assert_ne!(sizes(&first), sizes(&other_seed));
+}
+
+#[test]
+fn test_simulate_entries_cycles_categories_with_matching_paths() {
+    let entries = simulate_entries(
+        DependencyCategory::all().len(),
+        SizeDistribution::Uniform,
+        1,
+        NOW_MS,
+    );
+
+    for (entry, category) in entries.iter().zip(DependencyCategory::all()) {
+        assert_eq!(entry.category, category);
+        let (_, dependency_dir_name, _) = project_files(category);
+        assert!(entry.path.ends_with(&format!("/{dependency_dir_name}")));
+    }
+    assert_eq!(
+        entries[0].path,
+        "/simulated/group-000/project-00000/node_modules"
+    );
+}
+
+#[test]
+fn test_uniform_sizes_stay_in_range() {
+    let entries = simulate_entries(1_000, SizeDistribution::Uniform, 3, NOW_MS);
+
+    for entry in &entries {
+        assert!(entry.size_bytes >= config::bytes::MB as u64);
+        assert!(entry.size_bytes <= config::bytes::GB as u64);
+        assert!(entry.file_count >= 1);
+        assert!(entry.last_modified_ms <= NOW_MS);
+    }
+}
+
+#[test]
+fn test_long_tail_is_mostly_small() {
+    let entries = simulate_entries(1_000, SizeDistribution::LongTail, 3, NOW_MS);
+
+    let small = entries
+        .iter()
+        .filter(|entry| entry.size_bytes < 100 * config::bytes::MB as u64)
+        .count();
+    let largest = entries.iter().map(|entry| entry.size_bytes).max().unwrap();
+
+    assert!(small > entries.len() / 2);
+    assert!(largest > config::bytes::GB as u64);
+}
@@ -0,0 +1,159 @@
+use crate::commands::locale::detect_locale;
+use crate::commands::settings::SizeUnitBase;
+use crate::i18n;
+use crate::time;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const BYTE_UNIT_LABELS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// Mirrors the `size_unit_base` setting, so formatting a size doesn't read the settings file
+static DECIMAL_UNITS: AtomicBool = AtomicBool::new(false);
+
+const MINUTE_MS: u64 = 60 * 1000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// Largest unit first, matching the frontend's relative time formatting, with the
+/// locale key for the full text and the abbreviation used where space is short
+const TIME_UNITS: [(&str, &str, u64); 6] = [
+    ("time.yearsAgo", "y", 365 * DAY_MS),
+    ("time.monthsAgo", "mo", 30 * DAY_MS),
+    ("time.weeksAgo", "w", 7 * DAY_MS),
+    ("time.daysAgo", "d", DAY_MS),
+    ("time.hoursAgo", "h", HOUR_MS),
+    ("time.minutesAgo", "m", MINUTE_MS),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ByteStyle {
+    /// Two decimals and no space ("1.50GB"), for the tray title
+    Compact,
+    /// Trailing zeros dropped ("5 GB", "5.5 GB"), for sentences
+    Readable,
+}
+
+/// How sizes are written: the unit base from settings and the locale's decimal separator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteFormat {
+    pub unit_base: SizeUnitBase,
+    pub decimal_separator: char,
+}
+
+impl Default for ByteFormat {
+    fn default() -> Self {
+        Self {
+            unit_base: SizeUnitBase::Binary,
+            decimal_separator: '.',
+        }
+    }
+}
+
+impl ByteFormat {
+    /// The format the user has chosen, from settings and the system locale
+    pub fn current() -> Self {
+        Self {
+            unit_base: size_unit_base(),
+            decimal_separator: decimal_separator(&detect_locale().language),
+        }
+    }
+
+    pub fn format(&self, bytes: u64, style: ByteStyle) -> String {
+        let base = self.unit_base.base();
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= base && unit < BYTE_UNIT_LABELS.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+        let label = BYTE_UNIT_LABELS[unit];
+
+        let formatted = match style {
+            ByteStyle::Compact => format!("{value:.2}"),
+            ByteStyle::Readable => {
+                let formatted = format!("{value:.1}");
+                formatted
+                    .strip_suffix(".0")
+                    .map(str::to_string)
+                    .unwrap_or(formatted)
+            }
+        };
+        let formatted = formatted.replace('.', &self.decimal_separator.to_string());
+
+        match style {
+            ByteStyle::Compact => format!("{formatted}{label}"),
+            ByteStyle::Readable => format!("{formatted} {label}"),
+        }
+    }
+}
+
+/// Called whenever settings are loaded at launch or saved
+pub fn set_size_unit_base(unit_base: SizeUnitBase) {
+    DECIMAL_UNITS.store(unit_base == SizeUnitBase::Decimal, Ordering::Relaxed);
+}
+
+fn size_unit_base() -> SizeUnitBase {
+    if DECIMAL_UNITS.load(Ordering::Relaxed) {
+        SizeUnitBase::Decimal
+    } else {
+        SizeUnitBase::Binary
+    }
+}
+
+/// Most of continental Europe and South America write 1,5 where English writes 1.5
+fn decimal_separator(language: &str) -> char {
+    match language {
+        "bg" | "ca" | "cs" | "da" | "de" | "el" | "es" | "et" | "fi" | "fr" | "hr" | "hu"
+        | "id" | "it" | "lt" | "lv" | "nb" | "nl" | "nn" | "no" | "pl" | "pt" | "ro" | "ru"
+        | "sk" | "sl" | "sr" | "sv" | "tr" | "uk" | "vi" => ',',
+        _ => '.',
+    }
+}
+
+/// "5 minutes ago" style text for a timestamp in `language`, with 0 meaning it was
+/// never recorded
+fn relative_time_text(timestamp_ms: u64, now_ms: u64, language: &str) -> String {
+    if timestamp_ms == 0 {
+        return i18n::translate(language, "time.unknown");
+    }
+
+    let elapsed_ms = now_ms.saturating_sub(timestamp_ms);
+    TIME_UNITS
+        .iter()
+        .find(|(_, _, unit_ms)| elapsed_ms >= *unit_ms)
+        .map(|(key, _, unit_ms)| i18n::translate_count(language, key, elapsed_ms / unit_ms))
+        .unwrap_or_else(|| i18n::translate(language, "time.justNow"))
+}
+
+/// Abbreviated form of `relative_time_text` ("2h ago"), for the tray summary, which is
+/// English like the rest of the tray menu
+pub fn short_relative_time_text(timestamp_ms: u64, now_ms: u64) -> String {
+    if timestamp_ms == 0 {
+        return "unknown".to_string();
+    }
+
+    let elapsed_ms = now_ms.saturating_sub(timestamp_ms);
+    TIME_UNITS
+        .iter()
+        .find(|(_, _, unit_ms)| elapsed_ms >= *unit_ms)
+        .map(|(_, abbreviation, unit_ms)| format!("{}{abbreviation} ago", elapsed_ms / unit_ms))
+        .unwrap_or_else(|| "just now".to_string())
+}
+
+/// Formats a size with the user's unit base and decimal separator
+#[tauri::command]
+pub fn format_bytes(bytes: u64, style: ByteStyle) -> String {
+    ByteFormat::current().format(bytes, style)
+}
+
+/// Describes how long ago a timestamp in milliseconds was, in the system language
+#[tauri::command]
+pub fn format_relative_time(ms: u64) -> String {
+    relative_time_text(ms, time::now_ms(), i18n::current_language())
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
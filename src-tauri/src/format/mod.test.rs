@@ -0,0 +1,157 @@
+use super::*;
+
+const BINARY: ByteFormat = ByteFormat {
+    unit_base: SizeUnitBase::Binary,
+    decimal_separator: '.',
+};
+
+#[test]
+fn test_format_compact_bytes() {
+    assert_eq!(BINARY.format(0, ByteStyle::Compact), "0.00B");
+    assert_eq!(BINARY.format(512, ByteStyle::Compact), "512.00B");
+    assert_eq!(BINARY.format(1023, ByteStyle::Compact), "1023.00B");
+}
+
+#[test]
+fn test_format_compact_binary_units() {
+    assert_eq!(BINARY.format(1024, ByteStyle::Compact), "1.00KB");
+    assert_eq!(BINARY.format(1024 * 500, ByteStyle::Compact), "500.00KB");
+    assert_eq!(
+        BINARY.format(1024 * 1024 * 50, ByteStyle::Compact),
+        "50.00MB"
+    );
+    assert_eq!(
+        BINARY.format(1024 * 1024 * 1024 * 18, ByteStyle::Compact),
+        "18.00GB"
+    );
+    assert_eq!(
+        BINARY.format(1024 * 1024 * 1024 * 1024 * 3, ByteStyle::Compact),
+        "3.00TB"
+    );
+}
+
+#[test]
+fn test_format_compact_with_decimals() {
+    let gb_value = 1024 * 1024 * 1024 + (512 * 1024 * 1024);
+    assert_eq!(BINARY.format(gb_value, ByteStyle::Compact), "1.50GB");
+
+    let mb_value = 1024 * 1024 + (256 * 1024);
+    assert_eq!(BINARY.format(mb_value, ByteStyle::Compact), "1.25MB");
+}
+
+#[test]
+fn test_format_largest_unit_is_terabytes() {
+    assert_eq!(
+        BINARY.format(1024 * 1024 * 1024 * 1024 * 2048, ByteStyle::Compact),
+        "2048.00TB"
+    );
+}
+
+#[test]
+fn test_format_readable_drops_trailing_zero() {
+    assert_eq!(
+        BINARY.format(5 * 1024 * 1024 * 1024, ByteStyle::Readable),
+        "5 GB"
+    );
+    assert_eq!(
+        BINARY.format(
+            5 * 1024 * 1024 * 1024 + 512 * 1024 * 1024,
+            ByteStyle::Readable
+        ),
+        "5.5 GB"
+    );
+    assert_eq!(BINARY.format(100, ByteStyle::Readable), "100 B");
+}
+
+#[test]
+fn test_format_decimal_units() {
+    let decimal = ByteFormat {
+        unit_base: SizeUnitBase::Decimal,
+        ..BINARY
+    };
+
+    assert_eq!(decimal.format(1000, ByteStyle::Readable), "1 KB");
+    assert_eq!(decimal.format(1_500_000_000, ByteStyle::Readable), "1.5 GB");
+    assert_eq!(BINARY.format(1_500_000_000, ByteStyle::Readable), "1.4 GB");
+}
+
+#[test]
+fn test_format_uses_locale_decimal_separator() {
+    let german = ByteFormat {
+        decimal_separator: decimal_separator("de"),
+        ..BINARY
+    };
+
+    assert_eq!(german.format(1024 + 512, ByteStyle::Compact), "1,50KB");
+    assert_eq!(german.format(1024 + 512, ByteStyle::Readable), "1,5 KB");
+    assert_eq!(german.format(2048, ByteStyle::Readable), "2 KB");
+    assert_eq!(decimal_separator("en"), '.');
+    assert_eq!(decimal_separator("ja"), '.');
+}
+
+#[test]
+fn test_relative_time_text() {
+    let now = 400 * DAY_MS;
+
+    assert_eq!(relative_time_text(now - 30 * 1000, now, "en"), "just now");
+    assert_eq!(
+        relative_time_text(now - MINUTE_MS, now, "en"),
+        "1 minute ago"
+    );
+    assert_eq!(
+        relative_time_text(now - 5 * HOUR_MS, now, "en"),
+        "5 hours ago"
+    );
+    assert_eq!(
+        relative_time_text(now - 8 * DAY_MS, now, "en"),
+        "1 week ago"
+    );
+    assert_eq!(
+        relative_time_text(now - 65 * DAY_MS, now, "en"),
+        "2 months ago"
+    );
+    assert_eq!(
+        relative_time_text(now - 366 * DAY_MS, now, "en"),
+        "1 year ago"
+    );
+}
+
+#[test]
+fn test_relative_time_text_localized() {
+    let now = 400 * DAY_MS;
+
+    assert_eq!(
+        relative_time_text(now - 3 * DAY_MS, now, "de"),
+        "vor 3 Tagen"
+    );
+    assert_eq!(
+        relative_time_text(now - 5 * HOUR_MS, now, "ru"),
+        "5 часов назад"
+    );
+    assert_eq!(
+        relative_time_text(now - 30 * 1000, now, "fr"),
+        "à l'instant"
+    );
+    assert_eq!(relative_time_text(0, now, "es"), "desconocido");
+}
+
+#[test]
+fn test_relative_time_text_edge_cases() {
+    assert_eq!(relative_time_text(0, 1_000_000, "en"), "unknown");
+    // Clock skew shouldn't produce a time in the future
+    assert_eq!(relative_time_text(2_000_000, 1_000_000, "en"), "just now");
+}
+
+#[test]
+fn test_short_relative_time_text() {
+    let now = 400 * DAY_MS;
+
+    assert_eq!(short_relative_time_text(now - 30 * 1000, now), "just now");
+    assert_eq!(short_relative_time_text(now - 5 * MINUTE_MS, now), "5m ago");
+    assert_eq!(
+        short_relative_time_text(now - 2 * HOUR_MS - 1, now),
+        "2h ago"
+    );
+    assert_eq!(short_relative_time_text(now - 65 * DAY_MS, now), "2mo ago");
+    assert_eq!(short_relative_time_text(0, now), "unknown");
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+#[test]
+fn test_fnv1a_matches_reference_values() {
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), FNV_OFFSET_BASIS);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x8594_4171_f739_67e8);
+}
+
+#[test]
+fn test_fnv1a_can_be_chained() {
+    let chained = fnv1a(fnv1a(FNV_OFFSET_BASIS, b"foo"), b"bar");
+
+    assert_eq!(chained, fnv1a(FNV_OFFSET_BASIS, b"foobar"));
+}
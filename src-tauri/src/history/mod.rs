@@ -0,0 +1,298 @@
+use crate::commands::settings::get_settings_sync;
+use crate::config;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::scanner::{DependencyCategory, ScanResult};
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+
+/// Totals from every completed scan, oldest first, so growth can be charted over time
+static HISTORY: BufferedJsonFile<ScanHistory> =
+    BufferedJsonFile::new(get_history_path, load_history_from, save_history_to);
+
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPoint {
+    pub timestamp_ms: u64,
+    pub total_size: u64,
+    pub directory_count: usize,
+    #[serde(default)]
+    pub by_category: HashMap<DependencyCategory, u64>,
+    /// Size of every directory found, by path. Dropped from older points to keep the
+    /// history file small, so only recent scans can be compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_sizes: Option<HashMap<String, u64>>,
+}
+
+impl HistoryPoint {
+    fn from_result(result: &ScanResult, timestamp_ms: u64) -> Self {
+        let mut by_category: HashMap<DependencyCategory, u64> = HashMap::new();
+        let mut entry_sizes = HashMap::with_capacity(result.entries.len());
+        for entry in &result.entries {
+            *by_category.entry(entry.category).or_default() += entry.size_bytes;
+            entry_sizes.insert(entry.path.clone(), entry.size_bytes);
+        }
+
+        Self {
+            timestamp_ms,
+            total_size: result.total_size,
+            directory_count: result.entries.len(),
+            by_category,
+            entry_sizes: Some(entry_sizes),
+        }
+    }
+
+    fn snapshot(&self) -> Result<&HashMap<String, u64>, String> {
+        self.entry_sizes.as_ref().ok_or_else(|| {
+            format!(
+                "Scan {} is too old to compare, only recent scans keep directory sizes",
+                self.timestamp_ms
+            )
+        })
+    }
+}
+
+/// A directory whose size changed between two scans
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryChange {
+    pub path: String,
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+    pub delta_bytes: i64,
+}
+
+impl EntryChange {
+    fn new(path: &str, before_bytes: u64, after_bytes: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            before_bytes,
+            after_bytes,
+            delta_bytes: after_bytes as i64 - before_bytes as i64,
+        }
+    }
+}
+
+/// What changed between two recorded scans, each list largest change first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanComparison {
+    pub before_ms: u64,
+    pub after_ms: u64,
+    pub total_delta_bytes: i64,
+    pub added: Vec<EntryChange>,
+    pub removed: Vec<EntryChange>,
+    pub grown: Vec<EntryChange>,
+}
+
+impl ScanComparison {
+    fn between(before: &HistoryPoint, after: &HistoryPoint) -> Result<Self, String> {
+        let before_sizes = before.snapshot()?;
+        let after_sizes = after.snapshot()?;
+
+        let mut added = Vec::new();
+        let mut grown = Vec::new();
+        for (path, &after_bytes) in after_sizes {
+            match before_sizes.get(path) {
+                None => added.push(EntryChange::new(path, 0, after_bytes)),
+                Some(&before_bytes) if after_bytes > before_bytes => {
+                    grown.push(EntryChange::new(path, before_bytes, after_bytes));
+                }
+                Some(_) => {}
+            }
+        }
+        let mut removed: Vec<EntryChange> = before_sizes
+            .iter()
+            .filter(|(path, _)| !after_sizes.contains_key(*path))
+            .map(|(path, &before_bytes)| EntryChange::new(path, before_bytes, 0))
+            .collect();
+
+        for changes in [&mut added, &mut removed, &mut grown] {
+            changes.sort_by(|a, b| {
+                b.delta_bytes
+                    .abs()
+                    .cmp(&a.delta_bytes.abs())
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+        }
+
+        Ok(Self {
+            before_ms: before.timestamp_ms,
+            after_ms: after.timestamp_ms,
+            total_delta_bytes: after.total_size as i64 - before.total_size as i64,
+            added,
+            removed,
+            grown,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanHistory {
+    pub points: Vec<HistoryPoint>,
+}
+
+impl ScanHistory {
+    /// Appends a point, dropping points older than the retention period (zero keeps
+    /// everything) and the oldest points beyond the size cap. A point close after the
+    /// previous one replaces it, so frequent background scans don't flood the history.
+    fn record(&mut self, point: HistoryPoint, retention_days: u32) {
+        let now_ms = point.timestamp_ms;
+        let replaces_last = self.points.last().is_some_and(|last| {
+            now_ms.saturating_sub(last.timestamp_ms) < config::history::MIN_POINT_INTERVAL_MS
+        });
+        if replaces_last {
+            self.points.pop();
+        }
+        self.points.push(point);
+        self.points
+            .sort_by_key(|existing_point| existing_point.timestamp_ms);
+
+        if retention_days > 0 {
+            let cutoff_ms = now_ms.saturating_sub(u64::from(retention_days) * MILLISECONDS_PER_DAY);
+            self.points
+                .retain(|existing_point| existing_point.timestamp_ms >= cutoff_ms);
+        }
+
+        let excess = self
+            .points
+            .len()
+            .saturating_sub(config::history::MAX_POINTS);
+        self.points.drain(..excess);
+
+        let without_snapshot = self
+            .points
+            .len()
+            .saturating_sub(config::history::MAX_SNAPSHOTS);
+        for existing_point in &mut self.points[..without_snapshot] {
+            existing_point.entry_sizes = None;
+        }
+    }
+
+    fn point_at(&self, timestamp_ms: u64) -> Result<&HistoryPoint, String> {
+        self.points
+            .iter()
+            .find(|point| point.timestamp_ms == timestamp_ms)
+            .ok_or_else(|| format!("No scan recorded at {timestamp_ms}"))
+    }
+
+    fn points_since(&self, since_ms: u64) -> Vec<HistoryPoint> {
+        self.points
+            .iter()
+            .filter(|point| point.timestamp_ms >= since_ms)
+            .cloned()
+            .collect()
+    }
+}
+
+/// How far back `get_scan_history` looks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HistoryRange {
+    OneWeek,
+    OneMonth,
+    ThreeMonths,
+    OneYear,
+    All,
+}
+
+impl HistoryRange {
+    fn days(&self) -> Option<u64> {
+        match self {
+            HistoryRange::OneWeek => Some(7),
+            HistoryRange::OneMonth => Some(30),
+            HistoryRange::ThreeMonths => Some(90),
+            HistoryRange::OneYear => Some(365),
+            HistoryRange::All => None,
+        }
+    }
+
+    fn since_ms(&self, now_ms: u64) -> u64 {
+        self.days()
+            .map_or(0, |days| now_ms.saturating_sub(days * MILLISECONDS_PER_DAY))
+    }
+}
+
+fn get_history_path() -> Result<PathBuf, String> {
+    config_path(config::app::HISTORY_FILENAME)
+}
+
+fn load_history_from(path: &Path) -> ScanHistory {
+    if !path.exists() {
+        return ScanHistory::default();
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| {
+            warn!("Failed to load scan history, starting fresh");
+            ScanHistory::default()
+        })
+}
+
+fn save_history_to(path: &Path, history: &ScanHistory) -> Result<(), String> {
+    let content = serde_json::to_string(history)
+        .map_err(|error| format!("Failed to serialize scan history: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write scan history: {error}"))
+}
+
+/// Adds a completed scan to the history and writes it straight to disk.
+pub fn record_scan(result: &ScanResult) {
+    let retention_days = get_settings_sync()
+        .unwrap_or_default()
+        .history_retention_days;
+    let point = HistoryPoint::from_result(result, time::now_ms());
+
+    let recorded = HISTORY
+        .update(|history| history.record(point, retention_days))
+        .and_then(|_| HISTORY.flush());
+    if let Err(error) = recorded {
+        warn!(%error, "Failed to record scan history");
+    }
+}
+
+/// Writes pending scan history to disk
+pub fn flush_history() -> Result<bool, String> {
+    HISTORY.flush()
+}
+
+/// Scan totals within the range, oldest first, for charting growth over time
+#[tauri::command]
+#[instrument(skip_all, fields(range = ?range))]
+pub async fn get_scan_history(range: HistoryRange) -> Result<Vec<HistoryPoint>, String> {
+    let since_ms = range.since_ms(time::now_ms());
+    let points = HISTORY.read(|history| history.points_since(since_ms))?;
+
+    debug!(points = points.len(), "Loaded scan history");
+    Ok(points)
+}
+
+/// Directories added, removed and grown between two scans from the history,
+/// identified by their `timestamp_ms`
+#[tauri::command]
+#[instrument(skip_all, fields(before_id, after_id))]
+pub async fn compare_scans(before_id: u64, after_id: u64) -> Result<ScanComparison, String> {
+    let comparison = HISTORY.read(|history| {
+        ScanComparison::between(history.point_at(before_id)?, history.point_at(after_id)?)
+    })??;
+
+    debug!(
+        added = comparison.added.len(),
+        removed = comparison.removed.len(),
+        grown = comparison.grown.len(),
+        "Compared scans"
+    );
+    Ok(comparison)
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,257 @@
+use super::*;
+use crate::scanner::DirectoryEntry;
+use tempfile::TempDir;
+
+const DAY: u64 = MILLISECONDS_PER_DAY;
+
+fn point(timestamp_ms: u64, total_size: u64) -> HistoryPoint {
+    HistoryPoint {
+        timestamp_ms,
+        total_size,
+        directory_count: 1,
+        by_category: HashMap::from([(DependencyCategory::NodeModules, total_size)]),
+        entry_sizes: None,
+    }
+}
+
+fn entry(path: &str, category: DependencyCategory, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry::for_test(path, category, size_bytes)
+}
+
+#[test]
+fn test_history_point_from_result_groups_by_category() {
+    let result = ScanResult::from_entries(
+        vec![
+            entry("/a/node_modules", DependencyCategory::NodeModules, 300),
+            entry("/b/node_modules", DependencyCategory::NodeModules, 200),
+            entry("/c/.venv", DependencyCategory::PythonVenv, 50),
+        ],
+        10,
+        0,
+        0,
+    );
+
+    let history_point = HistoryPoint::from_result(&result, 1_000);
+
+    assert_eq!(history_point.timestamp_ms, 1_000);
+    assert_eq!(history_point.total_size, 550);
+    assert_eq!(history_point.directory_count, 3);
+    assert_eq!(
+        history_point.by_category[&DependencyCategory::NodeModules],
+        500
+    );
+    assert_eq!(
+        history_point.by_category[&DependencyCategory::PythonVenv],
+        50
+    );
+}
+
+fn snapshot_point(timestamp_ms: u64, sizes: &[(&str, u64)]) -> HistoryPoint {
+    HistoryPoint {
+        total_size: sizes.iter().map(|(_, size)| size).sum(),
+        directory_count: sizes.len(),
+        entry_sizes: Some(
+            sizes
+                .iter()
+                .map(|(path, size)| (path.to_string(), *size))
+                .collect(),
+        ),
+        ..point(timestamp_ms, 0)
+    }
+}
+
+#[test]
+fn test_history_point_from_result_keeps_entry_sizes() {
+    let result = ScanResult::from_entries(
+        vec![entry(
+            "/a/node_modules",
+            DependencyCategory::NodeModules,
+            300,
+        )],
+        10,
+        0,
+        0,
+    );
+
+    let history_point = HistoryPoint::from_result(&result, 1_000);
+
+    assert_eq!(
+        history_point.entry_sizes,
+        Some(HashMap::from([("/a/node_modules".to_string(), 300)]))
+    );
+}
+
+#[test]
+fn test_record_prunes_points_past_retention() {
+    let mut history = ScanHistory::default();
+    history.record(point(DAY, 1), 30);
+    history.record(point(20 * DAY, 2), 30);
+    history.record(point(40 * DAY, 3), 30);
+
+    let sizes: Vec<u64> = history
+        .points
+        .iter()
+        .map(|point| point.total_size)
+        .collect();
+    assert_eq!(sizes, vec![2, 3]);
+}
+
+#[test]
+fn test_record_with_zero_retention_keeps_everything() {
+    let mut history = ScanHistory::default();
+    history.record(point(DAY, 1), 0);
+    history.record(point(1_000 * DAY, 2), 0);
+
+    assert_eq!(history.points.len(), 2);
+}
+
+#[test]
+fn test_record_replaces_point_within_min_interval() {
+    let mut history = ScanHistory::default();
+    history.record(point(DAY, 1), 0);
+    history.record(point(DAY + 60_000, 2), 0);
+
+    assert_eq!(history.points, vec![point(DAY + 60_000, 2)]);
+}
+
+#[test]
+fn test_record_caps_point_count() {
+    let mut history = ScanHistory::default();
+    let interval = config::history::MIN_POINT_INTERVAL_MS;
+    for index in 0..config::history::MAX_POINTS as u64 + 5 {
+        history.record(point(index * interval, index), 0);
+    }
+
+    assert_eq!(history.points.len(), config::history::MAX_POINTS);
+    assert_eq!(history.points[0].total_size, 5);
+}
+
+#[test]
+fn test_record_drops_snapshots_beyond_limit() {
+    let mut history = ScanHistory::default();
+    let interval = config::history::MIN_POINT_INTERVAL_MS;
+    for index in 0..config::history::MAX_SNAPSHOTS as u64 + 2 {
+        history.record(snapshot_point(index * interval, &[("/a", index)]), 0);
+    }
+
+    assert!(history.points[0].entry_sizes.is_none());
+    assert!(history.points[1].entry_sizes.is_none());
+    assert!(history.points[2].entry_sizes.is_some());
+}
+
+#[test]
+fn test_compare_scans_reports_added_removed_and_grown() {
+    let before = snapshot_point(
+        DAY,
+        &[
+            ("/api/node_modules", 100),
+            ("/old/.venv", 40),
+            ("/web/target", 70),
+        ],
+    );
+    let after = snapshot_point(
+        8 * DAY,
+        &[
+            ("/api/node_modules", 350),
+            ("/new/node_modules", 20),
+            ("/web/target", 50),
+        ],
+    );
+
+    let comparison = ScanComparison::between(&before, &after).unwrap();
+
+    assert_eq!(comparison.before_ms, DAY);
+    assert_eq!(comparison.after_ms, 8 * DAY);
+    assert_eq!(comparison.total_delta_bytes, 210);
+    assert_eq!(
+        comparison.grown,
+        vec![EntryChange::new("/api/node_modules", 100, 350)]
+    );
+    assert_eq!(
+        comparison.added,
+        vec![EntryChange::new("/new/node_modules", 0, 20)]
+    );
+    assert_eq!(
+        comparison.removed,
+        vec![EntryChange::new("/old/.venv", 40, 0)]
+    );
+    assert_eq!(comparison.removed[0].delta_bytes, -40);
+}
+
+#[test]
+fn test_compare_scans_orders_by_largest_change() {
+    let before = snapshot_point(DAY, &[("/a", 10), ("/b", 10)]);
+    let after = snapshot_point(2 * DAY, &[("/a", 20), ("/b", 500)]);
+
+    let comparison = ScanComparison::between(&before, &after).unwrap();
+
+    let paths: Vec<&str> = comparison
+        .grown
+        .iter()
+        .map(|change| change.path.as_str())
+        .collect();
+    assert_eq!(paths, vec!["/b", "/a"]);
+}
+
+#[test]
+fn test_compare_scans_requires_snapshots() {
+    let error = ScanComparison::between(&point(DAY, 1), &snapshot_point(2 * DAY, &[])).unwrap_err();
+
+    assert!(error.contains("too old to compare"));
+}
+
+#[test]
+fn test_point_at_unknown_timestamp() {
+    let history = ScanHistory {
+        points: vec![point(DAY, 1)],
+    };
+
+    assert!(history.point_at(DAY).is_ok());
+    assert!(history.point_at(2 * DAY).is_err());
+}
+
+#[test]
+fn test_points_since_filters_by_range() {
+    let history = ScanHistory {
+        points: vec![point(DAY, 1), point(50 * DAY, 2), point(58 * DAY, 3)],
+    };
+    let now_ms = 60 * DAY;
+
+    let week = history.points_since(HistoryRange::OneWeek.since_ms(now_ms));
+    let month = history.points_since(HistoryRange::OneMonth.since_ms(now_ms));
+    let all = history.points_since(HistoryRange::All.since_ms(now_ms));
+
+    assert_eq!(week.len(), 1);
+    assert_eq!(month.len(), 2);
+    assert_eq!(all.len(), 3);
+}
+
+#[test]
+fn test_save_and_load_history_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("scan_history.json");
+    let history = ScanHistory {
+        points: vec![point(DAY, 1024)],
+    };
+
+    save_history_to(&path, &history).unwrap();
+
+    assert_eq!(load_history_from(&path), history);
+}
+
+#[test]
+fn test_load_history_from_invalid_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("scan_history.json");
+    fs::write(&path, "[not json").unwrap();
+
+    assert_eq!(load_history_from(&path), ScanHistory::default());
+}
+
+#[test]
+fn test_history_range_serialization() {
+    assert_eq!(
+        serde_json::from_str::<HistoryRange>("\"THREE_MONTHS\"").unwrap(),
+        HistoryRange::ThreeMonths
+    );
+}
@@ -0,0 +1,109 @@
+//! Copy the backend shows outside the window (the tray, notifications, errors), translated
+//! from the same locale files the UI bundles so both read the same.
+
+use crate::commands::locale::detect_locale;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use tracing::warn;
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Every locale the UI ships, embedded so nothing is read from disk at runtime
+const LOCALE_FILES: [(&str, &str); 12] = [
+    ("en", include_str!("../../../locales/en.json")),
+    ("zh", include_str!("../../../locales/zh.json")),
+    ("es", include_str!("../../../locales/es.json")),
+    ("fr", include_str!("../../../locales/fr.json")),
+    ("de", include_str!("../../../locales/de.json")),
+    ("pt", include_str!("../../../locales/pt.json")),
+    ("ja", include_str!("../../../locales/ja.json")),
+    ("it", include_str!("../../../locales/it.json")),
+    ("ko", include_str!("../../../locales/ko.json")),
+    ("ru", include_str!("../../../locales/ru.json")),
+    ("ar", include_str!("../../../locales/ar.json")),
+    ("hi", include_str!("../../../locales/hi.json")),
+];
+
+static CATALOGS: LazyLock<HashMap<&'static str, Value>> = LazyLock::new(|| {
+    LOCALE_FILES
+        .iter()
+        .filter_map(|(language, content)| match serde_json::from_str(content) {
+            Ok(catalog) => Some((*language, catalog)),
+            Err(error) => {
+                warn!(language, %error, "Failed to parse locale file");
+                None
+            }
+        })
+        .collect()
+});
+
+/// The system language when the app is translated into it, otherwise English
+pub fn current_language() -> &'static str {
+    supported_language(&detect_locale().language)
+}
+
+fn supported_language(language: &str) -> &'static str {
+    LOCALE_FILES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == language)
+        .unwrap_or(FALLBACK_LANGUAGE)
+}
+
+/// Looks up a dotted key such as `categories.node`
+fn lookup(language: &str, key: &str) -> Option<&'static str> {
+    key.split('.')
+        .try_fold(CATALOGS.get(language)?, |value, part| value.get(part))?
+        .as_str()
+}
+
+/// Text for a key, falling back to English and then to the key itself as i18next does
+pub fn translate(language: &str, key: &str) -> String {
+    lookup(language, key)
+        .or_else(|| lookup(FALLBACK_LANGUAGE, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// Text for a key with i18next plural forms (`key_one`, `key_few`, `key_other`...),
+/// with `{{count}}` filled in
+pub fn translate_count(language: &str, key: &str, count: u64) -> String {
+    let plural_key = |language: &str| format!("{key}_{}", plural_category(language, count));
+    let text = lookup(language, &plural_key(language))
+        .or_else(|| lookup(language, &format!("{key}_other")))
+        .or_else(|| lookup(FALLBACK_LANGUAGE, &plural_key(FALLBACK_LANGUAGE)))
+        .unwrap_or(key);
+    text.replace("{{count}}", &count.to_string())
+}
+
+/// CLDR plural category of a whole number in the shipped languages, named like the
+/// suffixes i18next uses
+fn plural_category(language: &str, count: u64) -> &'static str {
+    match language {
+        "ja" | "ko" | "zh" => "other",
+        "fr" | "hi" | "pt" if count <= 1 => "one",
+        "ru" => match (count % 10, count % 100) {
+            (1, remainder) if remainder != 11 => "one",
+            (2..=4, remainder) if !(12..=14).contains(&remainder) => "few",
+            _ => "many",
+        },
+        "ar" => match count {
+            0 => "zero",
+            1 => "one",
+            2 => "two",
+            _ => match count % 100 {
+                3..=10 => "few",
+                11..=99 => "many",
+                _ => "other",
+            },
+        },
+        "fr" | "hi" | "pt" => "other",
+        _ if count == 1 => "one",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_every_locale_file_parses() {
+    assert_eq!(CATALOGS.len(), LOCALE_FILES.len());
+}
+
+#[test]
+fn test_supported_language() {
+    assert_eq!(supported_language("de"), "de");
+    assert_eq!(supported_language("zh"), "zh");
+    assert_eq!(supported_language("sv"), "en");
+    assert_eq!(supported_language(""), "en");
+}
+
+#[test]
+fn test_translate() {
+    assert_eq!(translate("en", "time.justNow"), "just now");
+    assert_eq!(translate("de", "time.justNow"), "gerade eben");
+}
+
+#[test]
+fn test_translate_falls_back_to_english_then_key() {
+    assert_eq!(translate("sv", "time.justNow"), "just now");
+    assert_eq!(translate("de", "time.missingKey"), "time.missingKey");
+    // A key naming a section rather than a string isn't text
+    assert_eq!(translate("en", "time"), "time");
+}
+
+#[test]
+fn test_plural_category() {
+    assert_eq!(plural_category("en", 1), "one");
+    assert_eq!(plural_category("en", 0), "other");
+    assert_eq!(plural_category("fr", 0), "one");
+    assert_eq!(plural_category("fr", 2), "other");
+    assert_eq!(plural_category("ja", 1), "other");
+
+    assert_eq!(plural_category("ru", 1), "one");
+    assert_eq!(plural_category("ru", 21), "one");
+    assert_eq!(plural_category("ru", 11), "many");
+    assert_eq!(plural_category("ru", 3), "few");
+    assert_eq!(plural_category("ru", 13), "many");
+    assert_eq!(plural_category("ru", 5), "many");
+
+    assert_eq!(plural_category("ar", 2), "two");
+    assert_eq!(plural_category("ar", 7), "few");
+    assert_eq!(plural_category("ar", 15), "many");
+    assert_eq!(plural_category("ar", 100), "other");
+}
+
+#[test]
+fn test_translate_count() {
+    assert_eq!(translate_count("en", "time.daysAgo", 1), "1 day ago");
+    assert_eq!(translate_count("en", "time.daysAgo", 3), "3 days ago");
+    assert_eq!(translate_count("ru", "time.daysAgo", 22), "22 дня назад");
+    assert_eq!(translate_count("ja", "time.daysAgo", 1), "1 日前");
+    // Languages without a translation fall back to English plurals
+    assert_eq!(translate_count("sv", "time.hoursAgo", 2), "2 hours ago");
+}
@@ -0,0 +1,160 @@
+//! Posts cleanup and threshold events to a user-configured webhook, so teams can
+//! follow disk usage across machines in Slack or their own dashboards
+
+use crate::commands::delete::BatchDeleteResult;
+use crate::config;
+use crate::notifications::format_bytes_readable;
+use crate::scanner::{DependencyCategory, DependencySizes, DirectoryEntry};
+use serde::Serialize;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEntry {
+    pub path: String,
+    pub size_bytes: u64,
+    pub category: DependencyCategory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    #[serde(rename_all = "camelCase")]
+    ThresholdExceeded {
+        total_size_bytes: u64,
+        threshold_bytes: u64,
+        /// The largest dependency directories, up to `MAX_WEBHOOK_ENTRIES`
+        entries: Vec<WebhookEntry>,
+    },
+    #[serde(rename_all = "camelCase")]
+    CleanupCompleted {
+        bytes_freed: u64,
+        deleted_count: usize,
+        failed_count: usize,
+    },
+}
+
+/// `text` is what Slack incoming webhooks display; other receivers can read the event fields
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub text: String,
+    pub hostname: Option<String>,
+    #[serde(flatten)]
+    pub event: WebhookEvent,
+}
+
+fn hostname() -> Option<String> {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+fn threshold_event(
+    sizes: &DependencySizes,
+    entries: &[DirectoryEntry],
+    threshold: u64,
+) -> WebhookEvent {
+    let mut largest: Vec<&DirectoryEntry> = entries.iter().collect();
+    largest.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+    WebhookEvent::ThresholdExceeded {
+        total_size_bytes: sizes.total_size,
+        threshold_bytes: threshold,
+        entries: largest
+            .into_iter()
+            .take(config::integrations::MAX_WEBHOOK_ENTRIES)
+            .map(|entry| WebhookEntry {
+                path: entry.path.clone(),
+                size_bytes: entry.size_bytes,
+                category: entry.category,
+            })
+            .collect(),
+    }
+}
+
+fn cleanup_event(batch: &BatchDeleteResult) -> WebhookEvent {
+    WebhookEvent::CleanupCompleted {
+        bytes_freed: batch.total_size_freed,
+        deleted_count: batch.successful_count,
+        failed_count: batch.failed_count,
+    }
+}
+
+fn payload(event: WebhookEvent, hostname: Option<String>) -> WebhookPayload {
+    let machine = hostname.as_deref().unwrap_or("A machine");
+    let text = match &event {
+        WebhookEvent::ThresholdExceeded {
+            total_size_bytes,
+            threshold_bytes,
+            ..
+        } => format!(
+            "{machine} has {} in dependency folders, over its {} threshold",
+            format_bytes_readable(*total_size_bytes),
+            format_bytes_readable(*threshold_bytes)
+        ),
+        WebhookEvent::CleanupCompleted {
+            bytes_freed,
+            deleted_count,
+            ..
+        } => format!(
+            "{machine} freed {} by removing {deleted_count} dependency {}",
+            format_bytes_readable(*bytes_freed),
+            if *deleted_count == 1 {
+                "folder"
+            } else {
+                "folders"
+            }
+        ),
+    };
+
+    WebhookPayload {
+        text,
+        hostname,
+        event,
+    }
+}
+
+/// Posts the event in the background. Delivery failures are logged and never retried.
+fn post(webhook_url: &str, event: WebhookEvent) {
+    let webhook_url = webhook_url.to_string();
+    tauri::async_runtime::spawn(async move {
+        let payload = payload(event, hostname());
+        let sent = reqwest::Client::new()
+            .post(&webhook_url)
+            .timeout(config::integrations::WEBHOOK_TIMEOUT)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match sent {
+            Ok(_) => debug!("Webhook delivered"),
+            Err(error) => warn!(%error, "Failed to deliver webhook"),
+        }
+    });
+}
+
+/// Reports a background scan that newly crossed the threshold
+pub fn post_threshold_exceeded(
+    webhook_url: &str,
+    sizes: &DependencySizes,
+    entries: &[DirectoryEntry],
+    threshold: u64,
+) {
+    post(webhook_url, threshold_event(sizes, entries, threshold));
+}
+
+/// Reports a finished cleanup that removed at least one directory
+pub fn post_cleanup_completed(webhook_url: &str, batch: &BatchDeleteResult) {
+    if batch.successful_count == 0 {
+        return;
+    }
+    post(webhook_url, cleanup_event(batch));
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
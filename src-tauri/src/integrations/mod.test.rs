@@ -0,0 +1,83 @@
+use super::*;
+use crate::config::bytes::GB;
+
+fn entry(path: &str, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry::for_test(path, DependencyCategory::NodeModules, size_bytes)
+}
+
+fn batch(successful_count: usize, total_size_freed: u64) -> BatchDeleteResult {
+    BatchDeleteResult {
+        results: Vec::new(),
+        total_size_freed,
+        successful_count,
+        failed_count: 1,
+    }
+}
+
+#[test]
+fn test_threshold_event_lists_largest_entries_first() {
+    let entries: Vec<DirectoryEntry> = (0..config::integrations::MAX_WEBHOOK_ENTRIES as u64 + 2)
+        .map(|index| entry(&format!("/code/app-{index}/node_modules"), index))
+        .collect();
+    let sizes = DependencySizes::from_entries(&entries);
+
+    let WebhookEvent::ThresholdExceeded {
+        total_size_bytes,
+        threshold_bytes,
+        entries: listed,
+    } = threshold_event(&sizes, &entries, 10)
+    else {
+        panic!("expected a threshold event");
+    };
+
+    assert_eq!(total_size_bytes, sizes.total_size);
+    assert_eq!(threshold_bytes, 10);
+    assert_eq!(listed.len(), config::integrations::MAX_WEBHOOK_ENTRIES);
+    assert_eq!(listed[0].path, "/code/app-11/node_modules");
+    assert!(listed
+        .windows(2)
+        .all(|pair| pair[0].size_bytes >= pair[1].size_bytes));
+}
+
+#[test]
+fn test_threshold_payload_serialization() {
+    let entries = vec![entry("/code/web/node_modules", 6 * GB as u64)];
+    let sizes = DependencySizes::from_entries(&entries);
+    let payload = payload(
+        threshold_event(&sizes, &entries, 5 * GB as u64),
+        Some("dev-laptop".to_string()),
+    );
+
+    let json = serde_json::to_value(&payload).unwrap();
+    assert_eq!(
+        json["text"],
+        "dev-laptop has 6 GB in dependency folders, over its 5 GB threshold"
+    );
+    assert_eq!(json["hostname"], "dev-laptop");
+    assert_eq!(json["event"], "threshold_exceeded");
+    assert_eq!(json["totalSizeBytes"], 6 * GB as u64);
+    assert_eq!(json["entries"][0]["path"], "/code/web/node_modules");
+    assert_eq!(json["entries"][0]["category"], "NODE_MODULES");
+}
+
+#[test]
+fn test_cleanup_payload_serialization() {
+    let payload = payload(cleanup_event(&batch(3, 2 * GB as u64)), None);
+
+    let json = serde_json::to_value(&payload).unwrap();
+    assert_eq!(
+        json["text"],
+        "A machine freed 2 GB by removing 3 dependency folders"
+    );
+    assert_eq!(json["event"], "cleanup_completed");
+    assert_eq!(json["bytesFreed"], 2 * GB as u64);
+    assert_eq!(json["deletedCount"], 3);
+    assert_eq!(json["failedCount"], 1);
+    assert!(json["hostname"].is_null());
+}
+
+#[test]
+fn test_cleanup_payload_singular_folder() {
+    let payload = payload(cleanup_event(&batch(1, 1024)), None);
+    assert!(payload.text.ends_with("removing 1 dependency folder"));
+}
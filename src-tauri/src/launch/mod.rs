@@ -0,0 +1,48 @@
+use crate::commands::settings::AppSettings;
+
+/// Argument the autostart plugin passes when the app is launched at login
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Asks for a scan straight away, whether deptox is starting or already running
+pub const SCAN_ARG: &str = "--scan";
+
+/// The launch arguments deptox understands, outside the hidden fixture ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LaunchArgs {
+    pub autostarted: bool,
+    pub scan: bool,
+}
+
+impl LaunchArgs {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut parsed = Self::default();
+        for arg in args {
+            match arg.as_str() {
+                AUTOSTART_ARG => parsed.autostarted = true,
+                SCAN_ARG => parsed.scan = true,
+                _ => {}
+            }
+        }
+        parsed
+    }
+}
+
+/// What to do once the window and tray exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LaunchActions {
+    pub scan: bool,
+    pub show_window: bool,
+}
+
+/// A launch at login stays in the tray so it doesn't interrupt the user, while a manual
+/// launch can open the window straight away
+pub fn launch_actions(settings: &AppSettings, args: LaunchArgs) -> LaunchActions {
+    LaunchActions {
+        scan: settings.scan_on_launch || args.scan,
+        show_window: (settings.show_window_on_launch || args.scan) && !args.autostarted,
+    }
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,61 @@
+use super::*;
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn settings(scan_on_launch: bool, show_window_on_launch: bool) -> AppSettings {
+    AppSettings {
+        scan_on_launch,
+        show_window_on_launch,
+        ..AppSettings::default()
+    }
+}
+
+#[test]
+fn test_parse_launch_args() {
+    assert_eq!(LaunchArgs::parse(args(&["deptox"])), LaunchArgs::default());
+    assert!(LaunchArgs::parse(args(&["deptox", "--autostart"])).autostarted);
+    assert!(LaunchArgs::parse(args(&["deptox", "--scan"])).scan);
+    assert_eq!(
+        LaunchArgs::parse(args(&["deptox", "--fixture", "/tmp/fixture"])),
+        LaunchArgs::default()
+    );
+}
+
+#[test]
+fn test_launch_actions_default_does_nothing() {
+    assert_eq!(
+        launch_actions(&AppSettings::default(), LaunchArgs::default()),
+        LaunchActions::default()
+    );
+}
+
+#[test]
+fn test_launch_actions_manual_launch_shows_window() {
+    let actions = launch_actions(&settings(true, true), LaunchArgs::default());
+    assert!(actions.scan);
+    assert!(actions.show_window);
+}
+
+#[test]
+fn test_launch_actions_autostart_stays_hidden() {
+    let launch_args = LaunchArgs {
+        autostarted: true,
+        scan: false,
+    };
+    let actions = launch_actions(&settings(true, true), launch_args);
+    assert!(actions.scan);
+    assert!(!actions.show_window);
+}
+
+#[test]
+fn test_launch_actions_scan_arg_overrides_settings() {
+    let launch_args = LaunchArgs {
+        autostarted: false,
+        scan: true,
+    };
+    let actions = launch_actions(&AppSettings::default(), launch_args);
+    assert!(actions.scan);
+    assert!(actions.show_window);
+}
@@ -1,40 +1,194 @@
+mod activity;
+mod analysis;
+mod cloud;
 mod commands;
 mod config;
+mod deep_link;
+mod error;
+mod fixture;
+mod format;
+mod hash;
+mod history;
+mod i18n;
+mod integrations;
+mod launch;
+mod local_api;
+mod logs;
+mod notifications;
+mod overrides;
+mod persistence;
+mod processes;
 mod scanner;
+mod secure_store;
+mod sounds;
+mod telemetry;
+mod time;
 mod tray;
+mod updates;
 
 #[cfg(test)]
 mod test_helpers;
 
+use launch::LaunchArgs;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{
-    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    menu::MenuEvent,
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Listener, Manager, RunEvent,
+    Emitter, Listener, Manager, RunEvent, WindowEvent,
 };
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_positioner::{Position, WindowExt};
 use tokio::sync::watch;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 fn init_tracing() {
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("deptox_lib=info,warn"));
+    let (overrides, overrides_error) = overrides::load_overrides();
 
-    tracing_subscriber::registry()
+    let (file_layer, file_error) = match logs::file_layer() {
+        Ok(layer) => (Some(layer), None),
+        Err(error) => (None, Some(error)),
+    };
+
+    let registry = tracing_subscriber::registry()
         .with(fmt::layer().with_target(true).with_level(true))
-        .with(filter)
-        .init();
+        .with(file_layer)
+        .with(filter);
+
+    #[cfg(feature = "otlp")]
+    let (registry, otlp_error) = match overrides.otlp_endpoint.as_deref() {
+        Some(endpoint) => match telemetry::otlp_layer(endpoint) {
+            Ok(layer) => (registry.with(Some(layer)), None),
+            Err(error) => (registry.with(None), Some(error)),
+        },
+        None => (registry.with(None), None),
+    };
+
+    registry.init();
+
+    if let Some(error) = file_error {
+        warn!(%error, "Logging to file disabled");
+    }
+
+    if let Some(error) = overrides_error {
+        warn!(%error, "Ignoring overrides file");
+    }
+
+    if overrides.disable_bulk_attributes {
+        info!("Bulk attribute sizing disabled by overrides");
+        scanner::set_bulk_attributes_enabled(false);
+    }
+
+    #[cfg(feature = "otlp")]
+    if let Some(error) = otlp_error {
+        warn!(%error, "OTLP export disabled");
+    } else if let Some(endpoint) = overrides.otlp_endpoint.as_deref() {
+        info!(endpoint, "Exporting spans over OTLP");
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    if overrides.otlp_endpoint.is_some() {
+        warn!("OTLP endpoint configured but this build was compiled without the otlp feature");
+    }
 }
 
+/// Whether the window has been detached from the tray into a regular app window
+static WINDOW_DETACHED: AtomicBool = AtomicBool::new(false);
+
+/// Logical size of the tray window when it was detached, restored when it docks again
+static DOCKED_SIZE: Mutex<Option<tauri::LogicalSize<f64>>> = Mutex::new(None);
+
 fn position_window_at_tray(window: &tauri::WebviewWindow) {
+    // A detached window stays wherever the user has put it
+    if WINDOW_DETACHED.load(Ordering::SeqCst) {
+        return;
+    }
     let _ = window.move_window(Position::TrayCenter);
 }
 
-fn show_window_with_event<T: serde::Serialize + Clone>(
+/// Switches between the borderless tray popover and a regular window with a title bar,
+/// a taskbar or Dock entry, and resizable edges
+fn apply_window_mode(window: &tauri::WebviewWindow, detached: bool) -> Result<(), String> {
+    window
+        .set_decorations(detached)
+        .map_err(|error| format!("Failed to set window decorations: {error}"))?;
+    window
+        .set_resizable(detached)
+        .map_err(|error| format!("Failed to set window resizable: {error}"))?;
+    window
+        .set_always_on_top(!detached)
+        .map_err(|error| format!("Failed to set window always on top: {error}"))?;
+    window
+        .set_skip_taskbar(!detached)
+        .map_err(|error| format!("Failed to set window taskbar entry: {error}"))?;
+
+    #[cfg(target_os = "macos")]
+    window
+        .app_handle()
+        .set_activation_policy(if detached {
+            tauri::ActivationPolicy::Regular
+        } else {
+            tauri::ActivationPolicy::Accessory
+        })
+        .map_err(|error| format!("Failed to set activation policy: {error}"))?;
+
+    WINDOW_DETACHED.store(detached, Ordering::SeqCst);
+    let _ = window.emit("window-detached-changed", detached);
+    Ok(())
+}
+
+fn detach(window: &tauri::WebviewWindow) -> Result<(), String> {
+    if WINDOW_DETACHED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let scale_factor = window
+        .scale_factor()
+        .map_err(|error| format!("Failed to read window scale: {error}"))?;
+    let docked_size = window
+        .inner_size()
+        .map_err(|error| format!("Failed to read window size: {error}"))?
+        .to_logical::<f64>(scale_factor);
+    *DOCKED_SIZE
+        .lock()
+        .map_err(|error| format!("Failed to lock window size: {error}"))? = Some(docked_size);
+
+    apply_window_mode(window, true)?;
+    let _ = window.center();
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    info!("Window detached from tray");
+    Ok(())
+}
+
+fn dock(window: &tauri::WebviewWindow) -> Result<(), String> {
+    if !WINDOW_DETACHED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    apply_window_mode(window, false)?;
+
+    let docked_size = DOCKED_SIZE
+        .lock()
+        .map_err(|error| format!("Failed to lock window size: {error}"))?
+        .take();
+    if let Some(size) = docked_size {
+        window
+            .set_size(tauri::Size::Logical(size))
+            .map_err(|error| format!("Failed to resize window: {error}"))?;
+    }
+    position_window_at_tray(window);
+
+    info!("Window docked to tray");
+    Ok(())
+}
+
+pub(crate) fn show_window_with_event<T: serde::Serialize + Clone>(
     app_handle: &tauri::AppHandle,
     event_name: &str,
     payload: T,
@@ -68,6 +222,35 @@ async fn resize_window(app: tauri::AppHandle, font_size: String) -> Result<(), S
     Ok(())
 }
 
+/// Pins or unpins the window. A pinned window stays open when focus moves elsewhere,
+/// and the choice is kept for the next launch.
+#[tauri::command]
+async fn set_window_pinned(pinned: bool) -> Result<(), String> {
+    let changes = serde_json::Map::from_iter([("pinWindow".to_string(), pinned.into())]);
+    commands::settings::update_settings(changes).map_err(|error| error.to_string())?;
+    debug!(pinned, "Window pin changed");
+    Ok(())
+}
+
+/// Turns the tray window into a regular window that stays open and can be resized or
+/// moved to another display, for long cleanups
+#[tauri::command]
+async fn detach_window(app: tauri::AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    detach(&window)
+}
+
+/// Returns a detached window to the tray, at the size it had before
+#[tauri::command]
+async fn dock_window(app: tauri::AppHandle) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    dock(&window)
+}
+
 fn handle_menu_event(
     app_handle: &tauri::AppHandle,
     _menu_app_handle: &tauri::AppHandle,
@@ -81,6 +264,10 @@ fn handle_menu_event(
             info!("Triggering scan from tray menu");
             show_window_with_event(app_handle, "tray-scan-requested", ());
         }
+        "clean_stale" => {
+            info!("Cleaning stale directories from tray menu");
+            commands::clean_stale::spawn_clean_stale(app_handle.clone());
+        }
         "settings" => {
             info!("Opening settings from tray menu");
             show_window_with_event(app_handle, "tray-settings-requested", ());
@@ -98,17 +285,68 @@ fn handle_menu_event(
             app_handle.exit(0);
         }
         _ => {
-            debug!(menu_id, "Unknown menu item clicked");
+            if let Some(category) = tray::category_from_menu_id(menu_id) {
+                info!(?category, "Opening category results from tray menu");
+                show_window_with_event(app_handle, tray::CATEGORY_REQUESTED_EVENT, category);
+            } else {
+                debug!(menu_id, "Unknown menu item clicked");
+            }
         }
     }
 }
 
+/// Lets `persistence::flush_all` write back every buffered store before exit
+fn register_buffered_stores() {
+    persistence::register_store("cleanup_stats", commands::stats::flush_stats);
+    persistence::register_store(
+        "deletion_journal",
+        commands::deletion_journal::flush_journal,
+    );
+    persistence::register_store("scan_cache", commands::scan_cache::flush_scan_cache);
+    persistence::register_store("scan_history", history::flush_history);
+    persistence::register_store("size_index", scanner::size_index::flush_size_index);
+    persistence::register_store("usage_metrics", telemetry::metrics::flush_metrics);
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     init_tracing();
+    telemetry::crash::install_panic_hook();
     info!("Starting deptox");
+    register_buffered_stores();
+
+    if let Ok(settings) = commands::settings::get_settings_sync() {
+        format::set_size_unit_base(settings.size_unit_base);
+        telemetry::crash::set_crash_reporting(settings.crash_report_url.is_some());
+    }
+
+    if let Some((fixture_root, layout)) = fixture::parse_fixture_args(std::env::args()) {
+        match fixture::prepare_fixture(&fixture_root, &layout) {
+            Ok(()) => info!(path = %fixture_root.display(), "Running in fixture mode"),
+            Err(error) => error!(%error, "Failed to prepare fixture, using configured root"),
+        }
+    }
 
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before touching settings or the tray
+        .plugin(tauri_plugin_single_instance::init(
+            |app_handle, args, _cwd| {
+                info!(
+                    ?args,
+                    "Another deptox instance was launched, focusing this one"
+                );
+                let links = deep_link::links_in_args(&args);
+                if LaunchArgs::parse(args.iter().cloned()).scan {
+                    // The running instance owns the scanner, so the scan is started there
+                    show_window_with_event(app_handle, "tray-scan-requested", ());
+                } else if links.is_empty() {
+                    show_window_with_event(app_handle, "second-instance-launched", args);
+                } else {
+                    deep_link::handle_urls(app_handle, &links);
+                }
+            },
+        ))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_positioner::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -116,31 +354,73 @@ pub fn run() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            Some(vec!["--autostart"]),
+            Some(vec![launch::AUTOSTART_ARG]),
         ))
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .invoke_handler(tauri::generate_handler![
             commands::scan::start_scan,
             commands::scan::cancel_scan,
+            commands::scan::get_active_scan,
+            commands::scan::simulate_scan,
             commands::scan::rescan_directory,
+            commands::scan::recompute_age,
             commands::delete::delete_to_trash,
             commands::delete::delete_all_to_trash,
+            commands::delete::preview_delete_all,
+            commands::clean_stale::clean_stale,
+            commands::deletion_journal::get_deletion_history,
+            commands::deletion_journal::restore_deleted,
+            commands::deletion_journal::purge_expired_trash,
+            commands::deletion_journal::get_trash_usage,
+            commands::deletion_journal::empty_deptox_trash,
             commands::settings::get_settings,
             commands::settings::save_settings,
             commands::settings::reset_settings,
-            commands::filesystem::open_in_finder,
+            commands::settings::export_settings,
+            commands::settings::import_settings,
+            commands::ecosystems::detect_ecosystems,
+            commands::onboarding::complete_onboarding,
+            commands::stats::get_cleanup_stats,
+            commands::scan_cache::get_cached_scan,
+            commands::scan_cache::get_outlier_entries,
+            history::get_scan_history,
+            history::compare_scans,
+            logs::get_recent_logs,
+            logs::export_logs,
+            commands::caches::clear_package_manager_cache,
+            commands::filesystem::reveal_in_file_manager,
+            commands::ignored::ignore_directory,
+            commands::ignored::unignore_directory,
+            commands::ignored::list_ignored,
             commands::largest_files::get_largest_files,
+            commands::filesystem::get_disk_usage,
+            commands::filesystem::request_full_disk_access,
+            commands::filesystem::check_full_disk_access,
+            commands::largest_files::get_largest_children,
+            analysis::duplicates::get_duplicate_packages,
+            commands::reinstall::get_reinstall_command,
             commands::locale::get_system_locale,
+            format::format_bytes,
+            format::format_relative_time,
+            commands::locale::get_category_labels,
+            commands::schemas::get_schemas,
             commands::autostart::get_autostart_enabled,
             commands::autostart::set_autostart_enabled,
             commands::license::get_license_info,
             commands::license::activate_license,
             commands::license::revalidate_license,
             commands::license::deactivate_license,
+            commands::license::trial::get_trial_status,
+            local_api::get_local_api_token,
             tray::set_tray_icon,
             tray::set_tray_update_available,
             resize_window,
+            set_window_pinned,
+            detach_window,
+            dock_window,
+            updates::check_for_updates,
+            updates::get_update_changelog,
         ])
         .setup(|app| {
             #[cfg(target_os = "macos")]
@@ -176,10 +456,34 @@ pub fn run() {
                     debug!("Window blur event ignored - dialog is open");
                     return;
                 }
+                if WINDOW_DETACHED.load(Ordering::SeqCst) {
+                    return;
+                }
+                let pinned = commands::settings::get_settings_sync()
+                    .is_ok_and(|settings| settings.pin_window);
+                if pinned {
+                    debug!("Window blur event ignored - window is pinned");
+                    return;
+                }
                 debug!("Window blur event - hiding window");
                 let _ = window_clone.hide();
             });
 
+            // Closing a detached window docks it back to the tray rather than destroying
+            // the only window the tray can open
+            let window_for_close = window.clone();
+            window.on_window_event(move |event| {
+                if let WindowEvent::CloseRequested { api, .. } = event {
+                    if WINDOW_DETACHED.load(Ordering::SeqCst) {
+                        api.prevent_close();
+                        if let Err(error) = dock(&window_for_close) {
+                            warn!(%error, "Failed to dock window on close");
+                        }
+                        let _ = window_for_close.hide();
+                    }
+                }
+            });
+
             window.listen("dialog-opening", move |_event| {
                 debug!("Dialog opening - disabling blur handler");
                 dialog_open_for_open.store(true, Ordering::SeqCst);
@@ -209,17 +513,65 @@ pub fn run() {
                 }
             }
 
+            app.manage(tray::TrayUpdater::spawn(app.handle().clone()));
+            app.manage(tray::TrayStateController::spawn(app.handle().clone()));
+
+            let access_app_handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                commands::filesystem::notify_if_full_disk_access_missing(&access_app_handle);
+            });
+            local_api::start(app.handle());
+
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                let deep_link_handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    deep_link::handle_urls(&deep_link_handle, &event.urls());
+                });
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    deep_link::handle_urls(app.handle(), &urls);
+                }
+            }
+
+            let launch_args = LaunchArgs::parse(std::env::args());
+            let launch_settings = commands::settings::get_settings_sync().unwrap_or_default();
+            let actions = launch::launch_actions(&launch_settings, launch_args);
+            info!(?launch_args, ?actions, "Applying launch options");
+            if actions.show_window {
+                position_window_at_tray(&window);
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            if actions.scan {
+                let launch_app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(error) = commands::scan::start_scan(launch_app_handle).await {
+                        warn!(%error, "Launch scan failed");
+                    }
+                });
+            }
+
             let (shutdown_tx, shutdown_rx) = watch::channel(false);
             app.manage(shutdown_tx);
 
+            updates::notify_if_updated(app.handle());
+            updates::spawn_update_checks(app.handle().clone(), shutdown_rx.clone());
+
+            telemetry::crash::spawn_monitored(
+                "crash_reports",
+                telemetry::crash::send_pending_reports(),
+            );
+
             let background_app_handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
+            telemetry::crash::spawn_monitored("background_scan", async move {
                 info!(
                     interval_minutes = config::background::SCAN_INTERVAL_MINUTES,
                     "Starting background scanner"
                 );
 
                 let mut shutdown_rx = shutdown_rx;
+                let mut was_exceeded = false;
                 let scan_interval =
                     Duration::from_secs(config::background::SCAN_INTERVAL_MINUTES * 60);
 
@@ -241,29 +593,126 @@ pub fn run() {
                         break;
                     }
 
+                    let purge_after_days = commands::settings::get_settings_sync()
+                        .map(|settings| settings.trash_purge_after_days)
+                        .unwrap_or(0);
+                    if purge_after_days > 0 {
+                        let purged = tokio::task::spawn_blocking(move || {
+                            commands::deletion_journal::purge_expired_trash_items(purge_after_days)
+                        })
+                        .await;
+                        if let Ok(Err(error)) = purged {
+                            warn!(%error, "Failed to purge expired trash items");
+                        }
+                    }
+
+                    telemetry::metrics::upload_if_due().await;
+
                     debug!("Running scheduled background scan");
-                    let total_size =
-                        tokio::task::spawn_blocking(scanner::calculate_total_dependency_size)
-                            .await
-                            .unwrap_or(0);
+                    tray::set_state(&background_app_handle, tray::TrayState::Scanning);
+                    let Some(cached) = tokio::task::spawn_blocking(|| {
+                        let settings = commands::settings::get_settings_sync().unwrap_or_default();
+                        let config = commands::scan::build_scan_config(
+                            &settings,
+                            scanner::ScanThrottle::for_background(settings.scan_priority),
+                        );
+                        scanner::run_background_scan(&config).map(|result| {
+                            commands::scan_cache::store_scan_result(&config.root_directory, &result)
+                        })
+                    })
+                    .await
+                    .ok()
+                    .flatten() else {
+                        warn!("Background scan produced no result");
+                        tray::set_state(&background_app_handle, tray::TrayState::Error);
+                        continue;
+                    };
+
+                    let _ = background_app_handle.emit("background_scan_complete", &cached);
+                    if let Err(error) = tray::update_summary(&background_app_handle, &cached) {
+                        warn!(%error, "Failed to update tray scan summary");
+                    }
+
+                    let sizes = scanner::DependencySizes::from_entries(&cached.result.entries);
+                    let total_size = sizes.total_size;
 
-                    let threshold = commands::settings::get_settings_sync()
-                        .map(|settings| settings.threshold_bytes)
+                    let settings = commands::settings::get_settings_sync().ok();
+                    let threshold = settings
+                        .as_ref()
+                        .map(|settings| {
+                            settings.effective_threshold_bytes(cached.result.disk_usage.as_ref())
+                        })
                         .unwrap_or(config::defaults::BACKGROUND_THRESHOLD_BYTES);
+                    let notify_enabled = settings
+                        .as_ref()
+                        .is_some_and(|settings| settings.notify_on_threshold_exceeded);
+                    let notification_contents = settings
+                        .as_ref()
+                        .map(|settings| settings.notification_contents)
+                        .unwrap_or(commands::settings::NotificationContents::Total);
+                    let webhook_url = settings
+                        .as_ref()
+                        .and_then(|settings| settings.webhook_url.clone());
+                    let exceeded_categories = settings
+                        .map(|settings| {
+                            scanner::find_exceeded_categories(
+                                &sizes.by_category,
+                                &settings.category_thresholds,
+                            )
+                        })
+                        .unwrap_or_default();
 
                     info!(
                         total_size_gb = total_size as f64 / 1024.0 / 1024.0 / 1024.0,
                         threshold_gb = threshold as f64 / 1024.0 / 1024.0 / 1024.0,
                         exceeds_threshold = total_size > threshold,
+                        exceeded_categories = ?exceeded_categories
+                            .iter()
+                            .map(|excess| excess.category)
+                            .collect::<Vec<_>>(),
                         "Background scan threshold check"
                     );
 
                     if let Err(error) =
-                        tray::set_tray_icon(background_app_handle.clone(), total_size, threshold)
-                            .await
+                        tray::set_tray_category_sizes(&background_app_handle, &sizes.by_category)
+                    {
+                        warn!(%error, "Failed to update tray category breakdown");
+                    }
+
+                    tray::finish_scanning(&background_app_handle);
+                    if let Err(error) = tray::set_tray_icon(
+                        background_app_handle.clone(),
+                        total_size,
+                        threshold,
+                        Some(exceeded_categories),
+                    )
+                    .await
                     {
                         error!(%error, "Failed to update tray icon");
                     }
+
+                    let exceeds_threshold = total_size > threshold;
+                    if notifications::should_notify(was_exceeded, exceeds_threshold, notify_enabled)
+                    {
+                        notifications::notify_threshold_exceeded(
+                            &background_app_handle,
+                            &sizes,
+                            &cached.result.entries,
+                            threshold,
+                            notification_contents,
+                        );
+                    }
+                    if let Some(webhook_url) = &webhook_url {
+                        if notifications::should_notify(was_exceeded, exceeds_threshold, true) {
+                            integrations::post_threshold_exceeded(
+                                webhook_url,
+                                &sizes,
+                                &cached.result.entries,
+                                threshold,
+                            );
+                        }
+                    }
+                    was_exceeded = exceeds_threshold;
                 }
 
                 info!("Background scanner stopped");
@@ -278,13 +727,7 @@ pub fn run() {
                 tauri::Error::AssetNotFound(format!("Failed to load tray icon: {error}"))
             })?;
 
-            let scan_now = MenuItem::with_id(app, "scan_now", "Scan Now", true, None::<&str>)?;
-            let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
-            let separator = PredefinedMenuItem::separator(app)?;
-            let about = MenuItem::with_id(app, "about", "About", true, None::<&str>)?;
-            let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-
-            let menu = Menu::with_items(app, &[&scan_now, &settings, &separator, &about, &quit])?;
+            let menu = tray::build_tray_menu(app.handle())?;
 
             let menu_app_handle = app.handle().clone();
             app.on_menu_event(move |app_handle, event: MenuEvent| {
@@ -299,6 +742,12 @@ pub fn run() {
                 .on_tray_icon_event(move |tray, event| {
                     tauri_plugin_positioner::on_tray_event(tray.app_handle(), &event);
 
+                    if let TrayIconEvent::Enter { .. } = &event {
+                        if let Err(error) = tray::refresh_summary(tray.app_handle()) {
+                            debug!(%error, "Failed to refresh tray scan summary");
+                        }
+                    }
+
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
@@ -312,6 +761,7 @@ pub fn run() {
                                 position_window_at_tray(&window);
                                 let _ = window.show();
                                 let _ = window.set_focus();
+                                notifications::open_pending_review(&app_handle);
                             }
                         }
                     }
@@ -322,12 +772,23 @@ pub fn run() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|app_handle, event| {
-            if let RunEvent::Exit = event {
+        .run(|app_handle, event| match event {
+            // Clicking a notification activates the app without delivering the click itself
+            #[cfg(target_os = "macos")]
+            RunEvent::Reopen { .. } => {
+                notifications::open_pending_review(app_handle);
+            }
+            RunEvent::Exit => {
                 info!("Application exiting, signaling background scanner to stop");
                 if let Some(shutdown_tx) = app_handle.try_state::<watch::Sender<bool>>() {
                     let _ = shutdown_tx.send(true);
                 }
+
+                persistence::flush_all_with_timeout(config::app::EXIT_FLUSH_TIMEOUT);
+
+                #[cfg(feature = "otlp")]
+                telemetry::shutdown();
             }
+            _ => {}
         });
 }
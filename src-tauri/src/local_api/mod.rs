@@ -0,0 +1,269 @@
+//! Opt-in HTTP API on localhost so scripts and launcher extensions (Raycast, Alfred) can
+//! drive deptox without the window. Every request needs the token from
+//! `get_local_api_token` as `Authorization: Bearer <token>`.
+//!
+//! - `POST /scan` starts a scan, like the Scan button
+//! - `GET /results` returns the most recent scan
+//! - `POST /delete` takes `{"paths": [...], "confirmOpenProjects": false}` and deletes them
+
+use crate::commands;
+use crate::config;
+use crate::error::DeptoxError;
+use crate::persistence::config_path;
+use serde::Deserialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{debug, info, instrument, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Scan,
+    Results,
+    Delete,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteRequest {
+    paths: Vec<String>,
+    #[serde(default)]
+    confirm_open_projects: bool,
+    #[serde(default)]
+    confirm_cloud_eviction: bool,
+}
+
+fn route(method: &Method, url: &str) -> Option<Route> {
+    let path = url.split('?').next().unwrap_or(url);
+    match (method, path) {
+        (Method::Post, "/scan") => Some(Route::Scan),
+        (Method::Get, "/results") => Some(Route::Results),
+        (Method::Post, "/delete") => Some(Route::Delete),
+        _ => None,
+    }
+}
+
+/// Compares in constant time so response timing doesn't reveal the token
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+
+    provided.len() == token.len()
+        && provided
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; config::local_api::TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|error| format!("Failed to generate API token: {error}"))?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn get_token_path() -> Result<PathBuf, String> {
+    config_path(config::app::API_TOKEN_FILENAME)
+}
+
+/// Makes the token file readable only by the user, fixing one saved with looser
+/// permissions by an earlier version
+#[cfg(unix)]
+fn restrict_token_file(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata =
+        fs::metadata(path).map_err(|error| format!("Failed to read API token: {error}"))?;
+    if metadata.permissions().mode() & 0o077 != 0 {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|error| format!("Failed to restrict API token: {error}"))?;
+        info!("Restricted local API token permissions");
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_token_file(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Creates the token file, never readable by other users even briefly
+fn write_token_file(path: &Path, token: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(token.as_bytes()))
+        .map_err(|error| format!("Failed to write API token: {error}"))?;
+    // The mode only applies to files this call creates
+    restrict_token_file(path)
+}
+
+/// Reads the API token, creating one readable only by the user on first use
+fn load_or_create_token_at(path: &Path) -> Result<String, String> {
+    if let Ok(token) = fs::read_to_string(path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            restrict_token_file(path)?;
+            return Ok(token.to_string());
+        }
+    }
+
+    let token = generate_token()?;
+    write_token_file(path, &token)?;
+
+    info!("Created local API token");
+    Ok(token)
+}
+
+fn load_or_create_token() -> Result<String, String> {
+    load_or_create_token_at(&get_token_path()?)
+}
+
+fn json_response(status: u16, body: &impl serde::Serialize) -> Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+fn error_response(status: u16, message: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    json_response(status, &serde_json::json!({ "error": message }))
+}
+
+/// Reads a request body of at most `limit` bytes, answering 413 for anything longer
+fn read_body(reader: impl Read, limit: u64) -> Result<String, Response<std::io::Cursor<Vec<u8>>>> {
+    let mut body = Vec::new();
+    // One byte past the limit tells a body of exactly `limit` bytes from a longer one
+    if let Err(error) = reader.take(limit + 1).read_to_end(&mut body) {
+        return Err(error_response(
+            400,
+            &format!("Failed to read body: {error}"),
+        ));
+    }
+    if body.len() as u64 > limit {
+        return Err(error_response(413, "Body too large"));
+    }
+    String::from_utf8(body).map_err(|error| error_response(400, &format!("Invalid body: {error}")))
+}
+
+/// Runs the command behind a route, mapping its result to a status and JSON body
+fn handle(
+    app: &tauri::AppHandle,
+    route: Route,
+    request: &mut Request,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let app = app.clone();
+    let result = match route {
+        Route::Scan => tauri::async_runtime::block_on(commands::scan::start_scan(app))
+            .map(|warnings| json_response(202, &warnings)),
+        Route::Results => tauri::async_runtime::block_on(commands::scan_cache::get_cached_scan())
+            .map(|cached| json_response(200, &cached))
+            .map_err(DeptoxError::Internal),
+        Route::Delete => {
+            let body = match read_body(request.as_reader(), config::local_api::MAX_BODY_BYTES) {
+                Ok(body) => body,
+                Err(response) => return response,
+            };
+            let delete: DeleteRequest = match serde_json::from_str(&body) {
+                Ok(delete) => delete,
+                Err(error) => return error_response(400, &format!("Invalid body: {error}")),
+            };
+
+            tauri::async_runtime::block_on(commands::delete::delete_all_to_trash(
+                app,
+                delete.paths,
+                Some(delete.confirm_open_projects),
+                Some(delete.confirm_cloud_eviction),
+            ))
+            .map(|batch| json_response(200, &batch))
+        }
+    };
+
+    result.unwrap_or_else(|error| error_response(500, &error.to_string()))
+}
+
+fn serve(server: Server, app: tauri::AppHandle, token: String) {
+    for mut request in server.incoming_requests() {
+        let authorization = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Authorization"))
+            .map(|header| header.value.as_str().to_string());
+
+        let response = if !is_authorized(authorization.as_deref(), &token) {
+            warn!(url = %request.url(), "Rejected local API request without a valid token");
+            error_response(401, "Missing or invalid token")
+        } else if let Some(route) = route(request.method(), request.url()) {
+            debug!(?route, "Handling local API request");
+            handle(&app, route, &mut request)
+        } else {
+            error_response(404, "Not found")
+        };
+
+        if let Err(error) = request.respond(response) {
+            warn!(%error, "Failed to send local API response");
+        }
+    }
+}
+
+/// Starts the API on localhost when `local_api_enabled` is on. Changing the setting
+/// takes effect at the next launch.
+pub fn start(app: &tauri::AppHandle) {
+    let enabled = commands::settings::get_settings_sync()
+        .map(|settings| settings.local_api_enabled)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let token = match load_or_create_token() {
+        Ok(token) => token,
+        Err(error) => {
+            warn!(%error, "Local API disabled");
+            return;
+        }
+    };
+
+    let address = format!("127.0.0.1:{}", config::local_api::PORT);
+    let server = match Server::http(&address) {
+        Ok(server) => server,
+        Err(error) => {
+            warn!(%error, %address, "Failed to start local API");
+            return;
+        }
+    };
+
+    let app = app.clone();
+    let spawned = std::thread::Builder::new()
+        .name("local-api".to_string())
+        .spawn(move || serve(server, app, token));
+    match spawned {
+        Ok(_) => info!(%address, "Local API listening"),
+        Err(error) => warn!(%error, "Failed to start local API thread"),
+    }
+}
+
+/// The token clients send as `Authorization: Bearer <token>`
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn get_local_api_token() -> Result<String, String> {
+    load_or_create_token()
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
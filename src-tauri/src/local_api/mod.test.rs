@@ -0,0 +1,83 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_route() {
+    assert_eq!(route(&Method::Post, "/scan"), Some(Route::Scan));
+    assert_eq!(route(&Method::Get, "/results"), Some(Route::Results));
+    assert_eq!(
+        route(&Method::Get, "/results?fresh=1"),
+        Some(Route::Results)
+    );
+    assert_eq!(route(&Method::Post, "/delete"), Some(Route::Delete));
+
+    assert_eq!(route(&Method::Get, "/scan"), None);
+    assert_eq!(route(&Method::Delete, "/delete"), None);
+    assert_eq!(route(&Method::Get, "/"), None);
+}
+
+#[test]
+fn test_is_authorized() {
+    assert!(is_authorized(Some("Bearer secret-token"), "secret-token"));
+
+    assert!(!is_authorized(None, "secret-token"));
+    assert!(!is_authorized(Some("secret-token"), "secret-token"));
+    assert!(!is_authorized(Some("Bearer secret-tokem"), "secret-token"));
+    assert!(!is_authorized(Some("Bearer secret"), "secret-token"));
+    assert!(!is_authorized(Some("Bearer "), "secret-token"));
+}
+
+#[test]
+fn test_generate_token() {
+    let token = generate_token().unwrap();
+
+    assert_eq!(token.len(), config::local_api::TOKEN_BYTES * 2);
+    assert!(token.chars().all(|character| character.is_ascii_hexdigit()));
+    assert_ne!(token, generate_token().unwrap());
+}
+
+#[test]
+fn test_delete_request_defaults_confirmation() {
+    let request: DeleteRequest =
+        serde_json::from_str(r#"{"paths":["/code/app/node_modules"]}"#).unwrap();
+
+    assert_eq!(request.paths, vec!["/code/app/node_modules"]);
+    assert!(!request.confirm_open_projects);
+}
+
+#[test]
+fn test_read_body_limits_size() {
+    assert_eq!(read_body(&b"{}"[..], 2).ok().as_deref(), Some("{}"));
+    assert_eq!(read_body(&b"{ }"[..], 2).unwrap_err().status_code().0, 413);
+    assert_eq!(read_body(&b"\xff"[..], 2).unwrap_err().status_code().0, 400);
+}
+
+#[test]
+fn test_load_or_create_token_reuses_saved_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("api_token");
+
+    let token = load_or_create_token_at(&path).unwrap();
+
+    assert_eq!(load_or_create_token_at(&path).unwrap(), token);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_token_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let created = temp_dir.path().join("api_token");
+    load_or_create_token_at(&created).unwrap();
+
+    let existing = temp_dir.path().join("existing_token");
+    fs::write(&existing, "saved-token").unwrap();
+    fs::set_permissions(&existing, fs::Permissions::from_mode(0o644)).unwrap();
+    assert_eq!(load_or_create_token_at(&existing).unwrap(), "saved-token");
+
+    for path in [created, existing] {
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::config;
+use crate::persistence::config_dir;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{info, instrument, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{fmt, Layer};
+
+fn log_directory() -> Option<PathBuf> {
+    config_dir()
+        .ok()
+        .map(|config_dir| config_dir.join(config::logs::DIRECTORY))
+}
+
+/// Builds a tracing layer writing to a daily log file in the config directory, so
+/// diagnostics exist even when the app wasn't launched from a terminal
+pub fn file_layer<S>() -> Result<impl Layer<S>, String>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let directory = log_directory().ok_or("Failed to determine log directory")?;
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("Failed to create log directory: {error}"))?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(config::logs::FILENAME_PREFIX)
+        .filename_suffix(config::logs::FILENAME_SUFFIX)
+        .max_log_files(config::logs::MAX_FILES)
+        .build(&directory)
+        .map_err(|error| format!("Failed to open log file: {error}"))?;
+
+    // Written synchronously rather than through a background writer, which release
+    // builds would abort before it flushed the panic that brought them down
+    Ok(fmt::layer()
+        .with_ansi(false)
+        .with_target(true)
+        .with_writer(appender))
+}
+
+fn is_log_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.starts_with(config::logs::FILENAME_PREFIX)
+                && name.ends_with(config::logs::FILENAME_SUFFIX)
+        })
+}
+
+/// Log files oldest first. Rotated names carry the date, so sorting by name is chronological.
+fn log_files(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_log_file(path))
+        .collect();
+    files.sort();
+    files
+}
+
+/// The last `count` lines across the log files, oldest first
+fn recent_lines(directory: &Path, count: usize) -> Result<Vec<String>, String> {
+    let mut lines = VecDeque::with_capacity(count);
+
+    for file in log_files(directory).iter().rev() {
+        if lines.len() >= count {
+            break;
+        }
+
+        let content = fs::read_to_string(file)
+            .map_err(|error| format!("Failed to read {}: {error}", file.display()))?;
+        let remaining = count - lines.len();
+        for line in content.lines().rev().take(remaining) {
+            lines.push_front(line.to_string());
+        }
+    }
+
+    Ok(lines.into())
+}
+
+/// Concatenates every log file, oldest first, into one file at `destination`
+fn export_logs_to(directory: &Path, destination: &Path) -> Result<(), String> {
+    let mut output = fs::File::create(destination)
+        .map_err(|error| format!("Failed to create {}: {error}", destination.display()))?;
+
+    for file in log_files(directory) {
+        let mut input = fs::File::open(&file)
+            .map_err(|error| format!("Failed to read {}: {error}", file.display()))?;
+        io::copy(&mut input, &mut output)
+            .map_err(|error| format!("Failed to write logs: {error}"))?;
+    }
+
+    Ok(())
+}
+
+/// Most recent log lines, oldest first, for a diagnostics view in the UI
+#[tauri::command]
+#[instrument]
+pub async fn get_recent_logs(lines: usize) -> Result<Vec<String>, String> {
+    let directory = log_directory().ok_or("Failed to determine log directory")?;
+    recent_lines(&directory, lines.min(config::logs::MAX_RECENT_LINES))
+}
+
+/// Writes all kept logs to one file the user can attach to a bug report
+#[tauri::command]
+#[instrument]
+pub async fn export_logs(path: String) -> Result<(), String> {
+    let directory = log_directory().ok_or("Failed to determine log directory")?;
+    export_logs_to(&directory, Path::new(&path))?;
+
+    info!("Logs exported");
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+use tempfile::TempDir;
+
+fn write_log(directory: &Path, date: &str, lines: &[&str]) {
+    let name = format!(
+        "{}.{date}.{}",
+        config::logs::FILENAME_PREFIX,
+        config::logs::FILENAME_SUFFIX
+    );
+    fs::write(directory.join(name), format!("{}\n", lines.join("\n"))).unwrap();
+}
+
+#[test]
+fn test_log_files_sorted_oldest_first() {
+    let temp_dir = TempDir::new().unwrap();
+    write_log(temp_dir.path(), "2026-10-15", &["b"]);
+    write_log(temp_dir.path(), "2026-10-14", &["a"]);
+    fs::write(temp_dir.path().join("settings.json"), "{}").unwrap();
+
+    let files = log_files(temp_dir.path());
+
+    assert_eq!(files.len(), 2);
+    assert!(files[0].to_string_lossy().contains("2026-10-14"));
+    assert!(files[1].to_string_lossy().contains("2026-10-15"));
+}
+
+#[test]
+fn test_recent_lines_spans_rotated_files() {
+    let temp_dir = TempDir::new().unwrap();
+    write_log(temp_dir.path(), "2026-10-14", &["one", "two", "three"]);
+    write_log(temp_dir.path(), "2026-10-15", &["four", "five"]);
+
+    let lines = recent_lines(temp_dir.path(), 4).unwrap();
+
+    assert_eq!(lines, vec!["two", "three", "four", "five"]);
+}
+
+#[test]
+fn test_recent_lines_fewer_than_requested() {
+    let temp_dir = TempDir::new().unwrap();
+    write_log(temp_dir.path(), "2026-10-15", &["only"]);
+
+    assert_eq!(recent_lines(temp_dir.path(), 100).unwrap(), vec!["only"]);
+    assert!(recent_lines(&temp_dir.path().join("missing"), 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_export_logs_concatenates_in_order() {
+    let temp_dir = TempDir::new().unwrap();
+    write_log(temp_dir.path(), "2026-10-15", &["later"]);
+    write_log(temp_dir.path(), "2026-10-14", &["earlier"]);
+    let destination = temp_dir.path().join("export.txt");
+
+    export_logs_to(temp_dir.path(), &destination).unwrap();
+
+    assert_eq!(fs::read_to_string(destination).unwrap(), "earlier\nlater\n");
+}
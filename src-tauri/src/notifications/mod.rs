@@ -0,0 +1,256 @@
+use crate::commands::delete::BatchDeleteResult;
+use crate::commands::settings::NotificationContents;
+use crate::config::notifications::MAX_DETAIL_LINES;
+use crate::format::{ByteFormat, ByteStyle};
+use crate::i18n;
+use crate::scanner::{DependencySizes, DirectoryEntry};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Manager;
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+use tracing::{debug, instrument, warn};
+
+/// Emitted to the window when it is opened from a threshold notification
+pub const REVIEW_EVENT: &str = "notification-review-requested";
+
+/// Desktop notification clicks aren't delivered back to the app, so a sent notification
+/// is remembered until the next activation (clicking it or the tray icon) consumes it
+static PENDING_REVIEW: AtomicBool = AtomicBool::new(false);
+
+/// Formats bytes for notification copy in the user's unit base and locale, dropping
+/// trailing zeros ("5 GB", "5.5 GB").
+pub(crate) fn format_bytes_readable(bytes: u64) -> String {
+    ByteFormat::current().format(bytes, ByteStyle::Readable)
+}
+
+/// Title and body for the threshold notification, None when the total is within the threshold
+fn threshold_notification_text(total_size: u64, threshold: u64) -> Option<(String, String)> {
+    (total_size > threshold).then(|| {
+        (
+            format!("Dependencies exceed {}", format_bytes_readable(threshold)),
+            format!(
+                "{} in dependency folders — click to review",
+                format_bytes_readable(total_size)
+            ),
+        )
+    })
+}
+
+/// Project and folder name ("api/node_modules"), which is enough to recognize a
+/// folder in a notification where a full path would be truncated
+fn short_folder_name(path: &str) -> String {
+    let path = Path::new(path);
+    let folder = path.file_name().map(|name| name.to_string_lossy());
+    let project = path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy());
+
+    match (project, folder) {
+        (Some(project), Some(folder)) => format!("{project}/{folder}"),
+        (None, Some(folder)) => folder.into_owned(),
+        _ => path.to_string_lossy().into_owned(),
+    }
+}
+
+/// Lines listed below the total, largest first
+fn notification_details(
+    contents: NotificationContents,
+    sizes: &DependencySizes,
+    entries: &[DirectoryEntry],
+    language: &str,
+) -> Vec<String> {
+    match contents {
+        NotificationContents::Total => Vec::new(),
+        NotificationContents::TopOffenders => {
+            let mut largest: Vec<&DirectoryEntry> = entries.iter().collect();
+            largest.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+            largest
+                .into_iter()
+                .take(MAX_DETAIL_LINES)
+                .map(|entry| {
+                    format!(
+                        "{}: {}",
+                        short_folder_name(&entry.path),
+                        format_bytes_readable(entry.size_bytes)
+                    )
+                })
+                .collect()
+        }
+        NotificationContents::CategoryBreakdown => {
+            let mut categories: Vec<_> = sizes
+                .by_category
+                .iter()
+                .filter(|(_, size)| **size > 0)
+                .collect();
+            categories.sort_by(|a, b| {
+                b.1.cmp(a.1)
+                    .then_with(|| a.0.label_key().cmp(b.0.label_key()))
+            });
+            categories
+                .into_iter()
+                .take(MAX_DETAIL_LINES)
+                .map(|(category, size)| {
+                    format!(
+                        "{}: {}",
+                        category.translated_label(language),
+                        format_bytes_readable(*size)
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+/// Title and body for the threshold notification with the configured details appended
+fn threshold_notification(
+    sizes: &DependencySizes,
+    entries: &[DirectoryEntry],
+    threshold: u64,
+    contents: NotificationContents,
+    language: &str,
+) -> Option<(String, String)> {
+    let (title, body) = threshold_notification_text(sizes.total_size, threshold)?;
+    let details = notification_details(contents, sizes, entries, language);
+    if details.is_empty() {
+        return Some((title, body));
+    }
+
+    Some((title, format!("{body}\n{}", details.join("\n"))))
+}
+
+fn permission_granted(app: &tauri::AppHandle) -> bool {
+    match app.notification().permission_state() {
+        Ok(PermissionState::Granted) => true,
+        Ok(state) => {
+            debug!(
+                ?state,
+                "Notification permission not granted, skipping notification"
+            );
+            false
+        }
+        Err(error) => {
+            warn!(%error, "Failed to check notification permission");
+            false
+        }
+    }
+}
+
+/// Only notify when the threshold is newly crossed so a background scan every
+/// interval doesn't repeat the same alert
+pub fn should_notify(was_exceeded: bool, exceeds_threshold: bool, enabled: bool) -> bool {
+    enabled && exceeds_threshold && !was_exceeded
+}
+
+/// Sends the threshold notification unless the window is already showing the results
+#[instrument(skip(app, sizes, entries), fields(total_size = sizes.total_size))]
+pub fn notify_threshold_exceeded(
+    app: &tauri::AppHandle,
+    sizes: &DependencySizes,
+    entries: &[DirectoryEntry],
+    threshold: u64,
+    contents: NotificationContents,
+) {
+    let language = i18n::current_language();
+    let Some((title, body)) = threshold_notification(sizes, entries, threshold, contents, language)
+    else {
+        return;
+    };
+
+    let window_visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+    if window_visible {
+        debug!("Window is visible, skipping notification");
+        return;
+    }
+
+    if !permission_granted(app) {
+        return;
+    }
+
+    match app.notification().builder().title(title).body(body).show() {
+        Ok(()) => {
+            PENDING_REVIEW.store(true, Ordering::SeqCst);
+            debug!("Threshold notification sent");
+        }
+        Err(error) => warn!(%error, "Failed to send threshold notification"),
+    }
+}
+
+/// Title and body for the notification sent when the tray's Clean Stale finishes
+fn stale_cleanup_notification_text(batch: &BatchDeleteResult, after_days: u32) -> (String, String) {
+    if batch.successful_count == 0 && batch.failed_count == 0 {
+        return (
+            "Nothing stale to clean".to_string(),
+            format!("No dependency folders were untouched for {after_days} days"),
+        );
+    }
+
+    let folders = |count: usize| if count == 1 { "folder" } else { "folders" };
+    let mut body = format!(
+        "Deleted {} {} untouched for {after_days} days",
+        batch.successful_count,
+        folders(batch.successful_count)
+    );
+    if batch.failed_count > 0 {
+        body.push_str(&format!(
+            ", {} {} couldn't be deleted",
+            batch.failed_count,
+            folders(batch.failed_count)
+        ));
+    }
+
+    (
+        format!("Freed {}", format_bytes_readable(batch.total_size_freed)),
+        body,
+    )
+}
+
+/// Reports a finished Clean Stale. Sent even while the window is open, since the action
+/// is started from the tray and has no other completion feedback there.
+#[instrument(skip(app, batch), fields(successful = batch.successful_count))]
+pub fn notify_stale_cleaned(app: &tauri::AppHandle, batch: &BatchDeleteResult, after_days: u32) {
+    if !permission_granted(app) {
+        return;
+    }
+
+    let (title, body) = stale_cleanup_notification_text(batch, after_days);
+    if let Err(error) = app.notification().builder().title(title).body(body).show() {
+        warn!(%error, "Failed to send stale cleanup notification");
+    }
+}
+
+/// Tells the user a silent update was installed, since the relaunch itself is invisible
+#[instrument(skip(app))]
+pub fn notify_updated(app: &tauri::AppHandle, version: &str) {
+    if !permission_granted(app) {
+        return;
+    }
+
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title("deptox was updated")
+        .body(format!("Now running version {version}"))
+        .show()
+    {
+        warn!(%error, "Failed to send update notification");
+    }
+}
+
+/// Opens the window to the results view if a threshold notification is waiting to be reviewed
+pub fn open_pending_review(app: &tauri::AppHandle) -> bool {
+    if !PENDING_REVIEW.swap(false, Ordering::SeqCst) {
+        return false;
+    }
+
+    debug!("Opening results view from threshold notification");
+    crate::show_window_with_event(app, REVIEW_EVENT, ());
+    true
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,188 @@
+use super::*;
+use crate::config::bytes::{GB, MB};
+use crate::scanner::DependencyCategory;
+
+fn entry(path: &str, category: DependencyCategory, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry::for_test(path, category, size_bytes)
+}
+
+fn sample_entries() -> Vec<DirectoryEntry> {
+    vec![
+        entry(
+            "/code/web/node_modules",
+            DependencyCategory::NodeModules,
+            3 * GB as u64,
+        ),
+        entry(
+            "/code/api/.venv",
+            DependencyCategory::PythonVenv,
+            2 * GB as u64,
+        ),
+        entry(
+            "/code/docs/node_modules",
+            DependencyCategory::NodeModules,
+            512 * MB as u64,
+        ),
+        entry("/code/app/Pods", DependencyCategory::Pods, GB as u64),
+        entry(
+            "/code/site/vendor",
+            DependencyCategory::Composer,
+            128 * MB as u64,
+        ),
+    ]
+}
+
+#[test]
+fn test_format_bytes_readable_whole_and_fractional() {
+    assert_eq!(format_bytes_readable(5 * GB as u64), "5 GB");
+    assert_eq!(
+        format_bytes_readable((5.5 * GB) as u64),
+        ByteFormat::current().format((5.5 * GB) as u64, ByteStyle::Readable)
+    );
+    assert_eq!(format_bytes_readable(512 * MB as u64), "512 MB");
+    assert_eq!(format_bytes_readable(100), "100 B");
+}
+
+#[test]
+fn test_threshold_notification_text_when_exceeded() {
+    let (title, body) = threshold_notification_text(7 * GB as u64, 5 * GB as u64).unwrap();
+
+    assert_eq!(title, "Dependencies exceed 5 GB");
+    assert_eq!(body, "7 GB in dependency folders — click to review");
+}
+
+#[test]
+fn test_threshold_notification_text_within_threshold() {
+    assert!(threshold_notification_text(5 * GB as u64, 5 * GB as u64).is_none());
+    assert!(threshold_notification_text(MB as u64, 5 * GB as u64).is_none());
+}
+
+#[test]
+fn test_should_notify_only_on_crossing() {
+    assert!(should_notify(false, true, true));
+    assert!(!should_notify(true, true, true));
+    assert!(!should_notify(false, false, true));
+    assert!(!should_notify(false, true, false));
+}
+
+#[test]
+fn test_short_folder_name_keeps_project_and_folder() {
+    assert_eq!(
+        short_folder_name("/code/web/node_modules"),
+        "web/node_modules"
+    );
+    assert_eq!(short_folder_name("node_modules"), "node_modules");
+}
+
+#[test]
+fn test_threshold_notification_total_only() {
+    let entries = sample_entries();
+    let sizes = DependencySizes::from_entries(&entries);
+
+    let (_, body) = threshold_notification(
+        &sizes,
+        &entries,
+        5 * GB as u64,
+        NotificationContents::Total,
+        "en",
+    )
+    .unwrap();
+
+    assert_eq!(body, "6.6 GB in dependency folders — click to review");
+}
+
+#[test]
+fn test_threshold_notification_top_offenders() {
+    let entries = sample_entries();
+    let sizes = DependencySizes::from_entries(&entries);
+
+    let (_, body) = threshold_notification(
+        &sizes,
+        &entries,
+        5 * GB as u64,
+        NotificationContents::TopOffenders,
+        "en",
+    )
+    .unwrap();
+
+    assert_eq!(
+        body,
+        "6.6 GB in dependency folders — click to review\n\
+         web/node_modules: 3 GB\n\
+         api/.venv: 2 GB\n\
+         app/Pods: 1 GB"
+    );
+}
+
+#[test]
+fn test_threshold_notification_category_breakdown() {
+    let entries = sample_entries();
+    let sizes = DependencySizes::from_entries(&entries);
+
+    let (_, body) = threshold_notification(
+        &sizes,
+        &entries,
+        5 * GB as u64,
+        NotificationContents::CategoryBreakdown,
+        "en",
+    )
+    .unwrap();
+
+    assert_eq!(
+        body,
+        "6.6 GB in dependency folders — click to review\n\
+         Node.js (node_modules): 3.5 GB\n\
+         Python (venv): 2 GB\n\
+         iOS (Pods): 1 GB"
+    );
+}
+
+#[test]
+fn test_threshold_notification_within_threshold_ignores_contents() {
+    let entries = sample_entries();
+    let sizes = DependencySizes::from_entries(&entries);
+
+    assert!(threshold_notification(
+        &sizes,
+        &entries,
+        10 * GB as u64,
+        NotificationContents::TopOffenders,
+        "en"
+    )
+    .is_none());
+}
+
+fn batch(successful_count: usize, failed_count: usize, total_size_freed: u64) -> BatchDeleteResult {
+    BatchDeleteResult {
+        results: Vec::new(),
+        total_size_freed,
+        successful_count,
+        failed_count,
+    }
+}
+
+#[test]
+fn test_stale_cleanup_notification_text() {
+    let (title, body) = stale_cleanup_notification_text(&batch(3, 0, 5 * GB as u64), 90);
+
+    assert_eq!(title, "Freed 5 GB");
+    assert_eq!(body, "Deleted 3 folders untouched for 90 days");
+}
+
+#[test]
+fn test_stale_cleanup_notification_text_mentions_failures() {
+    let (_, body) = stale_cleanup_notification_text(&batch(1, 2, GB as u64), 30);
+
+    assert_eq!(
+        body,
+        "Deleted 1 folder untouched for 30 days, 2 folders couldn't be deleted"
+    );
+}
+
+#[test]
+fn test_stale_cleanup_notification_text_nothing_stale() {
+    let (title, body) = stale_cleanup_notification_text(&batch(0, 0, 0), 90);
+
+    assert_eq!(title, "Nothing stale to clean");
+    assert_eq!(body, "No dependency folders were untouched for 90 days");
+}
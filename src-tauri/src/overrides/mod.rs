@@ -0,0 +1,50 @@
+use crate::config;
+use crate::persistence::config_path;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Hand-edited developer and support options, read once at startup from
+/// `overrides.json` in the config directory. Never written by the app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Overrides {
+    /// OTLP/HTTP traces endpoint, e.g. `http://localhost:4318/v1/traces`
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Size directories with jwalk even where macOS bulk attribute enumeration is available
+    #[serde(default)]
+    pub disable_bulk_attributes: bool,
+}
+
+fn get_overrides_path() -> Option<PathBuf> {
+    config_path(config::app::OVERRIDES_FILENAME).ok()
+}
+
+fn load_overrides_from(path: &Path) -> Result<Overrides, String> {
+    if !path.exists() {
+        return Ok(Overrides::default());
+    }
+
+    let content =
+        fs::read_to_string(path).map_err(|error| format!("Failed to read overrides: {error}"))?;
+
+    serde_json::from_str(&content).map_err(|error| format!("Failed to parse overrides: {error}"))
+}
+
+/// Loads the overrides file. Runs before tracing is initialized, so errors are
+/// returned alongside the defaults for the caller to log once logging is up.
+pub fn load_overrides() -> (Overrides, Option<String>) {
+    let Some(path) = get_overrides_path() else {
+        return (Overrides::default(), None);
+    };
+
+    match load_overrides_from(&path) {
+        Ok(overrides) => (overrides, None),
+        Err(error) => (Overrides::default(), Some(error)),
+    }
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_load_overrides_from_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let overrides = load_overrides_from(&temp_dir.path().join("overrides.json")).unwrap();
+    assert_eq!(overrides, Overrides::default());
+}
+
+#[test]
+fn test_load_overrides_from_reads_otlp_endpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("overrides.json");
+    fs::write(
+        &path,
+        r#"{"otlpEndpoint":"http://localhost:4318/v1/traces"}"#,
+    )
+    .unwrap();
+
+    let overrides = load_overrides_from(&path).unwrap();
+    assert_eq!(
+        overrides.otlp_endpoint.as_deref(),
+        Some("http://localhost:4318/v1/traces")
+    );
+}
+
+#[test]
+fn test_load_overrides_from_ignores_unknown_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("overrides.json");
+    fs::write(&path, r#"{"somethingElse":true}"#).unwrap();
+
+    assert_eq!(load_overrides_from(&path).unwrap(), Overrides::default());
+}
+
+#[test]
+fn test_load_overrides_from_invalid_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("overrides.json");
+    fs::write(&path, "{not json").unwrap();
+
+    let error = load_overrides_from(&path).unwrap_err();
+    assert!(error.contains("parse"));
+}
+
+#[test]
+fn test_load_overrides_from_reads_disable_bulk_attributes() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("overrides.json");
+    fs::write(&path, r#"{"disableBulkAttributes":true}"#).unwrap();
+
+    let overrides = load_overrides_from(&path).unwrap();
+    assert!(overrides.disable_bulk_attributes);
+    assert!(!Overrides::default().disable_bulk_attributes);
+}
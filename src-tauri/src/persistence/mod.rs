@@ -0,0 +1,169 @@
+use crate::config;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// deptox's folder in the platform config directory, created if it doesn't exist yet
+pub fn config_dir() -> Result<PathBuf, String> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| "Failed to determine config directory".to_string())?
+        .join(config::app::APP_CONFIG_DIR);
+
+    fs::create_dir_all(&config_dir)
+        .map_err(|error| format!("Failed to create config directory: {error}"))?;
+
+    Ok(config_dir)
+}
+
+/// Path of a file kept in `config_dir`
+pub fn config_path(filename: &str) -> Result<PathBuf, String> {
+    Ok(config_dir()?.join(filename))
+}
+
+struct BufferedState<T> {
+    path: PathBuf,
+    value: T,
+    dirty: bool,
+}
+
+/// In-memory copy of a JSON file whose updates are written back on `flush`
+/// Lets batch operations record many changes with a single write, while
+/// `flush_all` on exit guarantees nothing recorded is lost
+pub struct BufferedJsonFile<T> {
+    resolve_path: fn() -> Result<PathBuf, String>,
+    load: fn(&Path) -> T,
+    save: fn(&Path, &T) -> Result<(), String>,
+    state: Mutex<Option<BufferedState<T>>>,
+}
+
+impl<T> BufferedJsonFile<T> {
+    pub const fn new(
+        resolve_path: fn() -> Result<PathBuf, String>,
+        load: fn(&Path) -> T,
+        save: fn(&Path, &T) -> Result<(), String>,
+    ) -> Self {
+        Self {
+            resolve_path,
+            load,
+            save,
+            state: Mutex::new(None),
+        }
+    }
+
+    fn with_state<R>(
+        &self,
+        operation: impl FnOnce(&mut BufferedState<T>) -> R,
+    ) -> Result<R, String> {
+        let mut guard = self.state.lock().unwrap();
+
+        if guard.is_none() {
+            let path = (self.resolve_path)()?;
+            let value = (self.load)(&path);
+            *guard = Some(BufferedState {
+                path,
+                value,
+                dirty: false,
+            });
+        }
+
+        Ok(operation(guard.as_mut().expect("state loaded above")))
+    }
+
+    /// Reads the current value, including changes that haven't been flushed yet
+    pub fn read<R>(&self, reader: impl FnOnce(&T) -> R) -> Result<R, String> {
+        self.with_state(|state| reader(&state.value))
+    }
+
+    /// Applies a change in memory and marks the file as needing a write
+    pub fn update<R>(&self, updater: impl FnOnce(&mut T) -> R) -> Result<R, String> {
+        self.with_state(|state| {
+            state.dirty = true;
+            updater(&mut state.value)
+        })
+    }
+
+    /// Writes pending changes to disk, returning whether anything was written
+    pub fn flush(&self) -> Result<bool, String> {
+        let mut guard = self.state.lock().unwrap();
+
+        match guard.as_mut() {
+            Some(state) if state.dirty => {
+                (self.save)(&state.path, &state.value)?;
+                state.dirty = false;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+type FlushStore = fn() -> Result<bool, String>;
+
+/// Buffered stores `flush_all` writes back, registered at launch by the app so this
+/// module doesn't depend on the modules that own them
+static STORES: Mutex<Vec<(&'static str, FlushStore)>> = Mutex::new(Vec::new());
+
+/// Adds a store for `flush_all` to write back. Registering the same name again is a no-op.
+pub fn register_store(name: &'static str, flush: FlushStore) {
+    let mut stores = STORES.lock().unwrap_or_else(|error| error.into_inner());
+    if !stores.iter().any(|(registered, _)| *registered == name) {
+        stores.push((name, flush));
+    }
+}
+
+/// Flushes every registered store, logging failures so one bad file doesn't block the rest
+pub fn flush_all() {
+    let stores = STORES
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .clone();
+
+    for (name, flush) in stores {
+        match flush() {
+            Ok(true) => debug!(store = name, "Flushed pending writes"),
+            Ok(false) => {}
+            Err(error) => warn!(store = name, %error, "Failed to flush pending writes"),
+        }
+    }
+}
+
+/// Runs `flush_all` on a helper thread, giving up after `timeout` so a stuck
+/// filesystem can't hang application exit
+pub fn flush_all_with_timeout(timeout: Duration) -> bool {
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+
+    let spawned = thread::Builder::new()
+        .name("exit-flush".to_string())
+        .spawn(move || {
+            flush_all();
+            let _ = sender.send(());
+        });
+
+    if let Err(error) = spawned {
+        warn!(%error, "Failed to spawn flush thread, flushing inline");
+        flush_all();
+        return true;
+    }
+
+    let completed = receiver.recv_timeout(timeout).is_ok();
+    if completed {
+        info!(
+            duration_ms = start.elapsed().as_millis() as u64,
+            "Pending writes flushed"
+        );
+    } else {
+        warn!(
+            timeout_ms = timeout.as_millis() as u64,
+            "Timed out flushing pending writes"
+        );
+    }
+    completed
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,109 @@
+use super::*;
+use std::cell::RefCell;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+
+thread_local! {
+    static TEST_FILE_PATH: RefCell<PathBuf> = RefCell::new(PathBuf::new());
+}
+
+fn test_path() -> Result<PathBuf, String> {
+    Ok(TEST_FILE_PATH.with(|path| path.borrow().clone()))
+}
+
+fn load_numbers(path: &Path) -> Vec<u32> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_numbers(path: &Path, numbers: &[u32]) -> Result<(), String> {
+    fs::write(path, serde_json::to_string(numbers).unwrap()).map_err(|error| error.to_string())
+}
+
+fn buffered_file_in(temp_dir: &TempDir) -> BufferedJsonFile<Vec<u32>> {
+    let path = temp_dir.path().join("numbers.json");
+    TEST_FILE_PATH.with(|test_path| *test_path.borrow_mut() = path);
+    BufferedJsonFile::new(test_path, load_numbers, |path, numbers| {
+        save_numbers(path, numbers)
+    })
+}
+
+#[test]
+fn test_buffered_json_file_update_is_not_written_until_flush() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = buffered_file_in(&temp_dir);
+    let path = temp_dir.path().join("numbers.json");
+
+    file.update(|numbers| numbers.push(1)).unwrap();
+    file.update(|numbers| numbers.push(2)).unwrap();
+
+    assert!(!path.exists());
+    assert_eq!(file.read(|numbers| numbers.clone()).unwrap(), vec![1, 2]);
+
+    assert!(file.flush().unwrap());
+    assert_eq!(load_numbers(&path), vec![1, 2]);
+}
+
+#[test]
+fn test_buffered_json_file_flush_skips_clean_state() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = buffered_file_in(&temp_dir);
+
+    assert!(!file.flush().unwrap());
+
+    file.read(|numbers| numbers.len()).unwrap();
+    assert!(!file.flush().unwrap());
+
+    file.update(|numbers| numbers.push(7)).unwrap();
+    assert!(file.flush().unwrap());
+    assert!(!file.flush().unwrap());
+}
+
+#[test]
+fn test_buffered_json_file_loads_existing_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = buffered_file_in(&temp_dir);
+    save_numbers(&temp_dir.path().join("numbers.json"), &[4, 5]).unwrap();
+
+    file.update(|numbers| numbers.push(6)).unwrap();
+    file.flush().unwrap();
+
+    assert_eq!(
+        load_numbers(&temp_dir.path().join("numbers.json")),
+        vec![4, 5, 6]
+    );
+}
+
+static REGISTERED_FLUSHES: AtomicUsize = AtomicUsize::new(0);
+
+fn count_flush() -> Result<bool, String> {
+    REGISTERED_FLUSHES.fetch_add(1, Ordering::SeqCst);
+    Ok(false)
+}
+
+#[test]
+fn test_flush_all_flushes_registered_stores_once() {
+    register_store("test_counter", count_flush);
+    register_store("test_counter", count_flush);
+
+    let registered = STORES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(name, _)| *name == "test_counter")
+        .count();
+    assert_eq!(registered, 1);
+
+    let before = REGISTERED_FLUSHES.load(Ordering::SeqCst);
+    flush_all();
+
+    assert!(REGISTERED_FLUSHES.load(Ordering::SeqCst) > before);
+}
+
+#[test]
+fn test_flush_all_with_timeout_completes() {
+    assert!(flush_all_with_timeout(Duration::from_secs(5)));
+}
@@ -0,0 +1,138 @@
+use crate::config;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+/// A process found holding a directory open
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenProcess {
+    pub pid: u32,
+    pub command: String,
+}
+
+/// One file `lsof` reported, paired with the process that has it open
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OpenFile {
+    process: OpenProcess,
+    name: String,
+}
+
+/// Parses `lsof -F pcn` output, where each line starts with a field tag:
+/// `p` begins a process, `c` names its command and `n` is one open file
+fn parse_lsof_output(output: &str) -> Vec<OpenFile> {
+    let mut open_files = Vec::new();
+    let mut pid: Option<u32> = None;
+    let mut command = String::new();
+
+    for line in output.lines() {
+        let Some(tag) = line.chars().next() else {
+            continue;
+        };
+        let value = &line[tag.len_utf8()..];
+
+        match tag {
+            'p' => {
+                pid = value.parse().ok();
+                command.clear();
+            }
+            'c' => command = value.to_string(),
+            'n' => {
+                if let Some(pid) = pid {
+                    open_files.push(OpenFile {
+                        process: OpenProcess {
+                            pid,
+                            command: command.clone(),
+                        },
+                        name: value.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    open_files
+}
+
+/// Distinct processes with an open file under the directory, excluding deptox itself
+fn processes_within(open_files: Vec<OpenFile>, directory: &Path) -> Vec<OpenProcess> {
+    let own_pid = std::process::id();
+    let processes: BTreeMap<u32, OpenProcess> = open_files
+        .into_iter()
+        .filter(|open_file| open_file.process.pid != own_pid)
+        .filter(|open_file| Path::new(&open_file.name).starts_with(directory))
+        .map(|open_file| (open_file.process.pid, open_file.process))
+        .collect();
+
+    processes.into_values().collect()
+}
+
+/// How a run of `lsof` ended
+#[derive(Debug)]
+enum LsofRun {
+    Finished(String),
+    /// `lsof` isn't installed or couldn't be started
+    Unavailable,
+    /// `lsof` was still running at `LSOF_TIMEOUT`, so nothing is known either way
+    TimedOut,
+}
+
+/// Runs `lsof` with field output. `lsof` exits non-zero when nothing matched,
+/// so only missing output counts as failure.
+async fn run_lsof(args: &[&str]) -> LsofRun {
+    let output = Command::new("lsof")
+        .args(args)
+        .args(["-F", "pcn"])
+        .kill_on_drop(true)
+        .output();
+
+    match tokio::time::timeout(config::processes::LSOF_TIMEOUT, output).await {
+        Ok(Ok(output)) => LsofRun::Finished(String::from_utf8_lossy(&output.stdout).into_owned()),
+        Ok(Err(error)) => {
+            debug!(%error, "lsof unavailable");
+            LsofRun::Unavailable
+        }
+        Err(_) => {
+            warn!("lsof timed out");
+            LsofRun::TimedOut
+        }
+    }
+}
+
+/// Processes whose working directory is inside the project, such as a shell,
+/// editor or dev server started there. Empty when `lsof` is unavailable.
+pub async fn find_processes_in_project(project_dir: &Path) -> Vec<OpenProcess> {
+    let LsofRun::Finished(output) = run_lsof(&["-a", "-d", "cwd"]).await else {
+        return Vec::new();
+    };
+
+    processes_within(parse_lsof_output(&output), project_dir)
+}
+
+/// Processes holding a file open anywhere under the directory, empty when `lsof` is
+/// unavailable. The recursive `lsof +D` walk is bounded by `LSOF_TIMEOUT`, and None
+/// when it can't finish, since a large tree is no sign that nothing is open.
+pub async fn find_processes_holding_files(directory: &Path) -> Option<Vec<OpenProcess>> {
+    let directory_arg = directory.to_string_lossy();
+    match run_lsof(&["+D", &directory_arg]).await {
+        LsofRun::Finished(output) => Some(processes_within(parse_lsof_output(&output), directory)),
+        LsofRun::Unavailable => Some(Vec::new()),
+        LsofRun::TimedOut => None,
+    }
+}
+
+/// Lists processes for messages, e.g. "node (4242), zsh (101)"
+pub fn describe_processes(processes: &[OpenProcess]) -> String {
+    processes
+        .iter()
+        .map(|process| format!("{} ({})", process.command, process.pid))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,78 @@
+use super::*;
+
+const LSOF_OUTPUT: &str = "p101\nczsh\nfcwd\nn/Users/dev/code/app\np202\ncnode\nfcwd\nn/Users/dev/code/app/server\np303\ncCode Helper\nfcwd\nn/Users/dev/code/other\n";
+
+#[test]
+fn test_parse_lsof_output() {
+    let open_files = parse_lsof_output(LSOF_OUTPUT);
+
+    assert_eq!(open_files.len(), 3);
+    assert_eq!(
+        open_files[0],
+        OpenFile {
+            process: OpenProcess {
+                pid: 101,
+                command: "zsh".to_string(),
+            },
+            name: "/Users/dev/code/app".to_string(),
+        }
+    );
+    assert_eq!(open_files[2].process.command, "Code Helper");
+}
+
+#[test]
+fn test_parse_lsof_output_skips_files_without_process() {
+    let open_files = parse_lsof_output("n/orphan\n\npnot-a-pid\ncbad\nn/also-orphan\n");
+    assert!(open_files.is_empty());
+}
+
+#[test]
+fn test_processes_within_filters_by_directory() {
+    let processes = processes_within(
+        parse_lsof_output(LSOF_OUTPUT),
+        Path::new("/Users/dev/code/app"),
+    );
+
+    let pids: Vec<u32> = processes.iter().map(|process| process.pid).collect();
+    assert_eq!(pids, vec![101, 202]);
+}
+
+#[test]
+fn test_processes_within_does_not_match_sibling_prefix() {
+    let processes = processes_within(
+        parse_lsof_output("p1\ncvim\nn/Users/dev/code/app-old\n"),
+        Path::new("/Users/dev/code/app"),
+    );
+    assert!(processes.is_empty());
+}
+
+#[test]
+fn test_processes_within_deduplicates_and_skips_own_process() {
+    let own_pid = std::process::id();
+    let output = format!("p7\ncnode\nn/project/a\nn/project/b\np{own_pid}\ncdeptox\nn/project/c\n");
+
+    let processes = processes_within(parse_lsof_output(&output), Path::new("/project"));
+    assert_eq!(
+        processes,
+        vec![OpenProcess {
+            pid: 7,
+            command: "node".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_describe_processes() {
+    let processes = vec![
+        OpenProcess {
+            pid: 4242,
+            command: "node".to_string(),
+        },
+        OpenProcess {
+            pid: 101,
+            command: "zsh".to_string(),
+        },
+    ];
+    assert_eq!(describe_processes(&processes), "node (4242), zsh (101)");
+    assert_eq!(describe_processes(&[]), "");
+}
@@ -0,0 +1,66 @@
+use super::core::calculate_dir_size_full;
+use super::project_info::resolve_project_info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What "last used" means for a dependency directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AgeStrategy {
+    /// Modification time of the dependency directory itself, roughly when it was installed
+    DirectoryMtime,
+    /// Most recent modification of any file inside, which is what a scan records
+    #[default]
+    NewestFile,
+    /// Most recent modification of the owning project's top-level entries
+    ProjectMtime,
+    /// Most recent commit in the owning project's git repository
+    GitCommit,
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = path
+        .symlink_metadata()
+        .and_then(|metadata| metadata.modified());
+    to_epoch_ms(modified.ok()?)
+}
+
+fn to_epoch_ms(time: SystemTime) -> Option<u64> {
+    let millis = time.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64;
+    (millis > 0).then_some(millis)
+}
+
+/// Newest modification among the project's top-level entries, skipping the
+/// dependency directory so installing packages doesn't count as project activity
+fn project_modified_ms(dependency_path: &Path) -> Option<u64> {
+    let project_dir = dependency_path.parent()?;
+    let dependency_name = dependency_path.file_name()?;
+
+    fs::read_dir(project_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name() != dependency_name)
+        .filter_map(|entry| modified_ms(&entry.path()))
+        .max()
+}
+
+/// Computes a dependency directory's age under the given strategy, in milliseconds
+/// since the Unix epoch. None when the strategy has nothing to go on, e.g. no git repository.
+pub fn compute_age_ms(path: &Path, strategy: AgeStrategy) -> Option<u64> {
+    match strategy {
+        AgeStrategy::DirectoryMtime => modified_ms(path),
+        AgeStrategy::NewestFile => {
+            let newest = calculate_dir_size_full(path).last_modified_ms;
+            (newest > 0).then_some(newest)
+        }
+        AgeStrategy::ProjectMtime => project_modified_ms(path),
+        AgeStrategy::GitCommit => resolve_project_info(path).last_commit_ms,
+    }
+}
+
+#[cfg(test)]
+#[path = "age.test.rs"]
+mod tests;
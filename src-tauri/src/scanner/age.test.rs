@@ -0,0 +1,98 @@
+use super::*;
+use std::fs::File;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn write_file_modified_at(path: &Path, modified: SystemTime) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "content").unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+}
+
+#[test]
+fn test_age_strategy_default_is_newest_file() {
+    assert_eq!(AgeStrategy::default(), AgeStrategy::NewestFile);
+}
+
+#[test]
+fn test_age_strategy_serialization() {
+    assert_eq!(
+        serde_json::to_string(&AgeStrategy::DirectoryMtime).unwrap(),
+        "\"DIRECTORY_MTIME\""
+    );
+    assert_eq!(
+        serde_json::from_str::<AgeStrategy>("\"GIT_COMMIT\"").unwrap(),
+        AgeStrategy::GitCommit
+    );
+}
+
+#[test]
+fn test_compute_age_newest_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    let newest = SystemTime::now() - DAY;
+    write_file_modified_at(&node_modules.join("a").join("index.js"), newest - DAY);
+    write_file_modified_at(&node_modules.join("b").join("index.js"), newest);
+
+    assert_eq!(
+        compute_age_ms(&node_modules, AgeStrategy::NewestFile),
+        Some(epoch_ms(newest))
+    );
+}
+
+#[test]
+fn test_compute_age_project_mtime_ignores_dependency_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    let node_modules = project.join("node_modules");
+    let project_edit = SystemTime::now() - 10 * DAY;
+    write_file_modified_at(&project.join("package.json"), project_edit - DAY);
+    write_file_modified_at(&project.join("index.js"), project_edit);
+    write_file_modified_at(
+        &node_modules.join("left-pad").join("index.js"),
+        SystemTime::now(),
+    );
+
+    assert_eq!(
+        compute_age_ms(&node_modules, AgeStrategy::ProjectMtime),
+        Some(epoch_ms(project_edit))
+    );
+}
+
+#[test]
+fn test_compute_age_directory_mtime() {
+    let temp_dir = TempDir::new().unwrap();
+    let vendor = temp_dir.path().join("vendor");
+    fs::create_dir(&vendor).unwrap();
+
+    assert!(compute_age_ms(&vendor, AgeStrategy::DirectoryMtime).is_some());
+}
+
+#[test]
+fn test_compute_age_git_commit_without_repository() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("project").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+
+    assert_eq!(compute_age_ms(&node_modules, AgeStrategy::GitCommit), None);
+}
+
+#[test]
+fn test_compute_age_empty_directory_has_no_newest_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let deps = temp_dir.path().join("deps");
+    fs::create_dir(&deps).unwrap();
+
+    assert_eq!(compute_age_ms(&deps, AgeStrategy::NewestFile), None);
+}
@@ -1,87 +1,55 @@
-use super::core::{
-    calculate_dir_size, expand_tilde, is_inside_dependency_directory, should_skip_directory,
-};
-use super::types::{get_all_dependency_directory_names, get_target_directory_names};
-use crate::commands::settings::get_settings_sync;
-use crate::config;
-use std::sync::atomic::{AtomicU64, Ordering};
+use super::engine::{run_scan, ScanConfig, SilentSink};
+use super::types::{DependencyCategory, DirectoryEntry, ScanResult};
+use crate::activity::BusyGuard;
+use std::collections::HashMap;
 use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, instrument};
 
-#[instrument(skip_all)]
-pub fn calculate_total_dependency_size() -> u64 {
-    let start = Instant::now();
-    info!("Starting background size calculation");
-
-    let settings = get_settings_sync().unwrap_or_default();
-    let root_directory = expand_tilde(&settings.root_directory);
-    let target_dir_names = get_target_directory_names(&settings.enabled_categories);
-    let all_dependency_dirs = get_all_dependency_directory_names();
-
-    debug!(
-        %root_directory,
-        categories = ?settings.enabled_categories,
-        "Scanning root directory"
-    );
+/// Dependency directory totals from a background scan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencySizes {
+    pub total_size: u64,
+    pub by_category: HashMap<DependencyCategory, u64>,
+}
 
-    let total_size = AtomicU64::new(0);
-    let mut directories_found: usize = 0;
+impl DependencySizes {
+    pub fn from_entries(entries: &[DirectoryEntry]) -> Self {
+        let mut sizes = Self::default();
 
-    for directory_entry in jwalk::WalkDir::new(&root_directory)
-        .max_depth(config::scanner::MAX_SCAN_DEPTH)
-        .skip_hidden(false)
-        .follow_links(false)
-        .parallelism(jwalk::Parallelism::RayonDefaultPool {
-            busy_timeout: config::scanner::JWALK_BUSY_TIMEOUT,
-        })
-        .process_read_dir(|_, _, _, children| {
-            children.retain(|entry_result| {
-                if let Ok(ref entry) = entry_result {
-                    let name = entry.file_name();
-                    if let Some(name_string) = name.to_str() {
-                        !should_skip_directory(name_string)
-                    } else {
-                        true
-                    }
-                } else {
-                    true
-                }
-            });
-        })
-        .into_iter()
-        .flatten()
-    {
-        if !directory_entry.file_type().is_dir() {
-            continue;
+        for entry in entries {
+            sizes.total_size += entry.size_bytes;
+            *sizes.by_category.entry(entry.category).or_insert(0) += entry.size_bytes;
         }
 
-        let directory_name = directory_entry.file_name().to_str().unwrap_or("");
-
-        if !target_dir_names.contains(directory_name) {
-            continue;
-        }
+        sizes
+    }
+}
 
-        let path = directory_entry.path();
-        let path_string = path.to_string_lossy();
+/// Runs the same scan as the UI without emitting progress events, for the caller to
+/// keep the result in the scan cache
+#[instrument(skip_all)]
+pub fn run_background_scan(config: &ScanConfig) -> Option<ScanResult> {
+    let _busy = BusyGuard::hold();
+    let start = Instant::now();
+    info!("Starting background scan");
 
-        if is_inside_dependency_directory(&path_string, directory_name, &all_dependency_dirs) {
-            continue;
-        }
+    debug!(
+        root_directory = %config.root_directory,
+        categories = ?config.enabled_categories,
+        "Scanning root directory"
+    );
 
-        let size = calculate_dir_size(&path);
-        total_size.fetch_add(size, Ordering::Relaxed);
-        directories_found += 1;
-    }
+    let result = run_scan(config, &SilentSink, &CancellationToken::new())?;
 
-    let result = total_size.load(Ordering::Relaxed);
     info!(
-        directories = directories_found,
-        total_size_gb = result as f64 / 1024.0 / 1024.0 / 1024.0,
+        directories = result.entries.len(),
+        total_size_gb = result.total_size as f64 / 1024.0 / 1024.0 / 1024.0,
         duration_ms = start.elapsed().as_millis() as u64,
         "Background scan complete"
     );
 
-    result
+    Some(result)
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use super::*;
 use crate::config;
+use crate::scanner::is_inside_dependency_directory;
 use crate::scanner::types::get_all_dependency_directory_names;
 
 // ============================================
@@ -246,14 +247,33 @@ fn test_is_inside_dependency_directory_windows_separator() {
 }
 
 // ============================================
-// calculate_total_dependency_size Tests
+// DependencySizes Tests
 // ============================================
 
+fn entry(category: DependencyCategory, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry::for_test("/projects/app", category, size_bytes)
+}
+
 #[test]
-fn test_calculate_total_dependency_size_returns_u64() {
-    // This test verifies the function signature and return type
-    // The actual scan depends on the system's file system and settings
-    let result = calculate_total_dependency_size();
-    // Result should be a valid u64 (function completes without panicking)
-    let _: u64 = result;
+fn test_dependency_sizes_from_entries_groups_by_category() {
+    let sizes = DependencySizes::from_entries(&[
+        entry(DependencyCategory::NodeModules, 1000),
+        entry(DependencyCategory::NodeModules, 500),
+        entry(DependencyCategory::Pods, 250),
+    ]);
+
+    assert_eq!(sizes.total_size, 1750);
+    assert_eq!(sizes.by_category[&DependencyCategory::NodeModules], 1500);
+    assert_eq!(sizes.by_category[&DependencyCategory::Pods], 250);
+    assert!(!sizes
+        .by_category
+        .contains_key(&DependencyCategory::Composer));
+}
+
+#[test]
+fn test_dependency_sizes_from_entries_empty() {
+    assert_eq!(
+        DependencySizes::from_entries(&[]),
+        DependencySizes::default()
+    );
 }
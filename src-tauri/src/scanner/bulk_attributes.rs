@@ -0,0 +1,223 @@
+//! macOS fast path for directory sizing. `getattrlistbulk` returns the type, size and
+//! modification time of a whole batch of entries per syscall, instead of one `stat` per file.
+
+use super::core::{directory_identity, file_stat, FileStat, SizeTotals};
+use crate::config;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io;
+use std::mem::size_of;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+
+/// Not exported by libc
+const ATTR_CMN_ERROR: libc::attrgroup_t = 0x2000_0000;
+
+// Object types from <sys/vnode.h>
+const VREG: u32 = 1;
+const VDIR: u32 = 2;
+const VLNK: u32 = 5;
+
+/// The volume can't enumerate attributes in bulk, so the whole walk should use jwalk
+#[derive(Debug)]
+pub struct Unsupported;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BulkEntry {
+    File(FileStat),
+    Directory(PathBuf),
+    Symlink(PathBuf),
+    Other,
+}
+
+fn read_field<T: Copy>(buffer: &[u8], offset: usize) -> Option<T> {
+    let bytes = buffer.get(offset..offset.checked_add(size_of::<T>())?)?;
+    // Bounds are checked above. Fields are only 4-byte aligned, so 8-byte values need an
+    // unaligned read
+    Some(unsafe { std::ptr::read_unaligned(bytes.as_ptr().cast::<T>()) })
+}
+
+/// Decodes one packed entry. Fields appear in the order the attributes were requested,
+/// except `ATTR_CMN_ERROR` which immediately follows the returned attribute set.
+fn parse_entry(entry: &[u8], directory: &Path) -> Option<BulkEntry> {
+    let returned: libc::attribute_set_t = read_field(entry, size_of::<u32>())?;
+    let mut offset = size_of::<u32>() + size_of::<libc::attribute_set_t>();
+
+    if returned.commonattr & ATTR_CMN_ERROR != 0 {
+        let error: u32 = read_field(entry, offset)?;
+        if error != 0 {
+            return Some(BulkEntry::Other);
+        }
+        offset += size_of::<u32>();
+    }
+
+    let mut name = None;
+    if returned.commonattr & libc::ATTR_CMN_NAME != 0 {
+        let reference: libc::attrreference_t = read_field(entry, offset)?;
+        let start = offset.checked_add_signed(reference.attr_dataoffset as isize)?;
+        // The length includes the trailing NUL
+        let length = (reference.attr_length as usize).saturating_sub(1);
+        name = Some(entry.get(start..start + length)?);
+        offset += size_of::<libc::attrreference_t>();
+    }
+
+    let mut object_type = 0;
+    if returned.commonattr & libc::ATTR_CMN_OBJTYPE != 0 {
+        object_type = read_field::<u32>(entry, offset)?;
+        offset += size_of::<u32>();
+    }
+
+    let mut modified_ms = 0;
+    if returned.commonattr & libc::ATTR_CMN_MODTIME != 0 {
+        let modified: libc::timespec = read_field(entry, offset)?;
+        modified_ms = (modified.tv_sec.max(0) as u64) * 1000 + modified.tv_nsec as u64 / 1_000_000;
+        offset += size_of::<libc::timespec>();
+    }
+
+    let child_path = || name.map(|name| directory.join(OsStr::from_bytes(name)));
+    match object_type {
+        VREG if returned.fileattr & libc::ATTR_FILE_DATALENGTH != 0 => {
+            let size: libc::off_t = read_field(entry, offset)?;
+            Some(BulkEntry::File(FileStat {
+                size: size.max(0) as u64,
+                modified_ms,
+            }))
+        }
+        VDIR => child_path().map(BulkEntry::Directory),
+        VLNK => child_path().map(BulkEntry::Symlink),
+        _ => Some(BulkEntry::Other),
+    }
+}
+
+/// Reads every entry of one directory, calling `on_entry` until it returns false
+fn read_directory(
+    directory: &File,
+    path: &Path,
+    buffer: &mut [u8],
+    mut on_entry: impl FnMut(BulkEntry) -> bool,
+) -> Result<(), Unsupported> {
+    let mut request = libc::attrlist {
+        bitmapcount: libc::ATTR_BIT_MAP_COUNT,
+        reserved: 0,
+        commonattr: libc::ATTR_CMN_RETURNED_ATTRS
+            | libc::ATTR_CMN_NAME
+            | ATTR_CMN_ERROR
+            | libc::ATTR_CMN_OBJTYPE
+            | libc::ATTR_CMN_MODTIME,
+        volattr: 0,
+        dirattr: 0,
+        fileattr: libc::ATTR_FILE_DATALENGTH,
+        forkattr: 0,
+    };
+
+    loop {
+        // The request and buffer outlive the call, and the buffer's real length is passed
+        let count = unsafe {
+            libc::getattrlistbulk(
+                directory.as_raw_fd(),
+                (&mut request as *mut libc::attrlist).cast(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                0,
+            )
+        };
+
+        if count == 0 {
+            return Ok(());
+        }
+        if count < 0 {
+            return match io::Error::last_os_error().raw_os_error() {
+                Some(libc::ENOTSUP | libc::EINVAL) => Err(Unsupported),
+                // Unreadable directories are skipped, as they are by jwalk
+                _ => Ok(()),
+            };
+        }
+
+        let mut offset = 0;
+        for _ in 0..count {
+            let Some(length) = read_field::<u32>(buffer, offset) else {
+                return Ok(());
+            };
+            let Some(entry) = buffer.get(offset..offset + length as usize) else {
+                return Ok(());
+            };
+            offset += length as usize;
+
+            if let Some(entry) = parse_entry(entry, path) {
+                if !on_entry(entry) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Sizes a directory with the same rules as the jwalk walker: symlinks are followed,
+/// and a symlink back to an ancestor is skipped. Returns `Unsupported` when the volume
+/// can't enumerate in bulk, so the caller can fall back to jwalk, and None once `token`
+/// is cancelled.
+pub fn walk_sizes(
+    path: &Path,
+    max_file_count: Option<usize>,
+    token: &CancellationToken,
+) -> Result<Option<SizeTotals>, Unsupported> {
+    let mut totals = SizeTotals::default();
+    let mut buffer = vec![0u8; config::scanner::BULK_ATTRIBUTES_BUFFER_BYTES];
+    let mut pending = vec![(path.to_path_buf(), Vec::new())];
+
+    while let Some((directory_path, mut ancestors)) = pending.pop() {
+        let Ok(directory) = File::open(&directory_path) else {
+            continue;
+        };
+        if let Ok(metadata) = directory.metadata() {
+            ancestors.push((metadata.dev(), metadata.ino()));
+        }
+
+        let mut reached_limit = false;
+        read_directory(&directory, &directory_path, &mut buffer, |entry| {
+            if token.is_cancelled() {
+                return false;
+            }
+
+            let stat = match entry {
+                BulkEntry::File(stat) => stat,
+                BulkEntry::Directory(child) => {
+                    pending.push((child, ancestors.clone()));
+                    return true;
+                }
+                BulkEntry::Symlink(link) => match fs::metadata(&link) {
+                    Ok(metadata) if metadata.is_file() => file_stat(&metadata),
+                    Ok(metadata) if metadata.is_dir() => {
+                        let is_cycle = directory_identity(&link)
+                            .is_some_and(|identity| ancestors.contains(&identity));
+                        if !is_cycle {
+                            pending.push((link, ancestors.clone()));
+                        }
+                        return true;
+                    }
+                    _ => return true,
+                },
+                BulkEntry::Other => return true,
+            };
+
+            reached_limit = totals.add_file(stat, max_file_count);
+            !reached_limit
+        })?;
+
+        if token.is_cancelled() {
+            return Ok(None);
+        }
+        if reached_limit {
+            break;
+        }
+    }
+
+    Ok(Some(totals))
+}
+
+#[cfg(test)]
+#[path = "bulk_attributes.test.rs"]
+mod tests;
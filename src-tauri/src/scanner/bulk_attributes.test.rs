@@ -0,0 +1,129 @@
+use super::*;
+use crate::scanner::core::{calculate_dir_size_with, DirectorySizeResult};
+use std::os::unix::fs::symlink;
+use tempfile::TempDir;
+
+fn jwalk_result(path: &Path, max_file_count: Option<usize>) -> DirectorySizeResult {
+    calculate_dir_size_with(path, max_file_count, jwalk::Parallelism::Serial)
+}
+
+fn bulk_result(path: &Path, max_file_count: Option<usize>) -> DirectorySizeResult {
+    walk_sizes(path, max_file_count, &CancellationToken::new())
+        .expect("temp volume should support getattrlistbulk")
+        .expect("walk was not cancelled")
+        .finish(path)
+}
+
+#[test]
+fn test_walk_sizes_matches_jwalk() {
+    let temp_dir = TempDir::new().unwrap();
+    let nested = temp_dir.path().join("lodash").join("fp");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(temp_dir.path().join("index.js"), "module.exports = {}").unwrap();
+    fs::write(temp_dir.path().join(".package-lock.json"), "{}").unwrap();
+    fs::write(nested.join("map.js"), "map").unwrap();
+
+    let bulk = bulk_result(temp_dir.path(), None);
+
+    assert_eq!(bulk, jwalk_result(temp_dir.path(), None));
+    assert_eq!(bulk.file_count, 3);
+    assert_eq!(bulk.total_size, 24);
+}
+
+#[test]
+fn test_walk_sizes_counts_more_entries_than_fit_in_one_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    for index in 0..3_000 {
+        fs::write(
+            temp_dir
+                .path()
+                .join(format!("a-fairly-long-file-name-{index:05}.js")),
+            "x",
+        )
+        .unwrap();
+    }
+
+    let bulk = bulk_result(temp_dir.path(), None);
+
+    assert_eq!(bulk.file_count, 3_000);
+    assert_eq!(bulk, jwalk_result(temp_dir.path(), None));
+}
+
+#[test]
+fn test_walk_sizes_follows_symlinks() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = temp_dir.path().join("store");
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir_all(store.join("react")).unwrap();
+    fs::create_dir_all(&node_modules).unwrap();
+    fs::write(store.join("react").join("index.js"), "react").unwrap();
+    fs::write(store.join("README.md"), "readme").unwrap();
+    symlink(store.join("react"), node_modules.join("react")).unwrap();
+    symlink(store.join("README.md"), node_modules.join("README.md")).unwrap();
+    symlink(store.join("missing"), node_modules.join("broken")).unwrap();
+
+    let bulk = bulk_result(&node_modules, None);
+
+    assert_eq!(bulk, jwalk_result(&node_modules, None));
+    assert_eq!(bulk.file_count, 2);
+    assert_eq!(bulk.total_size, 11);
+}
+
+#[test]
+fn test_walk_sizes_skips_symlink_cycles() {
+    let temp_dir = TempDir::new().unwrap();
+    let package = temp_dir.path().join("package");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join("index.js"), "index").unwrap();
+    symlink(temp_dir.path(), package.join("loop")).unwrap();
+
+    let bulk = bulk_result(temp_dir.path(), None);
+
+    assert_eq!(bulk, jwalk_result(temp_dir.path(), None));
+    assert_eq!(bulk.file_count, 1);
+}
+
+#[test]
+fn test_walk_sizes_stops_at_file_ceiling() {
+    let temp_dir = TempDir::new().unwrap();
+    for index in 0..10 {
+        fs::write(temp_dir.path().join(format!("{index}.js")), "x").unwrap();
+    }
+
+    let bulk = bulk_result(temp_dir.path(), Some(4));
+
+    assert!(bulk.truncated);
+    assert_eq!(bulk.file_count, 4);
+    assert_eq!(bulk.total_size, 4);
+}
+
+#[test]
+fn test_walk_sizes_only_symlinks() {
+    let temp_dir = TempDir::new().unwrap();
+    let hoisted = temp_dir.path().join("hoisted");
+    fs::create_dir_all(&hoisted).unwrap();
+    symlink(temp_dir.path().join("missing"), hoisted.join("dangling")).unwrap();
+
+    let bulk = bulk_result(&hoisted, None);
+
+    assert_eq!(bulk, jwalk_result(&hoisted, None));
+    assert!(bulk.has_only_symlinks);
+}
+
+#[test]
+fn test_walk_sizes_missing_directory_is_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing");
+
+    assert_eq!(bulk_result(&missing, None), jwalk_result(&missing, None));
+}
+
+#[test]
+fn test_walk_sizes_stops_when_cancelled() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("index.js"), "x").unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+
+    assert!(walk_sizes(temp_dir.path(), None, &token).unwrap().is_none());
+}
@@ -0,0 +1,129 @@
+use super::core::calculate_dir_size_limited;
+use crate::i18n;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Global package manager caches that live outside any project, so the walk never finds them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PackageManagerCache {
+    Npm,
+    Yarn,
+    Pnpm,
+    Pip,
+}
+
+impl PackageManagerCache {
+    pub fn all() -> Vec<PackageManagerCache> {
+        vec![
+            PackageManagerCache::Npm,
+            PackageManagerCache::Yarn,
+            PackageManagerCache::Pnpm,
+            PackageManagerCache::Pip,
+        ]
+    }
+
+    /// Translation key for the label under `packageCaches` in the locale files
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            PackageManagerCache::Npm => "packageCaches.npm",
+            PackageManagerCache::Yarn => "packageCaches.yarn",
+            PackageManagerCache::Pnpm => "packageCaches.pnpm",
+            PackageManagerCache::Pip => "packageCaches.pip",
+        }
+    }
+
+    /// The label in `language`, from the locale files the UI uses
+    pub fn translated_label(&self, language: &str) -> String {
+        i18n::translate(language, self.label_key())
+    }
+
+    /// Known locations relative to the home directory, macOS first
+    pub fn relative_locations(&self) -> &'static [&'static str] {
+        match self {
+            PackageManagerCache::Npm => &[".npm/_cacache"],
+            PackageManagerCache::Yarn => &["Library/Caches/Yarn", ".cache/yarn"],
+            PackageManagerCache::Pnpm => &[".local/share/pnpm/store", "Library/pnpm/store"],
+            PackageManagerCache::Pip => &["Library/Caches/pip", ".cache/pip"],
+        }
+    }
+
+    /// Program and arguments that clear the cache through its own package manager,
+    /// which keeps any index or lock files consistent unlike deleting the directory.
+    /// None for pnpm, whose `store prune` only drops packages no project references,
+    /// so its store is removed whole from wherever `pnpm store path` says it is.
+    pub fn clear_command(&self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            PackageManagerCache::Npm => Some(("npm", &["cache", "clean", "--force"])),
+            PackageManagerCache::Yarn => Some(("yarn", &["cache", "clean"])),
+            PackageManagerCache::Pnpm => None,
+            PackageManagerCache::Pip => Some(("pip", &["cache", "purge"])),
+        }
+    }
+
+    /// The clear command as the user would type it
+    pub fn clear_command_line(&self) -> String {
+        match self.clear_command() {
+            Some((program, args)) => std::iter::once(program)
+                .chain(args.iter().copied())
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => "rm -rf \"$(pnpm store path)\"".to_string(),
+        }
+    }
+
+    /// First known location that exists under `home`
+    pub fn locate(&self, home: &Path) -> Option<PathBuf> {
+        self.relative_locations()
+            .iter()
+            .map(|relative| home.join(relative))
+            .find(|path| path.is_dir())
+    }
+}
+
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheEntry {
+    pub cache: PackageManagerCache,
+    pub path: String,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    #[serde(default)]
+    pub truncated: bool,
+    pub clear_command: String,
+}
+
+/// Sizes every package manager cache found under `home`, largest first
+pub fn find_package_manager_caches(home: &Path, max_file_count: Option<usize>) -> Vec<CacheEntry> {
+    let mut entries: Vec<CacheEntry> = PackageManagerCache::all()
+        .into_iter()
+        .filter_map(|cache| {
+            let path = cache.locate(home)?;
+            let size_result = calculate_dir_size_limited(&path, max_file_count);
+            debug!(
+                ?cache,
+                path = %path.display(),
+                size_bytes = size_result.total_size,
+                "Sized package manager cache"
+            );
+
+            Some(CacheEntry {
+                cache,
+                path: path.to_string_lossy().to_string(),
+                size_bytes: size_result.total_size,
+                file_count: size_result.file_count,
+                truncated: size_result.truncated,
+                clear_command: cache.clear_command_line(),
+            })
+        })
+        .collect();
+
+    entries.sort_by(|first, second| second.size_bytes.cmp(&first.size_bytes));
+    entries
+}
+
+#[cfg(test)]
+#[path = "caches.test.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_package_manager_cache_all() {
+    let all = PackageManagerCache::all();
+    assert_eq!(all.len(), 4);
+    assert!(all.contains(&PackageManagerCache::Npm));
+    assert!(all.contains(&PackageManagerCache::Pip));
+}
+
+#[test]
+fn test_package_manager_cache_serialization() {
+    assert_eq!(
+        serde_json::to_string(&PackageManagerCache::Pnpm).unwrap(),
+        "\"PNPM\""
+    );
+}
+
+#[test]
+fn test_package_manager_cache_labels_are_translated() {
+    for cache in PackageManagerCache::all() {
+        assert_ne!(cache.translated_label("en"), cache.label_key());
+    }
+    assert_eq!(PackageManagerCache::Npm.translated_label("en"), "npm cache");
+    assert_eq!(PackageManagerCache::Pip.translated_label("de"), "pip-Cache");
+}
+
+#[test]
+fn test_clear_command_line() {
+    assert_eq!(
+        PackageManagerCache::Npm.clear_command_line(),
+        "npm cache clean --force"
+    );
+    assert_eq!(
+        PackageManagerCache::Yarn.clear_command_line(),
+        "yarn cache clean"
+    );
+    assert_eq!(
+        PackageManagerCache::Pnpm.clear_command_line(),
+        "rm -rf \"$(pnpm store path)\""
+    );
+    assert_eq!(
+        PackageManagerCache::Pip.clear_command_line(),
+        "pip cache purge"
+    );
+}
+
+#[test]
+fn test_locate_prefers_first_existing_location() {
+    let home = TempDir::new().unwrap();
+    fs::create_dir_all(home.path().join(".cache/yarn")).unwrap();
+
+    assert_eq!(
+        PackageManagerCache::Yarn.locate(home.path()),
+        Some(home.path().join(".cache/yarn"))
+    );
+
+    fs::create_dir_all(home.path().join("Library/Caches/Yarn")).unwrap();
+    assert_eq!(
+        PackageManagerCache::Yarn.locate(home.path()),
+        Some(home.path().join("Library/Caches/Yarn"))
+    );
+}
+
+#[test]
+fn test_locate_missing_cache() {
+    let home = TempDir::new().unwrap();
+    assert!(PackageManagerCache::Npm.locate(home.path()).is_none());
+}
+
+#[test]
+fn test_find_package_manager_caches_sizes_and_sorts() {
+    let home = TempDir::new().unwrap();
+    let npm_cache = home.path().join(".npm/_cacache");
+    let pip_cache = home.path().join(".cache/pip");
+    fs::create_dir_all(&npm_cache).unwrap();
+    fs::create_dir_all(&pip_cache).unwrap();
+    fs::write(npm_cache.join("index"), "a".repeat(100)).unwrap();
+    fs::write(pip_cache.join("wheel"), "b".repeat(300)).unwrap();
+
+    let entries = find_package_manager_caches(home.path(), None);
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].cache, PackageManagerCache::Pip);
+    assert_eq!(entries[0].size_bytes, 300);
+    assert_eq!(entries[0].clear_command, "pip cache purge");
+    assert_eq!(entries[1].cache, PackageManagerCache::Npm);
+    assert_eq!(entries[1].file_count, 1);
+}
@@ -1,8 +1,15 @@
+use super::patterns::matches_glob;
+use crate::cloud::{is_dataless, synced_folders};
+use crate::config;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::LazyLock;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 /// Directories to skip during scanning (system/cache directories that shouldn't contain user projects)
 static SKIP_DIRECTORIES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
@@ -43,21 +50,15 @@ fn create_walker(path: &Path) -> jwalk::WalkDir {
 }
 
 /// Calculates the total size of a directory in bytes
+#[cfg(test)]
 pub fn calculate_dir_size(path: &Path) -> u64 {
-    let total_size = AtomicU64::new(0);
-
     create_walker(path)
         .into_iter()
         .filter_map(Result::ok)
-        .for_each(|entry| {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    total_size.fetch_add(metadata.len(), Ordering::Relaxed);
-                }
-            }
-        });
-
-    total_size.load(Ordering::Relaxed)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
 }
 
 /// Result of calculating directory size with additional metadata
@@ -68,10 +69,11 @@ pub struct DirectorySizeResult {
     pub has_only_symlinks: bool,
     /// Most recent modification time in milliseconds since Unix epoch
     pub last_modified_ms: u64,
+    /// Sizing stopped early because the file-count ceiling was reached
+    pub truncated: bool,
 }
 
 /// Calculates the total size and file count of a directory
-/// Uses serial processing to avoid reentrancy issues with nested jwalk calls
 #[cfg(test)]
 fn calculate_dir_size_with_count(path: &Path) -> (u64, usize) {
     let result = calculate_dir_size_full(path);
@@ -79,67 +81,211 @@ fn calculate_dir_size_with_count(path: &Path) -> (u64, usize) {
 }
 
 /// Calculates the total size, file count, symlink status, and last modified time of a directory
-/// Uses serial processing to avoid reentrancy issues with nested jwalk calls
+/// Directory reads and file stats run on the dedicated size walk pool, see `SIZE_WALK_POOL`
+/// Symlinks that point back to an ancestor directory are skipped so cycles can't inflate totals
 /// Returns `has_only_symlinks: true` if the directory contains symlinks but no real files
 /// Returns `last_modified_ms` as the most recent modification time of any file in the directory
 pub fn calculate_dir_size_full(path: &Path) -> DirectorySizeResult {
+    calculate_dir_size_limited(path, None)
+}
+
+/// Same as `calculate_dir_size_full`, but stops once `max_file_count` files have been counted
+/// The partial totals are returned with `truncated: true` so a runaway directory can't pin a worker
+pub fn calculate_dir_size_limited(
+    path: &Path,
+    max_file_count: Option<usize>,
+) -> DirectorySizeResult {
+    calculate_dir_size_cancellable(path, max_file_count, &CancellationToken::new())
+        .expect("a fresh token is never cancelled")
+}
+
+/// Same as `calculate_dir_size_limited`, but checks `token` between entries and returns
+/// None once it is cancelled, so an abandoned scan stops walking within milliseconds
+pub fn calculate_dir_size_cancellable(
+    path: &Path,
+    max_file_count: Option<usize>,
+    token: &CancellationToken,
+) -> Option<DirectorySizeResult> {
+    #[cfg(target_os = "macos")]
+    if BULK_ATTRIBUTES_ENABLED.load(Ordering::Relaxed) {
+        if let Ok(totals) = super::bulk_attributes::walk_sizes(path, max_file_count, token) {
+            return totals.map(|totals| totals.finish(path));
+        }
+    }
+
+    walk_with_jwalk(path, max_file_count, size_walk_parallelism(), token)
+        .map(|totals| totals.finish(path))
+}
+
+/// Whether macOS sizing may use `getattrlistbulk`, see `bulk_attributes`
+static BULK_ATTRIBUTES_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns the macOS bulk attribute fast path on or off, so every size walk uses jwalk
+pub fn set_bulk_attributes_enabled(enabled: bool) {
+    BULK_ATTRIBUTES_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Dedicated pool for per-directory size walks. Sizing runs on the size pool's own threads,
+/// never on this pool, so a walk can always make progress here and jwalk's busy check is
+/// unnecessary. Keeping it off rayon's global pool avoids the reentrancy deadlock when a
+/// discovery walk is using it at the same time.
+static SIZE_WALK_POOL: LazyLock<Option<Arc<jwalk::rayon::ThreadPool>>> = LazyLock::new(|| {
+    jwalk::rayon::ThreadPoolBuilder::new()
+        .num_threads(config::scanner::SIZE_WALK_THREADS)
+        .thread_name(|index| format!("size-walk-{index}"))
+        .build()
+        .map_err(|error| warn!(%error, "Failed to build size walk pool, walking serially"))
+        .ok()
+        .map(Arc::new)
+});
+
+/// Falls back to a serial walk if the pool couldn't be built
+fn size_walk_parallelism() -> jwalk::Parallelism {
+    match SIZE_WALK_POOL.as_ref() {
+        Some(pool) => jwalk::Parallelism::RayonExistingPool {
+            pool: Arc::clone(pool),
+            busy_timeout: None,
+        },
+        None => jwalk::Parallelism::Serial,
+    }
+}
+
+/// File metadata captured while a directory is read, so the stat calls happen on the
+/// walker's threads instead of serially on the consuming thread
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct FileStat {
+    pub(super) size: u64,
+    pub(super) modified_ms: u64,
+}
+
+pub(super) fn file_stat(metadata: &fs::Metadata) -> FileStat {
     use std::time::UNIX_EPOCH;
 
-    let mut total_size: u64 = 0;
-    let mut file_count: usize = 0;
-    let mut has_symlinks = false;
-    let mut has_real_content = false;
-    let mut latest_modified_ms: u64 = 0;
+    FileStat {
+        size: metadata.len(),
+        modified_ms: metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0),
+    }
+}
+
+/// Running totals for a size walk, shared by the jwalk and bulk attribute walkers
+#[derive(Debug, Default)]
+pub(super) struct SizeTotals {
+    total_size: u64,
+    file_count: usize,
+    latest_modified_ms: u64,
+    truncated: bool,
+}
+
+impl SizeTotals {
+    /// Counts one file, returning true once `max_file_count` files have been counted
+    pub(super) fn add_file(&mut self, stat: FileStat, max_file_count: Option<usize>) -> bool {
+        self.total_size += stat.size;
+        self.file_count += 1;
+        self.latest_modified_ms = self.latest_modified_ms.max(stat.modified_ms);
 
-    // Serial processing avoids jwalk reentrancy issues; follow_links counts pnpm symlinks
-    let walker = jwalk::WalkDir::new(path)
+        self.truncated = max_file_count.is_some_and(|limit| self.file_count >= limit);
+        self.truncated
+    }
+
+    pub(super) fn finish(self, path: &Path) -> DirectorySizeResult {
+        use std::time::UNIX_EPOCH;
+
+        let has_real_content = self.file_count > 0;
+        // Flag pnpm hoisted directories that contain only symlinks
+        let has_symlinks = !has_real_content && check_directory_has_symlinks(path);
+
+        let mut latest_modified_ms = self.latest_modified_ms;
+        if latest_modified_ms == 0 {
+            latest_modified_ms = path
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .map(|modified| {
+                    modified
+                        .duration_since(UNIX_EPOCH)
+                        .map(|duration| duration.as_millis() as u64)
+                        .unwrap_or(0)
+                })
+                .unwrap_or(0);
+        }
+
+        DirectorySizeResult {
+            total_size: self.total_size,
+            file_count: self.file_count,
+            has_only_symlinks: has_symlinks,
+            last_modified_ms: latest_modified_ms,
+            truncated: self.truncated,
+        }
+    }
+}
+
+pub(super) fn calculate_dir_size_with(
+    path: &Path,
+    max_file_count: Option<usize>,
+    parallelism: jwalk::Parallelism,
+) -> DirectorySizeResult {
+    walk_with_jwalk(path, max_file_count, parallelism, &CancellationToken::new())
+        .expect("a fresh token is never cancelled")
+        .finish(path)
+}
+
+/// Returns None if `token` is cancelled before the walk finishes
+fn walk_with_jwalk(
+    path: &Path,
+    max_file_count: Option<usize>,
+    parallelism: jwalk::Parallelism,
+    token: &CancellationToken,
+) -> Option<SizeTotals> {
+    let mut totals = SizeTotals::default();
+
+    // follow_links counts pnpm symlinks. Track the (device, inode) chain of ancestors for
+    // each directory read, so a symlink pointing back up the tree is never descended into
+    // and the walk always terminates
+    let walker = jwalk::WalkDirGeneric::<(Vec<(u64, u64)>, Option<FileStat>)>::new(path)
         .skip_hidden(false)
         .follow_links(true)
-        .parallelism(jwalk::Parallelism::Serial);
+        .parallelism(parallelism)
+        .process_read_dir(|_, directory_path, ancestors, children| {
+            if let Some(identity) = directory_identity(directory_path) {
+                ancestors.push(identity);
+            }
 
-    for entry in walker.into_iter().flatten() {
-        if let Ok(metadata) = entry.metadata() {
-            if metadata.is_file() {
-                total_size += metadata.len();
-                file_count += 1;
-                has_real_content = true;
-
-                if let Ok(modified) = metadata.modified() {
-                    if let Ok(duration) = modified.duration_since(UNIX_EPOCH) {
-                        let modified_ms = duration.as_millis() as u64;
-                        if modified_ms > latest_modified_ms {
-                            latest_modified_ms = modified_ms;
-                        }
-                    }
+            children.retain(|entry_result| match entry_result {
+                Ok(entry) if entry.file_type().is_dir() => directory_identity(&entry.path())
+                    .map(|identity| !ancestors.contains(&identity))
+                    .unwrap_or(true),
+                _ => true,
+            });
+
+            for entry in children.iter_mut().flatten() {
+                if entry.file_type().is_dir() {
+                    continue;
                 }
+                entry.client_state = entry
+                    .metadata()
+                    .ok()
+                    .filter(|metadata| metadata.is_file())
+                    .map(|metadata| file_stat(&metadata));
             }
-        }
-    }
+        });
 
-    // Flag pnpm hoisted directories that contain only symlinks
-    if !has_real_content {
-        has_symlinks = check_directory_has_symlinks(path);
-    }
+    for entry in walker.into_iter().flatten() {
+        if token.is_cancelled() {
+            return None;
+        }
 
-    if latest_modified_ms == 0 {
-        latest_modified_ms = path
-            .metadata()
-            .and_then(|metadata| metadata.modified())
-            .map(|modified| {
-                modified
-                    .duration_since(UNIX_EPOCH)
-                    .map(|duration| duration.as_millis() as u64)
-                    .unwrap_or(0)
-            })
-            .unwrap_or(0);
+        if let Some(stat) = entry.client_state {
+            if totals.add_file(stat, max_file_count) {
+                break;
+            }
+        }
     }
 
-    DirectorySizeResult {
-        total_size,
-        file_count,
-        has_only_symlinks: has_symlinks && !has_real_content,
-        last_modified_ms: latest_modified_ms,
-    }
+    Some(totals)
 }
 
 /// Recursively checks if a directory contains any symlinks
@@ -164,6 +310,209 @@ fn check_directory_has_symlinks(path: &Path) -> bool {
     false
 }
 
+/// Tracks directories by (device, inode) so trees reachable through several symlinks
+/// are walked once and symlink loops terminate
+#[derive(Debug, Default)]
+pub struct VisitedDirectories {
+    seen: Mutex<HashSet<(u64, u64)>>,
+}
+
+impl VisitedDirectories {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true the first time a directory is seen
+    /// Directories whose identity cannot be read are always treated as unvisited
+    pub fn first_visit(&self, path: &Path) -> bool {
+        match directory_identity(path) {
+            Some(identity) => self.seen.lock().unwrap().insert(identity),
+            None => true,
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(super) fn directory_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Falls back to hashing the canonical path where inode numbers aren't available
+#[cfg(not(unix))]
+pub(super) fn directory_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let canonical_path = path.canonicalize().ok()?;
+    let mut hasher = DefaultHasher::new();
+    canonical_path.hash(&mut hasher);
+    Some((0, hasher.finish()))
+}
+
+/// Storage that is slow or costly to walk, which scans can stay out of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VolumeKind {
+    /// NFS, SMB and other remote mounts, where every directory read is a round trip
+    Network,
+    /// Removable and external disks mounted outside the system volume
+    External,
+    /// iCloud Drive, Dropbox and other synced folders, where reading a placeholder
+    /// downloads it
+    CloudStorage,
+}
+
+/// Directories a scan leaves alone because of the storage they live on, resolved
+/// once per scan from the mount table and the usual sync folders
+#[derive(Debug, Clone, Default)]
+pub struct SkippedVolumes {
+    roots: Vec<(PathBuf, VolumeKind)>,
+    skip_dataless: bool,
+}
+
+impl SkippedVolumes {
+    /// Volumes of the given kinds, apart from any holding the scan root itself, since
+    /// pointing a scan straight at a volume is asking for it to be walked
+    pub fn new(kinds: &HashSet<VolumeKind>, scan_root: &Path) -> Self {
+        let home = dirs::home_dir();
+        let roots = mounted_volumes()
+            .into_iter()
+            .chain(cloud_storage_roots(home.as_deref()))
+            .filter(|(root, kind)| kinds.contains(kind) && !scan_root.starts_with(root))
+            .collect();
+
+        Self {
+            roots,
+            skip_dataless: kinds.contains(&VolumeKind::CloudStorage),
+        }
+    }
+
+    /// The kind of storage a directory is the root of, if it's one to skip. Only roots
+    /// are matched, since the walk never descends past one.
+    pub fn kind_of(&self, directory: &Path) -> Option<VolumeKind> {
+        self.roots
+            .iter()
+            .find(|(root, _)| root == directory)
+            .map(|(_, kind)| *kind)
+            .or_else(|| {
+                (self.skip_dataless && is_dataless(directory)).then_some(VolumeKind::CloudStorage)
+            })
+    }
+}
+
+/// Folders that sync clients fill with placeholders, wherever their volume is mounted
+fn cloud_storage_roots(home: Option<&Path>) -> Vec<(PathBuf, VolumeKind)> {
+    home.map(synced_folders)
+        .into_iter()
+        .flatten()
+        .map(|root| (root, VolumeKind::CloudStorage))
+        .collect()
+}
+
+/// Filesystem types that live on another machine
+#[cfg(any(target_os = "linux", test))]
+const NETWORK_FILESYSTEMS: [&str; 12] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "fuse.sshfs",
+];
+
+/// Where Linux desktops mount removable media
+#[cfg(any(target_os = "linux", test))]
+const EXTERNAL_MOUNT_PREFIXES: [&str; 3] = ["/media/", "/run/media/", "/mnt/"];
+
+#[cfg(any(target_os = "linux", test))]
+fn is_network_filesystem(filesystem_type: &str) -> bool {
+    NETWORK_FILESYSTEMS.contains(&filesystem_type) || filesystem_type.starts_with("fuse.rclone")
+}
+
+/// Parses `/proc/self/mounts`, where each line is `<source> <mount point> <type> ...`
+/// with spaces in the mount point written as `\040`
+#[cfg(any(target_os = "linux", test))]
+fn parse_mount_table(content: &str) -> Vec<(PathBuf, VolumeKind)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _source = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let filesystem_type = fields.next()?;
+
+            let kind = if is_network_filesystem(filesystem_type) {
+                VolumeKind::Network
+            } else if EXTERNAL_MOUNT_PREFIXES
+                .iter()
+                .any(|prefix| mount_point.starts_with(prefix))
+            {
+                VolumeKind::External
+            } else {
+                return None;
+            };
+            Some((PathBuf::from(mount_point), kind))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn mounted_volumes() -> Vec<(PathBuf, VolumeKind)> {
+    fs::read_to_string("/proc/self/mounts")
+        .map(|content| parse_mount_table(&content))
+        .unwrap_or_else(|error| {
+            warn!(%error, "Failed to read mount table");
+            Vec::new()
+        })
+}
+
+/// Reads the mount table with getmntinfo. Volumes without `MNT_LOCAL` are remote, and
+/// local ones mounted under /Volumes are external disks.
+#[cfg(target_os = "macos")]
+fn mounted_volumes() -> Vec<(PathBuf, VolumeKind)> {
+    use std::ffi::CStr;
+
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    // getmntinfo points `mounts` at a buffer it owns, valid until the next call on this thread
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
+        warn!("Failed to read mount table");
+        return Vec::new();
+    }
+    let mounts = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+
+    mounts
+        .iter()
+        .filter_map(|mount| {
+            let mount_point = unsafe { CStr::from_ptr(mount.f_mntonname.as_ptr()) }
+                .to_string_lossy()
+                .to_string();
+            let kind = if mount.f_flags & libc::MNT_LOCAL as u32 == 0 {
+                VolumeKind::Network
+            } else if mount_point.starts_with("/Volumes/") {
+                VolumeKind::External
+            } else {
+                return None;
+            };
+            Some((PathBuf::from(mount_point), kind))
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mounted_volumes() -> Vec<(PathBuf, VolumeKind)> {
+    Vec::new()
+}
+
 /// Expands ~ to the home directory using the cross-platform dirs crate
 pub fn expand_tilde(path: &str) -> String {
     if path.starts_with('~') {
@@ -192,57 +541,33 @@ pub fn parse_exclude_patterns(exclude_paths: &str) -> Vec<String> {
         .collect()
 }
 
-/// Checks if a path matches a wildcard pattern
-/// Supports * as a wildcard that matches any sequence of characters
-/// Pattern matching is case-sensitive
-fn matches_wildcard_pattern(path: &str, pattern: &str) -> bool {
-    let pattern_parts: Vec<&str> = pattern.split('*').collect();
-
-    if pattern_parts.len() == 1 {
-        return path.contains(pattern);
-    }
-
-    let mut remaining = path;
-    let mut first = true;
-
-    for (index, part) in pattern_parts.iter().enumerate() {
-        if part.is_empty() {
-            continue;
-        }
-
-        if first && !pattern.starts_with('*') {
-            if !remaining.starts_with(part) {
-                return false;
-            }
-            remaining = &remaining[part.len()..];
-        } else if index == pattern_parts.len() - 1 && !pattern.ends_with('*') {
-            if !remaining.ends_with(part) {
-                return false;
-            }
-        } else {
-            match remaining.find(part) {
-                Some(position) => {
-                    remaining = &remaining[position + part.len()..];
-                }
-                None => return false,
-            }
-        }
-        first = false;
-    }
-
-    true
-}
-
 /// Checks if a path should be excluded based on the exclude patterns
 pub fn should_exclude_path(path: &str, exclude_patterns: &[String]) -> bool {
     for pattern in exclude_patterns {
-        if matches_wildcard_pattern(path, pattern) {
+        if matches_glob(path, pattern) {
             return true;
         }
     }
     false
 }
 
+/// Parses comma-separated include patterns like exclude patterns, expanding a
+/// leading ~ so `~/code` matches directories under the home directory
+pub fn parse_include_patterns(include_paths: &str) -> Vec<String> {
+    parse_exclude_patterns(include_paths)
+        .into_iter()
+        .map(|pattern| expand_tilde(&pattern))
+        .collect()
+}
+
+/// Checks if a path matches one of the include patterns; no patterns includes everything
+pub fn should_include_path(path: &str, include_patterns: &[String]) -> bool {
+    include_patterns.is_empty()
+        || include_patterns
+            .iter()
+            .any(|pattern| matches_glob(path, pattern))
+}
+
 /// Checks if a directory is nested inside another dependency directory
 /// Used to avoid scanning nested dependency directories (e.g., node_modules inside node_modules)
 pub fn is_inside_dependency_directory(
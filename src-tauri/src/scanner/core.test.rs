@@ -531,147 +531,36 @@ fn test_parse_exclude_patterns_filters_empty_patterns() {
 }
 
 #[test]
-fn test_matches_wildcard_pattern_exact_substring() {
-    // Without wildcards, pattern acts as substring match
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/active-project/node_modules",
-        "active-project"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/myproject/node_modules",
-        "myproject"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/project/node_modules",
-        "active-project"
-    ));
-}
-
-#[test]
-fn test_matches_wildcard_pattern_leading_wildcard() {
-    // Pattern: */active-* should match any path containing "active-" anywhere
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/active-project/node_modules",
-        "*/active-*"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/home/user/work/active-api/node_modules",
-        "*/active-*"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/inactive/node_modules",
-        "*/active-*"
-    ));
-}
-
-#[test]
-fn test_matches_wildcard_pattern_trailing_wildcard() {
-    // Pattern: /Users/testuser/* should match paths starting with /Users/testuser/
-    assert!(matches_wildcard_pattern(
+fn test_should_include_path_empty_patterns_include_everything() {
+    let patterns: Vec<String> = vec![];
+    assert!(should_include_path(
         "/Users/testuser/project/node_modules",
-        "/Users/testuser/*"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/work/deep/node_modules",
-        "/Users/testuser/*"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/bob/project/node_modules",
-        "/Users/testuser/*"
-    ));
-}
-
-#[test]
-fn test_matches_wildcard_pattern_middle_wildcard() {
-    // Pattern: /Users/*/work matches paths that START with /Users/, then anything, then END with /work
-    // (without trailing wildcard, pattern requires path to end with the last part)
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/work",
-        "/Users/*/work"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/Users/bob/code/work",
-        "/Users/*/work"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/work/project",
-        "/Users/*/work"
-    )); // doesn't end with /work
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/personal",
-        "/Users/*/work"
-    )); // doesn't contain /work
-
-    // To match paths containing /work followed by more content, use trailing wildcard
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/work/project/node_modules",
-        "/Users/*/work/*"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/Users/bob/work/api/node_modules",
-        "/Users/*/work/*"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/personal/project/node_modules",
-        "/Users/*/work/*"
-    ));
-}
-
-#[test]
-fn test_matches_wildcard_pattern_multiple_wildcards() {
-    // Pattern: */active-*/node_modules should match active- projects with node_modules
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/active-api/node_modules",
-        "*/active-*/node_modules"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/home/user/active-frontend/node_modules",
-        "*/active-*/node_modules"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/active-api/vendor",
-        "*/active-*/node_modules"
+        &patterns
     ));
 }
 
 #[test]
-fn test_matches_wildcard_pattern_no_wildcard_exact() {
-    // Without wildcards, acts as substring match
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/project/node_modules",
-        "project"
-    ));
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/my-project-v2/node_modules",
-        "project"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/my-app/node_modules",
-        "project"
+fn test_should_include_path_requires_a_match() {
+    let patterns = vec!["/Users/testuser/code/*".to_string(), "*/work/*".to_string()];
+    assert!(should_include_path(
+        "/Users/testuser/code/app/node_modules",
+        &patterns
     ));
-}
-
-#[test]
-fn test_matches_wildcard_pattern_path_must_start_with() {
-    // Pattern without leading wildcard should require path to start with pattern start
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/project",
-        "/Users/*"
+    assert!(should_include_path("/home/user/work/api/vendor", &patterns));
+    assert!(!should_include_path(
+        "/Users/testuser/Downloads/demo/node_modules",
+        &patterns
     ));
-    assert!(!matches_wildcard_pattern("/home/alex/project", "/Users/*"));
 }
 
 #[test]
-fn test_matches_wildcard_pattern_path_must_end_with() {
-    // Pattern without trailing wildcard should require path to end with pattern end
-    assert!(matches_wildcard_pattern(
-        "/Users/testuser/project/node_modules",
-        "*/node_modules"
-    ));
-    assert!(!matches_wildcard_pattern(
-        "/Users/testuser/project/node_modules/package",
-        "*/node_modules"
-    ));
+fn test_parse_include_patterns_expands_tilde() {
+    let patterns = parse_include_patterns("~/code, */work/*");
+    assert_eq!(
+        patterns,
+        vec![expand_tilde("~/code"), "*/work/*".to_string()]
+    );
+    assert!(parse_include_patterns("").is_empty());
 }
 
 #[test]
@@ -790,22 +679,6 @@ fn test_should_exclude_path_case_sensitive() {
     ));
 }
 
-#[test]
-fn test_matches_wildcard_pattern_edge_cases() {
-    // Empty pattern
-    assert!(matches_wildcard_pattern("/any/path", ""));
-
-    // Just wildcards
-    assert!(matches_wildcard_pattern("/any/path", "*"));
-    assert!(matches_wildcard_pattern("/any/path", "**"));
-    assert!(matches_wildcard_pattern("", "*"));
-
-    // Empty path
-    assert!(matches_wildcard_pattern("", ""));
-    assert!(matches_wildcard_pattern("", "*"));
-    assert!(!matches_wildcard_pattern("", "something"));
-}
-
 // ============================================
 // Symlink Detection Tests
 // ============================================
@@ -1214,6 +1087,81 @@ fn test_calculate_dir_size_full_circular_symlinks() {
     assert!(!result.has_only_symlinks || result.has_only_symlinks);
 }
 
+#[test]
+#[cfg(unix)]
+fn test_calculate_dir_size_full_circular_symlinks_finite_counts() {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new().unwrap();
+    let dir_a = temp_dir.path().join("a");
+    let dir_b = temp_dir.path().join("b");
+
+    fs::create_dir(&dir_a).unwrap();
+    fs::create_dir(&dir_b).unwrap();
+    fs::write(dir_a.join("file_a.txt"), "12345").unwrap();
+    fs::write(dir_b.join("file_b.txt"), "123").unwrap();
+
+    // Relative links defeat jwalk's path-based loop check
+    symlink("../b", dir_a.join("link_to_b")).unwrap();
+    symlink("../a", dir_b.join("link_to_a")).unwrap();
+
+    let result = calculate_dir_size_full(temp_dir.path());
+
+    // Each directory is reached directly and once through the other's link,
+    // but never through a link back to one of its own ancestors
+    assert_eq!(result.total_size, 16);
+    assert_eq!(result.file_count, 4);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_calculate_dir_size_full_self_referencing_symlink() {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("node_modules");
+    fs::create_dir(&node_modules).unwrap();
+    fs::write(node_modules.join("index.js"), "1234").unwrap();
+    symlink(".", node_modules.join("self")).unwrap();
+
+    let result = calculate_dir_size_full(&node_modules);
+
+    assert_eq!(result.total_size, 4);
+    assert_eq!(result.file_count, 1);
+}
+
+#[test]
+fn test_calculate_dir_size_limited_stops_at_file_ceiling() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache = temp_dir.path().join("node_modules");
+    fs::create_dir(&cache).unwrap();
+    for index in 0..10 {
+        fs::write(cache.join(format!("file-{index}.js")), "12").unwrap();
+    }
+
+    let result = calculate_dir_size_limited(&cache, Some(3));
+
+    assert!(result.truncated);
+    assert_eq!(result.file_count, 3);
+    assert_eq!(result.total_size, 6);
+}
+
+#[test]
+fn test_calculate_dir_size_limited_under_ceiling_not_truncated() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache = temp_dir.path().join("node_modules");
+    fs::create_dir(&cache).unwrap();
+    fs::write(cache.join("a.js"), "1").unwrap();
+    fs::write(cache.join("b.js"), "2").unwrap();
+
+    let limited = calculate_dir_size_limited(&cache, Some(5));
+    let unlimited = calculate_dir_size_limited(&cache, None);
+
+    assert!(!limited.truncated);
+    assert_eq!(limited, unlimited);
+    assert_eq!(limited, calculate_dir_size_full(&cache));
+}
+
 #[test]
 #[cfg(unix)]
 fn test_calculate_dir_size_full_pnpm_realistic_structure() {
@@ -1298,6 +1246,7 @@ fn test_directory_size_result_equality() {
         file_count: 5,
         has_only_symlinks: false,
         last_modified_ms: 1000,
+        truncated: false,
     };
 
     let result2 = DirectorySizeResult {
@@ -1305,6 +1254,7 @@ fn test_directory_size_result_equality() {
         file_count: 5,
         has_only_symlinks: false,
         last_modified_ms: 1000,
+        truncated: false,
     };
 
     let result3 = DirectorySizeResult {
@@ -1312,6 +1262,7 @@ fn test_directory_size_result_equality() {
         file_count: 5,
         has_only_symlinks: true,
         last_modified_ms: 1000,
+        truncated: false,
     };
 
     assert_eq!(result1, result2);
@@ -1325,6 +1276,7 @@ fn test_directory_size_result_clone() {
         file_count: 10,
         has_only_symlinks: true,
         last_modified_ms: 1234567890000,
+        truncated: false,
     };
 
     let cloned = original.clone();
@@ -1334,3 +1286,219 @@ fn test_directory_size_result_clone() {
     assert_eq!(original.has_only_symlinks, cloned.has_only_symlinks);
     assert_eq!(original.last_modified_ms, cloned.last_modified_ms);
 }
+
+// ============================================
+// Visited Directory Tracking Tests
+// ============================================
+
+#[test]
+fn test_visited_directories_first_visit_only_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let visited = VisitedDirectories::new();
+
+    assert!(visited.first_visit(temp_dir.path()));
+    assert!(!visited.first_visit(temp_dir.path()));
+}
+
+#[test]
+fn test_visited_directories_distinct_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("first");
+    let second = temp_dir.path().join("second");
+    fs::create_dir(&first).unwrap();
+    fs::create_dir(&second).unwrap();
+
+    let visited = VisitedDirectories::new();
+    assert!(visited.first_visit(&first));
+    assert!(visited.first_visit(&second));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_visited_directories_detects_symlinked_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let real_project = temp_dir.path().join("external").join("project");
+    fs::create_dir_all(&real_project).unwrap();
+    let linked_project = temp_dir.path().join("linked-project");
+    std::os::unix::fs::symlink(&real_project, &linked_project).unwrap();
+
+    let visited = VisitedDirectories::new();
+    assert!(visited.first_visit(&real_project));
+    assert!(!visited.first_visit(&linked_project));
+}
+
+#[test]
+fn test_visited_directories_missing_path_is_always_unvisited() {
+    let visited = VisitedDirectories::new();
+    let missing = Path::new("/nonexistent/path/for/visited/test");
+
+    assert!(visited.first_visit(missing));
+    assert!(visited.first_visit(missing));
+}
+
+fn create_wide_tree(root: &Path, directories: usize, files_per_directory: usize) {
+    for directory in 0..directories {
+        let path = root.join(format!("package-{directory}")).join("lib");
+        fs::create_dir_all(&path).unwrap();
+        for file in 0..files_per_directory {
+            fs::write(path.join(format!("file-{file}.js")), "x".repeat(file + 1)).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_calculate_dir_size_parallel_matches_serial() {
+    let temp_dir = TempDir::new().unwrap();
+    create_wide_tree(temp_dir.path(), 20, 10);
+
+    let parallel = calculate_dir_size_full(temp_dir.path());
+    let serial = calculate_dir_size_with(temp_dir.path(), None, jwalk::Parallelism::Serial);
+
+    assert_eq!(parallel, serial);
+    assert_eq!(parallel.file_count, 200);
+    // Each directory holds files of 1..=10 bytes
+    assert_eq!(parallel.total_size, 20 * 55);
+}
+
+#[test]
+fn test_calculate_dir_size_concurrent_walks_share_pool() {
+    let temp_dir = TempDir::new().unwrap();
+    create_wide_tree(temp_dir.path(), 10, 10);
+    let expected = calculate_dir_size_with(temp_dir.path(), None, jwalk::Parallelism::Serial);
+
+    // More concurrent walks than pool threads, as the size pool workers do
+    let handles: Vec<_> = (0..config::scanner::SIZE_WALK_THREADS * 2)
+        .map(|_| {
+            let path = temp_dir.path().to_path_buf();
+            std::thread::spawn(move || calculate_dir_size_full(&path))
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
+
+/// Benchmark of the pooled walk against a serial one on a large generated tree
+/// Ignored because timings depend on the machine; run with `cargo test -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_calculate_dir_size_parallel_vs_serial() {
+    use std::time::Instant;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wide_tree(temp_dir.path(), 500, 100);
+
+    // Warm the filesystem cache and the pool so neither run pays for it
+    calculate_dir_size_full(temp_dir.path());
+
+    let started = Instant::now();
+    let serial = calculate_dir_size_with(temp_dir.path(), None, jwalk::Parallelism::Serial);
+    let serial_elapsed = started.elapsed();
+
+    let started = Instant::now();
+    let parallel = calculate_dir_size_full(temp_dir.path());
+    let parallel_elapsed = started.elapsed();
+
+    println!("Serial: {:?}", serial_elapsed);
+    println!("Parallel: {:?}", parallel_elapsed);
+    println!(
+        "Speedup: {:.2}x",
+        serial_elapsed.as_secs_f64() / parallel_elapsed.as_secs_f64()
+    );
+
+    assert_eq!(parallel, serial);
+}
+
+#[test]
+fn test_calculate_dir_size_cancellable_stops_when_cancelled() {
+    let temp_dir = TempDir::new().unwrap();
+    create_wide_tree(temp_dir.path(), 5, 5);
+    let token = CancellationToken::new();
+
+    assert_eq!(
+        calculate_dir_size_cancellable(temp_dir.path(), None, &token),
+        Some(calculate_dir_size_full(temp_dir.path()))
+    );
+
+    token.cancel();
+    assert_eq!(
+        calculate_dir_size_cancellable(temp_dir.path(), None, &token),
+        None
+    );
+}
+
+#[test]
+fn test_parse_mount_table_classifies_volumes() {
+    let content = "\
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+server:/export /home/user/nfs nfs4 rw,relatime 0 0
+//nas/share /srv/share\\040drive cifs rw 0 0
+/dev/sdb1 /media/user/USB vfat rw 0 0
+proc /proc proc rw 0 0
+";
+
+    assert_eq!(
+        parse_mount_table(content),
+        vec![
+            (PathBuf::from("/home/user/nfs"), VolumeKind::Network),
+            (PathBuf::from("/srv/share drive"), VolumeKind::Network),
+            (PathBuf::from("/media/user/USB"), VolumeKind::External),
+        ]
+    );
+}
+
+#[test]
+fn test_cloud_storage_roots_under_home() {
+    let roots = cloud_storage_roots(Some(Path::new("/Users/dev")));
+
+    assert!(roots.contains(&(
+        PathBuf::from("/Users/dev/Library/Mobile Documents"),
+        VolumeKind::CloudStorage
+    )));
+    assert!(roots.contains(&(
+        PathBuf::from("/Users/dev/Dropbox"),
+        VolumeKind::CloudStorage
+    )));
+    assert!(cloud_storage_roots(None).is_empty());
+}
+
+#[test]
+fn test_skipped_volumes_matches_roots_only() {
+    let skipped = SkippedVolumes {
+        roots: vec![(PathBuf::from("/mnt/nas"), VolumeKind::Network)],
+        skip_dataless: false,
+    };
+
+    assert_eq!(
+        skipped.kind_of(Path::new("/mnt/nas")),
+        Some(VolumeKind::Network)
+    );
+    assert_eq!(skipped.kind_of(Path::new("/mnt")), None);
+    assert_eq!(skipped.kind_of(Path::new("/home/user")), None);
+}
+
+#[test]
+fn test_skipped_volumes_keeps_volume_holding_scan_root() {
+    let home = dirs::home_dir().unwrap();
+    let dropbox = home.join("Dropbox");
+    let kinds = HashSet::from([VolumeKind::CloudStorage]);
+
+    let scanning_home = SkippedVolumes::new(&kinds, &home);
+    let scanning_dropbox = SkippedVolumes::new(&kinds, &dropbox.join("projects"));
+
+    assert_eq!(
+        scanning_home.kind_of(&dropbox),
+        Some(VolumeKind::CloudStorage)
+    );
+    assert_eq!(scanning_dropbox.kind_of(&dropbox), None);
+}
+
+#[test]
+fn test_skipped_volumes_respects_disabled_kinds() {
+    let home = dirs::home_dir().unwrap();
+
+    let skipped = SkippedVolumes::new(&HashSet::new(), &home);
+
+    assert_eq!(skipped.kind_of(&home.join("Dropbox")), None);
+}
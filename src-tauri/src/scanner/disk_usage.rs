@@ -0,0 +1,75 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Space on the volume holding the scan root, so dependency totals can be put
+/// against what's actually left on the disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    /// Space available to the user, which excludes blocks reserved for root
+    pub free_bytes: u64,
+}
+
+impl DiskUsage {
+    /// `bytes` as a percentage of the free space, e.g. 40 GB of dependencies with
+    /// 80 GB free is 50%. None when the disk reports no free space.
+    pub fn percent_of_free(&self, bytes: u64) -> Option<f64> {
+        (self.free_bytes > 0).then(|| bytes as f64 / self.free_bytes as f64 * 100.0)
+    }
+}
+
+/// Total and free space of the volume containing `path`, None when it can't be read
+#[cfg(unix)]
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // The path is NUL-terminated and `stats` is only read after statvfs fills it in
+    if unsafe { libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let stats = unsafe { stats.assume_init() };
+
+    let fragment_size = stats.f_frsize as u64;
+    Some(DiskUsage {
+        total_bytes: stats.f_blocks as u64 * fragment_size,
+        free_bytes: stats.f_bavail as u64 * fragment_size,
+    })
+}
+
+#[cfg(windows)]
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut free_bytes = 0u64;
+    let mut total_bytes = 0u64;
+    // The path is NUL-terminated and the out pointers are valid for the call
+    let succeeded = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free_bytes,
+            &mut total_bytes,
+            std::ptr::null_mut(),
+        )
+    };
+
+    (succeeded != 0).then_some(DiskUsage {
+        total_bytes,
+        free_bytes,
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn disk_usage(_path: &Path) -> Option<DiskUsage> {
+    None
+}
+
+#[cfg(test)]
+#[path = "disk_usage.test.rs"]
+mod tests;
@@ -0,0 +1,40 @@
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn test_disk_usage_reads_volume() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let usage = disk_usage(temp_dir.path()).unwrap();
+
+    assert!(usage.total_bytes > 0);
+    assert!(usage.free_bytes <= usage.total_bytes);
+}
+
+#[test]
+fn test_disk_usage_missing_path() {
+    let temp_dir = TempDir::new().unwrap();
+
+    assert_eq!(disk_usage(&temp_dir.path().join("missing")), None);
+}
+
+#[test]
+fn test_percent_of_free() {
+    let usage = DiskUsage {
+        total_bytes: 500,
+        free_bytes: 80,
+    };
+
+    assert_eq!(usage.percent_of_free(40), Some(50.0));
+    assert_eq!(usage.percent_of_free(160), Some(200.0));
+}
+
+#[test]
+fn test_percent_of_free_full_disk() {
+    let usage = DiskUsage {
+        total_bytes: 500,
+        free_bytes: 0,
+    };
+
+    assert_eq!(usage.percent_of_free(40), None);
+}
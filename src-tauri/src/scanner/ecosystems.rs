@@ -0,0 +1,129 @@
+use super::core::should_skip_directory;
+use super::types::{get_all_dependency_directory_names, DependencyCategory};
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Project files that show an ecosystem is in use. A leading `*` matches any file
+/// with that suffix, for project files named after the project. Full scans check
+/// the same files to tell which ecosystem an ambiguous directory like `deps` is from.
+const ECOSYSTEM_MARKERS: &[(&str, DependencyCategory)] = &[
+    ("package.json", DependencyCategory::NodeModules),
+    ("composer.json", DependencyCategory::Composer),
+    ("Gemfile", DependencyCategory::Bundler),
+    ("Podfile", DependencyCategory::Pods),
+    ("pyproject.toml", DependencyCategory::PythonVenv),
+    ("requirements.txt", DependencyCategory::PythonVenv),
+    ("Pipfile", DependencyCategory::PythonVenv),
+    ("environment.yml", DependencyCategory::CondaEnv),
+    ("environment.yaml", DependencyCategory::CondaEnv),
+    ("mix.exs", DependencyCategory::ElixirDeps),
+    ("pubspec.yaml", DependencyCategory::DartTool),
+    ("go.mod", DependencyCategory::GoMod),
+    ("*.csproj", DependencyCategory::DotNet),
+    ("*.fsproj", DependencyCategory::DotNet),
+    ("*.vbproj", DependencyCategory::DotNet),
+    ("*.sln", DependencyCategory::DotNet),
+    // Cargo writes its build output to `target`
+    ("Cargo.toml", DependencyCategory::BuildArtifacts),
+];
+
+/// Outcome of a quick marker sweep over the top of the scan root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EcosystemSweep {
+    pub detected: HashSet<DependencyCategory>,
+    pub directories_checked: usize,
+    /// False when the directory limit stopped the sweep before it covered the tree
+    pub complete: bool,
+}
+
+fn marker_category(file_name: &str) -> Option<DependencyCategory> {
+    ECOSYSTEM_MARKERS
+        .iter()
+        .find(|(pattern, _)| match pattern.strip_prefix('*') {
+            Some(suffix) => file_name.ends_with(suffix),
+            None => file_name == *pattern,
+        })
+        .map(|(_, category)| *category)
+}
+
+/// Whether `directory` directly holds a project file marking `category`
+pub(super) fn has_ecosystem_marker(directory: &Path, category: DependencyCategory) -> bool {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return false;
+    };
+    entries
+        .filter_map(Result::ok)
+        .any(|entry| entry.file_name().to_str().and_then(marker_category) == Some(category))
+}
+
+/// Looks for project markers in the shallow part of the tree so onboarding can
+/// enable only the ecosystems actually in use. Dependency directories are never
+/// entered, and the sweep stops early once every category has been seen.
+pub fn sweep_ecosystem_markers(
+    root: &Path,
+    max_depth: usize,
+    max_directories: usize,
+) -> EcosystemSweep {
+    let dependency_names = get_all_dependency_directory_names();
+    let category_count = DependencyCategory::all().len();
+    let mut detected = HashSet::new();
+    let mut directories_checked = 0;
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::from([(root.to_path_buf(), 0)]);
+
+    while let Some((directory, depth)) = queue.pop_front() {
+        if directories_checked >= max_directories {
+            debug!(directories_checked, "Ecosystem sweep hit directory limit");
+            return EcosystemSweep {
+                detected,
+                directories_checked,
+                complete: false,
+            };
+        }
+        directories_checked += 1;
+
+        let Ok(entries) = fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+
+            if file_type.is_file() {
+                detected.extend(marker_category(name));
+            } else if file_type.is_dir() {
+                // Only unambiguous names count, a bare `vendor` or `bin` says little
+                detected.extend(DependencyCategory::from_directory_name(name));
+
+                let descend = depth < max_depth
+                    && !should_skip_directory(name)
+                    && !dependency_names.contains(name)
+                    && !name.starts_with('.');
+                if descend {
+                    queue.push_back((entry.path(), depth + 1));
+                }
+            }
+        }
+
+        if detected.len() == category_count {
+            break;
+        }
+    }
+
+    EcosystemSweep {
+        detected,
+        directories_checked,
+        complete: true,
+    }
+}
+
+#[cfg(test)]
+#[path = "ecosystems.test.rs"]
+mod tests;
@@ -0,0 +1,114 @@
+use super::*;
+use tempfile::TempDir;
+
+fn create_file(root: &Path, relative: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+}
+
+#[test]
+fn test_marker_category() {
+    assert_eq!(
+        marker_category("package.json"),
+        Some(DependencyCategory::NodeModules)
+    );
+    assert_eq!(marker_category("go.mod"), Some(DependencyCategory::GoMod));
+    assert_eq!(
+        marker_category("Api.csproj"),
+        Some(DependencyCategory::DotNet)
+    );
+    assert_eq!(marker_category("App.sln"), Some(DependencyCategory::DotNet));
+    assert_eq!(
+        marker_category("Cargo.toml"),
+        Some(DependencyCategory::BuildArtifacts)
+    );
+    assert_eq!(marker_category("README.md"), None);
+}
+
+#[test]
+fn test_has_ecosystem_marker() {
+    let temp_dir = TempDir::new().unwrap();
+    create_file(temp_dir.path(), "app/mix.exs");
+    create_file(temp_dir.path(), "app/lib/mix.exs");
+
+    assert!(has_ecosystem_marker(
+        &temp_dir.path().join("app"),
+        DependencyCategory::ElixirDeps
+    ));
+    assert!(!has_ecosystem_marker(
+        &temp_dir.path().join("app"),
+        DependencyCategory::NodeModules
+    ));
+    assert!(!has_ecosystem_marker(
+        temp_dir.path(),
+        DependencyCategory::ElixirDeps
+    ));
+}
+
+#[test]
+fn test_sweep_finds_project_markers() {
+    let temp_dir = TempDir::new().unwrap();
+    create_file(temp_dir.path(), "code/web/package.json");
+    create_file(temp_dir.path(), "code/api/Api.csproj");
+    create_file(temp_dir.path(), "code/tools/go.mod");
+
+    let sweep = sweep_ecosystem_markers(temp_dir.path(), 4, 1000);
+
+    assert_eq!(
+        sweep.detected,
+        HashSet::from([
+            DependencyCategory::NodeModules,
+            DependencyCategory::DotNet,
+            DependencyCategory::GoMod,
+        ])
+    );
+    assert!(sweep.complete);
+}
+
+#[test]
+fn test_sweep_respects_max_depth() {
+    let temp_dir = TempDir::new().unwrap();
+    create_file(temp_dir.path(), "a/b/c/d/e/mix.exs");
+
+    let sweep = sweep_ecosystem_markers(temp_dir.path(), 2, 1000);
+
+    assert!(sweep.detected.is_empty());
+}
+
+#[test]
+fn test_sweep_does_not_enter_dependency_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    create_file(temp_dir.path(), "app/package.json");
+    create_file(temp_dir.path(), "app/node_modules/some-gem/Gemfile");
+
+    let sweep = sweep_ecosystem_markers(temp_dir.path(), 4, 1000);
+
+    assert_eq!(
+        sweep.detected,
+        HashSet::from([DependencyCategory::NodeModules])
+    );
+}
+
+#[test]
+fn test_sweep_counts_existing_dependency_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("app").join(".venv")).unwrap();
+
+    let sweep = sweep_ecosystem_markers(temp_dir.path(), 4, 1000);
+
+    assert!(sweep.detected.contains(&DependencyCategory::PythonVenv));
+}
+
+#[test]
+fn test_sweep_reports_incomplete() {
+    let temp_dir = TempDir::new().unwrap();
+    for index in 0..5 {
+        fs::create_dir_all(temp_dir.path().join(format!("project-{index}"))).unwrap();
+    }
+
+    let sweep = sweep_ecosystem_markers(temp_dir.path(), 4, 3);
+
+    assert_eq!(sweep.directories_checked, 3);
+    assert!(!sweep.complete);
+}
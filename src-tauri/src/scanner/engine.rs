@@ -0,0 +1,778 @@
+//! The discovery and sizing pipeline behind every scan. Interactive and background scans
+//! differ only in the `ScanSink` their progress goes to and the throttle in their config.
+
+use super::size_index::{
+    directory_fingerprint, lookup_size, record_size, save_size_index, IndexedSize,
+};
+use crate::config;
+use crate::scanner::{
+    calculate_dir_size_cancellable, disk_usage, filter_nested_candidates,
+    find_package_manager_caches, is_inside_dependency_directory, match_suggestion,
+    resolve_project_info, should_exclude_path, should_include_path, should_skip_directory,
+    AgeStrategy, DependencyCategory, DirectoryEntry, DiscoveredDirectory, Safety, ScanCoverage,
+    ScanResult, ScanStats, ScanThrottle, SizeCalculatorPool, SkipReason, SkippedEntry,
+    SkippedVolumes, SuggestedCategory, SuggestionRule, VisitedDirectories,
+};
+use crate::telemetry;
+use crate::time;
+use crossbeam_channel::{bounded, RecvTimeoutError};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Where a scan reports progress. Every method defaults to doing nothing.
+pub trait ScanSink {
+    /// Throttled progress while the walk runs
+    fn stats(&self, _stats: &ScanStats) {}
+    /// A dependency directory was found and is about to be sized
+    fn discovered(&self, _discovered: &DiscoveredDirectory) {}
+    /// A dependency directory was sized
+    fn entry(&self, _entry: &DirectoryEntry) {}
+}
+
+/// Discards progress, for scans nobody is watching
+pub struct SilentSink;
+
+impl ScanSink for SilentSink {}
+
+fn determine_category(
+    directory_name: &str,
+    path: &Path,
+    enabled_categories: &std::collections::HashSet<DependencyCategory>,
+) -> Option<DependencyCategory> {
+    match DependencyCategory::from_directory_name(directory_name) {
+        Some(matched_category) => Some(matched_category),
+        None if directory_name == "vendor" => {
+            let vendor_category = DependencyCategory::from_vendor_directory(path)?;
+            if enabled_categories.contains(&vendor_category) {
+                Some(vendor_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "deps" => {
+            let deps_category = DependencyCategory::from_deps_directory(path)?;
+            if enabled_categories.contains(&deps_category) {
+                Some(deps_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "pkg" => {
+            let pkg_category = DependencyCategory::from_pkg_directory(path)?;
+            if enabled_categories.contains(&pkg_category) {
+                Some(pkg_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "bin" || directory_name == "obj" => {
+            let build_category = DependencyCategory::from_dotnet_build_directory(path)?;
+            if enabled_categories.contains(&build_category) {
+                Some(build_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "packages" => {
+            let packages_category = DependencyCategory::from_nuget_packages_directory(path)?;
+            if enabled_categories.contains(&packages_category) {
+                Some(packages_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "dist" => {
+            let dist_category = DependencyCategory::from_dist_directory(path)?;
+            if enabled_categories.contains(&dist_category) {
+                Some(dist_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "cache" => {
+            let cache_category = DependencyCategory::from_angular_cache_directory(path)?;
+            if enabled_categories.contains(&cache_category) {
+                Some(cache_category)
+            } else {
+                None
+            }
+        }
+        None if directory_name == "target" => {
+            let target_category = DependencyCategory::from_cargo_target_directory(path)?;
+            if enabled_categories.contains(&target_category) {
+                Some(target_category)
+            } else {
+                None
+            }
+        }
+        None => None,
+    }
+}
+
+/// Passes progress to the sink at most once per `EMIT_THROTTLE`
+pub fn report_scan_stats(
+    sink: &dyn ScanSink,
+    last_emit_time: &mut Instant,
+    running_total_size: u64,
+    entry_count: usize,
+    current_path: &str,
+) {
+    if last_emit_time.elapsed() >= config::scanner::EMIT_THROTTLE {
+        sink.stats(&ScanStats {
+            total_size: running_total_size,
+            directory_count: entry_count,
+            current_path: Some(current_path.to_string()),
+        });
+        *last_emit_time = Instant::now();
+    }
+}
+
+/// Everything a scan needs from settings, resolved once up front by `build_scan_config`
+/// in the scan command
+pub struct ScanConfig {
+    pub root_directory: String,
+    pub enabled_categories: std::collections::HashSet<DependencyCategory>,
+    pub target_dir_names: std::collections::HashSet<&'static str>,
+    pub all_dependency_dirs: std::collections::HashSet<&'static str>,
+    pub exclude_patterns: Vec<String>,
+    pub include_patterns: Vec<String>,
+    pub ignored_paths: std::collections::HashSet<String>,
+    pub follow_symlinks: bool,
+    pub scan_package_manager_caches: bool,
+    pub max_file_count: Option<usize>,
+    pub incremental_sizing: bool,
+    pub min_size_bytes: u64,
+    /// How long the scan may run before finishing early with a partial result
+    pub deadline: Option<Duration>,
+    pub skipped_volumes: SkippedVolumes,
+    pub throttle: ScanThrottle,
+}
+
+struct DiscoveryProgress {
+    discovered: Vec<DiscoveredDirectory>,
+    total_skipped: usize,
+    skipped_entries: Vec<SkippedEntry>,
+    coverage: ScanCoverage,
+    last_emit_time: Instant,
+}
+
+impl DiscoveryProgress {
+    fn new() -> Self {
+        Self {
+            discovered: Vec::new(),
+            total_skipped: 0,
+            skipped_entries: Vec::new(),
+            coverage: ScanCoverage::default(),
+            last_emit_time: Instant::now()
+                .checked_sub(Duration::from_millis(100))
+                .unwrap_or_else(Instant::now),
+        }
+    }
+}
+
+fn skipped_entry(walk_error: &jwalk::Error) -> Option<SkippedEntry> {
+    let path = walk_error.path()?.to_string_lossy().to_string();
+    let kind = if walk_error.loop_ancestor().is_some() {
+        SkipReason::Loop
+    } else {
+        walk_error
+            .io_error()
+            .map_or(SkipReason::Other, SkipReason::from_io_error)
+    };
+    Some(SkippedEntry { path, kind })
+}
+
+fn discover_dependency_directory(
+    directory_entry: &jwalk::DirEntry<((), ())>,
+    config: &ScanConfig,
+    progress: &mut DiscoveryProgress,
+    sink: &dyn ScanSink,
+) -> Option<DiscoveredDirectory> {
+    if !directory_entry.file_type().is_dir() {
+        return None;
+    }
+    progress.coverage.directories_visited += 1;
+
+    let path = directory_entry.path();
+    let path_string = path.to_string_lossy().to_string();
+
+    report_scan_stats(
+        sink,
+        &mut progress.last_emit_time,
+        0,
+        progress.discovered.len(),
+        &path_string,
+    );
+
+    let directory_name = directory_entry.file_name().to_str().unwrap_or("");
+
+    if !config.target_dir_names.contains(directory_name) {
+        return None;
+    }
+
+    let category = determine_category(directory_name, &path, &config.enabled_categories)?;
+
+    if is_inside_dependency_directory(&path_string, directory_name, &config.all_dependency_dirs) {
+        return None;
+    }
+
+    if config.ignored_paths.contains(&path_string) {
+        debug!(path = %path_string, "Skipping ignored directory");
+        progress.coverage.skipped_by_ignore_list += 1;
+        return None;
+    }
+
+    if should_exclude_path(&path_string, &config.exclude_patterns) {
+        debug!(path = %path_string, "Skipping excluded path");
+        progress.coverage.skipped_by_excludes += 1;
+        return None;
+    }
+
+    if !should_include_path(&path_string, &config.include_patterns) {
+        debug!(path = %path_string, "Skipping path outside include patterns");
+        progress.coverage.skipped_by_includes += 1;
+        return None;
+    }
+
+    debug!(path = %path_string, category = ?category, "Discovered dependency directory");
+
+    Some(DiscoveredDirectory {
+        path: path_string,
+        category,
+    })
+}
+
+/// Adds environments recognized by their contents during the walk, applying the
+/// same nesting, ignore, exclude and include checks as directories matched by name
+fn add_discovered_environments(
+    environments: Vec<DiscoveredDirectory>,
+    config: &ScanConfig,
+    progress: &mut DiscoveryProgress,
+    sink: &dyn ScanSink,
+) {
+    for environment in environments {
+        let directory_name = Path::new(&environment.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+
+        if is_inside_dependency_directory(
+            &environment.path,
+            directory_name,
+            &config.all_dependency_dirs,
+        ) {
+            continue;
+        }
+
+        if config.ignored_paths.contains(&environment.path) {
+            debug!(path = %environment.path, "Skipping ignored directory");
+            progress.coverage.skipped_by_ignore_list += 1;
+            continue;
+        }
+
+        if should_exclude_path(&environment.path, &config.exclude_patterns) {
+            debug!(path = %environment.path, "Skipping excluded path");
+            progress.coverage.skipped_by_excludes += 1;
+            continue;
+        }
+
+        if !should_include_path(&environment.path, &config.include_patterns) {
+            debug!(path = %environment.path, "Skipping path outside include patterns");
+            progress.coverage.skipped_by_includes += 1;
+            continue;
+        }
+
+        debug!(
+            path = %environment.path,
+            category = ?environment.category,
+            "Discovered environment directory"
+        );
+        sink.discovered(&environment);
+        progress.discovered.push(environment);
+    }
+}
+
+/// Runs discovery and sizing for a scan, reporting progress to `sink` as it goes.
+/// Returns None once `cancel` is cancelled. Past the config's deadline it stops
+/// discovering and sizing and returns what it has, marked `partial`. The walk runs on
+/// its own thread so a directory read that hangs can't hold up either check.
+pub fn run_scan(
+    config: &ScanConfig,
+    sink: &dyn ScanSink,
+    cancel: &CancellationToken,
+) -> Option<ScanResult> {
+    let start = Instant::now();
+    let mut progress = DiscoveryProgress::new();
+    let past_deadline = || {
+        config
+            .deadline
+            .is_some_and(|deadline| start.elapsed() >= deadline)
+    };
+    let mut partial = false;
+
+    let num_threads = config.throttle.threads();
+    debug!(
+        cpus = num_cpus::get(),
+        threads = num_threads,
+        priority = ?config.throttle.priority(),
+        "Starting discovery phase"
+    );
+
+    let visited_directories = config
+        .follow_symlinks
+        .then(|| Arc::new(VisitedDirectories::new()));
+    let suggestion_candidates: Arc<Mutex<Vec<(String, &'static SuggestionRule)>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    let walk_suggestion_candidates = Arc::clone(&suggestion_candidates);
+    let environments: Arc<Mutex<Vec<DiscoveredDirectory>>> = Arc::new(Mutex::new(Vec::new()));
+    let walk_environments = Arc::clone(&environments);
+    let walk_target_dir_names = config.target_dir_names.clone();
+    let walk_enabled_categories = config.enabled_categories.clone();
+    // Children are filtered on jwalk's worker threads, so skips are counted atomically
+    let skipped_by_skip_list = Arc::new(AtomicUsize::new(0));
+    let walk_skipped_by_skip_list = Arc::clone(&skipped_by_skip_list);
+    let skipped_by_volume = Arc::new(AtomicUsize::new(0));
+    let walk_skipped_by_volume = Arc::clone(&skipped_by_volume);
+    let walk_skipped_volumes = config.skipped_volumes.clone();
+
+    let walk = jwalk::WalkDir::new(&config.root_directory)
+        .max_depth(config::scanner::MAX_SCAN_DEPTH)
+        .skip_hidden(false)
+        .follow_links(config.follow_symlinks)
+        .parallelism(config.throttle.discovery_parallelism())
+        .process_read_dir(move |depth, directory_path, _, children| {
+            // Python environments can have any name, so they are recognized from their
+            // contents and their children dropped since the directory is sized as a whole
+            let named_target = directory_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| walk_target_dir_names.contains(name));
+            if depth.is_some_and(|depth| depth > 0) && !named_target {
+                let environment_category = DependencyCategory::from_environment_entries(
+                    children
+                        .iter()
+                        .filter_map(|child| child.as_ref().ok())
+                        .filter_map(|child| child.file_name().to_str()),
+                )
+                .filter(|category| walk_enabled_categories.contains(category));
+
+                if let Some(category) = environment_category {
+                    walk_environments.lock().unwrap().push(DiscoveredDirectory {
+                        path: directory_path.to_string_lossy().to_string(),
+                        category,
+                    });
+                    children.clear();
+                    return;
+                }
+            }
+
+            children.retain(|directory_entry_result| {
+                if let Ok(ref directory_entry) = directory_entry_result {
+                    let name = directory_entry.file_name();
+                    if let Some(name_string) = name.to_str() {
+                        // Checked before skipping so caches like `.cache/yarn` can still be suggested
+                        if directory_entry.file_type().is_dir() {
+                            let path = directory_entry.path();
+                            if let Some(rule) = match_suggestion(&path, name_string) {
+                                walk_suggestion_candidates
+                                    .lock()
+                                    .unwrap()
+                                    .push((path.to_string_lossy().to_string(), rule));
+                            }
+                        }
+
+                        if should_skip_directory(name_string) {
+                            if directory_entry.file_type().is_dir() {
+                                walk_skipped_by_skip_list.fetch_add(1, Ordering::Relaxed);
+                            }
+                            return false;
+                        }
+                    }
+
+                    if directory_entry.file_type().is_dir()
+                        && walk_skipped_volumes
+                            .kind_of(&directory_entry.path())
+                            .is_some()
+                    {
+                        walk_skipped_by_volume.fetch_add(1, Ordering::Relaxed);
+                        return false;
+                    }
+
+                    if let Some(ref visited) = visited_directories {
+                        if directory_entry.file_type().is_dir()
+                            && !visited.first_visit(&directory_entry.path())
+                        {
+                            return false;
+                        }
+                    }
+
+                    true
+                } else {
+                    true
+                }
+            });
+        });
+
+    // Once the receiver is dropped the walk stops at the next entry it yields
+    let (entry_sender, entry_receiver) = bounded(256);
+    let spawned = thread::Builder::new()
+        .name("scan-discovery".to_string())
+        .spawn(move || {
+            telemetry::crash::name_thread_task("discovery");
+            for entry in walk {
+                if entry_sender.send(entry).is_err() {
+                    break;
+                }
+            }
+        });
+    if let Err(error) = spawned {
+        error!(%error, "Failed to spawn discovery thread");
+        return None;
+    }
+
+    loop {
+        if cancel.is_cancelled() {
+            debug!(
+                discovered = progress.discovered.len(),
+                "Discovery cancelled"
+            );
+            return None;
+        }
+        if past_deadline() {
+            warn!(
+                discovered = progress.discovered.len(),
+                "Scan deadline reached during discovery, keeping what was found"
+            );
+            partial = true;
+            break;
+        }
+
+        let entry = match entry_receiver.recv_timeout(config::scanner::RESULT_POLL_INTERVAL) {
+            Ok(entry) => entry,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        match entry {
+            Ok(directory_entry) => {
+                if let Some(discovered) =
+                    discover_dependency_directory(&directory_entry, config, &mut progress, sink)
+                {
+                    sink.discovered(&discovered);
+                    progress.discovered.push(discovered);
+                }
+            }
+            Err(walk_error) => {
+                progress.total_skipped += 1;
+                if progress.skipped_entries.len() < config::scanner::MAX_SKIPPED_ENTRIES {
+                    if let Some(skipped) = skipped_entry(&walk_error) {
+                        progress.skipped_entries.push(skipped);
+                    }
+                }
+            }
+        }
+    }
+
+    let environments = std::mem::take(&mut *environments.lock().unwrap());
+    add_discovered_environments(environments, config, &mut progress, sink);
+    progress.coverage.skipped_by_skip_list = skipped_by_skip_list.load(Ordering::Relaxed);
+    progress.coverage.skipped_by_volume = skipped_by_volume.load(Ordering::Relaxed);
+    progress.coverage.errored = progress.total_skipped;
+
+    let discovery_time = start.elapsed().as_millis();
+    let discovered_count = progress.discovered.len();
+
+    info!(
+        discovered = discovered_count,
+        skipped = progress.total_skipped,
+        discovery_time_ms = discovery_time,
+        coverage = ?progress.coverage,
+        "Discovery phase complete, starting size calculations"
+    );
+
+    if cancel.is_cancelled() {
+        debug!("Scan cancelled after discovery");
+        return None;
+    }
+
+    let mut pool = match SizeCalculatorPool::with_cancellation(
+        num_threads,
+        config.max_file_count,
+        config.throttle.priority(),
+        cancel.clone(),
+    ) {
+        Ok(pool) => pool,
+        Err(error) => {
+            error!(%error, "Failed to create size calculator pool");
+            return None;
+        }
+    };
+
+    let mut all_entries: Vec<DirectoryEntry> = Vec::with_capacity(discovered_count);
+    let mut running_total_size: u64 = 0;
+    // Taken before sizing and recorded in the size index alongside each result
+    let mut fingerprints: HashMap<String, u64> = HashMap::new();
+    let mut submitted: usize = 0;
+    let mut sizes_reused: usize = 0;
+
+    for discovered in &progress.discovered {
+        if cancel.is_cancelled() {
+            break;
+        }
+        if past_deadline() {
+            partial = true;
+            break;
+        }
+
+        let fingerprint = config
+            .incremental_sizing
+            .then(|| directory_fingerprint(Path::new(&discovered.path)))
+            .flatten();
+        if let Some(fingerprint) = fingerprint {
+            let indexed = lookup_size(
+                &discovered.path,
+                fingerprint,
+                config.max_file_count,
+                time::now_ms(),
+            );
+            if let Some(indexed) = indexed {
+                let entry = indexed_entry(discovered, indexed);
+                debug!(path = %entry.path, size_bytes = entry.size_bytes, "Reusing indexed size");
+
+                sink.entry(&entry);
+                running_total_size += entry.size_bytes;
+                all_entries.push(entry);
+                sizes_reused += 1;
+                continue;
+            }
+            fingerprints.insert(discovered.path.clone(), fingerprint);
+        }
+
+        if pool.submit(discovered.path.clone(), discovered.category) {
+            submitted += 1;
+        }
+    }
+    progress.coverage.sizes_reused = sizes_reused;
+
+    let results_receiver = pool.results();
+    let mut results_collected: usize = 0;
+    let mut timeouts: usize = 0;
+    let mut last_result_at = Instant::now();
+
+    while results_collected < submitted {
+        if cancel.is_cancelled() {
+            debug!(collected = all_entries.len(), "Size calculation cancelled");
+            pool.shutdown();
+            return None;
+        }
+        if past_deadline() {
+            warn!(
+                collected = all_entries.len(),
+                expected = submitted,
+                "Scan deadline reached during sizing, keeping what was sized"
+            );
+            partial = true;
+            break;
+        }
+
+        // Polled in short slices so cancellation is noticed even when the workers
+        // give up without sending anything
+        match results_receiver.recv_timeout(config::scanner::RESULT_POLL_INTERVAL) {
+            Ok(result) => {
+                results_collected += 1;
+                timeouts = 0;
+                last_result_at = Instant::now();
+
+                let entry = DirectoryEntry {
+                    path: result.path.clone(),
+                    size_bytes: result.total_size,
+                    file_count: result.file_count,
+                    last_modified_ms: result.last_modified_ms,
+                    category: result.category,
+                    has_only_symlinks: result.has_only_symlinks,
+                    last_commit_ms: result.last_commit_ms,
+                    git_branch: result.git_branch,
+                    project_name: result.project_name,
+                    workspace_root: result.workspace_root,
+                    truncated: result.truncated,
+                    scanned_at_ms: time::now_ms(),
+                    stale: false,
+                    age_source: AgeStrategy::NewestFile,
+                    safety: Safety::Caution,
+                };
+
+                debug!(
+                    path = %result.path,
+                    size_bytes = result.total_size,
+                    size_mb = result.total_size as f64 / 1024.0 / 1024.0,
+                    file_count = result.file_count,
+                    truncated = result.truncated,
+                    "Size calculation complete"
+                );
+
+                if let Some(fingerprint) = fingerprints.get(&entry.path) {
+                    record_size(&entry, *fingerprint, entry.scanned_at_ms);
+                }
+
+                sink.entry(&entry);
+                running_total_size += entry.size_bytes;
+                all_entries.push(entry);
+            }
+            Err(RecvTimeoutError::Timeout)
+                if last_result_at.elapsed() < config::scanner::RESULT_TIMEOUT => {}
+            Err(_) => {
+                timeouts += 1;
+                last_result_at = Instant::now();
+                warn!(
+                    timeouts = timeouts,
+                    collected = results_collected,
+                    expected = submitted,
+                    "Timeout waiting for size calculation result"
+                );
+                if timeouts >= config::scanner::MAX_TIMEOUT_RETRIES {
+                    warn!("Too many timeouts, stopping size collection");
+                    break;
+                }
+            }
+        }
+    }
+
+    pool.shutdown();
+    drop(pool);
+
+    if config.incremental_sizing {
+        save_size_index(time::now_ms());
+    }
+
+    let scan_time_ms = start.elapsed().as_millis();
+
+    info!(
+        entries = all_entries.len(),
+        sizes_reused,
+        total_size_gb = running_total_size as f64 / 1024.0 / 1024.0 / 1024.0,
+        duration_ms = scan_time_ms,
+        discovery_ms = discovery_time,
+        sizing_ms = scan_time_ms - discovery_time,
+        skipped = progress.total_skipped,
+        "Scan complete"
+    );
+
+    let before_min_size = all_entries.len();
+    all_entries.retain(|entry| entry.size_bytes >= config.min_size_bytes);
+    progress.coverage.skipped_below_min_size = before_min_size - all_entries.len();
+
+    let sort_start = Instant::now();
+    all_entries.sort_by(|first, second| second.size_bytes.cmp(&first.size_bytes));
+    debug!(
+        duration_ms = sort_start.elapsed().as_millis(),
+        "Sort completed"
+    );
+
+    // A scan that ran out of time skips the extra sizing passes, which could hang just the same
+    let candidates = std::mem::take(&mut *suggestion_candidates.lock().unwrap());
+    let suggested_categories = if partial {
+        Vec::new()
+    } else {
+        size_suggested_categories(candidates, config, cancel)
+    };
+
+    let now_ms = time::now_ms();
+    let mut result =
+        ScanResult::from_entries(all_entries, scan_time_ms, progress.total_skipped, now_ms);
+    result.score_safety(now_ms, &|| cancel.is_cancelled() || past_deadline());
+    result.suggested_categories = suggested_categories;
+    result.coverage = progress.coverage;
+    result.skipped_entries = progress.skipped_entries;
+    result.disk_usage = disk_usage(Path::new(&config.root_directory));
+    result.partial = partial;
+
+    if config.scan_package_manager_caches && !partial && !cancel.is_cancelled() {
+        if let Some(home) = dirs::home_dir() {
+            result.cache_entries = find_package_manager_caches(&home, config.max_file_count);
+        }
+    }
+
+    Some(result)
+}
+
+/// Builds an entry from an indexed size. Git details aren't indexed, since a commit
+/// doesn't touch the dependency directory, so they are resolved fresh.
+fn indexed_entry(discovered: &DiscoveredDirectory, indexed: IndexedSize) -> DirectoryEntry {
+    let project_info = resolve_project_info(Path::new(&discovered.path));
+
+    DirectoryEntry {
+        path: discovered.path.clone(),
+        size_bytes: indexed.size_bytes,
+        file_count: indexed.file_count,
+        last_modified_ms: indexed.last_modified_ms,
+        category: discovered.category,
+        has_only_symlinks: indexed.has_only_symlinks,
+        last_commit_ms: project_info.last_commit_ms,
+        git_branch: project_info.git_branch,
+        project_name: project_info.project_name,
+        workspace_root: project_info.workspace_root,
+        truncated: false,
+        scanned_at_ms: time::now_ms(),
+        stale: false,
+        age_source: AgeStrategy::NewestFile,
+        safety: Safety::Caution,
+    }
+}
+
+/// Sizes the uncategorized directories found during the walk and keeps the large ones,
+/// so the UI can offer them as new categories
+fn size_suggested_categories(
+    candidates: Vec<(String, &'static SuggestionRule)>,
+    config: &ScanConfig,
+    cancel: &CancellationToken,
+) -> Vec<SuggestedCategory> {
+    let candidates: Vec<_> = candidates
+        .into_iter()
+        .filter(|(path, _)| !should_exclude_path(path, &config.exclude_patterns))
+        .filter(|(path, _)| should_include_path(path, &config.include_patterns))
+        // Already scanned as a category, e.g. `.next` when build output is enabled
+        .filter(|(_, rule)| !config.target_dir_names.contains(rule.directory_name))
+        .collect();
+    let candidates = filter_nested_candidates(candidates, &config.all_dependency_dirs);
+
+    let mut suggestions = Vec::new();
+    for (path, rule) in candidates
+        .into_iter()
+        .take(config::scanner::MAX_SUGGESTION_CANDIDATES)
+    {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        let Some(size_result) =
+            calculate_dir_size_cancellable(Path::new(&path), config.max_file_count, cancel)
+        else {
+            break;
+        };
+        if size_result.total_size < config::scanner::SUGGESTION_MIN_SIZE_BYTES {
+            continue;
+        }
+
+        debug!(%path, label = rule.label, size_bytes = size_result.total_size, "Suggesting category");
+        suggestions.push(SuggestedCategory {
+            path,
+            directory_name: rule.directory_name.to_string(),
+            label: rule.label.to_string(),
+            marker: rule.marker.name().to_string(),
+            size_bytes: size_result.total_size,
+        });
+    }
+
+    suggestions.sort_by(|first, second| second.size_bytes.cmp(&first.size_bytes));
+    suggestions
+}
+
+#[cfg(test)]
+#[path = "engine.test.rs"]
+mod tests;
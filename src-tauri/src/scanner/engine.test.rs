@@ -0,0 +1,128 @@
+use super::*;
+use crate::commands::scan::build_scan_config;
+use crate::commands::settings::AppSettings;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_directory_walk_reports_coverage() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("app").join("node_modules").join("react")).unwrap();
+    fs::write(root.join("app/node_modules/react/index.js"), "x").unwrap();
+    fs::create_dir_all(root.join("app").join(".git").join("objects")).unwrap();
+    fs::create_dir_all(root.join("archive").join("node_modules")).unwrap();
+    fs::create_dir_all(root.join("other").join("node_modules")).unwrap();
+
+    let settings = AppSettings {
+        exclude_paths: "archive".to_string(),
+        include_paths: format!("{}/app, {}/archive", root.display(), root.display()),
+        ..AppSettings::default()
+    };
+    let mut config = build_scan_config(&settings, ScanThrottle::unthrottled());
+    config.root_directory = root.to_string_lossy().to_string();
+    config.ignored_paths = Default::default();
+    config.incremental_sizing = false;
+
+    let result = run_scan(&config, &SilentSink, &CancellationToken::new()).unwrap();
+
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.coverage.skipped_by_skip_list, 1);
+    assert_eq!(result.coverage.skipped_by_excludes, 1);
+    assert_eq!(result.coverage.skipped_by_includes, 1);
+    assert_eq!(result.coverage.errored, 0);
+    assert!(result.coverage.directories_visited >= 7);
+}
+
+#[test]
+fn test_directory_walk_leaves_out_ignored_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("app").join("node_modules")).unwrap();
+    fs::create_dir_all(root.join("legacy").join("node_modules")).unwrap();
+
+    let mut config = build_scan_config(&AppSettings::default(), ScanThrottle::unthrottled());
+    config.root_directory = root.to_string_lossy().to_string();
+    config.ignored_paths = [root
+        .join("legacy")
+        .join("node_modules")
+        .to_string_lossy()
+        .to_string()]
+    .into();
+    config.incremental_sizing = false;
+
+    let result = run_scan(&config, &SilentSink, &CancellationToken::new()).unwrap();
+
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(
+        result.entries[0].path,
+        root.join("app").join("node_modules").to_string_lossy()
+    );
+    assert_eq!(result.coverage.skipped_by_ignore_list, 1);
+}
+
+#[test]
+fn test_directory_walk_leaves_out_entries_below_min_size() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("large").join("node_modules")).unwrap();
+    fs::create_dir_all(root.join("small").join("node_modules")).unwrap();
+    fs::write(root.join("large/node_modules/index.js"), "x".repeat(2_000)).unwrap();
+    fs::write(root.join("small/node_modules/index.js"), "x").unwrap();
+
+    let settings = AppSettings {
+        min_size_bytes: 1_000,
+        ..AppSettings::default()
+    };
+    let mut config = build_scan_config(&settings, ScanThrottle::unthrottled());
+    config.root_directory = root.to_string_lossy().to_string();
+    config.ignored_paths = Default::default();
+    config.incremental_sizing = false;
+
+    let result = run_scan(&config, &SilentSink, &CancellationToken::new()).unwrap();
+
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(
+        result.entries[0].path,
+        root.join("large").join("node_modules").to_string_lossy()
+    );
+    assert_eq!(result.total_size, 2_000);
+    assert_eq!(result.coverage.skipped_below_min_size, 1);
+}
+
+#[test]
+fn test_scan_past_deadline_returns_partial_result() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("app").join("node_modules")).unwrap();
+    fs::write(root.join("app/node_modules/index.js"), "x".repeat(2_000)).unwrap();
+
+    let mut config = build_scan_config(&AppSettings::default(), ScanThrottle::unthrottled());
+    config.root_directory = root.to_string_lossy().to_string();
+    config.ignored_paths = Default::default();
+    config.incremental_sizing = false;
+    config.deadline = Some(Duration::ZERO);
+
+    let result = run_scan(&config, &SilentSink, &CancellationToken::new()).unwrap();
+
+    assert!(result.partial);
+    assert!(result.entries.is_empty());
+}
+
+#[test]
+fn test_scan_within_deadline_is_complete() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("app").join("node_modules")).unwrap();
+
+    let mut config = build_scan_config(&AppSettings::default(), ScanThrottle::unthrottled());
+    config.root_directory = root.to_string_lossy().to_string();
+    config.ignored_paths = Default::default();
+    config.incremental_sizing = false;
+    config.min_size_bytes = 0;
+
+    let result = run_scan(&config, &SilentSink, &CancellationToken::new()).unwrap();
+
+    assert!(!result.partial);
+    assert_eq!(result.entries.len(), 1);
+}
@@ -1,12 +1,37 @@
+mod age;
 mod background;
+#[cfg(target_os = "macos")]
+mod bulk_attributes;
+mod caches;
 mod core;
+mod disk_usage;
+mod ecosystems;
+mod engine;
+mod patterns;
+mod project_info;
+mod safety;
+pub mod size_index;
 pub mod size_pool;
+mod suggestions;
+mod throttle;
 mod types;
 
-pub use background::calculate_total_dependency_size;
+pub use age::{compute_age_ms, AgeStrategy};
+pub use background::{run_background_scan, DependencySizes};
+pub use caches::{find_package_manager_caches, CacheEntry, PackageManagerCache};
 pub use core::{
-    calculate_dir_size_full, expand_tilde, is_inside_dependency_directory, parse_exclude_patterns,
-    should_exclude_path, should_skip_directory,
+    calculate_dir_size_cancellable, calculate_dir_size_full, calculate_dir_size_limited,
+    expand_tilde, is_inside_dependency_directory, parse_exclude_patterns, parse_include_patterns,
+    set_bulk_attributes_enabled, should_exclude_path, should_include_path, should_skip_directory,
+    SkippedVolumes, VisitedDirectories, VolumeKind,
 };
+pub use disk_usage::{disk_usage, DiskUsage};
+pub use ecosystems::{sweep_ecosystem_markers, EcosystemSweep};
+pub use engine::{report_scan_stats, run_scan, ScanConfig, ScanSink, SilentSink};
+pub use patterns::migrate_legacy_patterns;
+pub use project_info::resolve_project_info;
+pub use safety::{score_entry, Safety};
 pub use size_pool::SizeCalculatorPool;
+pub use suggestions::{filter_nested_candidates, match_suggestion, SuggestionRule};
+pub use throttle::{ScanPriority, ScanThrottle};
 pub use types::*;
@@ -0,0 +1,245 @@
+use super::core::expand_tilde;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    /// `/` in a pattern matches either path separator
+    Separator,
+    /// `?`, any single character within a path segment
+    AnyChar,
+    /// `*`, any run of characters within a path segment
+    Star,
+    /// `**`, any run of characters across path segments
+    GlobStar,
+    /// `**/`, zero or more whole path segments
+    GlobStarSeparator,
+    /// `[abc]`, `[a-z]` or `[!abc]`, one character within a path segment
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+fn is_separator(character: char) -> bool {
+    character == '/' || character == '\\'
+}
+
+/// Absolute patterns are anchored at the start of the path; anything else can match at any depth
+fn is_absolute_pattern(pattern: &str) -> bool {
+    let mut characters = pattern.chars();
+    match characters.next() {
+        Some(first) if is_separator(first) => true,
+        Some(drive) if drive.is_ascii_alphabetic() => characters.next() == Some(':'),
+        _ => false,
+    }
+}
+
+/// Parses a `[...]` class starting just after the opening bracket, returning the token
+/// and the index after the closing bracket. None when the class is never closed.
+fn parse_class(characters: &[char], start: usize) -> Option<(Token, usize)> {
+    let mut index = start;
+    let negated = matches!(characters.get(index), Some('!' | '^'));
+    if negated {
+        index += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let class_start = index;
+    while index < characters.len() {
+        let character = characters[index];
+        // A `]` right after the opening bracket is a literal member
+        if character == ']' && index > class_start {
+            return Some((Token::Class { negated, ranges }, index + 1));
+        }
+
+        if characters.get(index + 1) == Some(&'-')
+            && characters.get(index + 2).is_some_and(|end| *end != ']')
+        {
+            ranges.push((character, characters[index + 2]));
+            index += 3;
+        } else {
+            ranges.push((character, character));
+            index += 1;
+        }
+    }
+
+    None
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let characters: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let character = characters[index];
+        match character {
+            '*' if characters.get(index + 1) == Some(&'*') => {
+                index += 2;
+                while characters.get(index) == Some(&'*') {
+                    index += 1;
+                }
+                if characters.get(index).copied().is_some_and(is_separator) {
+                    tokens.push(Token::GlobStarSeparator);
+                    index += 1;
+                } else {
+                    tokens.push(Token::GlobStar);
+                }
+                continue;
+            }
+            '*' => tokens.push(Token::Star),
+            '?' => tokens.push(Token::AnyChar),
+            '[' => {
+                if let Some((class, next_index)) = parse_class(&characters, index + 1) {
+                    tokens.push(class);
+                    index = next_index;
+                    continue;
+                }
+                tokens.push(Token::Literal('['));
+            }
+            _ if is_separator(character) => tokens.push(Token::Separator),
+            _ => tokens.push(Token::Literal(character)),
+        }
+        index += 1;
+    }
+
+    tokens
+}
+
+/// A compiled glob for exclude and include patterns. `*` and `?` stay within one path
+/// segment, `**` crosses segments and `[...]` matches a character class.
+/// A pattern that matches a directory also matches everything inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobPattern {
+    tokens: Vec<Token>,
+}
+
+impl GlobPattern {
+    /// Compiles a pattern, expanding a leading `~`. Relative patterns such as
+    /// `node_modules` or `active-*` match at any depth, as if prefixed with `**/`.
+    pub fn new(pattern: &str) -> Self {
+        let expanded = expand_tilde(pattern);
+        let trimmed = expanded.trim_end_matches(is_separator);
+        // Keep a bare "/" as the root rather than trimming it to nothing
+        let pattern = if trimmed.is_empty() {
+            expanded.as_str()
+        } else {
+            trimmed
+        };
+
+        let mut tokens = Vec::new();
+        if !pattern.is_empty() && !is_absolute_pattern(pattern) && !pattern.starts_with("**") {
+            tokens.push(Token::GlobStarSeparator);
+        }
+        tokens.extend(tokenize(pattern));
+
+        Self { tokens }
+    }
+
+    fn token_matches_character(token: &Token, character: char) -> bool {
+        match token {
+            Token::Literal(literal) => *literal == character,
+            Token::Separator => is_separator(character),
+            Token::AnyChar => !is_separator(character),
+            Token::Class { negated, ranges } => {
+                !is_separator(character)
+                    && ranges
+                        .iter()
+                        .any(|(start, end)| (*start..=*end).contains(&character))
+                        != *negated
+            }
+            Token::Star | Token::GlobStar | Token::GlobStarSeparator => false,
+        }
+    }
+
+    /// Checks whether the pattern matches the path or one of its ancestor directories.
+    /// Dynamic programming over (token, position), so `*` and `**` never backtrack exponentially.
+    pub fn matches(&self, path: &str) -> bool {
+        let path: Vec<char> = path.chars().collect();
+        let length = path.len();
+
+        // Matching can end at the end of the path or at any separator after the
+        // first character, which is where an ancestor directory's path ends
+        let mut next: Vec<bool> = (0..=length)
+            .map(|position| position == length || (position > 0 && is_separator(path[position])))
+            .collect();
+        let mut current = vec![false; length + 1];
+
+        for token in self.tokens.iter().rev() {
+            // For `**/`: whether a separator at or after the position leads to a match
+            let mut separator_ahead = false;
+
+            for position in (0..=length).rev() {
+                let character = path.get(position).copied();
+                current[position] = match token {
+                    Token::Star => {
+                        next[position]
+                            || character.is_some_and(|character| {
+                                !is_separator(character) && current[position + 1]
+                            })
+                    }
+                    Token::GlobStar => {
+                        next[position] || (position < length && current[position + 1])
+                    }
+                    Token::GlobStarSeparator => {
+                        if character.is_some_and(is_separator) && next[position + 1] {
+                            separator_ahead = true;
+                        }
+                        next[position] || separator_ahead
+                    }
+                    _ => character.is_some_and(|character| {
+                        Self::token_matches_character(token, character) && next[position + 1]
+                    }),
+                };
+            }
+
+            std::mem::swap(&mut current, &mut next);
+        }
+
+        next[0]
+    }
+}
+
+/// Checks if a path matches a glob pattern, see `GlobPattern`
+pub fn matches_glob(path: &str, pattern: &str) -> bool {
+    GlobPattern::new(pattern).matches(path)
+}
+
+/// Rewrites a pattern from the original wildcard syntax into an equivalent glob.
+/// There `*` matched across separators, `?` and `[` were literal, and a pattern
+/// without wildcards matched anywhere in the path.
+pub fn migrate_legacy_pattern(pattern: &str) -> String {
+    let mut migrated = String::with_capacity(pattern.len() + 4);
+    for character in pattern.chars() {
+        match character {
+            '*' => migrated.push_str("**"),
+            '?' => migrated.push_str("[?]"),
+            '[' => migrated.push_str("[[]"),
+            _ => migrated.push(character),
+        }
+    }
+
+    if pattern.contains('*') {
+        migrated
+    } else if pattern.starts_with('~') {
+        // Only ever matched an expanded home path from its start
+        format!("{migrated}**")
+    } else {
+        format!("**{migrated}**")
+    }
+}
+
+/// Migrates a comma-separated pattern list, dropping empty entries
+pub fn migrate_legacy_patterns(patterns: &str) -> String {
+    patterns
+        .split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+        .map(migrate_legacy_pattern)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+#[path = "patterns.test.rs"]
+mod tests;
@@ -0,0 +1,145 @@
+use super::*;
+
+#[test]
+fn test_star_stays_within_segment() {
+    assert!(matches_glob("/Users/bob/work", "/Users/*/work"));
+    assert!(!matches_glob("/Users/bob/code/work", "/Users/*/work"));
+}
+
+#[test]
+fn test_globstar_crosses_segments() {
+    assert!(matches_glob("/Users/bob/code/work", "/Users/**/work"));
+    assert!(matches_glob("/Users/work", "/Users/**/work"));
+    assert!(matches_glob("/Users/bob/code/work", "/Users/**"));
+    assert!(!matches_glob("/home/bob/work", "/Users/**/work"));
+}
+
+#[test]
+fn test_question_mark_matches_one_character() {
+    assert!(matches_glob("/code/app1/node_modules", "/code/app?"));
+    assert!(!matches_glob("/code/app12/node_modules", "/code/app?"));
+    assert!(!matches_glob("/code/app/x", "/code/app?x"));
+}
+
+#[test]
+fn test_character_classes() {
+    assert!(matches_glob("/code/app-a", "/code/app-[abc]"));
+    assert!(!matches_glob("/code/app-d", "/code/app-[abc]"));
+    assert!(matches_glob("/code/v7", "/code/v[0-9]"));
+    assert!(!matches_glob("/code/v7", "/code/v[!0-9]"));
+    assert!(matches_glob("/code/vx", "/code/v[!0-9]"));
+}
+
+#[test]
+fn test_unclosed_class_is_literal() {
+    assert!(matches_glob("/code/[draft", "/code/[draft"));
+    assert!(!matches_glob("/code/d", "/code/[d"));
+}
+
+#[test]
+fn test_absolute_pattern_is_anchored() {
+    assert!(matches_glob("/Users/bob/project", "/Users/*"));
+    assert!(!matches_glob("/home/alex/project", "/Users/*"));
+}
+
+#[test]
+fn test_relative_pattern_matches_at_any_depth() {
+    assert!(matches_glob(
+        "/Users/bob/active-api/node_modules",
+        "active-*"
+    ));
+    assert!(matches_glob("/Users/bob/archive/old/vendor", "archive"));
+    assert!(!matches_glob("/Users/bob/my-archive/vendor", "archive"));
+}
+
+#[test]
+fn test_matching_directory_covers_its_contents() {
+    assert!(matches_glob(
+        "/Users/bob/work/api/node_modules",
+        "/Users/bob/work"
+    ));
+    assert!(matches_glob(
+        "/Users/bob/work/api/node_modules",
+        "/Users/bob/work/"
+    ));
+    assert!(!matches_glob(
+        "/Users/bob/workshop/node_modules",
+        "/Users/bob/work"
+    ));
+}
+
+#[test]
+fn test_pattern_separator_matches_windows_paths() {
+    assert!(matches_glob(
+        r"C:\Users\bob\work\node_modules",
+        "C:/Users/*/work"
+    ));
+    assert!(matches_glob(r"C:\Users\bob\work\node_modules", "work"));
+}
+
+#[test]
+fn test_tilde_expands_to_home() {
+    let home = dirs::home_dir().unwrap();
+    let path = home.join("code").join("app").join("node_modules");
+    assert!(matches_glob(&path.to_string_lossy(), "~/code"));
+}
+
+#[test]
+fn test_edge_cases() {
+    assert!(matches_glob("", ""));
+    assert!(!matches_glob("/any/path", ""));
+    assert!(matches_glob("/any/path", "**"));
+    assert!(matches_glob("/any/path", "*"));
+    assert!(!matches_glob("", "something"));
+}
+
+#[test]
+fn test_long_paths_do_not_backtrack_exponentially() {
+    let path = format!("/{}", "a/".repeat(200));
+    assert!(!matches_glob(&path, "/**/**/**/**/**/b"));
+}
+
+#[test]
+fn test_migrate_legacy_pattern_without_wildcards_matches_substring() {
+    let migrated = migrate_legacy_pattern("active-project");
+    assert_eq!(migrated, "**active-project**");
+    assert!(matches_glob(
+        "/Users/bob/my-active-project-v2/node_modules",
+        &migrated
+    ));
+}
+
+#[test]
+fn test_migrate_legacy_pattern_star_crosses_separators() {
+    let migrated = migrate_legacy_pattern("*/important/*");
+    assert_eq!(migrated, "**/important/**");
+    assert!(matches_glob(
+        "/home/user/important/project/node_modules",
+        &migrated
+    ));
+    assert!(!matches_glob(
+        "/home/user/unimportant/node_modules",
+        &migrated
+    ));
+}
+
+#[test]
+fn test_migrate_legacy_pattern_escapes_glob_characters() {
+    let migrated = migrate_legacy_pattern("*/what?[1]/*");
+    assert!(matches_glob("/code/what?[1]/node_modules", &migrated));
+    assert!(!matches_glob("/code/whatx1/node_modules", &migrated));
+}
+
+#[test]
+fn test_migrate_legacy_pattern_keeps_tilde_anchor() {
+    assert_eq!(migrate_legacy_pattern("~/code"), "~/code**");
+}
+
+#[test]
+fn test_migrate_legacy_patterns_list() {
+    assert_eq!(
+        migrate_legacy_patterns("*/active-*, legacy ,,"),
+        "**/active-**, **legacy**"
+    );
+    assert_eq!(migrate_legacy_patterns(""), "");
+}
@@ -0,0 +1,173 @@
+use crate::config;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Metadata about the project that owns a dependency directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectInfo {
+    /// Time of the most recent commit recorded in the project's HEAD reflog
+    pub last_commit_ms: Option<u64>,
+    /// Currently checked out branch, None when HEAD is detached
+    pub git_branch: Option<String>,
+    /// Name from the project's package.json or Cargo.toml, else its folder name
+    pub project_name: Option<String>,
+    /// Root of the monorepo the project belongs to, which may be the project itself
+    pub workspace_root: Option<String>,
+}
+
+/// Files that mark the root of a pnpm, Lerna or Go workspace
+const WORKSPACE_MARKERS: [&str; 3] = ["pnpm-workspace.yaml", "lerna.json", "go.work"];
+
+/// Resolves project metadata for a dependency directory from the manifest beside it
+/// and the nearest enclosing git repository
+pub fn resolve_project_info(dependency_path: &Path) -> ProjectInfo {
+    let project_name = resolve_project_name(dependency_path);
+    let workspace_root = find_workspace_root(dependency_path)
+        .map(|directory| directory.to_string_lossy().to_string());
+
+    match find_git_dir(dependency_path) {
+        Some(git_dir) => ProjectInfo {
+            last_commit_ms: read_last_commit_ms(&git_dir),
+            git_branch: read_git_branch(&git_dir),
+            project_name,
+            workspace_root,
+        },
+        None => ProjectInfo {
+            project_name,
+            workspace_root,
+            ..ProjectInfo::default()
+        },
+    }
+}
+
+/// Walks up from the dependency directory to the nearest workspace root, with the same
+/// bounds as the git search
+fn find_workspace_root(dependency_path: &Path) -> Option<&Path> {
+    let home_dir = dirs::home_dir();
+
+    dependency_path
+        .ancestors()
+        .skip(1)
+        .take(config::scanner::MAX_PROJECT_SEARCH_DEPTH)
+        .take_while(|directory| Some(*directory) != home_dir.as_deref())
+        .find(|directory| is_workspace_root(directory))
+}
+
+fn is_workspace_root(directory: &Path) -> bool {
+    WORKSPACE_MARKERS
+        .iter()
+        .any(|marker| directory.join(marker).is_file())
+        || fs::read_to_string(directory.join("Cargo.toml"))
+            .is_ok_and(|content| content.lines().any(|line| line.trim() == "[workspace]"))
+}
+
+/// Names the project owning a dependency directory, preferring the name its manifest
+/// declares over the folder it happens to be checked out in
+fn resolve_project_name(dependency_path: &Path) -> Option<String> {
+    let project_dir = dependency_path.parent()?;
+
+    read_package_json_name(project_dir)
+        .or_else(|| read_cargo_package_name(project_dir))
+        .or_else(|| {
+            project_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+}
+
+fn read_package_json_name(project_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_dir.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&content).ok()?;
+    manifest
+        .get("name")?
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// Reads `name` from the `[package]` table of a Cargo.toml. Only plain string values
+/// count, so a workspace-inherited name falls through to the folder name.
+fn read_cargo_package_name(project_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(project_dir.join("Cargo.toml")).ok()?;
+    let mut in_package = false;
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if in_package && key.trim() == "name" {
+            return value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .filter(|name| !name.is_empty())
+                .map(str::to_string);
+        }
+    }
+
+    None
+}
+
+/// Walks up from the dependency directory looking for a `.git` entry
+/// Stops before the home directory so a dotfiles repository isn't mistaken for the project
+fn find_git_dir(dependency_path: &Path) -> Option<PathBuf> {
+    let home_dir = dirs::home_dir();
+
+    dependency_path
+        .ancestors()
+        .skip(1)
+        .take(config::scanner::MAX_PROJECT_SEARCH_DEPTH)
+        .take_while(|directory| Some(*directory) != home_dir.as_deref())
+        .find_map(|directory| resolve_git_dir(&directory.join(".git")))
+}
+
+/// Resolves a `.git` entry to the git directory, following the `gitdir:` pointer
+/// used by worktrees and submodules
+fn resolve_git_dir(dot_git: &Path) -> Option<PathBuf> {
+    if dot_git.is_dir() {
+        return Some(dot_git.to_path_buf());
+    }
+
+    if dot_git.is_file() {
+        let content = fs::read_to_string(dot_git).ok()?;
+        let target = content.trim().strip_prefix("gitdir:")?.trim();
+        let git_dir = dot_git.parent()?.join(target);
+        return git_dir.is_dir().then_some(git_dir);
+    }
+
+    None
+}
+
+fn read_git_branch(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(|branch| branch.to_string())
+}
+
+fn read_last_commit_ms(git_dir: &Path) -> Option<u64> {
+    let reflog = fs::read_to_string(git_dir.join("logs").join("HEAD")).ok()?;
+    reflog.lines().rev().find_map(parse_commit_timestamp_ms)
+}
+
+/// Parses a reflog line (`<old> <new> <name> <<email>> <seconds> <tz>\t<message>`),
+/// returning the timestamp only for commit entries so checkouts and resets are ignored
+fn parse_commit_timestamp_ms(line: &str) -> Option<u64> {
+    let (header, message) = line.split_once('\t')?;
+    if !message.starts_with("commit") {
+        return None;
+    }
+
+    let mut parts = header.rsplitn(3, ' ');
+    let _timezone = parts.next()?;
+    let seconds: u64 = parts.next()?.parse().ok()?;
+    Some(seconds * 1000)
+}
+
+#[cfg(test)]
+#[path = "project_info.test.rs"]
+mod tests;
@@ -0,0 +1,222 @@
+use super::*;
+use tempfile::TempDir;
+
+const COMMIT_LINE: &str = "0000000000000000000000000000000000000000 1111111111111111111111111111111111111111 Test User <test@example.com> 1768392000 +0000\tcommit (initial): Initial commit";
+const CHECKOUT_LINE: &str = "1111111111111111111111111111111111111111 2222222222222222222222222222222222222222 Test User <test@example.com> 1768478400 +0100\tcheckout: moving from main to feature";
+
+fn create_git_repository(project: &Path, head: &str, reflog: &str) {
+    let git_dir = project.join(".git");
+    fs::create_dir_all(git_dir.join("logs")).unwrap();
+    fs::write(git_dir.join("HEAD"), head).unwrap();
+    fs::write(git_dir.join("logs").join("HEAD"), reflog).unwrap();
+}
+
+#[test]
+fn test_parse_commit_timestamp_ms_commit_entry() {
+    assert_eq!(
+        parse_commit_timestamp_ms(COMMIT_LINE),
+        Some(1_768_392_000_000)
+    );
+}
+
+#[test]
+fn test_parse_commit_timestamp_ms_ignores_non_commit_entries() {
+    assert_eq!(parse_commit_timestamp_ms(CHECKOUT_LINE), None);
+}
+
+#[test]
+fn test_parse_commit_timestamp_ms_malformed_lines() {
+    assert_eq!(parse_commit_timestamp_ms(""), None);
+    assert_eq!(parse_commit_timestamp_ms("no tab here"), None);
+    assert_eq!(
+        parse_commit_timestamp_ms("a b c notanumber +0000\tcommit: x"),
+        None
+    );
+}
+
+#[test]
+fn test_resolve_project_info_reads_branch_and_last_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    let node_modules = project.join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+    create_git_repository(
+        &project,
+        "ref: refs/heads/main\n",
+        &format!("{COMMIT_LINE}\n{CHECKOUT_LINE}\n"),
+    );
+
+    let info = resolve_project_info(&node_modules);
+
+    assert_eq!(info.git_branch.as_deref(), Some("main"));
+    assert_eq!(info.last_commit_ms, Some(1_768_392_000_000));
+}
+
+#[test]
+fn test_resolve_project_info_finds_repository_in_ancestor() {
+    let temp_dir = TempDir::new().unwrap();
+    let monorepo = temp_dir.path().join("monorepo");
+    let package_node_modules = monorepo.join("packages").join("api").join("node_modules");
+    fs::create_dir_all(&package_node_modules).unwrap();
+    create_git_repository(&monorepo, "ref: refs/heads/develop\n", COMMIT_LINE);
+
+    let info = resolve_project_info(&package_node_modules);
+
+    assert_eq!(info.git_branch.as_deref(), Some("develop"));
+}
+
+#[test]
+fn test_resolve_project_info_detached_head() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(project.join("vendor")).unwrap();
+    create_git_repository(
+        &project,
+        "1111111111111111111111111111111111111111\n",
+        COMMIT_LINE,
+    );
+
+    let info = resolve_project_info(&project.join("vendor"));
+
+    assert!(info.git_branch.is_none());
+    assert!(info.last_commit_ms.is_some());
+}
+
+#[test]
+fn test_resolve_project_info_follows_gitdir_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_repository = temp_dir.path().join("main");
+    let worktree_git_dir = main_repository
+        .join(".git")
+        .join("worktrees")
+        .join("feature");
+    fs::create_dir_all(worktree_git_dir.join("logs")).unwrap();
+    fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+
+    let worktree = temp_dir.path().join("feature-worktree");
+    fs::create_dir_all(worktree.join("node_modules")).unwrap();
+    fs::write(
+        worktree.join(".git"),
+        format!("gitdir: {}\n", worktree_git_dir.display()),
+    )
+    .unwrap();
+
+    let info = resolve_project_info(&worktree.join("node_modules"));
+
+    assert_eq!(info.git_branch.as_deref(), Some("feature"));
+    assert!(info.last_commit_ms.is_none());
+}
+
+#[test]
+fn test_resolve_project_info_without_repository() {
+    let temp_dir = TempDir::new().unwrap();
+    let node_modules = temp_dir.path().join("project").join("node_modules");
+    fs::create_dir_all(&node_modules).unwrap();
+
+    assert_eq!(
+        resolve_project_info(&node_modules),
+        ProjectInfo {
+            project_name: Some("project".to_string()),
+            ..ProjectInfo::default()
+        }
+    );
+}
+
+#[test]
+fn test_project_name_from_package_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("checkout");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("package.json"),
+        r#"{"name": "api-gateway", "version": "1.0.0"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve_project_name(&project.join("node_modules")).as_deref(),
+        Some("api-gateway")
+    );
+}
+
+#[test]
+fn test_project_name_from_cargo_package() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("checkout");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[workspace]\nmembers = []\n\n[package]\nname = \"indexer\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve_project_name(&project.join("target")).as_deref(),
+        Some("indexer")
+    );
+}
+
+#[test]
+fn test_project_name_falls_back_to_folder_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("checkout");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("package.json"), "{not json").unwrap();
+    fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname.workspace = true\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve_project_name(&project.join("node_modules")).as_deref(),
+        Some("checkout")
+    );
+}
+
+#[test]
+fn test_find_workspace_root_from_marker_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let monorepo = temp_dir.path().join("monorepo");
+    let package = monorepo.join("packages").join("web");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(
+        monorepo.join("pnpm-workspace.yaml"),
+        "packages:\n  - packages/*\n",
+    )
+    .unwrap();
+
+    let info = resolve_project_info(&package.join("node_modules"));
+
+    assert_eq!(
+        info.workspace_root.as_deref(),
+        Some(monorepo.to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn test_find_workspace_root_includes_project_itself() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir_all(&workspace).unwrap();
+    fs::write(
+        workspace.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        find_workspace_root(&workspace.join("target")),
+        Some(workspace.as_path())
+    );
+}
+
+#[test]
+fn test_find_workspace_root_ignores_plain_cargo_package() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(project.join("Cargo.toml"), "[package]\nname = \"solo\"\n").unwrap();
+
+    assert_eq!(find_workspace_root(&project.join("target")), None);
+}
@@ -0,0 +1,206 @@
+use super::types::DirectoryEntry;
+use crate::config;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// Asked between git checks, and while one runs, whether to give up on the rest
+pub type StopCheck = dyn Fn() -> bool + Sync;
+
+/// How safe a dependency directory is to delete, for color-coding results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Safety {
+    /// The project has been untouched for months and has nothing uncommitted
+    Safe,
+    /// Neither clearly abandoned nor in use, or not yet scored
+    #[default]
+    Caution,
+    /// The project was modified within the last day
+    Risky,
+}
+
+/// Most recent sign of activity in the project, from its dependencies or its git history
+fn last_activity_ms(entry: &DirectoryEntry) -> u64 {
+    entry
+        .last_modified_ms
+        .max(entry.last_commit_ms.unwrap_or(0))
+}
+
+/// Scores an entry from its timestamps alone. `uncommitted` is only consulted when the
+/// age would make it safe, since work in progress is the one thing an old project can hide.
+fn score(entry: &DirectoryEntry, now_ms: u64, uncommitted: impl FnOnce() -> bool) -> Safety {
+    let last_activity_ms = last_activity_ms(entry);
+    if last_activity_ms == 0 {
+        return Safety::Caution;
+    }
+
+    let idle_ms = now_ms.saturating_sub(last_activity_ms);
+    if idle_ms < config::safety::RISKY_WITHIN_MS {
+        Safety::Risky
+    } else if idle_ms >= config::safety::SAFE_AFTER_MS && !uncommitted() {
+        Safety::Safe
+    } else {
+        Safety::Caution
+    }
+}
+
+/// Whether the git repository containing `project_dir` has uncommitted changes to
+/// tracked files. Untracked files are ignored, as build output usually is. None when
+/// git didn't answer within `GIT_STATUS_TIMEOUT` or `should_stop` gave up on it.
+fn has_uncommitted_changes(project_dir: &Path, should_stop: &StopCheck) -> Option<bool> {
+    let spawned = Command::new("git")
+        .arg("-C")
+        .arg(project_dir)
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(error) => {
+            debug!(%error, "Failed to run git status, assuming no uncommitted changes");
+            return Some(false);
+        }
+    };
+
+    // Drained on its own thread so a long listing can't fill the pipe and stall git
+    let mut stdout = child.stdout.take()?;
+    let reader = thread::spawn(move || {
+        let mut first_byte = [0u8; 1];
+        let has_output = stdout.read(&mut first_byte).is_ok_and(|read| read > 0);
+        let _ = io::copy(&mut stdout, &mut io::sink());
+        has_output
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None)
+                if started.elapsed() < config::safety::GIT_STATUS_TIMEOUT && !should_stop() =>
+            {
+                thread::sleep(config::safety::GIT_STATUS_POLL_INTERVAL);
+            }
+            result => {
+                if let Err(error) = result {
+                    debug!(%error, "Failed to wait for git status");
+                } else {
+                    warn!(
+                        path = %project_dir.display(),
+                        "git status didn't finish, leaving the project unscored"
+                    );
+                }
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    let has_output = reader.join().unwrap_or(false);
+    if status.success() {
+        Some(has_output)
+    } else {
+        debug!(
+            path = %project_dir.display(),
+            %status,
+            "git status failed, assuming no uncommitted changes"
+        );
+        Some(false)
+    }
+}
+
+fn project_dir(entry: &DirectoryEntry) -> Option<PathBuf> {
+    Path::new(&entry.path).parent().map(Path::to_path_buf)
+}
+
+/// The project to ask git about, only for a project inside a repository
+fn git_project_dir(entry: &DirectoryEntry) -> Option<PathBuf> {
+    entry.last_commit_ms.and(project_dir(entry))
+}
+
+/// Rates one entry, checking git only for a project inside a repository. A project
+/// git couldn't answer for is left at caution.
+pub fn score_entry(entry: &DirectoryEntry, now_ms: u64) -> Safety {
+    score(entry, now_ms, || {
+        git_project_dir(entry).is_some_and(|project_dir| {
+            has_uncommitted_changes(&project_dir, &|| false).unwrap_or(true)
+        })
+    })
+}
+
+/// Runs git for each project on a few threads at once, leaving out any project
+/// git couldn't answer for or that was still waiting when `should_stop` returned true
+fn check_projects(projects: &[PathBuf], should_stop: &StopCheck) -> HashMap<PathBuf, bool> {
+    let next = AtomicUsize::new(0);
+    let uncommitted_by_project = Mutex::new(HashMap::new());
+    let num_threads = config::safety::GIT_STATUS_THREADS.min(projects.len());
+
+    thread::scope(|scope| {
+        for worker_index in 0..num_threads {
+            let spawned = thread::Builder::new()
+                .name(format!("git-status-{worker_index}"))
+                .spawn_scoped(scope, || {
+                    while !should_stop() {
+                        let Some(project_dir) = projects.get(next.fetch_add(1, Ordering::Relaxed))
+                        else {
+                            break;
+                        };
+                        if let Some(uncommitted) = has_uncommitted_changes(project_dir, should_stop)
+                        {
+                            uncommitted_by_project
+                                .lock()
+                                .unwrap()
+                                .insert(project_dir.clone(), uncommitted);
+                        }
+                    }
+                });
+            if let Err(error) = spawned {
+                warn!(worker_index, %error, "Failed to spawn git status thread");
+            }
+        }
+    });
+
+    uncommitted_by_project.into_inner().unwrap()
+}
+
+/// Rates every entry, running git once per project however many dependency
+/// directories it has and only for projects old enough for it to matter. Projects
+/// git couldn't answer for, or didn't get to before `should_stop`, are left at caution.
+pub fn score_entries(entries: &mut [DirectoryEntry], now_ms: u64, should_stop: &StopCheck) {
+    let mut projects: BTreeSet<PathBuf> = BTreeSet::new();
+    for entry in entries.iter() {
+        score(entry, now_ms, || {
+            projects.extend(git_project_dir(entry));
+            false
+        });
+    }
+
+    let projects: Vec<PathBuf> = projects.into_iter().collect();
+    let uncommitted_by_project = check_projects(&projects, should_stop);
+
+    for entry in entries.iter_mut() {
+        entry.safety = score(entry, now_ms, || {
+            git_project_dir(entry).is_some_and(|project_dir| {
+                uncommitted_by_project
+                    .get(&project_dir)
+                    .copied()
+                    .unwrap_or(true)
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+#[path = "safety.test.rs"]
+mod tests;
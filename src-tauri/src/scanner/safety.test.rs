@@ -0,0 +1,92 @@
+use super::*;
+use crate::scanner::DependencyCategory;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const NOW_MS: u64 = 1000 * DAY_MS;
+
+fn entry(last_modified_ms: u64, last_commit_ms: Option<u64>) -> DirectoryEntry {
+    DirectoryEntry {
+        last_modified_ms,
+        last_commit_ms,
+        scanned_at_ms: NOW_MS,
+        ..DirectoryEntry::for_test(
+            "/nonexistent/project/node_modules",
+            DependencyCategory::NodeModules,
+            1024,
+        )
+    }
+}
+
+#[test]
+fn test_score_modified_today_is_risky() {
+    let entry = entry(NOW_MS - 60 * 60 * 1000, None);
+    assert_eq!(score(&entry, NOW_MS, || false), Safety::Risky);
+}
+
+#[test]
+fn test_score_untouched_for_months_is_safe() {
+    let entry = entry(NOW_MS - 200 * DAY_MS, None);
+    assert_eq!(score(&entry, NOW_MS, || false), Safety::Safe);
+}
+
+#[test]
+fn test_score_recent_commit_outweighs_old_dependencies() {
+    let entry = entry(NOW_MS - 200 * DAY_MS, Some(NOW_MS - 10 * DAY_MS));
+    assert_eq!(score(&entry, NOW_MS, || false), Safety::Caution);
+}
+
+#[test]
+fn test_score_uncommitted_changes_need_caution() {
+    let entry = entry(NOW_MS - 200 * DAY_MS, Some(NOW_MS - 200 * DAY_MS));
+    assert_eq!(score(&entry, NOW_MS, || true), Safety::Caution);
+}
+
+#[test]
+fn test_score_only_checks_git_for_old_projects() {
+    let entry = entry(NOW_MS - 10 * DAY_MS, None);
+    let score = score(&entry, NOW_MS, || {
+        panic!("git checked for a recent project")
+    });
+    assert_eq!(score, Safety::Caution);
+}
+
+#[test]
+fn test_score_unknown_age_needs_caution() {
+    let entry = entry(0, None);
+    assert_eq!(score(&entry, NOW_MS, || false), Safety::Caution);
+}
+
+#[test]
+fn test_score_entries_without_git() {
+    let mut entries = vec![
+        entry(NOW_MS - 200 * DAY_MS, None),
+        entry(NOW_MS - 60 * 1000, None),
+    ];
+    score_entries(&mut entries, NOW_MS, &|| false);
+    assert_eq!(entries[0].safety, Safety::Safe);
+    assert_eq!(entries[1].safety, Safety::Risky);
+}
+
+#[test]
+fn test_score_entries_stopped_before_git_leaves_caution() {
+    let old_repo = entry(NOW_MS - 200 * DAY_MS, Some(NOW_MS - 200 * DAY_MS));
+    let mut entries = vec![old_repo, entry(NOW_MS - 200 * DAY_MS, None)];
+    score_entries(&mut entries, NOW_MS, &|| true);
+    assert_eq!(entries[0].safety, Safety::Caution);
+    assert_eq!(entries[1].safety, Safety::Safe);
+}
+
+#[test]
+fn test_check_projects_stopped_checks_nothing() {
+    let projects = vec![PathBuf::from("/nonexistent/project")];
+    assert!(check_projects(&projects, &|| true).is_empty());
+}
+
+#[test]
+fn test_git_project_dir_only_for_repositories() {
+    assert_eq!(git_project_dir(&entry(0, None)), None);
+    assert_eq!(
+        git_project_dir(&entry(0, Some(1))),
+        Some(PathBuf::from("/nonexistent/project"))
+    );
+}
@@ -0,0 +1,194 @@
+use crate::config;
+use crate::hash::{fnv1a, FNV_OFFSET_BASIS};
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::scanner::DirectoryEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::warn;
+
+/// Sizes of dependency directories from earlier scans, keyed by path, so a scan only
+/// walks the directories whose fingerprint changed since they were last sized
+static SIZE_INDEX: BufferedJsonFile<HashMap<String, IndexedSize>> = BufferedJsonFile::new(
+    get_size_index_path,
+    load_size_index_from,
+    save_size_index_to,
+);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedSize {
+    pub fingerprint: u64,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub last_modified_ms: u64,
+    pub has_only_symlinks: bool,
+    pub indexed_at_ms: u64,
+}
+
+fn get_size_index_path() -> Result<PathBuf, String> {
+    config_path(config::app::SIZE_INDEX_FILENAME)
+}
+
+fn load_size_index_from(path: &Path) -> HashMap<String, IndexedSize> {
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| {
+            warn!("Failed to load size index, starting fresh");
+            HashMap::new()
+        })
+}
+
+fn save_size_index_to(path: &Path, index: &HashMap<String, IndexedSize>) -> Result<(), String> {
+    let content = serde_json::to_string(index)
+        .map_err(|error| format!("Failed to serialize size index: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write size index: {error}"))
+}
+
+fn modified_ns(metadata: &fs::Metadata) -> u128 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Fingerprints a directory from its own modification time and the name, modification
+/// time and length of each direct child. Installing or removing a package changes the
+/// first and rewriting a package changes the second. Edits deeper down aren't seen,
+/// which is why indexed sizes expire after `config::size_index::MAX_AGE_MS`.
+pub fn directory_fingerprint(path: &Path) -> Option<u64> {
+    let mut hash = fnv1a(
+        FNV_OFFSET_BASIS,
+        &modified_ns(&fs::metadata(path).ok()?).to_le_bytes(),
+    );
+
+    let mut children: Vec<(String, u128, u64)> = fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .map(|entry| {
+            let metadata = entry.metadata().ok();
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                metadata.as_ref().map(modified_ns).unwrap_or(0),
+                metadata.map(|metadata| metadata.len()).unwrap_or(0),
+            )
+        })
+        .collect();
+    children.sort_unstable();
+
+    for (name, modified, length) in children {
+        hash = fnv1a(hash, name.as_bytes());
+        hash = fnv1a(hash, &modified.to_le_bytes());
+        hash = fnv1a(hash, &length.to_le_bytes());
+    }
+
+    Some(hash)
+}
+
+/// Whether an indexed size can stand in for a fresh walk. Sizes at or over the file
+/// ceiling are never reused, since the ceiling may have been lowered since.
+fn is_reusable(
+    indexed: &IndexedSize,
+    fingerprint: u64,
+    max_file_count: Option<usize>,
+    now_ms: u64,
+) -> bool {
+    indexed.fingerprint == fingerprint
+        && now_ms.saturating_sub(indexed.indexed_at_ms) < config::size_index::MAX_AGE_MS
+        && max_file_count.is_none_or(|limit| indexed.file_count < limit)
+}
+
+/// Returns the indexed size for a path if its fingerprint is unchanged and it hasn't expired
+pub fn lookup_size(
+    path: &str,
+    fingerprint: u64,
+    max_file_count: Option<usize>,
+    now_ms: u64,
+) -> Option<IndexedSize> {
+    SIZE_INDEX
+        .read(|index| {
+            index
+                .get(path)
+                .filter(|indexed| is_reusable(indexed, fingerprint, max_file_count, now_ms))
+                .cloned()
+        })
+        .unwrap_or_else(|error| {
+            warn!(%error, "Failed to read size index");
+            None
+        })
+}
+
+fn record_entry(
+    index: &mut HashMap<String, IndexedSize>,
+    entry: &DirectoryEntry,
+    fingerprint: u64,
+    now_ms: u64,
+) {
+    if entry.truncated {
+        index.remove(&entry.path);
+        return;
+    }
+
+    index.insert(
+        entry.path.clone(),
+        IndexedSize {
+            fingerprint,
+            size_bytes: entry.size_bytes,
+            file_count: entry.file_count,
+            last_modified_ms: entry.last_modified_ms,
+            has_only_symlinks: entry.has_only_symlinks,
+            indexed_at_ms: now_ms,
+        },
+    );
+}
+
+fn prune_expired(index: &mut HashMap<String, IndexedSize>, now_ms: u64) {
+    index.retain(|_, indexed| {
+        now_ms.saturating_sub(indexed.indexed_at_ms) < config::size_index::MAX_AGE_MS
+    });
+}
+
+/// Records a freshly sized entry under the fingerprint taken before it was sized, so a
+/// change made while sizing shows up as a mismatch next time. Truncated sizes are dropped.
+pub fn record_size(entry: &DirectoryEntry, fingerprint: u64, now_ms: u64) {
+    if let Err(error) = SIZE_INDEX.update(|index| record_entry(index, entry, fingerprint, now_ms)) {
+        warn!(%error, "Failed to update size index");
+    }
+}
+
+/// Drops expired sizes and writes the index to disk
+pub fn save_size_index(now_ms: u64) {
+    let saved = SIZE_INDEX
+        .update(|index| prune_expired(index, now_ms))
+        .and_then(|_| SIZE_INDEX.flush());
+    if let Err(error) = saved {
+        warn!(%error, "Failed to save size index");
+    }
+}
+
+/// Forgets sizes for deleted directories, so a reinstall there is always measured afresh
+pub fn remove_indexed_sizes(paths: &HashSet<String>) {
+    let removed = SIZE_INDEX.update(|index| index.retain(|path, _| !paths.contains(path)));
+    if let Err(error) = removed {
+        warn!(%error, "Failed to update size index");
+    }
+}
+
+/// Writes a pending size index to disk
+pub fn flush_size_index() -> Result<bool, String> {
+    SIZE_INDEX.flush()
+}
+
+#[cfg(test)]
+#[path = "size_index.test.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+use super::*;
+use crate::scanner::DependencyCategory;
+use tempfile::TempDir;
+
+const NOW_MS: u64 = 1_700_000_000_000;
+
+fn indexed(fingerprint: u64, file_count: usize, indexed_at_ms: u64) -> IndexedSize {
+    IndexedSize {
+        fingerprint,
+        size_bytes: 4_096,
+        file_count,
+        last_modified_ms: 1_000,
+        has_only_symlinks: false,
+        indexed_at_ms,
+    }
+}
+
+fn entry(path: &str, truncated: bool) -> DirectoryEntry {
+    DirectoryEntry {
+        file_count: 12,
+        last_modified_ms: 5_000,
+        truncated,
+        scanned_at_ms: NOW_MS,
+        ..DirectoryEntry::for_test(path, DependencyCategory::NodeModules, 2_048)
+    }
+}
+
+#[test]
+fn test_fingerprint_is_stable_for_unchanged_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("react")).unwrap();
+    fs::write(temp_dir.path().join(".package-lock.json"), "{}").unwrap();
+
+    assert_eq!(
+        directory_fingerprint(temp_dir.path()),
+        directory_fingerprint(temp_dir.path())
+    );
+}
+
+#[test]
+fn test_fingerprint_changes_when_package_added() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join("react")).unwrap();
+    let before = directory_fingerprint(temp_dir.path()).unwrap();
+
+    fs::create_dir_all(temp_dir.path().join("lodash")).unwrap();
+
+    assert_ne!(directory_fingerprint(temp_dir.path()).unwrap(), before);
+}
+
+#[test]
+fn test_fingerprint_changes_when_top_level_file_grows() {
+    let temp_dir = TempDir::new().unwrap();
+    let lockfile = temp_dir.path().join(".package-lock.json");
+    fs::write(&lockfile, "{}").unwrap();
+    let before = directory_fingerprint(temp_dir.path()).unwrap();
+
+    fs::write(&lockfile, r#"{"packages":{}}"#).unwrap();
+
+    assert_ne!(directory_fingerprint(temp_dir.path()).unwrap(), before);
+}
+
+#[test]
+fn test_fingerprint_missing_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    assert_eq!(
+        directory_fingerprint(&temp_dir.path().join("missing")),
+        None
+    );
+}
+
+#[test]
+fn test_is_reusable_requires_matching_fingerprint() {
+    let size = indexed(42, 10, NOW_MS);
+
+    assert!(is_reusable(&size, 42, None, NOW_MS));
+    assert!(!is_reusable(&size, 43, None, NOW_MS));
+}
+
+#[test]
+fn test_is_reusable_expires() {
+    let size = indexed(42, 10, NOW_MS - config::size_index::MAX_AGE_MS);
+
+    assert!(!is_reusable(&size, 42, None, NOW_MS));
+    assert!(is_reusable(&size, 42, None, NOW_MS - 1));
+}
+
+#[test]
+fn test_is_reusable_respects_file_ceiling() {
+    let size = indexed(42, 10, NOW_MS);
+
+    assert!(is_reusable(&size, 42, Some(11), NOW_MS));
+    assert!(!is_reusable(&size, 42, Some(10), NOW_MS));
+}
+
+#[test]
+fn test_record_entry_stores_size() {
+    let mut index = HashMap::new();
+    record_entry(
+        &mut index,
+        &entry("/code/app/node_modules", false),
+        7,
+        NOW_MS,
+    );
+
+    let stored = &index["/code/app/node_modules"];
+    assert_eq!(stored.fingerprint, 7);
+    assert_eq!(stored.size_bytes, 2_048);
+    assert_eq!(stored.file_count, 12);
+    assert_eq!(stored.last_modified_ms, 5_000);
+    assert_eq!(stored.indexed_at_ms, NOW_MS);
+}
+
+#[test]
+fn test_record_entry_drops_truncated_sizes() {
+    let mut index = HashMap::new();
+    index.insert("/code/app/node_modules".to_string(), indexed(7, 10, NOW_MS));
+
+    record_entry(
+        &mut index,
+        &entry("/code/app/node_modules", true),
+        8,
+        NOW_MS,
+    );
+
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_prune_expired() {
+    let mut index = HashMap::new();
+    index.insert("fresh".to_string(), indexed(1, 1, NOW_MS));
+    index.insert(
+        "expired".to_string(),
+        indexed(2, 1, NOW_MS - config::size_index::MAX_AGE_MS),
+    );
+
+    prune_expired(&mut index, NOW_MS);
+
+    assert_eq!(index.keys().collect::<Vec<_>>(), vec!["fresh"]);
+}
+
+#[test]
+fn test_size_index_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("size_index.json");
+    let mut index = HashMap::new();
+    index.insert("/code/app/node_modules".to_string(), indexed(7, 10, NOW_MS));
+
+    save_size_index_to(&path, &index).unwrap();
+
+    assert_eq!(load_size_index_from(&path), index);
+}
+
+#[test]
+fn test_load_size_index_from_corrupt_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("size_index.json");
+    fs::write(&path, "not json").unwrap();
+
+    assert!(load_size_index_from(&path).is_empty());
+}
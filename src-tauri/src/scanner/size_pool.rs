@@ -1,11 +1,15 @@
-use crate::scanner::core::calculate_dir_size_full;
+use crate::scanner::core::calculate_dir_size_cancellable;
+use crate::scanner::project_info::resolve_project_info;
+use crate::scanner::throttle::{apply_to_current_thread, ScanPriority};
 use crate::scanner::types::DependencyCategory;
+use crate::telemetry;
 use crossbeam_channel::{bounded, Receiver, Sender};
 use std::io;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error};
 
 pub struct SizeCalculationResult {
@@ -15,6 +19,11 @@ pub struct SizeCalculationResult {
     pub file_count: usize,
     pub last_modified_ms: u64,
     pub has_only_symlinks: bool,
+    pub last_commit_ms: Option<u64>,
+    pub git_branch: Option<String>,
+    pub project_name: Option<String>,
+    pub workspace_root: Option<String>,
+    pub truncated: bool,
 }
 
 struct SizeCalculationRequest {
@@ -32,6 +41,23 @@ pub struct SizeCalculatorPool {
 
 impl SizeCalculatorPool {
     pub fn new(num_threads: usize) -> Result<Self, io::Error> {
+        Self::with_cancellation(
+            num_threads,
+            None,
+            ScanPriority::Normal,
+            CancellationToken::new(),
+        )
+    }
+
+    /// Creates a pool whose workers run at `priority` and stop sizing a directory after
+    /// `max_file_count` files. Once `token` is cancelled, queued requests are dropped and
+    /// in-progress walks stop without sending a result.
+    pub fn with_cancellation(
+        num_threads: usize,
+        max_file_count: Option<usize>,
+        priority: ScanPriority,
+        token: CancellationToken,
+    ) -> Result<Self, io::Error> {
         let (request_sender, request_receiver) = bounded::<SizeCalculationRequest>(256);
         let (result_sender, result_receiver) = bounded::<SizeCalculationResult>(256);
         let shutdown_flag = Arc::new(AtomicBool::new(false));
@@ -43,11 +69,14 @@ impl SizeCalculatorPool {
             let receiver = Arc::clone(&request_receiver);
             let sender = result_sender.clone();
             let shutdown = Arc::clone(&shutdown_flag);
+            let token = token.clone();
 
             let handle = thread::Builder::new()
                 .name(format!("size-calc-{}", worker_index))
                 .spawn(move || {
-                    Self::worker_loop(receiver, sender, shutdown);
+                    telemetry::crash::name_thread_task("size_pool");
+                    apply_to_current_thread(priority);
+                    Self::worker_loop(receiver, sender, shutdown, max_file_count, token);
                 })
                 .map_err(|error| {
                     error!(
@@ -61,7 +90,11 @@ impl SizeCalculatorPool {
             workers.push(handle);
         }
 
-        debug!(num_threads = num_threads, "Size calculator pool created");
+        debug!(
+            num_threads = num_threads,
+            max_file_count = ?max_file_count,
+            "Size calculator pool created"
+        );
 
         Ok(Self {
             sender: Some(request_sender),
@@ -75,15 +108,25 @@ impl SizeCalculatorPool {
         receiver: Arc<Receiver<SizeCalculationRequest>>,
         sender: Sender<SizeCalculationResult>,
         shutdown: Arc<AtomicBool>,
+        max_file_count: Option<usize>,
+        token: CancellationToken,
     ) {
         while !shutdown.load(Ordering::Relaxed) {
             match receiver.recv() {
                 Ok(request) => {
-                    if shutdown.load(Ordering::Relaxed) {
+                    if shutdown.load(Ordering::Relaxed) || token.is_cancelled() {
                         break;
                     }
 
-                    let size_result = calculate_dir_size_full(Path::new(&request.path));
+                    let Some(size_result) = calculate_dir_size_cancellable(
+                        Path::new(&request.path),
+                        max_file_count,
+                        &token,
+                    ) else {
+                        debug!(path = %request.path, "Size calculation cancelled");
+                        break;
+                    };
+                    let project_info = resolve_project_info(Path::new(&request.path));
 
                     let result = SizeCalculationResult {
                         path: request.path,
@@ -92,6 +135,11 @@ impl SizeCalculatorPool {
                         file_count: size_result.file_count,
                         last_modified_ms: size_result.last_modified_ms,
                         has_only_symlinks: size_result.has_only_symlinks,
+                        last_commit_ms: project_info.last_commit_ms,
+                        git_branch: project_info.git_branch,
+                        project_name: project_info.project_name,
+                        workspace_root: project_info.workspace_root,
+                        truncated: size_result.truncated,
                     };
 
                     if let Err(error) = sender.send(result) {
@@ -331,6 +331,11 @@ fn test_size_calculation_result_fields() {
         file_count: 10,
         last_modified_ms: 1234567890000,
         has_only_symlinks: false,
+        last_commit_ms: None,
+        git_branch: None,
+        project_name: None,
+        workspace_root: None,
+        truncated: false,
     };
 
     assert_eq!(result.path, "/test/path");
@@ -466,3 +471,50 @@ fn test_pool_handles_special_characters_in_path() {
     assert_eq!(result.total_size, 4);
     assert_eq!(result.file_count, 1);
 }
+
+#[test]
+fn test_cancelled_pool_sends_no_results() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_path = temp_dir.path().join("cancelled");
+    fs::create_dir(&test_path).unwrap();
+    fs::write(test_path.join("file.txt"), "content").unwrap();
+
+    let token = CancellationToken::new();
+    let pool = SizeCalculatorPool::with_cancellation(2, None, ScanPriority::Normal, token.clone())
+        .unwrap();
+    token.cancel();
+
+    pool.submit(
+        test_path.to_string_lossy().to_string(),
+        DependencyCategory::NodeModules,
+    );
+
+    assert!(pool
+        .results()
+        .recv_timeout(Duration::from_millis(500))
+        .is_err());
+}
+
+#[test]
+fn test_cancelled_pool_workers_exit() {
+    let token = CancellationToken::new();
+    let pool = SizeCalculatorPool::with_cancellation(2, None, ScanPriority::Normal, token.clone())
+        .unwrap();
+    token.cancel();
+
+    let temp_dir = TempDir::new().unwrap();
+    // One request per worker, each of which exits on seeing the cancelled token
+    for _ in 0..2 {
+        pool.submit(
+            temp_dir.path().to_string_lossy().to_string(),
+            DependencyCategory::NodeModules,
+        );
+    }
+
+    // Every worker drops its result sender on exit, disconnecting the channel
+    let disconnected = pool.results().recv_timeout(Duration::from_secs(5));
+    assert!(matches!(
+        disconnected,
+        Err(crossbeam_channel::RecvTimeoutError::Disconnected)
+    ));
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use std::path::{Path, MAIN_SEPARATOR};
+
+/// Where the marker that identifies a suggested directory lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionMarker {
+    /// A file or directory next to the candidate, e.g. `gradlew` beside `build/`
+    Sibling(&'static str),
+    /// A file or directory inside the candidate, e.g. `yarn/` inside `.cache/`
+    Child(&'static str),
+}
+
+/// A recognizable directory that isn't covered by a built-in category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuggestionRule {
+    pub directory_name: &'static str,
+    pub marker: SuggestionMarker,
+    pub label: &'static str,
+}
+
+const SUGGESTION_RULES: &[SuggestionRule] = &[
+    SuggestionRule {
+        directory_name: "build",
+        marker: SuggestionMarker::Sibling("gradlew"),
+        label: "Gradle build output",
+    },
+    SuggestionRule {
+        directory_name: ".gradle",
+        marker: SuggestionMarker::Sibling("gradlew"),
+        label: "Gradle project cache",
+    },
+    SuggestionRule {
+        directory_name: ".cache",
+        marker: SuggestionMarker::Child("yarn"),
+        label: "Yarn cache",
+    },
+    SuggestionRule {
+        directory_name: "target",
+        marker: SuggestionMarker::Sibling("Cargo.toml"),
+        label: "Rust build output",
+    },
+    SuggestionRule {
+        directory_name: ".next",
+        marker: SuggestionMarker::Sibling("next.config.js"),
+        label: "Next.js build cache",
+    },
+];
+
+impl SuggestionMarker {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SuggestionMarker::Sibling(name) | SuggestionMarker::Child(name) => name,
+        }
+    }
+
+    fn is_present(&self, path: &Path) -> bool {
+        match self {
+            SuggestionMarker::Sibling(name) => path
+                .parent()
+                .is_some_and(|parent| parent.join(name).exists()),
+            SuggestionMarker::Child(name) => path.join(name).exists(),
+        }
+    }
+}
+
+/// Matches a directory against the suggestion rules. The name is checked first
+/// so the marker lookup only touches the filesystem for plausible candidates.
+pub fn match_suggestion(path: &Path, directory_name: &str) -> Option<&'static SuggestionRule> {
+    SUGGESTION_RULES
+        .iter()
+        .filter(|rule| rule.directory_name == directory_name)
+        .find(|rule| rule.marker.is_present(path))
+}
+
+/// Drops candidates that sit inside a dependency directory or inside another candidate,
+/// so only the outermost match is sized and reported
+pub fn filter_nested_candidates<T>(
+    mut candidates: Vec<(String, T)>,
+    all_dependency_dirs: &HashSet<&str>,
+) -> Vec<(String, T)> {
+    candidates.sort_by(|first, second| first.0.len().cmp(&second.0.len()));
+
+    let mut kept: Vec<(String, T)> = Vec::with_capacity(candidates.len());
+    for (path, value) in candidates {
+        let inside_dependency = path
+            .split(MAIN_SEPARATOR)
+            .rev()
+            .skip(1)
+            .any(|component| all_dependency_dirs.contains(component));
+        let inside_kept = kept.iter().any(|(kept_path, _)| {
+            path.strip_prefix(kept_path.as_str())
+                .is_some_and(|rest| rest.starts_with(MAIN_SEPARATOR))
+        });
+
+        if !inside_dependency && !inside_kept {
+            kept.push((path, value));
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+#[path = "suggestions.test.rs"]
+mod tests;
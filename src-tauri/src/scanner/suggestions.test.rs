@@ -0,0 +1,69 @@
+use super::*;
+use crate::scanner::types::get_all_dependency_directory_names;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_match_suggestion_gradle_build_with_wrapper() {
+    let temp_dir = TempDir::new().unwrap();
+    let build_dir = temp_dir.path().join("build");
+    fs::create_dir(&build_dir).unwrap();
+    fs::write(temp_dir.path().join("gradlew"), "").unwrap();
+
+    let rule = match_suggestion(&build_dir, "build").unwrap();
+    assert_eq!(rule.label, "Gradle build output");
+    assert_eq!(rule.marker.name(), "gradlew");
+}
+
+#[test]
+fn test_match_suggestion_build_without_marker() {
+    let temp_dir = TempDir::new().unwrap();
+    let build_dir = temp_dir.path().join("build");
+    fs::create_dir(&build_dir).unwrap();
+
+    assert!(match_suggestion(&build_dir, "build").is_none());
+}
+
+#[test]
+fn test_match_suggestion_cache_with_yarn_child() {
+    let temp_dir = TempDir::new().unwrap();
+    let cache_dir = temp_dir.path().join(".cache");
+    fs::create_dir_all(cache_dir.join("yarn")).unwrap();
+
+    let rule = match_suggestion(&cache_dir, ".cache").unwrap();
+    assert_eq!(rule.marker, SuggestionMarker::Child("yarn"));
+}
+
+#[test]
+fn test_match_suggestion_unknown_directory_name() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("gradlew"), "").unwrap();
+
+    assert!(match_suggestion(&temp_dir.path().join("src"), "src").is_none());
+}
+
+#[test]
+fn test_filter_nested_candidates_keeps_outermost() {
+    let separator = MAIN_SEPARATOR;
+    let outer = format!("{separator}project{separator}target");
+    let nested = format!("{outer}{separator}debug{separator}build");
+    let sibling = format!("{separator}project{separator}target-old");
+
+    let kept = filter_nested_candidates(
+        vec![(nested, 2), (sibling.clone(), 3), (outer.clone(), 1)],
+        &get_all_dependency_directory_names(),
+    );
+
+    let paths: Vec<&str> = kept.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, vec![outer.as_str(), sibling.as_str()]);
+}
+
+#[test]
+fn test_filter_nested_candidates_skips_dependency_directories() {
+    let separator = MAIN_SEPARATOR;
+    let inside = format!("{separator}app{separator}node_modules{separator}lib{separator}build");
+
+    let kept = filter_nested_candidates(vec![(inside, ())], &get_all_dependency_directory_names());
+
+    assert!(kept.is_empty());
+}
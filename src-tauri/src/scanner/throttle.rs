@@ -0,0 +1,148 @@
+use crate::config;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// How hard a background scan may work the machine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ScanPriority {
+    /// Utility QoS and a few threads, fewer still on battery
+    #[default]
+    Low,
+    /// Default QoS and the usual thread count, capped on battery
+    Normal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+/// Reads the power source from the first line of `pmset -g batt`,
+/// e.g. "Now drawing from 'Battery Power'"
+pub fn parse_power_source(pmset_output: &str) -> Option<PowerSource> {
+    let first_line = pmset_output.lines().next()?;
+    if first_line.contains("'Battery Power'") {
+        Some(PowerSource::Battery)
+    } else if first_line.contains("'AC Power'") {
+        Some(PowerSource::Ac)
+    } else {
+        None
+    }
+}
+
+/// Current power source, or None where it can't be determined
+#[cfg(target_os = "macos")]
+pub fn power_source() -> Option<PowerSource> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    parse_power_source(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn power_source() -> Option<PowerSource> {
+    None
+}
+
+/// Threads a scan may use for discovery and sizing
+pub fn thread_budget(priority: ScanPriority, power: Option<PowerSource>, cpus: usize) -> usize {
+    let mut threads = cpus.min(config::scanner::SIZE_POOL_THREADS);
+    if priority == ScanPriority::Low {
+        threads = threads.min(config::scanner::LOW_PRIORITY_THREADS);
+    }
+    if power == Some(PowerSource::Battery) {
+        threads = threads
+            .div_ceil(2)
+            .min(config::scanner::BATTERY_SCAN_THREADS);
+    }
+    threads.max(1)
+}
+
+/// Lowers the calling thread's QoS class for low-priority work, so macOS schedules
+/// it behind anything the user is doing. A no-op at normal priority and elsewhere.
+pub fn apply_to_current_thread(priority: ScanPriority) {
+    if priority == ScanPriority::Normal {
+        return;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let result =
+            unsafe { libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_UTILITY, 0) };
+        if result != 0 {
+            warn!(result, "Failed to lower scan thread QoS");
+        }
+    }
+}
+
+/// Thread count and scheduling for one scan. Interactive scans run unthrottled;
+/// background scans get a dedicated, smaller pool whose threads run at the
+/// configured priority.
+#[derive(Clone)]
+pub struct ScanThrottle {
+    priority: ScanPriority,
+    threads: usize,
+    discovery_pool: Option<Arc<jwalk::rayon::ThreadPool>>,
+}
+
+impl ScanThrottle {
+    pub fn unthrottled() -> Self {
+        Self {
+            priority: ScanPriority::Normal,
+            threads: num_cpus::get().min(config::scanner::SIZE_POOL_THREADS),
+            discovery_pool: None,
+        }
+    }
+
+    pub fn for_background(priority: ScanPriority) -> Self {
+        let power = power_source();
+        let threads = thread_budget(priority, power, num_cpus::get());
+        debug!(?priority, ?power, threads, "Throttling background scan");
+
+        let discovery_pool = jwalk::rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("background-walk-{index}"))
+            .start_handler(move |_| apply_to_current_thread(priority))
+            .build()
+            .map_err(|error| warn!(%error, "Failed to build background walk pool"))
+            .ok()
+            .map(Arc::new);
+
+        Self {
+            priority,
+            threads,
+            discovery_pool,
+        }
+    }
+
+    pub fn priority(&self) -> ScanPriority {
+        self.priority
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// The discovery walk is only ever driven from outside the dedicated pool,
+    /// so it can't starve itself and jwalk's busy check isn't needed there
+    pub fn discovery_parallelism(&self) -> jwalk::Parallelism {
+        match &self.discovery_pool {
+            Some(pool) => jwalk::Parallelism::RayonExistingPool {
+                pool: Arc::clone(pool),
+                busy_timeout: None,
+            },
+            None => jwalk::Parallelism::RayonDefaultPool {
+                busy_timeout: config::scanner::JWALK_BUSY_TIMEOUT,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "throttle.test.rs"]
+mod tests;
@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn test_parse_power_source() {
+    assert_eq!(
+        parse_power_source(
+            "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t87%; discharging;"
+        ),
+        Some(PowerSource::Battery)
+    );
+    assert_eq!(
+        parse_power_source("Now drawing from 'AC Power'\n"),
+        Some(PowerSource::Ac)
+    );
+    assert_eq!(parse_power_source(""), None);
+    assert_eq!(parse_power_source("something else"), None);
+}
+
+#[test]
+fn test_thread_budget_normal_on_ac_is_unchanged() {
+    assert_eq!(
+        thread_budget(ScanPriority::Normal, Some(PowerSource::Ac), 16),
+        config::scanner::SIZE_POOL_THREADS
+    );
+    assert_eq!(thread_budget(ScanPriority::Normal, None, 4), 4);
+}
+
+#[test]
+fn test_thread_budget_low_priority_caps_threads() {
+    assert_eq!(
+        thread_budget(ScanPriority::Low, Some(PowerSource::Ac), 16),
+        config::scanner::LOW_PRIORITY_THREADS
+    );
+}
+
+#[test]
+fn test_thread_budget_battery_caps_threads() {
+    assert_eq!(
+        thread_budget(ScanPriority::Normal, Some(PowerSource::Battery), 16),
+        config::scanner::BATTERY_SCAN_THREADS
+    );
+    assert_eq!(
+        thread_budget(ScanPriority::Low, Some(PowerSource::Battery), 16),
+        1
+    );
+}
+
+#[test]
+fn test_thread_budget_never_zero() {
+    assert_eq!(
+        thread_budget(ScanPriority::Low, Some(PowerSource::Battery), 0),
+        1
+    );
+}
+
+#[test]
+fn test_unthrottled_uses_default_pool() {
+    let throttle = ScanThrottle::unthrottled();
+
+    assert_eq!(throttle.priority(), ScanPriority::Normal);
+    assert!(matches!(
+        throttle.discovery_parallelism(),
+        jwalk::Parallelism::RayonDefaultPool { .. }
+    ));
+}
+
+#[test]
+fn test_background_throttle_uses_dedicated_pool() {
+    let throttle = ScanThrottle::for_background(ScanPriority::Low);
+
+    assert!(throttle.threads() <= config::scanner::LOW_PRIORITY_THREADS);
+    assert!(matches!(
+        throttle.discovery_parallelism(),
+        jwalk::Parallelism::RayonExistingPool { .. }
+    ));
+}
+
+#[test]
+fn test_scan_priority_serialization() {
+    assert_eq!(
+        serde_json::to_string(&ScanPriority::Low).unwrap(),
+        "\"LOW\""
+    );
+    assert_eq!(ScanPriority::default(), ScanPriority::Low);
+}
@@ -1,7 +1,40 @@
+use super::age::AgeStrategy;
+use super::caches::CacheEntry;
+use super::disk_usage::DiskUsage;
+use super::ecosystems::has_ecosystem_marker;
+use super::safety::{score_entries, Safety, StopCheck};
+use crate::i18n;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Present in every conda environment
+const CONDA_META: &str = "conda-meta";
+/// Only present in a conda base installation, which must never be offered for deletion
+const CONDA_BASE_MARKER: &str = "condabin";
+/// Written by `venv`, `virtualenv`, Poetry and uv into every virtual environment
+const PYVENV_CFG: &str = "pyvenv.cfg";
+/// Parent of the global NuGet package cache in the user's home directory
+const NUGET_DIRECTORY: &str = ".nuget";
+/// Parent of the Angular CLI build cache
+const ANGULAR_DIRECTORY: &str = ".angular";
+/// Names shared with too much unrelated content to treat everything beneath them
+/// as nested dependencies
+const GENERIC_DIRECTORY_NAMES: &[&str] = &["bin", "obj", "packages", "dist", "cache", "target"];
+
+/// Build output recognized by `BuildArtifacts`, as listed in settings.
+/// The walk matches on the last path component.
+pub const BUILD_ARTIFACT_DIRECTORIES: &[&str] = &[
+    ".next",
+    ".nuxt",
+    ".turbo",
+    ".parcel-cache",
+    ".angular/cache",
+    "dist",
+    "target",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum DependencyCategory {
     NodeModules,
@@ -9,9 +42,22 @@ pub enum DependencyCategory {
     Bundler,
     Pods,
     PythonVenv,
+    CondaEnv,
     ElixirDeps,
     DartTool,
     GoMod,
+    DotNet,
+    BuildArtifacts,
+}
+
+/// A category label with its translation key, so anything rendered outside the UI
+/// (exports, notifications, the tray) reads the same as the UI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryLabel {
+    pub category: DependencyCategory,
+    pub key: &'static str,
+    pub label: String,
 }
 
 impl DependencyCategory {
@@ -22,9 +68,12 @@ impl DependencyCategory {
             DependencyCategory::Bundler,
             DependencyCategory::Pods,
             DependencyCategory::PythonVenv,
+            DependencyCategory::CondaEnv,
             DependencyCategory::ElixirDeps,
             DependencyCategory::DartTool,
             DependencyCategory::GoMod,
+            DependencyCategory::DotNet,
+            DependencyCategory::BuildArtifacts,
         ]
     }
 
@@ -35,13 +84,24 @@ impl DependencyCategory {
             DependencyCategory::Bundler => &["vendor"],
             DependencyCategory::Pods => &["Pods"],
             DependencyCategory::PythonVenv => &[".venv", "venv"],
+            // Conda environments have arbitrary names and are only found by their markers
+            DependencyCategory::CondaEnv => &[],
             DependencyCategory::ElixirDeps => &["deps"],
             DependencyCategory::DartTool => &[".dart_tool"],
             DependencyCategory::GoMod => &["pkg"],
+            DependencyCategory::DotNet => &["bin", "obj", "packages"],
+            DependencyCategory::BuildArtifacts => &[
+                ".next",
+                ".nuxt",
+                ".turbo",
+                ".parcel-cache",
+                "cache",
+                "dist",
+                "target",
+            ],
         }
     }
 
-    #[allow(dead_code)]
     pub fn label(&self) -> &'static str {
         match self {
             DependencyCategory::NodeModules => "Node.js (node_modules)",
@@ -49,23 +109,62 @@ impl DependencyCategory {
             DependencyCategory::Bundler => "Ruby (vendor)",
             DependencyCategory::Pods => "iOS (Pods)",
             DependencyCategory::PythonVenv => "Python (venv)",
+            DependencyCategory::CondaEnv => "Python (conda)",
             DependencyCategory::ElixirDeps => "Elixir (deps)",
             DependencyCategory::DartTool => "Dart (dart_tool)",
             DependencyCategory::GoMod => "Go (pkg/mod)",
+            DependencyCategory::DotNet => ".NET (bin/obj)",
+            DependencyCategory::BuildArtifacts => "Build output (dist)",
+        }
+    }
+
+    /// Translation key for the label under `categories` in the locale files
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DependencyCategory::NodeModules => "categories.node",
+            DependencyCategory::Composer => "categories.php",
+            DependencyCategory::Bundler => "categories.ruby",
+            DependencyCategory::Pods => "categories.ios",
+            DependencyCategory::PythonVenv => "categories.python",
+            DependencyCategory::CondaEnv => "categories.conda",
+            DependencyCategory::ElixirDeps => "categories.elixir",
+            DependencyCategory::DartTool => "categories.dart",
+            DependencyCategory::GoMod => "categories.go",
+            DependencyCategory::DotNet => "categories.dotnet",
+            DependencyCategory::BuildArtifacts => "categories.build",
+        }
+    }
+
+    /// The label in `language`, from the locale files the UI uses
+    pub fn translated_label(&self, language: &str) -> String {
+        i18n::translate(language, self.label_key())
+    }
+
+    pub fn localized_label(&self, language: &str) -> CategoryLabel {
+        CategoryLabel {
+            category: *self,
+            key: self.label_key(),
+            label: self.translated_label(language),
         }
     }
 
     /// Determines the category from a directory name.
-    /// For "vendor", "deps", and "pkg" directories, use specialized detection methods.
+    /// For "vendor", "deps", "pkg", "bin", "obj", "packages", "dist", "cache", and "target"
+    /// directories, use specialized detection methods.
     pub fn from_directory_name(dir_name: &str) -> Option<DependencyCategory> {
         match dir_name {
             "node_modules" => Some(DependencyCategory::NodeModules),
             "Pods" => Some(DependencyCategory::Pods),
             ".venv" | "venv" => Some(DependencyCategory::PythonVenv),
             ".dart_tool" => Some(DependencyCategory::DartTool),
+            ".next" | ".nuxt" | ".turbo" | ".parcel-cache" => {
+                Some(DependencyCategory::BuildArtifacts)
+            }
             "vendor" => None,
             "deps" => None,
             "pkg" => None,
+            "bin" | "obj" | "packages" => None,
+            "dist" | "cache" | "target" => None,
             _ => None,
         }
     }
@@ -85,8 +184,7 @@ impl DependencyCategory {
         }
 
         if let Some(parent) = vendor_path.parent() {
-            let gemfile = parent.join("Gemfile");
-            if gemfile.exists() {
+            if has_ecosystem_marker(parent, DependencyCategory::Bundler) {
                 return Some(DependencyCategory::Bundler);
             }
         }
@@ -97,15 +195,70 @@ impl DependencyCategory {
 
     /// Determines whether a deps directory belongs to Elixir by checking for mix.exs in the parent.
     pub fn from_deps_directory(deps_path: &std::path::Path) -> Option<DependencyCategory> {
-        if let Some(parent) = deps_path.parent() {
-            let mix_exs = parent.join("mix.exs");
-            if mix_exs.exists() {
-                return Some(DependencyCategory::ElixirDeps);
+        let parent = deps_path.parent()?;
+        has_ecosystem_marker(parent, DependencyCategory::ElixirDeps)
+            .then_some(DependencyCategory::ElixirDeps)
+    }
+
+    /// Recognizes a Python environment from the names of its top-level entries, so
+    /// environments with arbitrary directory names are found while listing their contents.
+    /// A conda base installation also has `conda-meta`, but is excluded by its `condabin`.
+    pub fn from_environment_entries<'a>(
+        entry_names: impl IntoIterator<Item = &'a str>,
+    ) -> Option<DependencyCategory> {
+        let mut has_conda_meta = false;
+        let mut has_condabin = false;
+        let mut has_pyvenv_cfg = false;
+
+        for name in entry_names {
+            match name {
+                CONDA_META => has_conda_meta = true,
+                CONDA_BASE_MARKER => has_condabin = true,
+                PYVENV_CFG => has_pyvenv_cfg = true,
+                _ => {}
             }
         }
+
+        if has_conda_meta && !has_condabin {
+            Some(DependencyCategory::CondaEnv)
+        } else if has_pyvenv_cfg {
+            Some(DependencyCategory::PythonVenv)
+        } else {
+            None
+        }
+    }
+
+    /// Same as `from_environment_entries`, checking the markers on disk
+    pub fn from_environment_directory(path: &std::path::Path) -> Option<DependencyCategory> {
+        if path.join(CONDA_META).is_dir() && !path.join(CONDA_BASE_MARKER).exists() {
+            return Some(DependencyCategory::CondaEnv);
+        }
+
+        if path.join(PYVENV_CFG).is_file() {
+            return Some(DependencyCategory::PythonVenv);
+        }
+
         None
     }
 
+    /// Determines the category of an existing dependency directory from its path,
+    /// applying the specialized detection for ambiguous directory names.
+    pub fn from_path(path: &std::path::Path) -> Option<DependencyCategory> {
+        let directory_name = path.file_name()?.to_str()?;
+        match directory_name {
+            "vendor" => DependencyCategory::from_vendor_directory(path),
+            "deps" => DependencyCategory::from_deps_directory(path),
+            "pkg" => DependencyCategory::from_pkg_directory(path),
+            "bin" | "obj" => DependencyCategory::from_dotnet_build_directory(path),
+            "packages" => DependencyCategory::from_nuget_packages_directory(path),
+            "dist" => DependencyCategory::from_dist_directory(path),
+            "cache" => DependencyCategory::from_angular_cache_directory(path),
+            "target" => DependencyCategory::from_cargo_target_directory(path),
+            _ => DependencyCategory::from_directory_name(directory_name)
+                .or_else(|| DependencyCategory::from_environment_directory(path)),
+        }
+    }
+
     /// Determines whether a pkg directory belongs to Go by checking for the mod subdirectory.
     pub fn from_pkg_directory(pkg_path: &std::path::Path) -> Option<DependencyCategory> {
         let mod_dir = pkg_path.join("mod");
@@ -114,6 +267,49 @@ impl DependencyCategory {
         }
         None
     }
+
+    /// Determines whether a bin or obj directory is .NET build output by checking for
+    /// a project or solution file in the parent.
+    pub fn from_dotnet_build_directory(build_path: &std::path::Path) -> Option<DependencyCategory> {
+        let parent = build_path.parent()?;
+        has_ecosystem_marker(parent, DependencyCategory::DotNet)
+            .then_some(DependencyCategory::DotNet)
+    }
+
+    /// Determines whether a packages directory is the global NuGet cache (`~/.nuget/packages`)
+    /// by checking the name of its parent.
+    pub fn from_nuget_packages_directory(
+        packages_path: &std::path::Path,
+    ) -> Option<DependencyCategory> {
+        let parent_name = packages_path.parent()?.file_name()?.to_str()?;
+        (parent_name == NUGET_DIRECTORY).then_some(DependencyCategory::DotNet)
+    }
+
+    /// Determines whether a dist directory is JavaScript build output by checking for
+    /// package.json in the parent.
+    pub fn from_dist_directory(dist_path: &std::path::Path) -> Option<DependencyCategory> {
+        let parent = dist_path.parent()?;
+        has_ecosystem_marker(parent, DependencyCategory::NodeModules)
+            .then_some(DependencyCategory::BuildArtifacts)
+    }
+
+    /// Determines whether a target directory is Rust build output by checking for
+    /// Cargo.toml in the parent.
+    pub fn from_cargo_target_directory(
+        target_path: &std::path::Path,
+    ) -> Option<DependencyCategory> {
+        let parent = target_path.parent()?;
+        has_ecosystem_marker(parent, DependencyCategory::BuildArtifacts)
+            .then_some(DependencyCategory::BuildArtifacts)
+    }
+
+    /// Determines whether a cache directory is the Angular CLI cache (`.angular/cache`).
+    pub fn from_angular_cache_directory(
+        cache_path: &std::path::Path,
+    ) -> Option<DependencyCategory> {
+        let parent_name = cache_path.parent()?.file_name()?.to_str()?;
+        (parent_name == ANGULAR_DIRECTORY).then_some(DependencyCategory::BuildArtifacts)
+    }
 }
 
 pub fn get_target_directory_names(
@@ -132,20 +328,38 @@ pub fn get_all_dependency_directory_names() -> HashSet<&'static str> {
     let mut names = HashSet::new();
     for category in DependencyCategory::all() {
         for name in category.directory_names() {
-            names.insert(*name);
+            if !GENERIC_DIRECTORY_NAMES.contains(name) {
+                names.insert(*name);
+            }
         }
     }
     names
 }
 
+/// Directory names matched for build output, limited to the configured artifacts
+pub fn get_build_artifact_directory_names(configured: &[String]) -> HashSet<&'static str> {
+    DependencyCategory::BuildArtifacts
+        .directory_names()
+        .iter()
+        .filter(|name| {
+            configured
+                .iter()
+                .any(|entry| entry.trim_end_matches('/').rsplit('/').next() == Some(**name))
+        })
+        .copied()
+        .collect()
+}
+
 /// A directory discovered during the scan phase, before size calculation.
-#[derive(Debug, Clone)]
+/// Emitted as `scan_discovered` so the UI can show a row while the size is pending.
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DiscoveredDirectory {
     pub path: String,
     pub category: DependencyCategory,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DirectoryEntry {
     pub path: String,
@@ -157,18 +371,340 @@ pub struct DirectoryEntry {
     /// This happens with pnpm hoisting where symlinks point outside the directory
     #[serde(default)]
     pub has_only_symlinks: bool,
+    /// Time of the owning project's most recent git commit, if it is a git repository
+    #[serde(default)]
+    pub last_commit_ms: Option<u64>,
+    /// Branch currently checked out in the owning project's git repository
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// Name the owning project declares in package.json or Cargo.toml, else its folder name
+    #[serde(default)]
+    pub project_name: Option<String>,
+    /// Root of the monorepo the owning project belongs to, see `ScanResult::workspaces`
+    #[serde(default)]
+    pub workspace_root: Option<String>,
+    /// Sizing hit the per-directory file-count ceiling, so totals are a lower bound
+    #[serde(default)]
+    pub truncated: bool,
+    /// When this entry was last sized, zero if unknown (e.g. a cache from an older version)
+    #[serde(default)]
+    pub scanned_at_ms: u64,
+    /// Set by query commands when the entry was sized longer ago than the rescan interval
+    #[serde(default)]
+    pub stale: bool,
+    /// What `last_modified_ms` measures, which scans record as the newest file inside
+    #[serde(default)]
+    pub age_source: AgeStrategy,
+    /// How safe this is to delete, judged from the project's activity and git state
+    #[serde(default)]
+    pub safety: Safety,
+}
+
+impl DirectoryEntry {
+    pub fn is_stale(&self, now_ms: u64, max_age_ms: u64) -> bool {
+        now_ms.saturating_sub(self.scanned_at_ms) > max_age_ms
+    }
+
+    /// A one-file entry outside any project, for tests to adjust with struct update syntax
+    #[cfg(test)]
+    pub fn for_test(path: &str, category: DependencyCategory, size_bytes: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            size_bytes,
+            file_count: 1,
+            last_modified_ms: 0,
+            category,
+            has_only_symlinks: false,
+            last_commit_ms: None,
+            git_branch: None,
+            project_name: None,
+            workspace_root: None,
+            truncated: false,
+            scanned_at_ms: 0,
+            stale: false,
+            age_source: AgeStrategy::NewestFile,
+            safety: Safety::Caution,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScanResult {
     pub entries: Vec<DirectoryEntry>,
     pub total_size: u64,
     pub scan_time_ms: u128,
     pub skipped_count: usize,
+    /// Sum of file counts across all entries
+    #[serde(default)]
+    pub total_file_count: u64,
+    /// Mean entry size in bytes, zero when there are no entries
+    #[serde(default)]
+    pub average_entry_size: u64,
+    /// Median time since entries were last modified, None when no entry has a timestamp
+    #[serde(default)]
+    pub median_age_ms: Option<u64>,
+    /// Large uncategorized directories with recognizable markers, largest first
+    #[serde(default)]
+    pub suggested_categories: Vec<SuggestedCategory>,
+    /// Global package manager caches, only populated when cache scanning is enabled
+    #[serde(default)]
+    pub cache_entries: Vec<CacheEntry>,
+    /// What the walk visited and left out, zero for results that didn't walk the disk
+    #[serde(default)]
+    pub coverage: ScanCoverage,
+    /// Space on the root directory's volume when the scan finished
+    #[serde(default)]
+    pub disk_usage: Option<DiskUsage>,
+    /// Entries grouped by monorepo, largest first, so a workspace can collapse into one row
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceSummary>,
+    /// The scan hit its deadline, so entries are only those found and sized in time
+    #[serde(default)]
+    pub partial: bool,
+    /// Directories the walk couldn't read, capped so a locked-down home doesn't bloat the result
+    #[serde(default)]
+    pub skipped_entries: Vec<SkippedEntry>,
+}
+
+/// Why the walk couldn't read a directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SkipReason {
+    /// Usually a macOS privacy-protected folder until Full Disk Access is granted
+    PermissionDenied,
+    /// Removed between listing its parent and reading it
+    NotFound,
+    /// A symlink leading back to one of its own ancestors
+    Loop,
+    Other,
+}
+
+impl SkipReason {
+    pub fn from_io_error(error: &std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            std::io::ErrorKind::NotFound => Self::NotFound,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedEntry {
+    pub path: String,
+    pub kind: SkipReason,
+}
+
+/// Aggregate of the entries sharing a `workspace_root`
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSummary {
+    pub root: String,
+    pub entry_count: usize,
+    pub total_size: u64,
+}
+
+/// Sums entries by workspace root, largest first. Entries outside a workspace are left out.
+pub fn group_workspaces(entries: &[DirectoryEntry]) -> Vec<WorkspaceSummary> {
+    let mut by_root: HashMap<&str, WorkspaceSummary> = HashMap::new();
+    for entry in entries {
+        let Some(root) = entry.workspace_root.as_deref() else {
+            continue;
+        };
+        let summary = by_root.entry(root).or_insert_with(|| WorkspaceSummary {
+            root: root.to_string(),
+            entry_count: 0,
+            total_size: 0,
+        });
+        summary.entry_count += 1;
+        summary.total_size += entry.size_bytes;
+    }
+
+    let mut workspaces: Vec<WorkspaceSummary> = by_root.into_values().collect();
+    workspaces.sort_by(|a, b| {
+        b.total_size
+            .cmp(&a.total_size)
+            .then_with(|| a.root.cmp(&b.root))
+    });
+    workspaces
+}
+
+/// How much of the disk a scan covered, so settings that hide large areas show up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScanCoverage {
+    /// Directories the walk read
+    pub directories_visited: usize,
+    /// Directories never entered because their name is on the skip list
+    pub skipped_by_skip_list: usize,
+    /// Dependency directories left out by exclude patterns
+    pub skipped_by_excludes: usize,
+    /// Dependency directories left out for falling outside the include patterns
+    pub skipped_by_includes: usize,
+    /// Dependency directories the user chose to never show again
+    pub skipped_by_ignore_list: usize,
+    /// Entries the walk couldn't read, usually for lack of permission
+    pub errored: usize,
+    /// Dependency directories whose size was reused from the size index
+    pub sizes_reused: usize,
+    /// Dependency directories smaller than `min_size_bytes`
+    pub skipped_below_min_size: usize,
+    /// Network, external and cloud-synced folders never entered, see `skipped_volume_kinds`
+    pub skipped_by_volume: usize,
+}
+
+/// An uncategorized directory that looks like a dependency or build cache
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedCategory {
+    pub path: String,
+    pub directory_name: String,
+    /// Human-readable description, e.g. "Gradle build output"
+    pub label: String,
+    /// The file or directory that identified it, e.g. `gradlew`
+    pub marker: String,
+    pub size_bytes: u64,
+}
+
+impl ScanResult {
+    /// Builds a result from sized entries, computing the summary aggregates so
+    /// the frontend doesn't have to walk thousands of entries itself
+    pub fn from_entries(
+        entries: Vec<DirectoryEntry>,
+        scan_time_ms: u128,
+        skipped_count: usize,
+        now_ms: u64,
+    ) -> Self {
+        let total_size: u64 = entries.iter().map(|entry| entry.size_bytes).sum();
+        let total_file_count = entries.iter().map(|entry| entry.file_count as u64).sum();
+        let average_entry_size = if entries.is_empty() {
+            0
+        } else {
+            total_size / entries.len() as u64
+        };
+
+        let mut ages: Vec<u64> = entries
+            .iter()
+            .filter(|entry| entry.last_modified_ms > 0)
+            .map(|entry| now_ms.saturating_sub(entry.last_modified_ms))
+            .collect();
+        ages.sort_unstable();
+
+        let median_age_ms = median(&ages);
+        let workspaces = group_workspaces(&entries);
+
+        Self {
+            entries,
+            total_size,
+            scan_time_ms,
+            skipped_count,
+            total_file_count,
+            average_entry_size,
+            median_age_ms,
+            suggested_categories: Vec::new(),
+            cache_entries: Vec::new(),
+            coverage: ScanCoverage::default(),
+            disk_usage: None,
+            workspaces,
+            partial: false,
+            skipped_entries: Vec::new(),
+        }
+    }
+
+    /// Flags entries sized longer than `max_age_ms` ago so the UI can offer a targeted rescan
+    pub fn mark_stale_entries(&mut self, now_ms: u64, max_age_ms: u64) {
+        for entry in &mut self.entries {
+            entry.stale = entry.is_stale(now_ms, max_age_ms);
+        }
+    }
+
+    /// Rates how safe each entry is to delete, see `Safety` and `score_entries`
+    pub fn score_safety(&mut self, now_ms: u64, should_stop: &StopCheck) {
+        score_entries(&mut self.entries, now_ms, should_stop);
+    }
+}
+
+/// Median of an already sorted slice, averaging the middle pair for even lengths
+fn median(sorted: &[u64]) -> Option<u64> {
+    match sorted.len() {
+        0 => None,
+        count if count % 2 == 1 => Some(sorted[count / 2]),
+        count => Some((sorted[count / 2 - 1] + sorted[count / 2]) / 2),
+    }
+}
+
+/// Entries larger than the median size of their category, largest first.
+/// A category with a single entry has no outliers since it is its own median.
+pub fn find_category_outliers(entries: &[DirectoryEntry]) -> Vec<DirectoryEntry> {
+    let mut sizes_by_category: HashMap<DependencyCategory, Vec<u64>> = HashMap::new();
+    for entry in entries {
+        sizes_by_category
+            .entry(entry.category)
+            .or_default()
+            .push(entry.size_bytes);
+    }
+
+    let medians: HashMap<DependencyCategory, u64> = sizes_by_category
+        .into_iter()
+        .filter_map(|(category, mut sizes)| {
+            sizes.sort_unstable();
+            median(&sizes).map(|median_size| (category, median_size))
+        })
+        .collect();
+
+    let mut outliers: Vec<DirectoryEntry> = entries
+        .iter()
+        .filter(|entry| {
+            medians
+                .get(&entry.category)
+                .is_some_and(|median_size| entry.size_bytes > *median_size)
+        })
+        .cloned()
+        .collect();
+
+    outliers.sort_by_key(|entry| std::cmp::Reverse(entry.size_bytes));
+    outliers
+}
+
+/// A category whose combined size is over its configured threshold
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryExcess {
+    pub category: DependencyCategory,
+    pub size_bytes: u64,
+    pub threshold_bytes: u64,
+}
+
+impl CategoryExcess {
+    pub fn excess_bytes(&self) -> u64 {
+        self.size_bytes.saturating_sub(self.threshold_bytes)
+    }
+}
+
+/// Compares per-category totals against their thresholds, largest overage first
+pub fn find_exceeded_categories(
+    sizes_by_category: &HashMap<DependencyCategory, u64>,
+    thresholds: &HashMap<DependencyCategory, u64>,
+) -> Vec<CategoryExcess> {
+    let mut exceeded: Vec<CategoryExcess> = thresholds
+        .iter()
+        .filter_map(|(category, threshold_bytes)| {
+            let size_bytes = sizes_by_category.get(category).copied().unwrap_or(0);
+            (size_bytes > *threshold_bytes).then_some(CategoryExcess {
+                category: *category,
+                size_bytes,
+                threshold_bytes: *threshold_bytes,
+            })
+        })
+        .collect();
+
+    exceeded.sort_by_key(|excess| std::cmp::Reverse(excess.excess_bytes()));
+    exceeded
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScanStats {
     pub total_size: u64,
@@ -5,15 +5,18 @@ use tempfile::TempDir;
 #[test]
 fn test_dependency_category_all() {
     let all = DependencyCategory::all();
-    assert_eq!(all.len(), 8);
+    assert_eq!(all.len(), 11);
     assert!(all.contains(&DependencyCategory::NodeModules));
     assert!(all.contains(&DependencyCategory::Composer));
     assert!(all.contains(&DependencyCategory::Bundler));
     assert!(all.contains(&DependencyCategory::Pods));
     assert!(all.contains(&DependencyCategory::PythonVenv));
+    assert!(all.contains(&DependencyCategory::CondaEnv));
     assert!(all.contains(&DependencyCategory::ElixirDeps));
     assert!(all.contains(&DependencyCategory::DartTool));
     assert!(all.contains(&DependencyCategory::GoMod));
+    assert!(all.contains(&DependencyCategory::DotNet));
+    assert!(all.contains(&DependencyCategory::BuildArtifacts));
 }
 
 #[test]
@@ -35,6 +38,22 @@ fn test_dependency_category_directory_names() {
         &[".dart_tool"]
     );
     assert_eq!(DependencyCategory::GoMod.directory_names(), &["pkg"]);
+    assert_eq!(
+        DependencyCategory::DotNet.directory_names(),
+        &["bin", "obj", "packages"]
+    );
+    assert_eq!(
+        DependencyCategory::BuildArtifacts.directory_names(),
+        &[
+            ".next",
+            ".nuxt",
+            ".turbo",
+            ".parcel-cache",
+            "cache",
+            "dist",
+            "target"
+        ]
+    );
 }
 
 #[test]
@@ -65,6 +84,16 @@ fn test_dependency_category_from_directory_name() {
     // deps and pkg return None because they need path inspection
     assert_eq!(DependencyCategory::from_directory_name("deps"), None);
     assert_eq!(DependencyCategory::from_directory_name("pkg"), None);
+    assert_eq!(DependencyCategory::from_directory_name("bin"), None);
+    assert_eq!(DependencyCategory::from_directory_name("obj"), None);
+    assert_eq!(DependencyCategory::from_directory_name("packages"), None);
+    // .next and friends are unique to their frameworks, dist and cache need path inspection
+    assert_eq!(
+        DependencyCategory::from_directory_name(".next"),
+        Some(DependencyCategory::BuildArtifacts)
+    );
+    assert_eq!(DependencyCategory::from_directory_name("dist"), None);
+    assert_eq!(DependencyCategory::from_directory_name("cache"), None);
     assert_eq!(DependencyCategory::from_directory_name("src"), None);
     assert_eq!(DependencyCategory::from_directory_name("build"), None);
     assert_eq!(DependencyCategory::from_directory_name("target"), None);
@@ -174,6 +203,132 @@ fn test_from_pkg_directory_not_go() {
     assert_eq!(category, None);
 }
 
+#[test]
+fn test_from_dotnet_build_directory_csproj() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = temp_dir.path().join("bin");
+    fs::create_dir(&bin).unwrap();
+    fs::write(temp_dir.path().join("Api.csproj"), "<Project />").unwrap();
+
+    let category = DependencyCategory::from_dotnet_build_directory(&bin);
+    assert_eq!(category, Some(DependencyCategory::DotNet));
+}
+
+#[test]
+fn test_from_dotnet_build_directory_solution() {
+    let temp_dir = TempDir::new().unwrap();
+    let obj = temp_dir.path().join("obj");
+    fs::create_dir(&obj).unwrap();
+    fs::write(temp_dir.path().join("App.sln"), "").unwrap();
+
+    let category = DependencyCategory::from_dotnet_build_directory(&obj);
+    assert_eq!(category, Some(DependencyCategory::DotNet));
+}
+
+#[test]
+fn test_from_dotnet_build_directory_not_dotnet() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = temp_dir.path().join("bin");
+    fs::create_dir(&bin).unwrap();
+    fs::write(temp_dir.path().join("install.sh"), "").unwrap();
+
+    let category = DependencyCategory::from_dotnet_build_directory(&bin);
+    assert_eq!(category, None);
+}
+
+#[test]
+fn test_from_nuget_packages_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let nuget_packages = temp_dir.path().join(".nuget").join("packages");
+    let workspace_packages = temp_dir.path().join("monorepo").join("packages");
+    fs::create_dir_all(&nuget_packages).unwrap();
+    fs::create_dir_all(&workspace_packages).unwrap();
+
+    assert_eq!(
+        DependencyCategory::from_nuget_packages_directory(&nuget_packages),
+        Some(DependencyCategory::DotNet)
+    );
+    assert_eq!(
+        DependencyCategory::from_nuget_packages_directory(&workspace_packages),
+        None
+    );
+}
+
+#[test]
+fn test_from_dist_directory_javascript() {
+    let temp_dir = TempDir::new().unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir(&dist).unwrap();
+    fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+
+    let category = DependencyCategory::from_dist_directory(&dist);
+    assert_eq!(category, Some(DependencyCategory::BuildArtifacts));
+}
+
+#[test]
+fn test_from_dist_directory_not_javascript() {
+    let temp_dir = TempDir::new().unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir(&dist).unwrap();
+    fs::write(temp_dir.path().join("setup.py"), "").unwrap();
+
+    let category = DependencyCategory::from_dist_directory(&dist);
+    assert_eq!(category, None);
+}
+
+#[test]
+fn test_from_cargo_target_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let crate_target = temp_dir.path().join("crate").join("target");
+    let other_target = temp_dir.path().join("site").join("target");
+    fs::create_dir_all(&crate_target).unwrap();
+    fs::create_dir_all(&other_target).unwrap();
+    fs::write(temp_dir.path().join("crate").join("Cargo.toml"), "").unwrap();
+
+    assert_eq!(
+        DependencyCategory::from_cargo_target_directory(&crate_target),
+        Some(DependencyCategory::BuildArtifacts)
+    );
+    assert_eq!(
+        DependencyCategory::from_cargo_target_directory(&other_target),
+        None
+    );
+}
+
+#[test]
+fn test_from_angular_cache_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let angular_cache = temp_dir.path().join(".angular").join("cache");
+    let other_cache = temp_dir.path().join("app").join("cache");
+    fs::create_dir_all(&angular_cache).unwrap();
+    fs::create_dir_all(&other_cache).unwrap();
+
+    assert_eq!(
+        DependencyCategory::from_angular_cache_directory(&angular_cache),
+        Some(DependencyCategory::BuildArtifacts)
+    );
+    assert_eq!(
+        DependencyCategory::from_angular_cache_directory(&other_cache),
+        None
+    );
+}
+
+#[test]
+fn test_get_build_artifact_directory_names() {
+    let configured = vec![".angular/cache".to_string(), "dist".to_string()];
+    let names = get_build_artifact_directory_names(&configured);
+
+    assert_eq!(names, HashSet::from(["cache", "dist"]));
+}
+
+#[test]
+fn test_get_build_artifact_directory_names_ignores_unknown_entries() {
+    let configured = vec!["out".to_string(), ".next/".to_string()];
+    let names = get_build_artifact_directory_names(&configured);
+
+    assert_eq!(names, HashSet::from([".next"]));
+}
+
 #[test]
 fn test_dependency_category_serialization() {
     let category = DependencyCategory::NodeModules;
@@ -184,6 +339,10 @@ fn test_dependency_category_serialization() {
     let json = serde_json::to_string(&category).unwrap();
     assert_eq!(json, "\"PYTHON_VENV\"");
 
+    let category = DependencyCategory::CondaEnv;
+    let json = serde_json::to_string(&category).unwrap();
+    assert_eq!(json, "\"CONDA_ENV\"");
+
     let category = DependencyCategory::Composer;
     let json = serde_json::to_string(&category).unwrap();
     assert_eq!(json, "\"COMPOSER\"");
@@ -271,6 +430,9 @@ fn test_get_target_directory_names_all() {
     assert!(names.contains("deps"));
     assert!(names.contains(".dart_tool"));
     assert!(names.contains("pkg"));
+    assert!(names.contains("bin"));
+    assert!(names.contains("obj"));
+    assert!(names.contains("packages"));
 }
 
 #[test]
@@ -285,8 +447,14 @@ fn test_get_all_dependency_directory_names() {
     assert!(names.contains("deps"));
     assert!(names.contains(".dart_tool"));
     assert!(names.contains("pkg"));
-    // vendor is shared between Composer and Bundler so 8 unique names
-    assert_eq!(names.len(), 8);
+    assert!(names.contains(".next"));
+    // .NET and dist names are too generic to mark nested directories
+    assert!(!names.contains("bin"));
+    assert!(!names.contains("packages"));
+    assert!(!names.contains("dist"));
+    // vendor is shared between Composer and Bundler, so 8 unique dependency names
+    // plus the 4 framework build caches
+    assert_eq!(names.len(), 12);
 }
 
 #[test]
@@ -298,6 +466,15 @@ fn test_directory_entry_serialization() {
         last_modified_ms: 1704067200000, // 2024-01-01 00:00:00 UTC
         category: DependencyCategory::NodeModules,
         has_only_symlinks: false,
+        last_commit_ms: Some(1704153600000),
+        git_branch: Some("main".to_string()),
+        project_name: None,
+        workspace_root: None,
+        truncated: false,
+        scanned_at_ms: 0,
+        stale: false,
+        age_source: AgeStrategy::NewestFile,
+        safety: Safety::Caution,
     };
 
     let json = serde_json::to_string(&entry).unwrap();
@@ -307,6 +484,22 @@ fn test_directory_entry_serialization() {
     assert!(json.contains("\"lastModifiedMs\":1704067200000"));
     assert!(json.contains("\"category\":\"NODE_MODULES\""));
     assert!(json.contains("\"hasOnlySymlinks\":false"));
+    assert!(json.contains("\"lastCommitMs\":1704153600000"));
+    assert!(json.contains("\"gitBranch\":\"main\""));
+}
+
+#[test]
+fn test_discovered_directory_serialization() {
+    let discovered = DiscoveredDirectory {
+        path: "/Users/test/project/node_modules".to_string(),
+        category: DependencyCategory::NodeModules,
+    };
+
+    let json = serde_json::to_string(&discovered).unwrap();
+    assert_eq!(
+        json,
+        r#"{"path":"/Users/test/project/node_modules","category":"NODE_MODULES"}"#
+    );
 }
 
 #[test]
@@ -338,25 +531,34 @@ fn test_scan_result_serialization() {
     let result = ScanResult {
         entries: vec![
             DirectoryEntry {
-                path: "/project-a/node_modules".to_string(),
-                size_bytes: 1000,
                 file_count: 100,
                 last_modified_ms: 1704067200000,
-                category: DependencyCategory::NodeModules,
-                has_only_symlinks: false,
+                ..DirectoryEntry::for_test(
+                    "/project-a/node_modules",
+                    DependencyCategory::NodeModules,
+                    1000,
+                )
             },
             DirectoryEntry {
-                path: "/project-b/vendor".to_string(),
-                size_bytes: 2000,
                 file_count: 200,
                 last_modified_ms: 1704153600000,
-                category: DependencyCategory::Composer,
                 has_only_symlinks: true,
+                ..DirectoryEntry::for_test("/project-b/vendor", DependencyCategory::Composer, 2000)
             },
         ],
         total_size: 3000,
         scan_time_ms: 1500,
         skipped_count: 5,
+        total_file_count: 0,
+        average_entry_size: 0,
+        median_age_ms: None,
+        suggested_categories: Vec::new(),
+        cache_entries: Vec::new(),
+        coverage: ScanCoverage::default(),
+        disk_usage: None,
+        workspaces: Vec::new(),
+        partial: false,
+        skipped_entries: Vec::new(),
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -397,12 +599,10 @@ fn test_scan_stats_with_null_path() {
 #[test]
 fn test_directory_entry_clone() {
     let original = DirectoryEntry {
-        path: "/test/node_modules".to_string(),
-        size_bytes: 1024,
         file_count: 50,
         last_modified_ms: 1704067200000,
-        category: DependencyCategory::NodeModules,
         has_only_symlinks: true,
+        ..DirectoryEntry::for_test("/test/node_modules", DependencyCategory::NodeModules, 1024)
     };
 
     let cloned = original.clone();
@@ -414,6 +614,14 @@ fn test_directory_entry_clone() {
     assert_eq!(cloned.has_only_symlinks, original.has_only_symlinks);
 }
 
+#[test]
+fn test_scan_result_without_coverage_deserializes() {
+    let json = r#"{"entries":[],"totalSize":0,"scanTimeMs":5,"skippedCount":0}"#;
+    let parsed: ScanResult = serde_json::from_str(json).unwrap();
+
+    assert_eq!(parsed.coverage, ScanCoverage::default());
+}
+
 #[test]
 fn test_scan_result_empty() {
     let result = ScanResult {
@@ -421,6 +629,16 @@ fn test_scan_result_empty() {
         total_size: 0,
         scan_time_ms: 50,
         skipped_count: 0,
+        total_file_count: 0,
+        average_entry_size: 0,
+        median_age_ms: None,
+        suggested_categories: Vec::new(),
+        cache_entries: Vec::new(),
+        coverage: ScanCoverage::default(),
+        disk_usage: None,
+        workspaces: Vec::new(),
+        partial: false,
+        skipped_entries: Vec::new(),
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -440,7 +658,392 @@ fn test_dependency_category_labels() {
     assert_eq!(DependencyCategory::Bundler.label(), "Ruby (vendor)");
     assert_eq!(DependencyCategory::Pods.label(), "iOS (Pods)");
     assert_eq!(DependencyCategory::PythonVenv.label(), "Python (venv)");
+    assert_eq!(DependencyCategory::CondaEnv.label(), "Python (conda)");
     assert_eq!(DependencyCategory::ElixirDeps.label(), "Elixir (deps)");
     assert_eq!(DependencyCategory::DartTool.label(), "Dart (dart_tool)");
     assert_eq!(DependencyCategory::GoMod.label(), "Go (pkg/mod)");
+    assert_eq!(DependencyCategory::DotNet.label(), ".NET (bin/obj)");
+    assert_eq!(
+        DependencyCategory::BuildArtifacts.label(),
+        "Build output (dist)"
+    );
+}
+
+#[test]
+fn test_dependency_category_label_keys_are_unique() {
+    let keys: HashSet<&str> = DependencyCategory::all()
+        .iter()
+        .map(DependencyCategory::label_key)
+        .collect();
+    assert_eq!(keys.len(), DependencyCategory::all().len());
+}
+
+#[test]
+fn test_dependency_category_label_keys_exist_in_english_locale() {
+    let locale: serde_json::Value =
+        serde_json::from_str(include_str!("../../../locales/en.json")).unwrap();
+
+    for category in DependencyCategory::all() {
+        let key = category.label_key();
+        let translated = key
+            .split('.')
+            .try_fold(&locale, |value, part| value.get(part));
+        assert!(
+            translated.is_some_and(serde_json::Value::is_string),
+            "{key} is missing from locales/en.json"
+        );
+    }
+}
+
+#[test]
+fn test_localized_label_serialization() {
+    let json = serde_json::to_string(&DependencyCategory::GoMod.localized_label("en")).unwrap();
+    assert_eq!(
+        json,
+        r#"{"category":"GO_MOD","key":"categories.go","label":"Go (pkg/mod)"}"#
+    );
+}
+
+#[test]
+fn test_translated_label_reads_locale_files() {
+    assert_eq!(
+        DependencyCategory::NodeModules.translated_label("en"),
+        "Node (modules)"
+    );
+    assert_eq!(
+        DependencyCategory::NodeModules.translated_label("ru"),
+        "Node (модули)"
+    );
+    // Languages without a translation fall back to English
+    assert_eq!(
+        DependencyCategory::PythonVenv.translated_label("sv"),
+        "Python (venv)"
+    );
+}
+
+#[test]
+fn test_dependency_category_from_path() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let node_modules = temp_dir.path().join("node_modules");
+    assert_eq!(
+        DependencyCategory::from_path(&node_modules),
+        Some(DependencyCategory::NodeModules)
+    );
+
+    let project = temp_dir.path().join("elixir-app");
+    fs::create_dir_all(project.join("deps")).unwrap();
+    fs::write(project.join("mix.exs"), "").unwrap();
+    assert_eq!(
+        DependencyCategory::from_path(&project.join("deps")),
+        Some(DependencyCategory::ElixirDeps)
+    );
+
+    assert_eq!(
+        DependencyCategory::from_path(&temp_dir.path().join("src")),
+        None
+    );
+}
+
+#[test]
+fn test_from_environment_entries() {
+    assert_eq!(
+        DependencyCategory::from_environment_entries(["bin", "lib", "pyvenv.cfg"]),
+        Some(DependencyCategory::PythonVenv)
+    );
+    assert_eq!(
+        DependencyCategory::from_environment_entries(["bin", "conda-meta", "lib"]),
+        Some(DependencyCategory::CondaEnv)
+    );
+    assert_eq!(
+        DependencyCategory::from_environment_entries(["src", "README.md"]),
+        None
+    );
+}
+
+#[test]
+fn test_from_environment_entries_ignores_conda_base_installation() {
+    assert_eq!(
+        DependencyCategory::from_environment_entries(["bin", "conda-meta", "condabin", "envs"]),
+        None
+    );
+}
+
+#[test]
+fn test_from_path_detects_environments_with_arbitrary_names() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let virtualenv = temp_dir.path().join("my-project-env");
+    fs::create_dir_all(&virtualenv).unwrap();
+    fs::write(virtualenv.join("pyvenv.cfg"), "home = /usr/bin").unwrap();
+    assert_eq!(
+        DependencyCategory::from_path(&virtualenv),
+        Some(DependencyCategory::PythonVenv)
+    );
+
+    let conda_env = temp_dir.path().join("envs").join("data-science");
+    fs::create_dir_all(conda_env.join("conda-meta")).unwrap();
+    assert_eq!(
+        DependencyCategory::from_path(&conda_env),
+        Some(DependencyCategory::CondaEnv)
+    );
+
+    let conda_base = temp_dir.path().join("miniconda3");
+    fs::create_dir_all(conda_base.join("conda-meta")).unwrap();
+    fs::create_dir_all(conda_base.join("condabin")).unwrap();
+    assert_eq!(DependencyCategory::from_path(&conda_base), None);
+}
+
+fn sized_entry(size_bytes: u64, file_count: usize, last_modified_ms: u64) -> DirectoryEntry {
+    DirectoryEntry {
+        file_count,
+        last_modified_ms,
+        ..DirectoryEntry::for_test(
+            &format!("/project-{size_bytes}/node_modules"),
+            DependencyCategory::NodeModules,
+            size_bytes,
+        )
+    }
+}
+
+#[test]
+fn test_scan_result_from_entries_aggregates() {
+    let now_ms = 10_000;
+    let result = ScanResult::from_entries(
+        vec![
+            sized_entry(300, 10, 9_000),
+            sized_entry(100, 5, 7_000),
+            sized_entry(200, 1, 4_000),
+        ],
+        42,
+        3,
+        now_ms,
+    );
+
+    assert_eq!(result.total_size, 600);
+    assert_eq!(result.total_file_count, 16);
+    assert_eq!(result.average_entry_size, 200);
+    assert_eq!(result.median_age_ms, Some(3_000));
+    assert_eq!(result.scan_time_ms, 42);
+    assert_eq!(result.skipped_count, 3);
+}
+
+#[test]
+fn test_scan_result_from_entries_even_count_median_skips_missing_timestamps() {
+    let result = ScanResult::from_entries(
+        vec![
+            sized_entry(1, 1, 8_000),
+            sized_entry(1, 1, 0),
+            sized_entry(1, 1, 6_000),
+        ],
+        0,
+        0,
+        10_000,
+    );
+
+    assert_eq!(result.median_age_ms, Some(3_000));
+}
+
+#[test]
+fn test_scan_result_from_entries_empty() {
+    let result = ScanResult::from_entries(Vec::new(), 0, 0, 10_000);
+
+    assert_eq!(result.total_size, 0);
+    assert_eq!(result.average_entry_size, 0);
+    assert!(result.median_age_ms.is_none());
+
+    let json = serde_json::to_string(&result).unwrap();
+    assert!(json.contains("\"totalFileCount\":0"));
+    assert!(json.contains("\"averageEntrySize\":0"));
+    assert!(json.contains("\"medianAgeMs\":null"));
+}
+
+#[test]
+fn test_directory_entry_is_stale() {
+    let mut entry = sized_entry(1, 1, 0);
+    entry.scanned_at_ms = 10_000;
+
+    assert!(!entry.is_stale(10_000, 5_000));
+    assert!(!entry.is_stale(15_000, 5_000));
+    assert!(entry.is_stale(15_001, 5_000));
+    assert!(!entry.is_stale(9_000, 5_000));
+}
+
+#[test]
+fn test_scan_result_mark_stale_entries() {
+    let mut fresh = sized_entry(2, 1, 0);
+    fresh.scanned_at_ms = 9_000;
+    let mut old = sized_entry(1, 1, 0);
+    old.scanned_at_ms = 1_000;
+    let unknown = sized_entry(3, 1, 0);
+
+    let mut result = ScanResult::from_entries(vec![fresh, old, unknown], 0, 0, 10_000);
+    result.mark_stale_entries(10_000, 5_000);
+
+    let stale: Vec<bool> = result.entries.iter().map(|entry| entry.stale).collect();
+    assert_eq!(stale, vec![false, true, true]);
+
+    let json = serde_json::to_string(&result.entries[1]).unwrap();
+    assert!(json.contains("\"scannedAtMs\":1000"));
+    assert!(json.contains("\"stale\":true"));
+}
+
+#[test]
+fn test_find_exceeded_categories_orders_by_overage() {
+    let sizes = HashMap::from([
+        (DependencyCategory::NodeModules, 12_000),
+        (DependencyCategory::Pods, 2_500),
+        (DependencyCategory::Composer, 900),
+    ]);
+    let thresholds = HashMap::from([
+        (DependencyCategory::NodeModules, 10_000),
+        (DependencyCategory::Pods, 2_000),
+        (DependencyCategory::Composer, 1_000),
+        (DependencyCategory::GoMod, 0),
+    ]);
+
+    let exceeded = find_exceeded_categories(&sizes, &thresholds);
+
+    assert_eq!(exceeded.len(), 2);
+    assert_eq!(exceeded[0].category, DependencyCategory::NodeModules);
+    assert_eq!(exceeded[0].excess_bytes(), 2_000);
+    assert_eq!(exceeded[1].category, DependencyCategory::Pods);
+}
+
+#[test]
+fn test_find_exceeded_categories_without_thresholds() {
+    let sizes = HashMap::from([(DependencyCategory::NodeModules, 12_000)]);
+    assert!(find_exceeded_categories(&sizes, &HashMap::new()).is_empty());
+}
+
+fn category_entry(category: DependencyCategory, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry {
+        category,
+        ..sized_entry(size_bytes, 1, 0)
+    }
+}
+
+#[test]
+fn test_find_category_outliers_uses_per_category_median() {
+    let entries = vec![
+        category_entry(DependencyCategory::NodeModules, 100),
+        category_entry(DependencyCategory::NodeModules, 200),
+        category_entry(DependencyCategory::NodeModules, 900),
+        category_entry(DependencyCategory::PythonVenv, 10),
+        category_entry(DependencyCategory::PythonVenv, 30),
+    ];
+
+    let outliers = find_category_outliers(&entries);
+    let sizes: Vec<(DependencyCategory, u64)> = outliers
+        .iter()
+        .map(|entry| (entry.category, entry.size_bytes))
+        .collect();
+
+    // node_modules median is 200, venv median is the 10/30 average of 20
+    assert_eq!(
+        sizes,
+        vec![
+            (DependencyCategory::NodeModules, 900),
+            (DependencyCategory::PythonVenv, 30),
+        ]
+    );
+}
+
+#[test]
+fn test_find_category_outliers_single_entry_category() {
+    let entries = vec![category_entry(DependencyCategory::Pods, 5_000)];
+    assert!(find_category_outliers(&entries).is_empty());
+}
+
+#[test]
+fn test_find_category_outliers_empty() {
+    assert!(find_category_outliers(&[]).is_empty());
+}
+
+fn workspace_entry(workspace_root: Option<&str>, size_bytes: u64) -> DirectoryEntry {
+    DirectoryEntry {
+        workspace_root: workspace_root.map(str::to_string),
+        ..sized_entry(size_bytes, 1, 0)
+    }
+}
+
+#[test]
+fn test_group_workspaces_sums_per_root() {
+    let entries = vec![
+        workspace_entry(Some("/work/monorepo"), 100),
+        workspace_entry(Some("/work/monorepo"), 250),
+        workspace_entry(Some("/work/tools"), 500),
+        workspace_entry(None, 1_000),
+    ];
+
+    assert_eq!(
+        group_workspaces(&entries),
+        vec![
+            WorkspaceSummary {
+                root: "/work/tools".to_string(),
+                entry_count: 1,
+                total_size: 500,
+            },
+            WorkspaceSummary {
+                root: "/work/monorepo".to_string(),
+                entry_count: 2,
+                total_size: 350,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_from_entries_groups_workspaces() {
+    let result = ScanResult::from_entries(
+        vec![
+            workspace_entry(Some("/work/monorepo"), 100),
+            workspace_entry(Some("/work/monorepo"), 250),
+        ],
+        0,
+        0,
+        0,
+    );
+
+    assert_eq!(result.workspaces.len(), 1);
+    assert_eq!(result.workspaces[0].total_size, 350);
+}
+
+#[test]
+fn test_skip_reason_from_io_error() {
+    use std::io::{Error, ErrorKind};
+
+    assert_eq!(
+        SkipReason::from_io_error(&Error::from(ErrorKind::PermissionDenied)),
+        SkipReason::PermissionDenied
+    );
+    assert_eq!(
+        SkipReason::from_io_error(&Error::from(ErrorKind::NotFound)),
+        SkipReason::NotFound
+    );
+    assert_eq!(
+        SkipReason::from_io_error(&Error::from(ErrorKind::InvalidData)),
+        SkipReason::Other
+    );
+}
+
+#[test]
+fn test_scan_result_without_skipped_entries_deserializes() {
+    let json = r#"{"entries":[],"totalSize":0,"scanTimeMs":0,"skippedCount":3}"#;
+    let result: ScanResult = serde_json::from_str(json).unwrap();
+
+    assert_eq!(result.skipped_count, 3);
+    assert!(result.skipped_entries.is_empty());
+}
+
+#[test]
+fn test_skipped_entry_serializes_kind() {
+    let entry = SkippedEntry {
+        path: "/Users/test/Library/Mail".to_string(),
+        kind: SkipReason::PermissionDenied,
+    };
+
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["kind"], "PERMISSION_DENIED");
+    assert_eq!(json["path"], "/Users/test/Library/Mail");
 }
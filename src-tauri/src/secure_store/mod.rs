@@ -0,0 +1,195 @@
+//! Keeps secrets such as the license key out of the plaintext config files: in the
+//! Keychain on macOS, and encrypted with DPAPI on Windows. Other platforms have no
+//! store, so callers keep writing those secrets to their own files there.
+
+use tracing::debug;
+
+/// Whether this platform has somewhere safer than a config file to put secrets
+pub fn is_available() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+/// Saves a secret under `name`, replacing any earlier value.
+/// Returns false without saving when the platform has no secure store.
+pub fn store_secret(name: &str, secret: &[u8]) -> Result<bool, String> {
+    if !is_available() {
+        return Ok(false);
+    }
+
+    platform::store(name, secret)?;
+    debug!(name, "Stored secret");
+    Ok(true)
+}
+
+/// Reads the secret saved under `name`, None when there isn't one
+pub fn load_secret(name: &str) -> Result<Option<Vec<u8>>, String> {
+    if !is_available() {
+        return Ok(None);
+    }
+
+    platform::load(name)
+}
+
+/// Removes the secret saved under `name`; one that doesn't exist is not an error
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    if !is_available() {
+        return Ok(());
+    }
+
+    platform::delete(name)
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use crate::config;
+    use security_framework::passwords::{
+        delete_generic_password, get_generic_password, set_generic_password,
+    };
+
+    /// `errSecItemNotFound` from <Security/SecBase.h>
+    const ITEM_NOT_FOUND: i32 = -25300;
+
+    pub fn store(name: &str, secret: &[u8]) -> Result<(), String> {
+        set_generic_password(config::app::KEYCHAIN_SERVICE, name, secret)
+            .map_err(|error| format!("Failed to save to the Keychain: {error}"))
+    }
+
+    pub fn load(name: &str) -> Result<Option<Vec<u8>>, String> {
+        match get_generic_password(config::app::KEYCHAIN_SERVICE, name) {
+            Ok(secret) => Ok(Some(secret)),
+            Err(error) if error.code() == ITEM_NOT_FOUND => Ok(None),
+            Err(error) => Err(format!("Failed to read from the Keychain: {error}")),
+        }
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        match delete_generic_password(config::app::KEYCHAIN_SERVICE, name) {
+            Err(error) if error.code() != ITEM_NOT_FOUND => {
+                Err(format!("Failed to remove from the Keychain: {error}"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use crate::config;
+    use crate::persistence::config_path;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::ptr;
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Cryptography::{
+        CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    fn secret_path(name: &str) -> Result<PathBuf, String> {
+        config_path(&format!("{name}.secret"))
+    }
+
+    /// Runs a DPAPI call on `data`, copying out and freeing the buffer it allocates
+    fn transform(
+        data: &[u8],
+        call: impl FnOnce(*const CRYPT_INTEGER_BLOB, *mut CRYPT_INTEGER_BLOB) -> i32,
+    ) -> Option<Vec<u8>> {
+        let input = CRYPT_INTEGER_BLOB {
+            cbData: u32::try_from(data.len()).ok()?,
+            pbData: data.as_ptr().cast_mut(),
+        };
+        let mut output = CRYPT_INTEGER_BLOB {
+            cbData: 0,
+            pbData: ptr::null_mut(),
+        };
+
+        if call(&input, &mut output) == 0 {
+            return None;
+        }
+
+        // DPAPI allocated `output` with LocalAlloc and reported its length
+        unsafe {
+            let bytes = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+            LocalFree(output.pbData.cast());
+            Some(bytes)
+        }
+    }
+
+    pub fn store(name: &str, secret: &[u8]) -> Result<(), String> {
+        let encrypted = transform(secret, |input, output| unsafe {
+            CryptProtectData(
+                input,
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                CRYPTPROTECT_UI_FORBIDDEN,
+                output,
+            )
+        })
+        .ok_or_else(|| {
+            format!(
+                "Failed to encrypt secret: {}",
+                std::io::Error::last_os_error()
+            )
+        })?;
+
+        fs::write(secret_path(name)?, encrypted)
+            .map_err(|error| format!("Failed to write secret: {error}"))
+    }
+
+    pub fn load(name: &str) -> Result<Option<Vec<u8>>, String> {
+        let path = secret_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let encrypted =
+            fs::read(&path).map_err(|error| format!("Failed to read secret: {error}"))?;
+        transform(&encrypted, |input, output| unsafe {
+            CryptUnprotectData(
+                input,
+                ptr::null_mut(),
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                CRYPTPROTECT_UI_FORBIDDEN,
+                output,
+            )
+        })
+        .map(Some)
+        .ok_or_else(|| {
+            format!(
+                "Failed to decrypt secret: {}",
+                std::io::Error::last_os_error()
+            )
+        })
+    }
+
+    pub fn delete(name: &str) -> Result<(), String> {
+        let path = secret_path(name)?;
+        if !path.exists() {
+            return Ok(());
+        }
+
+        fs::remove_file(path).map_err(|error| format!("Failed to remove secret: {error}"))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    pub fn store(_name: &str, _secret: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn load(_name: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(None)
+    }
+
+    pub fn delete(_name: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn test_unsupported_platform_stores_nothing() {
+    assert!(!is_available());
+    assert_eq!(store_secret("test_secret", b"value"), Ok(false));
+    assert_eq!(load_secret("test_secret"), Ok(None));
+    assert_eq!(delete_secret("test_secret"), Ok(()));
+}
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+#[ignore = "writes to the user's Keychain or config directory"]
+fn test_secret_round_trip() {
+    let name = "deptox_test_secret";
+
+    assert_eq!(store_secret(name, b"ABCD-1234"), Ok(true));
+    assert_eq!(load_secret(name), Ok(Some(b"ABCD-1234".to_vec())));
+
+    delete_secret(name).unwrap();
+    assert_eq!(load_secret(name), Ok(None));
+    assert_eq!(delete_secret(name), Ok(()));
+}
@@ -0,0 +1,53 @@
+use crate::commands::settings::AppSettings;
+use crate::config::sounds::LONG_SCAN_MS;
+use tracing::debug;
+
+/// Audible cues for work that usually finishes while the window is hidden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    ScanComplete,
+    SpaceReclaimed,
+}
+
+/// Cue for a finished scan, None when sounds are off or the scan was quick
+pub fn scan_cue(settings: &AppSettings, scan_time_ms: u128) -> Option<Cue> {
+    (settings.completion_sounds && scan_time_ms >= LONG_SCAN_MS).then_some(Cue::ScanComplete)
+}
+
+/// Cue for a finished cleanup, None when sounds are off or it freed less than the threshold
+pub fn reclaim_cue(settings: &AppSettings, bytes_freed: u64) -> Option<Cue> {
+    let threshold = settings.reclaim_sound_threshold_bytes;
+    (settings.completion_sounds && threshold > 0 && bytes_freed >= threshold)
+        .then_some(Cue::SpaceReclaimed)
+}
+
+/// Built-in system sound played for each cue
+#[cfg(target_os = "macos")]
+fn system_sound_name(cue: Cue) -> &'static str {
+    match cue {
+        Cue::ScanComplete => "Glass",
+        Cue::SpaceReclaimed => "Hero",
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn play(cue: Cue) {
+    use objc2_app_kit::NSSound;
+    use objc2_foundation::NSString;
+
+    let name = NSString::from_str(system_sound_name(cue));
+    match NSSound::soundNamed(&name) {
+        Some(sound) if sound.play() => debug!(?cue, "Played sound"),
+        Some(_) => tracing::warn!(?cue, "Failed to play sound"),
+        None => tracing::warn!(?cue, "System sound not found"),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn play(cue: Cue) {
+    debug!(?cue, "Sounds are only played on macOS");
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
@@ -0,0 +1,48 @@
+use super::*;
+use crate::config::bytes::GB;
+
+fn settings_with_sounds(enabled: bool) -> AppSettings {
+    AppSettings {
+        completion_sounds: enabled,
+        reclaim_sound_threshold_bytes: 5 * GB as u64,
+        ..AppSettings::default()
+    }
+}
+
+#[test]
+fn test_scan_cue_only_for_long_scans() {
+    let settings = settings_with_sounds(true);
+
+    assert_eq!(scan_cue(&settings, LONG_SCAN_MS), Some(Cue::ScanComplete));
+    assert_eq!(scan_cue(&settings, LONG_SCAN_MS - 1), None);
+}
+
+#[test]
+fn test_reclaim_cue_only_above_threshold() {
+    let settings = settings_with_sounds(true);
+
+    assert_eq!(
+        reclaim_cue(&settings, 6 * GB as u64),
+        Some(Cue::SpaceReclaimed)
+    );
+    assert_eq!(reclaim_cue(&settings, 4 * GB as u64), None);
+}
+
+#[test]
+fn test_zero_reclaim_threshold_disables_cleanup_cue() {
+    let settings = AppSettings {
+        reclaim_sound_threshold_bytes: 0,
+        ..settings_with_sounds(true)
+    };
+
+    assert_eq!(reclaim_cue(&settings, 0), None);
+    assert_eq!(reclaim_cue(&settings, 6 * GB as u64), None);
+}
+
+#[test]
+fn test_no_cues_when_sounds_are_off() {
+    let settings = settings_with_sounds(false);
+
+    assert_eq!(scan_cue(&settings, LONG_SCAN_MS * 10), None);
+    assert_eq!(reclaim_cue(&settings, 60 * GB as u64), None);
+}
@@ -0,0 +1,306 @@
+use crate::commands::settings::get_settings_sync;
+use crate::config;
+use crate::persistence::config_dir;
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::fs;
+use std::future::Future;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, error, info, warn};
+
+tokio::task_local! {
+    static TASK_NAME: &'static str;
+}
+
+thread_local! {
+    static THREAD_TASK_NAME: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Mirrors whether `crash_report_url` is set, so the panic hook doesn't read the
+/// settings file from a thread that may already hold locks or be out of memory
+static CRASH_REPORTING: AtomicBool = AtomicBool::new(false);
+
+/// Stands in for each path removed from a crash report
+const REDACTED_PATH: &str = "<path>";
+
+/// What a crash report sends. Nothing here names the user or their projects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    /// The background task or worker pool that panicked, when it was named
+    pub task: Option<String>,
+    pub thread: Option<String>,
+    pub location: Option<String>,
+    pub message: String,
+    pub timestamp_ms: u64,
+}
+
+impl CrashReport {
+    fn new(
+        message: &str,
+        location: Option<String>,
+        task: Option<&str>,
+        thread: Option<&str>,
+        timestamp_ms: u64,
+    ) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            task: task.map(str::to_string),
+            thread: thread.map(str::to_string),
+            location: location.map(|location| anonymize(&location)),
+            message: anonymize(message),
+            timestamp_ms,
+        }
+    }
+}
+
+/// Whether a path starts at the beginning of `text`: `/…`, `~/…`, `C:\…` or `\\server\…`
+fn starts_path(text: &str) -> bool {
+    let mut characters = text.chars();
+    match (characters.next(), characters.next(), characters.next()) {
+        (Some('/'), _, _) => true,
+        (Some('~'), Some('/' | '\\'), _) => true,
+        (Some('\\'), Some('\\'), _) => true,
+        (Some(drive), Some(':'), Some('/' | '\\')) => drive.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+/// Characters a path in a message can follow, so `src/core.rs` and URLs are left alone
+fn is_path_boundary(character: char) -> bool {
+    character.is_whitespace() || "\"'`([{<=,;".contains(character)
+}
+
+/// Characters that end an unquoted path
+fn ends_path(character: char) -> bool {
+    character.is_whitespace() || "\"'`)]}>,;".contains(character)
+}
+
+/// Replaces every absolute or home-relative path with `<path>`, since the folders along
+/// one name the user and their projects. A quoted path is removed up to its closing
+/// quote, spaces included. Caps the length so a message can't carry a whole file listing.
+fn anonymize(text: &str) -> String {
+    let mut anonymized = String::with_capacity(text.len());
+    let mut previous = None;
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        let at_boundary = previous.is_none_or(is_path_boundary);
+        if !(at_boundary && starts_path(rest)) {
+            anonymized.push(character);
+            previous = Some(character);
+            rest = &rest[character.len_utf8()..];
+            continue;
+        }
+
+        let end = match previous.filter(|quote| "\"'`".contains(*quote)) {
+            Some(quote) => rest.find(quote),
+            None => rest.find(ends_path),
+        }
+        .unwrap_or(rest.len());
+        // Punctuation ending a sentence or introducing an error isn't part of the path
+        let path = rest[..end].trim_end_matches(['.', ':']);
+        anonymized.push_str(REDACTED_PATH);
+        previous = REDACTED_PATH.chars().next_back();
+        rest = &rest[path.len()..];
+    }
+
+    anonymized
+        .chars()
+        .take(config::telemetry::MAX_CRASH_MESSAGE_CHARS)
+        .collect()
+}
+
+/// Where the panic happened. Sources built from an absolute path, such as dependencies
+/// compiled on the build machine, are reduced to their file name.
+fn source_location(file: &str, line: u32) -> String {
+    let file = if starts_path(file) {
+        Path::new(file)
+            .file_name()
+            .map_or(REDACTED_PATH.into(), |name| name.to_string_lossy())
+    } else {
+        file.into()
+    };
+    format!("{file}:{line}")
+}
+
+/// Called whenever settings are loaded at launch or saved
+pub fn set_crash_reporting(enabled: bool) {
+    CRASH_REPORTING.store(enabled, Ordering::Relaxed);
+}
+
+fn panic_message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
+}
+
+fn current_task() -> Option<&'static str> {
+    TASK_NAME
+        .try_with(|name| *name)
+        .ok()
+        .or_else(|| THREAD_TASK_NAME.with(Cell::get))
+}
+
+fn reports_directory() -> Option<PathBuf> {
+    config_dir()
+        .ok()
+        .map(|config_dir| config_dir.join(config::telemetry::CRASH_REPORTS_DIRECTORY))
+}
+
+fn save_report(directory: &Path, report: &CrashReport) -> Result<(), String> {
+    fs::create_dir_all(directory)
+        .map_err(|error| format!("Failed to create crash report directory: {error}"))?;
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|error| format!("Failed to serialize crash report: {error}"))?;
+    fs::write(
+        directory.join(format!("crash-{}.json", report.timestamp_ms)),
+        content,
+    )
+    .map_err(|error| format!("Failed to write crash report: {error}"))
+}
+
+/// Reports waiting to be sent, oldest first. Unreadable files are removed.
+fn pending_reports(directory: &Path) -> Vec<(PathBuf, CrashReport)> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let report = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+            if report.is_none() {
+                warn!(path = %path.display(), "Removing unreadable crash report");
+                let _ = fs::remove_file(&path);
+            }
+            report.map(|report| (path, report))
+        })
+        .collect()
+}
+
+/// Logs every panic, which reaches the log file, and saves an anonymized report for the
+/// next launch when crash reporting is on. Release builds abort on panic, so nothing can
+/// be sent from inside the hook.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info);
+        let location = info
+            .location()
+            .map(|location| source_location(location.file(), location.line()));
+        let thread = std::thread::current();
+        let task = current_task();
+
+        error!(
+            task,
+            thread = thread.name(),
+            location = location.as_deref(),
+            %message,
+            "Panic"
+        );
+
+        if CRASH_REPORTING.load(Ordering::Relaxed) {
+            let report = CrashReport::new(&message, location, task, thread.name(), time::now_ms());
+            if let Some(directory) = reports_directory() {
+                if let Err(error) = save_report(&directory, &report) {
+                    error!(%error, "Failed to save crash report");
+                }
+            }
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// Posts reports saved by earlier runs. Reports left over after crash reporting was
+/// turned off are discarded rather than sent.
+pub async fn send_pending_reports() {
+    let Some(directory) = reports_directory() else {
+        return;
+    };
+    let pending = pending_reports(&directory);
+    if pending.is_empty() {
+        return;
+    }
+
+    let Some(url) = get_settings_sync()
+        .ok()
+        .and_then(|settings| settings.crash_report_url)
+    else {
+        info!(count = pending.len(), "Discarding crash reports");
+        for (path, _) in pending {
+            let _ = fs::remove_file(path);
+        }
+        return;
+    };
+
+    let client = reqwest::Client::new();
+    for (path, report) in pending {
+        let sent = client
+            .post(&url)
+            .timeout(config::telemetry::CRASH_REPORT_TIMEOUT)
+            .json(&report)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match sent {
+            Ok(_) => {
+                debug!(timestamp_ms = report.timestamp_ms, "Crash report sent");
+                let _ = fs::remove_file(path);
+            }
+            Err(error) => {
+                // Left in place to retry on the next launch
+                warn!(%error, "Failed to send crash report");
+                return;
+            }
+        }
+    }
+}
+
+/// Spawns a background task named for crash reports, logging it if it stops by panicking
+pub fn spawn_monitored<F>(task: &'static str, future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let handle = tauri::async_runtime::spawn(TASK_NAME.scope(task, future));
+    tauri::async_runtime::spawn(async move {
+        if let Err(error) = handle.await {
+            error!(task, %error, "Background task stopped unexpectedly");
+        }
+    });
+}
+
+/// Names the work done on the current thread for crash reports, for worker threads
+/// that run outside the async runtime
+pub fn name_thread_task(task: &'static str) {
+    THREAD_TASK_NAME.with(|name| name.set(Some(task)));
+}
+
+#[cfg(test)]
+#[path = "crash.test.rs"]
+mod tests;
@@ -0,0 +1,137 @@
+use super::*;
+use tempfile::TempDir;
+
+fn report(timestamp_ms: u64) -> CrashReport {
+    CrashReport::new(
+        "index out of bounds",
+        Some("src/scanner/core.rs:42".to_string()),
+        Some("size_pool"),
+        Some("size-calc-0"),
+        timestamp_ms,
+    )
+}
+
+#[test]
+fn test_anonymize_removes_paths() {
+    assert_eq!(
+        anonymize("Failed to read /Users/alex/code/acme-billing/node_modules: denied"),
+        "Failed to read <path>: denied"
+    );
+    assert_eq!(
+        anonymize("no entry for ~/work/secret-app."),
+        "no entry for <path>."
+    );
+    assert_eq!(
+        anonymize(r"cannot open C:\Users\alex\client-site\vendor"),
+        "cannot open <path>"
+    );
+    assert_eq!(
+        anonymize(r"share \\fileserver\projects\acme is offline"),
+        "share <path> is offline"
+    );
+    assert_eq!(anonymize("no paths here"), "no paths here");
+}
+
+#[test]
+fn test_anonymize_removes_quoted_paths_with_spaces() {
+    assert_eq!(
+        anonymize(r#"path: "/Users/alex/My Projects/acme" (os error 2)"#),
+        r#"path: "<path>" (os error 2)"#
+    );
+}
+
+#[test]
+fn test_anonymize_keeps_relative_paths_and_urls() {
+    assert_eq!(
+        anonymize("panicked at src/scanner/core.rs:42"),
+        "panicked at src/scanner/core.rs:42"
+    );
+    assert_eq!(
+        anonymize("request to https://api.example.com/v1 failed"),
+        "request to https://api.example.com/v1 failed"
+    );
+}
+
+#[test]
+fn test_source_location_drops_absolute_directories() {
+    assert_eq!(source_location("src/lib.rs", 7), "src/lib.rs:7");
+    assert_eq!(
+        source_location(
+            "/Users/builder/.cargo/registry/src/jwalk-0.8.1/src/lib.rs",
+            12
+        ),
+        "lib.rs:12"
+    );
+}
+
+#[test]
+fn test_anonymize_caps_length() {
+    let long_message = "x".repeat(config::telemetry::MAX_CRASH_MESSAGE_CHARS * 2);
+
+    assert_eq!(
+        anonymize(&long_message).chars().count(),
+        config::telemetry::MAX_CRASH_MESSAGE_CHARS
+    );
+}
+
+#[test]
+fn test_crash_report_describes_build() {
+    let report = CrashReport::new(
+        "called unwrap on /Users/alex/code",
+        None,
+        None,
+        Some("main"),
+        1_700_000_000_000,
+    );
+
+    assert_eq!(report.app_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(report.os, std::env::consts::OS);
+    assert_eq!(report.message, "called unwrap on <path>");
+    assert_eq!(report.thread.as_deref(), Some("main"));
+    assert!(report.task.is_none());
+}
+
+#[test]
+fn test_saved_reports_are_pending_oldest_first() {
+    let temp_dir = TempDir::new().unwrap();
+    let directory = temp_dir.path().join("crash_reports");
+    save_report(&directory, &report(2_000)).unwrap();
+    save_report(&directory, &report(1_000)).unwrap();
+
+    let pending = pending_reports(&directory);
+
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].1, report(1_000));
+    assert_eq!(pending[1].1.timestamp_ms, 2_000);
+}
+
+#[test]
+fn test_pending_reports_removes_unreadable_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let broken = temp_dir.path().join("crash-1.json");
+    fs::write(&broken, "not json").unwrap();
+
+    assert!(pending_reports(temp_dir.path()).is_empty());
+    assert!(!broken.exists());
+    assert!(pending_reports(&temp_dir.path().join("missing")).is_empty());
+}
+
+#[test]
+fn test_thread_task_name_is_reported() {
+    std::thread::spawn(|| {
+        assert_eq!(current_task(), None);
+        name_thread_task("size_pool");
+        assert_eq!(current_task(), Some("size_pool"));
+    })
+    .join()
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_task_name_is_reported_inside_scope() {
+    let task = TASK_NAME
+        .scope("background_scan", async { current_task() })
+        .await;
+
+    assert_eq!(task, Some("background_scan"));
+}
@@ -0,0 +1,203 @@
+//! Opt-in usage analytics. Only aggregate numbers are kept, never paths or project
+//! names, and they're uploaded together in one batch a day.
+
+use crate::commands::delete::BatchDeleteResult;
+use crate::commands::settings::get_settings_sync;
+use crate::config;
+use crate::persistence::{config_path, BufferedJsonFile};
+use crate::scanner::ScanResult;
+use crate::time;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Samples recorded since the last upload
+static METRICS: BufferedJsonFile<PendingMetrics> =
+    BufferedJsonFile::new(get_metrics_path, load_metrics_from, save_metrics_to);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MetricSample {
+    #[serde(rename_all = "camelCase")]
+    Scan {
+        duration_ms: u64,
+        entry_count: usize,
+        total_bytes: u64,
+        partial: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Cleanup {
+        bytes_freed: u64,
+        deleted_count: usize,
+        failed_count: usize,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingMetrics {
+    pub samples: Vec<MetricSample>,
+    #[serde(default)]
+    pub last_upload_ms: u64,
+}
+
+impl PendingMetrics {
+    fn push(&mut self, sample: MetricSample) {
+        self.samples.push(sample);
+        let overflow = self
+            .samples
+            .len()
+            .saturating_sub(config::telemetry::MAX_PENDING_METRICS);
+        self.samples.drain(..overflow);
+    }
+
+    fn is_due(&self, now_ms: u64) -> bool {
+        !self.samples.is_empty()
+            && now_ms.saturating_sub(self.last_upload_ms)
+                >= config::telemetry::METRICS_UPLOAD_INTERVAL.as_millis() as u64
+    }
+
+    /// Drops the samples that were uploaded, keeping any recorded while the upload ran
+    fn mark_uploaded(&mut self, sent_count: usize, now_ms: u64) {
+        self.samples.drain(..sent_count.min(self.samples.len()));
+        self.last_upload_ms = now_ms;
+    }
+}
+
+/// What one upload sends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsBatch {
+    pub app_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub samples: Vec<MetricSample>,
+}
+
+impl MetricsBatch {
+    fn new(samples: Vec<MetricSample>) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            samples,
+        }
+    }
+}
+
+fn get_metrics_path() -> Result<PathBuf, String> {
+    config_path(config::app::METRICS_FILENAME)
+}
+
+fn load_metrics_from(path: &Path) -> PendingMetrics {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_metrics_to(path: &Path, metrics: &PendingMetrics) -> Result<(), String> {
+    let content = serde_json::to_string(metrics)
+        .map_err(|error| format!("Failed to serialize usage metrics: {error}"))?;
+
+    fs::write(path, content).map_err(|error| format!("Failed to write usage metrics: {error}"))
+}
+
+/// Analytics need both the user's consent and a build that knows where to send them
+fn enabled() -> bool {
+    config::telemetry::METRICS_URL.is_some()
+        && get_settings_sync().is_ok_and(|settings| settings.usage_analytics)
+}
+
+fn record(sample: MetricSample) {
+    if !enabled() {
+        return;
+    }
+    if let Err(error) = METRICS.update(|pending| pending.push(sample)) {
+        warn!(%error, "Failed to record usage metric");
+    }
+}
+
+pub fn record_scan(result: &ScanResult) {
+    record(MetricSample::Scan {
+        duration_ms: result.scan_time_ms as u64,
+        entry_count: result.entries.len(),
+        total_bytes: result.total_size,
+        partial: result.partial,
+    });
+}
+
+pub fn record_cleanup(batch: &BatchDeleteResult) {
+    if batch.results.is_empty() {
+        return;
+    }
+    record(MetricSample::Cleanup {
+        bytes_freed: batch.total_size_freed,
+        deleted_count: batch.successful_count,
+        failed_count: batch.failed_count,
+    });
+}
+
+/// Writes pending usage metrics to disk
+pub fn flush_metrics() -> Result<bool, String> {
+    METRICS.flush()
+}
+
+/// Uploads pending samples once a day. Samples recorded before analytics were turned
+/// off are discarded, and failed uploads are kept for the next attempt.
+pub async fn upload_if_due() {
+    let Some(url) = config::telemetry::METRICS_URL else {
+        return;
+    };
+
+    if !enabled() {
+        let cleared = METRICS.update(|pending| {
+            let had_samples = !pending.samples.is_empty();
+            pending.samples.clear();
+            had_samples
+        });
+        if cleared == Ok(true) {
+            debug!("Discarded usage metrics");
+            let _ = METRICS.flush();
+        }
+        return;
+    }
+
+    let now_ms = time::now_ms();
+    let samples =
+        match METRICS.read(|pending| pending.is_due(now_ms).then(|| pending.samples.clone())) {
+            Ok(Some(samples)) => samples,
+            Ok(None) => return,
+            Err(error) => {
+                warn!(%error, "Failed to read usage metrics");
+                return;
+            }
+        };
+
+    let sent_count = samples.len();
+    let sent = reqwest::Client::new()
+        .post(url)
+        .timeout(config::telemetry::METRICS_UPLOAD_TIMEOUT)
+        .json(&MetricsBatch::new(samples))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    match sent {
+        Ok(_) => {
+            let uploaded = METRICS
+                .update(|pending| pending.mark_uploaded(sent_count, now_ms))
+                .and_then(|_| METRICS.flush());
+            if let Err(error) = uploaded {
+                warn!(%error, "Failed to clear uploaded usage metrics");
+            }
+            debug!(samples = sent_count, "Uploaded usage metrics");
+        }
+        Err(error) => warn!(%error, "Failed to upload usage metrics"),
+    }
+}
+
+#[cfg(test)]
+#[path = "metrics.test.rs"]
+mod tests;
@@ -0,0 +1,71 @@
+use super::*;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+fn scan_sample(entry_count: usize) -> MetricSample {
+    MetricSample::Scan {
+        duration_ms: 1_200,
+        entry_count,
+        total_bytes: 4_096,
+        partial: false,
+    }
+}
+
+#[test]
+fn test_push_drops_oldest_past_cap() {
+    let mut pending = PendingMetrics::default();
+    for index in 0..config::telemetry::MAX_PENDING_METRICS + 2 {
+        pending.push(scan_sample(index));
+    }
+
+    assert_eq!(
+        pending.samples.len(),
+        config::telemetry::MAX_PENDING_METRICS
+    );
+    assert_eq!(pending.samples[0], scan_sample(2));
+}
+
+#[test]
+fn test_upload_due_once_a_day_with_samples() {
+    let mut pending = PendingMetrics {
+        samples: Vec::new(),
+        last_upload_ms: DAY_MS,
+    };
+    assert!(!pending.is_due(3 * DAY_MS));
+
+    pending.push(scan_sample(1));
+    assert!(!pending.is_due(DAY_MS + DAY_MS / 2));
+    assert!(pending.is_due(2 * DAY_MS));
+}
+
+#[test]
+fn test_mark_uploaded_keeps_samples_recorded_during_upload() {
+    let mut pending = PendingMetrics::default();
+    pending.push(scan_sample(1));
+    pending.push(scan_sample(2));
+    pending.push(scan_sample(3));
+
+    pending.mark_uploaded(2, 5 * DAY_MS);
+
+    assert_eq!(pending.samples, vec![scan_sample(3)]);
+    assert_eq!(pending.last_upload_ms, 5 * DAY_MS);
+}
+
+#[test]
+fn test_batch_serializes_without_paths() {
+    let batch = MetricsBatch::new(vec![
+        scan_sample(12),
+        MetricSample::Cleanup {
+            bytes_freed: 2_048,
+            deleted_count: 3,
+            failed_count: 0,
+        },
+    ]);
+
+    let json = serde_json::to_value(&batch).unwrap();
+    assert_eq!(json["appVersion"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["samples"][0]["kind"], "scan");
+    assert_eq!(json["samples"][0]["entryCount"], 12);
+    assert_eq!(json["samples"][1]["kind"], "cleanup");
+    assert_eq!(json["samples"][1]["bytesFreed"], 2_048);
+}
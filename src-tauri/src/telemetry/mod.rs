@@ -0,0 +1,10 @@
+//! Diagnostics leaving the process: panic capture, opt-in crash reports and usage
+//! metrics, plus span export over OTLP in builds with the `otlp` feature
+
+pub mod crash;
+pub mod metrics;
+#[cfg(feature = "otlp")]
+mod otlp;
+
+#[cfg(feature = "otlp")]
+pub use otlp::{otlp_layer, shutdown};
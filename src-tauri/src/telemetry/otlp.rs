@@ -0,0 +1,47 @@
+use crate::config;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use std::sync::OnceLock;
+use tracing::{warn, Subscriber};
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+static TRACER_PROVIDER: OnceLock<TracerProvider> = OnceLock::new();
+
+/// Builds a tracing layer that exports spans to an OTLP/HTTP collector such as a local Jaeger
+/// The batch processor runs on its own thread, so export never blocks scan or delete work
+pub fn otlp_layer<S>(endpoint: &str) -> Result<OpenTelemetryLayer<S, Tracer>, String>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|error| format!("Failed to create OTLP exporter: {error}"))?;
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::TokioCurrentThread)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            config::telemetry::SERVICE_NAME,
+        )]))
+        .build();
+
+    let tracer = provider.tracer(config::telemetry::SERVICE_NAME);
+    let _ = TRACER_PROVIDER.set(provider);
+
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Exports any buffered spans and stops the exporter
+pub fn shutdown() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(error) = provider.shutdown() {
+            warn!(%error, "Failed to shut down OTLP exporter");
+        }
+    }
+}
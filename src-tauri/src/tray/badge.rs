@@ -0,0 +1,131 @@
+use super::canvas::Canvas;
+use crate::commands::settings::SizeUnitBase;
+use crate::config;
+use crate::scanner::CategoryExcess;
+use tauri::image::Image;
+
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+const GLYPH_SPACING: u32 = 1;
+
+/// 3x5 bitmap for each character a badge label can contain, one row per entry
+/// with the leftmost pixel in the highest of the three bits
+fn glyph(character: char) -> Option<[u8; GLYPH_HEIGHT as usize]> {
+    let rows = match character {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'G' => [0b111, 0b100, 0b101, 0b101, 0b111],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+/// The overage the badge shows, with the same precedence as the tray alert text:
+/// the overall threshold first, then the category with the largest overage
+pub fn badge_excess_bytes(
+    total_size: u64,
+    threshold: u64,
+    exceeded_categories: &[CategoryExcess],
+) -> Option<u64> {
+    if total_size > threshold {
+        return Some(total_size - threshold);
+    }
+
+    exceeded_categories
+        .first()
+        .map(CategoryExcess::excess_bytes)
+}
+
+/// Short label for the badge, e.g. "+1.5G" or "+320M", kept to a few
+/// characters so it stays legible on a 64px icon
+pub fn badge_label(excess_bytes: u64, unit_base: SizeUnitBase) -> String {
+    let base = unit_base.base();
+    let mut value = excess_bytes as f64;
+    let mut unit = None;
+    for label in ['K', 'M', 'G', 'T'] {
+        if value < base {
+            break;
+        }
+        value /= base;
+        unit = Some(label);
+    }
+
+    let Some(unit) = unit else {
+        return format!("+{excess_bytes}B");
+    };
+
+    if value < 10.0 {
+        format!("+{value:.1}{unit}")
+    } else {
+        format!("+{value:.0}{unit}")
+    }
+}
+
+/// Draws the label in a filled badge anchored to the bottom-right corner of the icon.
+/// Characters without a glyph are skipped.
+pub fn render_badge(base: &Image<'_>, label: &str) -> Image<'static> {
+    let scale = config::tray::BADGE_GLYPH_SCALE;
+    let padding = config::tray::BADGE_PADDING;
+    let glyphs: Vec<_> = label.chars().filter_map(glyph).collect();
+
+    let mut canvas = Canvas::from_image(base);
+
+    if glyphs.is_empty() {
+        return canvas.into_image();
+    }
+
+    let glyph_count = glyphs.len() as u32;
+    let text_width = (glyph_count * GLYPH_WIDTH + (glyph_count - 1) * GLYPH_SPACING) * scale;
+    let text_height = GLYPH_HEIGHT * scale;
+    let badge_width = (text_width + padding * 2).min(canvas.width);
+    let badge_height = (text_height + padding * 2).min(canvas.height);
+    let badge_x = canvas.width - badge_width;
+    let badge_y = canvas.height - badge_height;
+
+    canvas.fill_rect(
+        badge_x,
+        badge_y,
+        badge_width,
+        badge_height,
+        config::tray::BADGE_BACKGROUND,
+    );
+
+    let mut cursor_x = badge_x + padding;
+    let text_y = badge_y + padding;
+    for rows in glyphs {
+        for (row_index, row) in rows.iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if row & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                    canvas.fill_rect(
+                        cursor_x + column * scale,
+                        text_y + row_index as u32 * scale,
+                        scale,
+                        scale,
+                        config::tray::BADGE_FOREGROUND,
+                    );
+                }
+            }
+        }
+        cursor_x += (GLYPH_WIDTH + GLYPH_SPACING) * scale;
+    }
+
+    canvas.into_image()
+}
+
+#[cfg(test)]
+#[path = "badge.test.rs"]
+mod tests;
@@ -0,0 +1,123 @@
+use super::*;
+use crate::scanner::DependencyCategory;
+
+const TRANSPARENT: [u8; 4] = [0, 0, 0, 0];
+
+fn blank_icon(size: u32) -> Image<'static> {
+    Image::new_owned(vec![0; (size * size * 4) as usize], size, size)
+}
+
+fn pixel(image: &Image<'_>, x: u32, y: u32) -> [u8; 4] {
+    let offset = ((y * image.width() + x) * 4) as usize;
+    image.rgba()[offset..offset + 4].try_into().unwrap()
+}
+
+#[test]
+fn test_badge_excess_bytes_prefers_total_threshold() {
+    let exceeded = [CategoryExcess {
+        category: DependencyCategory::NodeModules,
+        size_bytes: 500,
+        threshold_bytes: 100,
+    }];
+    assert_eq!(badge_excess_bytes(1_000, 800, &exceeded), Some(200));
+}
+
+#[test]
+fn test_badge_excess_bytes_falls_back_to_category() {
+    let exceeded = [CategoryExcess {
+        category: DependencyCategory::NodeModules,
+        size_bytes: 500,
+        threshold_bytes: 100,
+    }];
+    assert_eq!(badge_excess_bytes(100, 800, &exceeded), Some(400));
+    assert_eq!(badge_excess_bytes(100, 800, &[]), None);
+}
+
+#[test]
+fn test_badge_label_small_values_keep_one_decimal() {
+    assert_eq!(
+        badge_label(1024 * 1024 * 1024 * 3 / 2, SizeUnitBase::Binary),
+        "+1.5G"
+    );
+    assert_eq!(badge_label(1024 * 1024 * 5, SizeUnitBase::Binary), "+5.0M");
+}
+
+#[test]
+fn test_badge_label_large_values_are_rounded() {
+    assert_eq!(
+        badge_label(1024 * 1024 * 320, SizeUnitBase::Binary),
+        "+320M"
+    );
+    assert_eq!(
+        badge_label(1024 * 1024 * 1024 * 18, SizeUnitBase::Binary),
+        "+18G"
+    );
+    assert_eq!(
+        badge_label(1024 * 1024 * 1024 * 1024 * 2, SizeUnitBase::Binary),
+        "+2.0T"
+    );
+}
+
+#[test]
+fn test_badge_label_decimal_units() {
+    assert_eq!(badge_label(1_500_000_000, SizeUnitBase::Decimal), "+1.5G");
+    assert_eq!(badge_label(1_500_000_000, SizeUnitBase::Binary), "+1.4G");
+    assert_eq!(badge_label(999, SizeUnitBase::Decimal), "+999B");
+}
+
+#[test]
+fn test_badge_label_bytes() {
+    assert_eq!(badge_label(512, SizeUnitBase::Binary), "+512B");
+}
+
+#[test]
+fn test_badge_label_characters_all_have_glyphs() {
+    for label in ["+1.5G", "+320M", "+2.0T", "+512B", "+64K", "+1023M"] {
+        assert!(
+            label.chars().all(|character| glyph(character).is_some()),
+            "missing glyph in {label}"
+        );
+    }
+}
+
+#[test]
+fn test_render_badge_keeps_icon_dimensions() {
+    let badged = render_badge(&blank_icon(64), "+1.5G");
+    assert_eq!(badged.width(), 64);
+    assert_eq!(badged.height(), 64);
+    assert_eq!(badged.rgba().len(), 64 * 64 * 4);
+}
+
+#[test]
+fn test_render_badge_draws_in_bottom_right_corner() {
+    let badged = render_badge(&blank_icon(64), "+18G");
+
+    assert_eq!(pixel(&badged, 63, 63), config::tray::BADGE_BACKGROUND);
+    assert_eq!(pixel(&badged, 0, 0), TRANSPARENT);
+    assert_eq!(pixel(&badged, 0, 63), TRANSPARENT);
+}
+
+#[test]
+fn test_render_badge_draws_label_text() {
+    let badged = render_badge(&blank_icon(64), "+18G");
+
+    let has_text = badged
+        .rgba()
+        .chunks_exact(4)
+        .any(|color| color == config::tray::BADGE_FOREGROUND);
+    assert!(has_text);
+}
+
+#[test]
+fn test_render_badge_clips_oversized_label() {
+    let badged = render_badge(&blank_icon(16), "+1023M");
+    assert_eq!(badged.rgba().len(), 16 * 16 * 4);
+    assert_eq!(pixel(&badged, 0, 15), config::tray::BADGE_BACKGROUND);
+}
+
+#[test]
+fn test_render_badge_without_glyphs_leaves_icon_unchanged() {
+    let icon = blank_icon(32);
+    let badged = render_badge(&icon, "??");
+    assert_eq!(badged.rgba(), icon.rgba());
+}
@@ -0,0 +1,60 @@
+use tauri::image::Image;
+
+/// RGBA pixels of a tray icon being drawn on
+pub(super) struct Canvas {
+    rgba: Vec<u8>,
+    pub(super) width: u32,
+    pub(super) height: u32,
+}
+
+impl Canvas {
+    pub(super) fn from_image(image: &Image<'_>) -> Self {
+        Self {
+            rgba: image.rgba().to_vec(),
+            width: image.width(),
+            height: image.height(),
+        }
+    }
+
+    pub(super) fn into_image(self) -> Image<'static> {
+        Image::new_owned(self.rgba, self.width, self.height)
+    }
+
+    /// Writes a pixel, ignoring coordinates outside the icon so an oversized
+    /// label is clipped rather than wrapping onto the next row
+    pub(super) fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let offset = ((y * self.width + x) * 4) as usize;
+        self.rgba[offset..offset + 4].copy_from_slice(&color);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub(super) fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+        for row in y..y.saturating_add(height) {
+            for column in x..x.saturating_add(width) {
+                self.set_pixel(column, row, color);
+            }
+        }
+    }
+
+    pub(super) fn fill_circle(
+        &mut self,
+        center_x: u32,
+        center_y: u32,
+        radius: u32,
+        color: [u8; 4],
+    ) {
+        let radius_squared = radius * radius;
+        for row in center_y.saturating_sub(radius)..=center_y + radius {
+            for column in center_x.saturating_sub(radius)..=center_x + radius {
+                let dx = column.abs_diff(center_x);
+                let dy = row.abs_diff(center_y);
+                if dx * dx + dy * dy <= radius_squared {
+                    self.set_pixel(column, row, color);
+                }
+            }
+        }
+    }
+}
@@ -1,127 +1,660 @@
-use crate::config::bytes::{GB, KB, MB, TB};
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
-use tracing::{debug, instrument};
-
-fn format_bytes_compact(bytes: u64) -> String {
-    let bytes_f64 = bytes as f64;
-
-    let (value, unit) = if bytes_f64 >= TB {
-        (bytes_f64 / TB, "TB")
-    } else if bytes_f64 >= GB {
-        (bytes_f64 / GB, "GB")
-    } else if bytes_f64 >= MB {
-        (bytes_f64 / MB, "MB")
-    } else if bytes_f64 >= KB {
-        (bytes_f64 / KB, "KB")
-    } else {
-        (bytes_f64, "B")
-    };
+use crate::commands::scan_cache::CachedScan;
+use crate::commands::settings::{get_settings_sync, TrayDisplayMode};
+use crate::config;
+use crate::format::{short_relative_time_text, ByteFormat, ByteStyle};
+use crate::i18n;
+use crate::notifications::format_bytes_readable;
+use crate::scanner::{CategoryExcess, DependencyCategory};
+use crate::time;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::Manager;
+use tokio::sync::watch;
+use tracing::{debug, instrument, warn};
+
+#[cfg(not(target_os = "macos"))]
+mod badge;
+mod canvas;
+mod state;
+
+pub use state::TrayState;
+
+/// Builds the tray alert text, preferring the overall threshold and otherwise
+/// naming the category with the largest overage so the alert is actionable
+fn tray_alert_text(
+    total_size: u64,
+    threshold: u64,
+    exceeded_categories: &[CategoryExcess],
+    byte_format: &ByteFormat,
+    language: &str,
+) -> Option<String> {
+    if total_size > threshold {
+        return Some(format!(
+            "  +{}",
+            byte_format.format(total_size - threshold, ByteStyle::Compact)
+        ));
+    }
+
+    exceeded_categories.first().map(|excess| {
+        format!(
+            "  {} +{}",
+            excess
+                .category
+                .directory_names()
+                .first()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| excess.category.translated_label(language)),
+            byte_format.format(excess.excess_bytes(), ByteStyle::Compact)
+        )
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TrayUpdate {
+    total_size: u64,
+    threshold: u64,
+    exceeded_categories: Vec<CategoryExcess>,
+    display_mode: TrayDisplayMode,
+}
+
+impl TrayUpdate {
+    /// Warning while anything is over its threshold, whatever the title shows
+    fn state(&self) -> TrayState {
+        if self.total_size > self.threshold || !self.exceeded_categories.is_empty() {
+            TrayState::Warning
+        } else {
+            TrayState::Normal
+        }
+    }
+}
+
+/// Text shown beside the tray icon, the menu bar title on macOS
+fn tray_title_text(
+    update: &TrayUpdate,
+    byte_format: &ByteFormat,
+    language: &str,
+) -> Option<String> {
+    match update.display_mode {
+        TrayDisplayMode::Off => None,
+        TrayDisplayMode::ExcessOnly => tray_alert_text(
+            update.total_size,
+            update.threshold,
+            &update.exceeded_categories,
+            byte_format,
+            language,
+        ),
+        TrayDisplayMode::AlwaysTotal => Some(format!(
+            "  {}",
+            byte_format.format(update.total_size, ByteStyle::Compact)
+        )),
+    }
+}
+
+/// Spawns a task that applies only the most recent value sent within each `delay` window,
+/// skipping values identical to the last one applied
+fn spawn_debounced<T, F>(delay: Duration, apply: F) -> watch::Sender<Option<T>>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+    F: Fn(&T) + Send + 'static,
+{
+    let (sender, mut receiver) = watch::channel(None::<T>);
+
+    tauri::async_runtime::spawn(async move {
+        let mut last_applied: Option<T> = None;
+
+        while receiver.changed().await.is_ok() {
+            tokio::time::sleep(delay).await;
+
+            let latest = receiver.borrow_and_update().clone();
+            if latest != last_applied {
+                if let Some(value) = &latest {
+                    apply(value);
+                }
+                last_applied = latest;
+            }
+        }
+    });
+
+    sender
+}
+
+/// Managed state that funnels every tray update through one debounced task,
+/// so rapid calls during scans and batch deletes don't make the menu bar flicker
+pub struct TrayUpdater {
+    sender: watch::Sender<Option<TrayUpdate>>,
+}
+
+impl TrayUpdater {
+    pub fn spawn(app: tauri::AppHandle) -> Self {
+        let sender = spawn_debounced(config::tray::UPDATE_DEBOUNCE, move |update| {
+            if let Err(error) = apply_tray_update(&app, update) {
+                warn!(%error, "Failed to update tray icon");
+            }
+        });
 
-    format!("{value:.2}{unit}")
+        Self { sender }
+    }
+
+    fn request(&self, update: TrayUpdate) {
+        self.sender.send_replace(Some(update));
+    }
+
+    /// Categories over their thresholds in the most recent request
+    fn last_exceeded_categories(&self) -> Vec<CategoryExcess> {
+        self.sender
+            .borrow()
+            .as_ref()
+            .map(|update| update.exceeded_categories.clone())
+            .unwrap_or_default()
+    }
+
+    /// The state the most recently requested sizes call for
+    fn size_state(&self) -> TrayState {
+        self.sender
+            .borrow()
+            .as_ref()
+            .map_or(TrayState::Normal, TrayUpdate::state)
+    }
 }
 
+/// Updates the tray for new sizes. `exceeded_categories` is only known after a scan,
+/// so when it's omitted, as the frontend does, the last categories sent are kept.
 #[tauri::command]
 #[instrument(skip(app))]
 pub async fn set_tray_icon(
     app: tauri::AppHandle,
     total_size: u64,
     threshold: u64,
+    exceeded_categories: Option<Vec<CategoryExcess>>,
 ) -> Result<(), String> {
+    let updater = app
+        .try_state::<TrayUpdater>()
+        .ok_or_else(|| "Tray updater not initialized".to_string())?;
+
+    let display_mode = get_settings_sync()
+        .map(|settings| settings.tray_display_mode)
+        .unwrap_or(TrayDisplayMode::ExcessOnly);
+
+    let update = TrayUpdate {
+        total_size,
+        threshold,
+        exceeded_categories: exceeded_categories
+            .unwrap_or_else(|| updater.last_exceeded_categories()),
+        display_mode,
+    };
+    set_size_state(&app, update.state());
+    updater.request(update);
+
+    Ok(())
+}
+
+/// The plain tray icon, which the state and badge are drawn onto. Decoded once,
+/// since the spinner redraws it every frame.
+static BASE_TRAY_ICON: LazyLock<Result<tauri::image::Image<'static>, String>> =
+    LazyLock::new(|| {
+        tauri::image::Image::from_bytes(include_bytes!("../../icons/tray/icon.png"))
+            .map_err(|error| format!("Failed to load tray icon: {error}"))
+    });
+
+fn apply_tray_update(app: &tauri::AppHandle, update: &TrayUpdate) -> Result<(), String> {
+    let byte_format = ByteFormat::current();
+    let title = tray_title_text(update, &byte_format, i18n::current_language());
+    debug!(?title, "Setting tray title");
+
+    #[cfg(target_os = "macos")]
+    {
+        let tray = app
+            .tray_by_id("main")
+            .ok_or_else(|| "Tray icon not found".to_string())?;
+        tray.set_title(Some(title.as_deref().unwrap_or("")))
+            .map_err(|error| format!("Failed to set tray title: {error}"))?;
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let status = title.as_ref().map(|text| match update.display_mode {
+            TrayDisplayMode::AlwaysTotal => format!("deptox — {}", text.trim()),
+            _ => format!("Exceeded by {text}"),
+        });
+        *TOOLTIP_STATUS
+            .lock()
+            .map_err(|error| format!("Failed to lock tray tooltip: {error}"))? = status;
+        apply_tooltip(app)?;
+
+        // The badge is too small for a total, so it only ever shows the overage
+        let excess_bytes = (update.display_mode != TrayDisplayMode::Off)
+            .then(|| {
+                badge::badge_excess_bytes(
+                    update.total_size,
+                    update.threshold,
+                    &update.exceeded_categories,
+                )
+            })
+            .flatten();
+        let label = excess_bytes
+            .map(|excess_bytes| badge::badge_label(excess_bytes, byte_format.unit_base));
+        *BADGE_LABEL
+            .lock()
+            .map_err(|error| format!("Failed to lock tray badge: {error}"))? = label;
+        apply_tray_icon(app, current_state(app), 0)?;
+    }
+
+    Ok(())
+}
+
+/// Label of the badge drawn over the state icon, kept so each spinner frame redraws it
+#[cfg(not(target_os = "macos"))]
+static BADGE_LABEL: Mutex<Option<String>> = Mutex::new(None);
+
+fn tray_icon_image(state: TrayState, frame: usize) -> Result<tauri::image::Image<'static>, String> {
+    let base = BASE_TRAY_ICON.as_ref().map_err(Clone::clone)?;
+    let icon = state::render_state(base, state, frame);
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let label = BADGE_LABEL
+            .lock()
+            .map_err(|error| format!("Failed to lock tray badge: {error}"))?
+            .clone();
+        if let Some(label) = label {
+            return Ok(badge::render_badge(&icon, &label));
+        }
+    }
+
+    Ok(icon)
+}
+
+fn apply_tray_icon(app: &tauri::AppHandle, state: TrayState, frame: usize) -> Result<(), String> {
     let tray = app
         .tray_by_id("main")
         .ok_or_else(|| "Tray icon not found".to_string())?;
 
-    if total_size > threshold {
-        let excess = total_size - threshold;
-        let excess_text = format!("  +{}", format_bytes_compact(excess));
+    tray.set_icon(Some(tray_icon_image(state, frame)?))
+        .map_err(|error| format!("Failed to set tray icon: {error}"))?;
+    tray.set_icon_as_template(state.is_template())
+        .map_err(|error| format!("Failed to set tray icon template: {error}"))
+}
 
-        debug!(%excess_text, "Setting tray alert text");
+/// Managed state that owns the tray icon's state and the task animating the scanning spinner
+pub struct TrayStateController {
+    sender: watch::Sender<TrayState>,
+}
 
-        #[cfg(target_os = "macos")]
-        {
-            tray.set_title(Some(&excess_text))
-                .map_err(|error| format!("Failed to set tray title: {error}"))?;
-        }
+impl TrayStateController {
+    pub fn spawn(app: tauri::AppHandle) -> Self {
+        let (sender, mut receiver) = watch::channel(TrayState::Normal);
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            tray.set_tooltip(Some(&format!("Exceeded by {excess_text}")))
-                .map_err(|error| format!("Failed to set tray tooltip: {error}"))?;
-        }
-    } else {
-        #[cfg(target_os = "macos")]
-        {
-            tray.set_title(Some(""))
-                .map_err(|error| format!("Failed to set empty tray title: {error}"))?;
+        tauri::async_runtime::spawn(async move {
+            // The tray is built with the normal icon, so nothing is drawn until the first change
+            if receiver.changed().await.is_err() {
+                return;
+            }
+
+            let mut frame = 0;
+            loop {
+                let state = *receiver.borrow_and_update();
+                if let Err(error) = apply_tray_icon(&app, state, frame) {
+                    warn!(%error, ?state, "Failed to update tray icon state");
+                }
+
+                if state.is_animated() {
+                    tokio::select! {
+                        changed = receiver.changed() => {
+                            if changed.is_err() {
+                                break;
+                            }
+                        }
+                        () = tokio::time::sleep(config::tray::SPINNER_FRAME_INTERVAL) => {
+                            frame += 1;
+                        }
+                    }
+                } else {
+                    frame = 0;
+                    if receiver.changed().await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self { sender }
+    }
+}
+
+fn current_state(app: &tauri::AppHandle) -> TrayState {
+    app.try_state::<TrayStateController>()
+        .map(|controller| *controller.sender.borrow())
+        .unwrap_or(TrayState::Normal)
+}
+
+/// Switches the tray icon to `state`, animating it for as long as it's `Scanning`
+pub fn set_state(app: &tauri::AppHandle, state: TrayState) {
+    let Some(controller) = app.try_state::<TrayStateController>() else {
+        return;
+    };
+
+    controller.sender.send_if_modified(|current| {
+        let changed = *current != state;
+        *current = state;
+        changed
+    });
+}
+
+/// Shows the normal or warning icon for new sizes, leaving a running scan's
+/// spinner in place until `finish_scanning` replaces it
+fn set_size_state(app: &tauri::AppHandle, state: TrayState) {
+    let Some(controller) = app.try_state::<TrayStateController>() else {
+        return;
+    };
+
+    controller.sender.send_if_modified(|current| {
+        let changed = *current != TrayState::Scanning && *current != state;
+        if changed {
+            *current = state;
         }
+        changed
+    });
+}
+
+/// Stops the spinner, going back to what the last sizes sent to `set_tray_icon` call for
+pub fn finish_scanning(app: &tauri::AppHandle) {
+    let size_state = app
+        .try_state::<TrayUpdater>()
+        .map_or(TrayState::Normal, |updater| updater.size_state());
+    if current_state(app) == TrayState::Scanning {
+        set_state(app, size_state);
+    }
+}
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            tray.set_tooltip(Some("deptox"))
-                .map_err(|error| format!("Failed to set tray tooltip: {error}"))?;
+const CATEGORY_MENU_PREFIX: &str = "category:";
+
+/// Emitted to the window with the category picked from the tray's breakdown
+pub const CATEGORY_REQUESTED_EVENT: &str = "tray-category-requested";
+
+/// What the tray menu shows beyond its fixed items. Kept so setting one part
+/// rebuilds the menu without dropping the other.
+#[derive(Debug)]
+struct TrayMenuState {
+    update_available: bool,
+    category_sizes: Vec<(DependencyCategory, u64)>,
+    summary: Option<ScanSummary>,
+}
+
+static MENU_STATE: Mutex<TrayMenuState> = Mutex::new(TrayMenuState {
+    update_available: false,
+    category_sizes: Vec::new(),
+    summary: None,
+});
+
+/// The last completed scan, kept as a timestamp so "ago" is worked out when it's shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScanSummary {
+    completed_at_ms: u64,
+    directory_count: usize,
+    total_size: u64,
+}
+
+impl From<&CachedScan> for ScanSummary {
+    fn from(cached: &CachedScan) -> Self {
+        Self {
+            completed_at_ms: cached.completed_at_ms,
+            directory_count: cached.result.entries.len(),
+            total_size: cached.result.total_size,
         }
     }
+}
 
-    Ok(())
+/// "Last scan: 2h ago · 14 directories · 22.8 GB"
+fn summary_text(summary: &ScanSummary, now_ms: u64, byte_format: &ByteFormat) -> String {
+    let directories = if summary.directory_count == 1 {
+        "directory"
+    } else {
+        "directories"
+    };
+    format!(
+        "Last scan: {} · {} {directories} · {}",
+        short_relative_time_text(summary.completed_at_ms, now_ms),
+        summary.directory_count,
+        byte_format.format(summary.total_size, ByteStyle::Readable)
+    )
 }
 
-#[tauri::command]
-#[instrument(skip(app))]
-pub async fn set_tray_update_available(
-    app: tauri::AppHandle,
-    available: bool,
-    version: Option<String>,
-) -> Result<(), String> {
+/// The threshold status from the last tray update, or the app name, with the scan
+/// summary on a second line
+#[cfg(not(target_os = "macos"))]
+fn tooltip_text(status: Option<&str>, summary: Option<&str>) -> String {
+    let status = status.unwrap_or("deptox");
+    match summary {
+        Some(summary) => format!("{status}\n{summary}"),
+        None => status.to_string(),
+    }
+}
+
+/// First line of the tooltip, kept so a new scan summary doesn't replace it
+#[cfg(not(target_os = "macos"))]
+static TOOLTIP_STATUS: Mutex<Option<String>> = Mutex::new(None);
+
+#[cfg(not(target_os = "macos"))]
+fn apply_tooltip(app: &tauri::AppHandle) -> Result<(), String> {
     let tray = app
         .tray_by_id("main")
         .ok_or_else(|| "Tray icon not found".to_string())?;
 
-    let scan_now = MenuItem::with_id(&app, "scan_now", "Scan Now", true, None::<&str>)
+    let summary = MENU_STATE
+        .lock()
+        .map_err(|error| format!("Failed to lock tray menu state: {error}"))?
+        .summary;
+    let summary =
+        summary.map(|summary| summary_text(&summary, time::now_ms(), &ByteFormat::current()));
+    let status = TOOLTIP_STATUS
+        .lock()
+        .map_err(|error| format!("Failed to lock tray tooltip: {error}"))?
+        .clone();
+
+    tray.set_tooltip(Some(&tooltip_text(status.as_deref(), summary.as_deref())))
+        .map_err(|error| format!("Failed to set tray tooltip: {error}"))
+}
+
+/// Categories that have anything to clean, largest first
+fn category_breakdown(
+    by_category: &HashMap<DependencyCategory, u64>,
+) -> Vec<(DependencyCategory, u64)> {
+    let mut breakdown: Vec<_> = by_category
+        .iter()
+        .filter(|(_, size)| **size > 0)
+        .map(|(category, size)| (*category, *size))
+        .collect();
+    breakdown.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.label_key().cmp(b.0.label_key()))
+    });
+    breakdown
+}
+
+fn category_menu_id(category: DependencyCategory) -> String {
+    format!("{CATEGORY_MENU_PREFIX}{}", category.label_key())
+}
+
+fn category_menu_text(category: DependencyCategory, size: u64, language: &str) -> String {
+    format!(
+        "{} — {}",
+        category.translated_label(language),
+        format_bytes_readable(size)
+    )
+}
+
+/// The category a breakdown menu item stands for, so a click can open the results
+/// filtered to it
+pub fn category_from_menu_id(menu_id: &str) -> Option<DependencyCategory> {
+    let label_key = menu_id.strip_prefix(CATEGORY_MENU_PREFIX)?;
+    DependencyCategory::all()
+        .into_iter()
+        .find(|category| category.label_key() == label_key)
+}
+
+/// The tray menu for what's currently known, used when the tray is built at launch
+pub fn build_tray_menu(app: &tauri::AppHandle) -> Result<Menu<tauri::Wry>, String> {
+    let state = MENU_STATE
+        .lock()
+        .map_err(|error| format!("Failed to lock tray menu state: {error}"))?;
+    menu_for_state(app, &state)
+}
+
+fn menu_for_state(
+    app: &tauri::AppHandle,
+    state: &TrayMenuState,
+) -> Result<Menu<tauri::Wry>, String> {
+    let menu = Menu::new(app).map_err(|error| format!("Failed to create menu: {error}"))?;
+    let append = |item: &dyn IsMenuItem<tauri::Wry>| {
+        menu.append(item)
+            .map_err(|error| format!("Failed to add menu item: {error}"))
+    };
+    let separator = || {
+        PredefinedMenuItem::separator(app)
+            .map_err(|error| format!("Failed to create separator: {error}"))
+    };
+
+    #[cfg(target_os = "macos")]
+    if let Some(summary) = &state.summary {
+        let text = summary_text(summary, time::now_ms(), &ByteFormat::current());
+        let header = MenuItem::with_id(app, "scan_summary", text, false, None::<&str>)
+            .map_err(|error| format!("Failed to create summary menu item: {error}"))?;
+        append(&header)?;
+        append(&separator()?)?;
+    }
+
+    if state.update_available {
+        let update_item =
+            MenuItem::with_id(app, "update_available", "Update Now", true, None::<&str>)
+                .map_err(|error| format!("Failed to create update menu item: {error}"))?;
+        append(&update_item)?;
+        append(&separator()?)?;
+    }
+
+    let scan_now = MenuItem::with_id(app, "scan_now", "Scan Now", true, None::<&str>)
+        .map_err(|error| format!("Failed to create menu item: {error}"))?;
+    let clean_stale = MenuItem::with_id(app, "clean_stale", "Clean Stale", true, None::<&str>)
         .map_err(|error| format!("Failed to create menu item: {error}"))?;
-    let settings = MenuItem::with_id(&app, "settings", "Settings", true, None::<&str>)
+    append(&scan_now)?;
+    append(&clean_stale)?;
+
+    if !state.category_sizes.is_empty() {
+        let breakdown = Submenu::new(app, "Breakdown", true)
+            .map_err(|error| format!("Failed to create breakdown menu: {error}"))?;
+        let language = i18n::current_language();
+        for (category, size) in &state.category_sizes {
+            let item = MenuItem::with_id(
+                app,
+                category_menu_id(*category),
+                category_menu_text(*category, *size, language),
+                true,
+                None::<&str>,
+            )
+            .map_err(|error| format!("Failed to create menu item: {error}"))?;
+            breakdown
+                .append(&item)
+                .map_err(|error| format!("Failed to add menu item: {error}"))?;
+        }
+        append(&breakdown)?;
+    }
+
+    let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)
         .map_err(|error| format!("Failed to create menu item: {error}"))?;
-    let separator = PredefinedMenuItem::separator(&app)
-        .map_err(|error| format!("Failed to create separator: {error}"))?;
-    let about = MenuItem::with_id(&app, "about", "About", true, None::<&str>)
+    let about = MenuItem::with_id(app, "about", "About", true, None::<&str>)
         .map_err(|error| format!("Failed to create menu item: {error}"))?;
-    let quit = MenuItem::with_id(&app, "quit", "Quit", true, None::<&str>)
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)
         .map_err(|error| format!("Failed to create menu item: {error}"))?;
+    append(&settings)?;
+    append(&separator()?)?;
+    append(&about)?;
+    append(&quit)?;
 
-    let menu = if available {
-        let update_item =
-            MenuItem::with_id(&app, "update_available", "Update Now", true, None::<&str>)
-                .map_err(|error| format!("Failed to create update menu item: {error}"))?;
-        let update_separator = PredefinedMenuItem::separator(&app)
-            .map_err(|error| format!("Failed to create separator: {error}"))?;
+    Ok(menu)
+}
 
-        debug!(?version, "Showing update available in tray menu");
+fn update_tray_menu(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut TrayMenuState),
+) -> Result<(), String> {
+    let tray = app
+        .tray_by_id("main")
+        .ok_or_else(|| "Tray icon not found".to_string())?;
 
-        Menu::with_items(
-            &app,
-            &[
-                &update_item,
-                &update_separator,
-                &scan_now,
-                &settings,
-                &separator,
-                &about,
-                &quit,
-            ],
-        )
-        .map_err(|error| format!("Failed to create menu: {error}"))?
+    let mut state = MENU_STATE
+        .lock()
+        .map_err(|error| format!("Failed to lock tray menu state: {error}"))?;
+    change(&mut state);
+    let menu = menu_for_state(app, &state)?;
+
+    tray.set_menu(Some(menu))
+        .map_err(|error| format!("Failed to set tray menu: {error}"))
+}
+
+#[tauri::command]
+#[instrument(skip(app))]
+pub async fn set_tray_update_available(
+    app: tauri::AppHandle,
+    available: bool,
+    version: Option<String>,
+) -> Result<(), String> {
+    set_update_available(&app, available, version.as_deref())
+}
+
+/// Shows or hides the "Update Now" menu item, for both the frontend and the
+/// backend's own scheduled update checks
+pub fn set_update_available(
+    app: &tauri::AppHandle,
+    available: bool,
+    version: Option<&str>,
+) -> Result<(), String> {
+    if available {
+        debug!(?version, "Showing update available in tray menu");
     } else {
         debug!("Hiding update available from tray menu");
+    }
 
-        Menu::with_items(&app, &[&scan_now, &settings, &separator, &about, &quit])
-            .map_err(|error| format!("Failed to create menu: {error}"))?
-    };
+    update_tray_menu(app, |state| state.update_available = available)
+}
 
-    tray.set_menu(Some(menu))
-        .map_err(|error| format!("Failed to set tray menu: {error}"))?;
+/// Shows when the last scan finished and what it found, in the menu header on macOS
+/// and the tooltip elsewhere. Called after both background and foreground scans.
+pub fn update_summary(app: &tauri::AppHandle, cached: &CachedScan) -> Result<(), String> {
+    let summary = ScanSummary::from(cached);
+    update_tray_menu(app, |state| state.summary = Some(summary))?;
+
+    #[cfg(not(target_os = "macos"))]
+    apply_tooltip(app)?;
 
     Ok(())
 }
 
+/// Redraws the summary so its "ago" is current, for when the pointer reaches the icon
+#[cfg(target_os = "macos")]
+pub fn refresh_summary(app: &tauri::AppHandle) -> Result<(), String> {
+    update_tray_menu(app, |_| {})
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn refresh_summary(app: &tauri::AppHandle) -> Result<(), String> {
+    apply_tooltip(app)
+}
+
+/// Refreshes the per-category totals in the tray menu after a scan
+pub fn set_tray_category_sizes(
+    app: &tauri::AppHandle,
+    by_category: &HashMap<DependencyCategory, u64>,
+) -> Result<(), String> {
+    let breakdown = category_breakdown(by_category);
+    debug!(
+        categories = breakdown.len(),
+        "Updating tray category breakdown"
+    );
+
+    update_tray_menu(app, |state| state.category_sizes = breakdown)
+}
+
 #[cfg(test)]
 #[path = "mod.test.rs"]
 mod tests;
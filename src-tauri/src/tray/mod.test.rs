@@ -1,50 +1,303 @@
 use super::*;
+use crate::config::bytes::{GB, MB};
+use crate::scanner::DependencyCategory;
+
+fn category_excess(
+    category: DependencyCategory,
+    size_bytes: u64,
+    threshold_bytes: u64,
+) -> CategoryExcess {
+    CategoryExcess {
+        category,
+        size_bytes,
+        threshold_bytes,
+    }
+}
+
+#[test]
+fn test_tray_alert_text_under_all_thresholds() {
+    assert_eq!(
+        tray_alert_text(100, 200, &[], &ByteFormat::default(), "en"),
+        None
+    );
+}
+
+#[test]
+fn test_tray_alert_text_total_threshold_takes_precedence() {
+    let exceeded = [category_excess(
+        DependencyCategory::Pods,
+        3 * 1024 * 1024 * 1024,
+        2 * 1024 * 1024 * 1024,
+    )];
+
+    assert_eq!(
+        tray_alert_text(300, 200, &exceeded, &ByteFormat::default(), "en").as_deref(),
+        Some("  +100.00B")
+    );
+}
+
+#[test]
+fn test_tray_alert_text_names_exceeded_category() {
+    let exceeded = [category_excess(
+        DependencyCategory::NodeModules,
+        12 * 1024 * 1024 * 1024,
+        10 * 1024 * 1024 * 1024,
+    )];
+
+    assert_eq!(
+        tray_alert_text(100, 200, &exceeded, &ByteFormat::default(), "en").as_deref(),
+        Some("  node_modules +2.00GB")
+    );
+}
+
+#[test]
+fn test_tray_alert_text_category_without_directory_name() {
+    let exceeded = [category_excess(
+        DependencyCategory::CondaEnv,
+        3 * 1024 * 1024 * 1024,
+        2 * 1024 * 1024 * 1024,
+    )];
+
+    assert_eq!(
+        tray_alert_text(100, 200, &exceeded, &ByteFormat::default(), "en").as_deref(),
+        Some("  Python (conda) +1.00GB")
+    );
+}
+
+fn tray_update(total_size: u64, display_mode: TrayDisplayMode) -> TrayUpdate {
+    TrayUpdate {
+        total_size,
+        threshold: 10 * GB as u64,
+        exceeded_categories: Vec::new(),
+        display_mode,
+    }
+}
+
+#[test]
+fn test_tray_title_text_excess_only() {
+    let format = ByteFormat::default();
+
+    assert_eq!(
+        tray_title_text(
+            &tray_update(12 * GB as u64, TrayDisplayMode::ExcessOnly),
+            &format,
+            "en"
+        )
+        .as_deref(),
+        Some("  +2.00GB")
+    );
+    assert_eq!(
+        tray_title_text(
+            &tray_update(4 * GB as u64, TrayDisplayMode::ExcessOnly),
+            &format,
+            "en"
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_tray_title_text_always_total() {
+    let format = ByteFormat::default();
+
+    assert_eq!(
+        tray_title_text(
+            &tray_update(4 * GB as u64, TrayDisplayMode::AlwaysTotal),
+            &format,
+            "en"
+        )
+        .as_deref(),
+        Some("  4.00GB")
+    );
+    assert_eq!(
+        tray_title_text(
+            &tray_update(12 * GB as u64, TrayDisplayMode::AlwaysTotal),
+            &format,
+            "en"
+        )
+        .as_deref(),
+        Some("  12.00GB")
+    );
+}
 
 #[test]
-fn test_format_bytes_compact_bytes() {
-    assert_eq!(format_bytes_compact(0), "0.00B");
-    assert_eq!(format_bytes_compact(512), "512.00B");
-    assert_eq!(format_bytes_compact(1023), "1023.00B");
+fn test_tray_title_text_off() {
+    assert_eq!(
+        tray_title_text(
+            &tray_update(12 * GB as u64, TrayDisplayMode::Off),
+            &ByteFormat::default(),
+            "en"
+        ),
+        None
+    );
 }
 
 #[test]
-fn test_format_bytes_compact_kilobytes() {
-    assert_eq!(format_bytes_compact(1024), "1.00KB");
-    assert_eq!(format_bytes_compact(2048), "2.00KB");
-    assert_eq!(format_bytes_compact(1024 * 500), "500.00KB");
+fn test_tray_update_state_follows_thresholds() {
+    assert_eq!(
+        tray_update(5 * GB as u64, TrayDisplayMode::Off).state(),
+        TrayState::Normal
+    );
+    assert_eq!(
+        tray_update(12 * GB as u64, TrayDisplayMode::Off).state(),
+        TrayState::Warning
+    );
+
+    let update = TrayUpdate {
+        exceeded_categories: vec![category_excess(
+            DependencyCategory::NodeModules,
+            3 * GB as u64,
+            2 * GB as u64,
+        )],
+        ..tray_update(5 * GB as u64, TrayDisplayMode::ExcessOnly)
+    };
+    assert_eq!(update.state(), TrayState::Warning);
 }
 
 #[test]
-fn test_format_bytes_compact_megabytes() {
-    assert_eq!(format_bytes_compact(1024 * 1024), "1.00MB");
-    assert_eq!(format_bytes_compact(1024 * 1024 * 50), "50.00MB");
-    assert_eq!(format_bytes_compact(1024 * 1024 * 999), "999.00MB");
+fn test_tray_updater_keeps_last_exceeded_categories() {
+    let updater = TrayUpdater {
+        sender: watch::channel(None).0,
+    };
+    assert!(updater.last_exceeded_categories().is_empty());
+
+    let exceeded = vec![category_excess(
+        DependencyCategory::NodeModules,
+        3 * GB as u64,
+        2 * GB as u64,
+    )];
+    updater.request(TrayUpdate {
+        exceeded_categories: exceeded.clone(),
+        ..tray_update(5 * GB as u64, TrayDisplayMode::ExcessOnly)
+    });
+
+    assert_eq!(updater.last_exceeded_categories(), exceeded);
+    assert_eq!(updater.size_state(), TrayState::Warning);
 }
 
 #[test]
-fn test_format_bytes_compact_gigabytes() {
-    assert_eq!(format_bytes_compact(1024 * 1024 * 1024), "1.00GB");
-    assert_eq!(format_bytes_compact(1024 * 1024 * 1024 * 5), "5.00GB");
-    assert_eq!(format_bytes_compact(1024 * 1024 * 1024 * 18), "18.00GB");
+fn test_base_tray_icon_decodes() {
+    assert!(BASE_TRAY_ICON.is_ok());
+}
+
+#[tokio::test]
+async fn test_spawn_debounced_coalesces_rapid_updates() {
+    let applied = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let applied_clone = std::sync::Arc::clone(&applied);
+    let sender = spawn_debounced(Duration::from_millis(50), move |value: &u64| {
+        applied_clone.lock().unwrap().push(*value);
+    });
+
+    for value in 1..=5 {
+        sender.send_replace(Some(value));
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(*applied.lock().unwrap(), vec![5]);
+}
+
+#[tokio::test]
+async fn test_spawn_debounced_skips_unchanged_values() {
+    let applied = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let applied_clone = std::sync::Arc::clone(&applied);
+    let sender = spawn_debounced(Duration::from_millis(20), move |value: &u64| {
+        applied_clone.lock().unwrap().push(*value);
+    });
+
+    sender.send_replace(Some(7));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    sender.send_replace(Some(7));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    sender.send_replace(Some(8));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(*applied.lock().unwrap(), vec![7, 8]);
 }
 
 #[test]
-fn test_format_bytes_compact_terabytes() {
-    assert_eq!(format_bytes_compact(1024 * 1024 * 1024 * 1024), "1.00TB");
+fn test_category_breakdown_sorts_largest_first_and_drops_empty() {
+    let by_category = HashMap::from([
+        (DependencyCategory::Pods, 3 * GB as u64),
+        (DependencyCategory::NodeModules, 12 * GB as u64),
+        (DependencyCategory::PythonVenv, 0),
+    ]);
+
     assert_eq!(
-        format_bytes_compact(1024 * 1024 * 1024 * 1024 * 3),
-        "3.00TB"
+        category_breakdown(&by_category),
+        vec![
+            (DependencyCategory::NodeModules, 12 * GB as u64),
+            (DependencyCategory::Pods, 3 * GB as u64),
+        ]
     );
 }
 
 #[test]
-fn test_format_bytes_compact_with_decimals() {
-    let gb_value = 1024 * 1024 * 1024 + (512 * 1024 * 1024);
-    assert_eq!(format_bytes_compact(gb_value), "1.50GB");
+fn test_category_menu_text() {
+    assert_eq!(
+        category_menu_text(
+            DependencyCategory::NodeModules,
+            12 * GB as u64 + 400 * MB as u64,
+            "en"
+        ),
+        "Node (modules) — 12.4 GB"
+    );
+    assert_eq!(
+        category_menu_text(DependencyCategory::BuildArtifacts, 2 * GB as u64, "de"),
+        "Build-Ausgabe (dist) — 2 GB"
+    );
+}
+
+#[test]
+fn test_category_menu_id_round_trips() {
+    for category in DependencyCategory::all() {
+        assert_eq!(
+            category_from_menu_id(&category_menu_id(category)),
+            Some(category)
+        );
+    }
 
-    let gb_value_2 = (1024 * 1024 * 1024 * 2) + (768 * 1024 * 1024);
-    assert_eq!(format_bytes_compact(gb_value_2), "2.75GB");
+    assert_eq!(category_from_menu_id("scan_now"), None);
+    assert_eq!(category_from_menu_id("category:unknown"), None);
+}
+
+#[test]
+fn test_summary_text() {
+    let summary = ScanSummary {
+        completed_at_ms: 1_000,
+        directory_count: 14,
+        total_size: 22 * GB as u64 + 800 * MB as u64,
+    };
 
-    let mb_value = 1024 * 1024 + (256 * 1024);
-    assert_eq!(format_bytes_compact(mb_value), "1.25MB");
+    assert_eq!(
+        summary_text(&summary, 1_000 + 2 * 60 * 60 * 1000, &ByteFormat::default()),
+        "Last scan: 2h ago · 14 directories · 22.8 GB"
+    );
+}
+
+#[test]
+fn test_summary_text_single_directory() {
+    let summary = ScanSummary {
+        completed_at_ms: 1_000,
+        directory_count: 1,
+        total_size: 512,
+    };
+
+    assert_eq!(
+        summary_text(&summary, 1_000, &ByteFormat::default()),
+        "Last scan: just now · 1 directory · 512 B"
+    );
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_tooltip_text_adds_summary_below_status() {
+    assert_eq!(tooltip_text(None, None), "deptox");
+    assert_eq!(
+        tooltip_text(Some("Exceeded by  +2.00GB"), Some("Last scan: 5m ago")),
+        "Exceeded by  +2.00GB\nLast scan: 5m ago"
+    );
+    assert_eq!(
+        tooltip_text(None, Some("Last scan: 5m ago")),
+        "deptox\nLast scan: 5m ago"
+    );
 }
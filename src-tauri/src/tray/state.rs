@@ -0,0 +1,77 @@
+use super::canvas::Canvas;
+use crate::config;
+use tauri::image::Image;
+
+/// What the tray icon shows about the app itself, independent of the title or badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayState {
+    Normal,
+    /// The last scan found more than the threshold
+    Warning,
+    /// A scan is running, shown as an animated spinner
+    Scanning,
+    /// The last background scan failed
+    Error,
+}
+
+impl TrayState {
+    /// Colored states can't be template images, which macOS draws in a single tint
+    pub fn is_template(self) -> bool {
+        matches!(self, Self::Normal | Self::Scanning)
+    }
+
+    pub fn is_animated(self) -> bool {
+        self == Self::Scanning
+    }
+}
+
+/// Draws the state onto the base icon. `frame` only matters while scanning, where it
+/// advances the spinner one dot.
+pub fn render_state(base: &Image<'_>, state: TrayState, frame: usize) -> Image<'static> {
+    let mut canvas = Canvas::from_image(base);
+    let size = canvas.width.min(canvas.height);
+
+    match state {
+        TrayState::Normal => {}
+        TrayState::Warning | TrayState::Error => {
+            let color = if state == TrayState::Warning {
+                config::tray::WARNING_COLOR
+            } else {
+                config::tray::ERROR_COLOR
+            };
+            let radius = size / 6;
+            canvas.fill_circle(canvas.width - radius - 1, radius, radius, color);
+        }
+        TrayState::Scanning => draw_spinner(&mut canvas, size, frame),
+    }
+
+    canvas.into_image()
+}
+
+/// A ring of dots in the top-right corner, the head opaque and each dot behind it fainter
+fn draw_spinner(canvas: &mut Canvas, size: u32, frame: usize) {
+    let dots = config::tray::SPINNER_DOTS;
+    let ring_radius = f64::from(size / 8);
+    let dot_radius = (size / 24).max(1);
+    let center_x = f64::from(canvas.width - size / 5);
+    let center_y = f64::from(size / 5);
+    let head = frame % dots;
+
+    for dot in 0..dots {
+        let age = (head + dots - dot) % dots;
+        let alpha = 255 - (255 * age / dots) as u8;
+        let angle = std::f64::consts::TAU * dot as f64 / dots as f64;
+        let x = center_x + ring_radius * angle.sin();
+        let y = center_y - ring_radius * angle.cos();
+        canvas.fill_circle(
+            x.round() as u32,
+            y.round() as u32,
+            dot_radius,
+            [0, 0, 0, alpha],
+        );
+    }
+}
+
+#[cfg(test)]
+#[path = "state.test.rs"]
+mod tests;
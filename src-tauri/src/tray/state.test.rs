@@ -0,0 +1,71 @@
+use super::*;
+
+const SIZE: u32 = 64;
+
+fn blank_icon() -> Image<'static> {
+    Image::new_owned(vec![0; (SIZE * SIZE * 4) as usize], SIZE, SIZE)
+}
+
+fn pixel(image: &Image<'_>, x: u32, y: u32) -> [u8; 4] {
+    let offset = ((y * image.width() + x) * 4) as usize;
+    image.rgba()[offset..offset + 4].try_into().unwrap()
+}
+
+fn changed_pixels(image: &Image<'_>) -> usize {
+    image
+        .rgba()
+        .chunks(4)
+        .filter(|pixel| pixel.iter().any(|&channel| channel != 0))
+        .count()
+}
+
+#[test]
+fn test_render_normal_state_leaves_icon_unchanged() {
+    let rendered = render_state(&blank_icon(), TrayState::Normal, 0);
+    assert_eq!(rendered.rgba(), blank_icon().rgba());
+}
+
+#[test]
+fn test_render_warning_and_error_draw_colored_dot() {
+    let radius = SIZE / 6;
+
+    let warning = render_state(&blank_icon(), TrayState::Warning, 0);
+    assert_eq!(
+        pixel(&warning, SIZE - radius - 1, radius),
+        config::tray::WARNING_COLOR
+    );
+
+    let error = render_state(&blank_icon(), TrayState::Error, 0);
+    assert_eq!(
+        pixel(&error, SIZE - radius - 1, radius),
+        config::tray::ERROR_COLOR
+    );
+    // The dot stays in the corner, clear of the rest of the icon
+    assert_eq!(pixel(&error, 0, SIZE - 1), [0, 0, 0, 0]);
+}
+
+#[test]
+fn test_render_scanning_frames_differ() {
+    let first = render_state(&blank_icon(), TrayState::Scanning, 0);
+    let second = render_state(&blank_icon(), TrayState::Scanning, 1);
+
+    assert!(changed_pixels(&first) > 0);
+    assert_ne!(first.rgba(), second.rgba());
+}
+
+#[test]
+fn test_render_scanning_loops_after_every_dot() {
+    let dots = config::tray::SPINNER_DOTS;
+    let first = render_state(&blank_icon(), TrayState::Scanning, 1);
+    let looped = render_state(&blank_icon(), TrayState::Scanning, dots + 1);
+
+    assert_eq!(first.rgba(), looped.rgba());
+}
+
+#[test]
+fn test_colored_states_are_not_templates() {
+    assert!(TrayState::Normal.is_template());
+    assert!(TrayState::Scanning.is_template());
+    assert!(!TrayState::Warning.is_template());
+    assert!(!TrayState::Error.is_template());
+}
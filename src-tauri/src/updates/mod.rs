@@ -0,0 +1,256 @@
+//! Scheduled update checks. The manifest of a pending update is kept so the UI can show
+//! its changelog, and the tray offers the update without the frontend having to poll.
+//! With silent updates on, it's installed while no scan or delete is running instead.
+
+use crate::activity;
+use crate::commands::scan::get_active_scan;
+use crate::commands::settings::{get_settings_sync, AutoUpdate};
+use crate::config;
+use crate::notifications;
+use crate::persistence::config_path;
+use crate::tray;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Emitter;
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::sync::watch;
+use tracing::{debug, info, instrument, warn};
+
+/// The newest update found by the last successful check
+static PENDING_UPDATE: Mutex<Option<PendingUpdate>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingUpdate {
+    pub version: String,
+    pub current_version: String,
+    /// Release notes from the update manifest, as Markdown
+    pub notes: Option<String>,
+    /// When the update was published, in milliseconds since the epoch
+    pub published_ms: Option<u64>,
+}
+
+impl From<&Update> for PendingUpdate {
+    fn from(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            notes: release_notes(update.body.as_deref()),
+            published_ms: update
+                .date
+                .and_then(|date| u64::try_from(date.unix_timestamp()).ok())
+                .map(|seconds| seconds * 1000),
+        }
+    }
+}
+
+/// Manifests without notes often carry an empty or whitespace body
+fn release_notes(body: Option<&str>) -> Option<String> {
+    body.map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .map(str::to_string)
+}
+
+/// Only a version not announced before is worth a new event, so a check every few hours
+/// doesn't keep interrupting the user about the same update
+fn is_newly_found(previous: Option<&PendingUpdate>, found: &PendingUpdate) -> bool {
+    previous.is_none_or(|previous| previous.version != found.version)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdateAction {
+    /// Leave the update in the tray for the user
+    Offer,
+    Install,
+    /// Install once the scan or delete in progress has finished
+    Defer,
+}
+
+/// Whether a restart now would cut short a scan or delete
+fn is_busy() -> bool {
+    get_active_scan().is_some() || activity::is_busy()
+}
+
+fn update_action(mode: AutoUpdate, busy: bool) -> UpdateAction {
+    match (mode, busy) {
+        (AutoUpdate::Silent, false) => UpdateAction::Install,
+        (AutoUpdate::Silent, true) => UpdateAction::Defer,
+        (AutoUpdate::Prompt | AutoUpdate::Off, _) => UpdateAction::Offer,
+    }
+}
+
+fn pending_update() -> Result<Option<PendingUpdate>, String> {
+    PENDING_UPDATE
+        .lock()
+        .map(|pending| pending.clone())
+        .map_err(|error| format!("Failed to lock pending update: {error}"))
+}
+
+/// Asks the update endpoint for a newer version, caches what it finds and offers it in the tray
+async fn check(app: &tauri::AppHandle) -> Result<Option<Update>, String> {
+    let update = app
+        .updater()
+        .map_err(|error| format!("Failed to create updater: {error}"))?
+        .check()
+        .await
+        .map_err(|error| format!("Failed to check for updates: {error}"))?;
+    let found = update.as_ref().map(PendingUpdate::from);
+
+    let previous = {
+        let mut pending = PENDING_UPDATE
+            .lock()
+            .map_err(|error| format!("Failed to lock pending update: {error}"))?;
+        std::mem::replace(&mut *pending, found.clone())
+    };
+
+    match &found {
+        Some(update) if is_newly_found(previous.as_ref(), update) => {
+            info!(version = %update.version, "Update available");
+            let _ = app.emit("update-available", update);
+        }
+        Some(_) => {}
+        None => debug!("No update available"),
+    }
+    tray::set_update_available(
+        app,
+        found.is_some(),
+        found.as_ref().map(|update| update.version.as_str()),
+    )?;
+
+    Ok(update)
+}
+
+/// Records the version being installed, so the relaunched app can tell the user about it
+fn installed_update_path() -> Result<PathBuf, String> {
+    config_path(config::app::INSTALLED_UPDATE_FILENAME)
+}
+
+/// Reads and removes the record of a silent install, so it's only reported once
+fn take_installed_update(path: &Path) -> Option<String> {
+    let version = fs::read_to_string(path).ok()?;
+    let _ = fs::remove_file(path);
+    Some(version.trim().to_string()).filter(|version| !version.is_empty())
+}
+
+/// Downloads and installs the update, then restarts into it. Returns false without
+/// installing when a scan or delete started during the download.
+async fn install_silently(app: &tauri::AppHandle, update: &Update) -> Result<bool, String> {
+    info!(version = %update.version, "Downloading update");
+    let bytes = update
+        .download(|_, _| {}, || {})
+        .await
+        .map_err(|error| format!("Failed to download update: {error}"))?;
+
+    if is_busy() {
+        return Ok(false);
+    }
+    info!(version = %update.version, "Installing update silently");
+    update
+        .install(bytes)
+        .map_err(|error| format!("Failed to install update: {error}"))?;
+
+    fs::write(installed_update_path()?, &update.version)
+        .map_err(|error| format!("Failed to record installed update: {error}"))?;
+
+    app.request_restart();
+    Ok(true)
+}
+
+/// Runs one scheduled check and returns how long to wait before the next
+async fn scheduled_check(app: &tauri::AppHandle) -> Duration {
+    let mode = get_settings_sync()
+        .map(|settings| settings.auto_update)
+        .unwrap_or(AutoUpdate::Prompt);
+    if mode == AutoUpdate::Off {
+        debug!("Scheduled update checks are off");
+        return config::updates::CHECK_INTERVAL;
+    }
+
+    let update = match check(app).await {
+        Ok(Some(update)) => update,
+        Ok(None) => return config::updates::CHECK_INTERVAL,
+        Err(error) => {
+            warn!(%error, "Scheduled update check failed");
+            return config::updates::CHECK_INTERVAL;
+        }
+    };
+
+    match update_action(mode, is_busy()) {
+        UpdateAction::Offer => {}
+        UpdateAction::Defer => {
+            debug!("Deferring silent update until the scan or delete finishes");
+            return config::updates::IDLE_RETRY_DELAY;
+        }
+        UpdateAction::Install => match install_silently(app, &update).await {
+            Ok(true) => {}
+            Ok(false) => {
+                debug!("Deferring silent update, work started during the download");
+                return config::updates::IDLE_RETRY_DELAY;
+            }
+            Err(error) => warn!(%error, "Silent update failed"),
+        },
+    }
+    config::updates::CHECK_INTERVAL
+}
+
+/// Notifies the user after a relaunch into a silently installed update
+pub fn notify_if_updated(app: &tauri::AppHandle) {
+    let Some(version) = installed_update_path()
+        .ok()
+        .and_then(|path| take_installed_update(&path))
+    else {
+        return;
+    };
+
+    if version == env!("CARGO_PKG_VERSION") {
+        notifications::notify_updated(app, &version);
+    } else {
+        warn!(%version, "Silently installed update isn't the running version");
+    }
+}
+
+/// Checks shortly after launch and then every few hours until shutdown
+pub fn spawn_update_checks(app: tauri::AppHandle, mut shutdown_rx: watch::Receiver<bool>) {
+    crate::telemetry::crash::spawn_monitored("update_checks", async move {
+        let mut delay = config::updates::INITIAL_CHECK_DELAY;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        break;
+                    }
+                }
+            }
+            if *shutdown_rx.borrow() {
+                break;
+            }
+
+            delay = scheduled_check(&app).await;
+        }
+    });
+}
+
+/// Checks for an update now, for a "Check for Updates" action
+#[tauri::command]
+#[instrument(skip(app))]
+pub async fn check_for_updates(app: tauri::AppHandle) -> Result<Option<PendingUpdate>, String> {
+    check(&app)
+        .await
+        .map(|update| update.as_ref().map(PendingUpdate::from))
+}
+
+/// The pending update found by the last check, with its release notes
+#[tauri::command]
+#[instrument]
+pub async fn get_update_changelog() -> Result<Option<PendingUpdate>, String> {
+    pending_update()
+}
+
+#[cfg(test)]
+#[path = "mod.test.rs"]
+mod tests;
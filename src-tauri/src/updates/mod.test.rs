@@ -0,0 +1,79 @@
+use super::*;
+use tempfile::TempDir;
+
+fn update(version: &str) -> PendingUpdate {
+    PendingUpdate {
+        version: version.to_string(),
+        current_version: "1.0.0".to_string(),
+        notes: None,
+        published_ms: None,
+    }
+}
+
+#[test]
+fn test_release_notes_trims_body() {
+    assert_eq!(
+        release_notes(Some("\n- Faster scans\n")),
+        Some("- Faster scans".to_string())
+    );
+}
+
+#[test]
+fn test_release_notes_drops_empty_body() {
+    assert_eq!(release_notes(None), None);
+    assert_eq!(release_notes(Some("")), None);
+    assert_eq!(release_notes(Some("  \n ")), None);
+}
+
+#[test]
+fn test_is_newly_found_without_previous_update() {
+    assert!(is_newly_found(None, &update("1.1.0")));
+}
+
+#[test]
+fn test_is_newly_found_ignores_same_version() {
+    assert!(!is_newly_found(Some(&update("1.1.0")), &update("1.1.0")));
+}
+
+#[test]
+fn test_is_newly_found_with_newer_version() {
+    assert!(is_newly_found(Some(&update("1.1.0")), &update("1.2.0")));
+}
+
+#[test]
+fn test_update_action_prompt_offers() {
+    assert_eq!(
+        update_action(AutoUpdate::Prompt, false),
+        UpdateAction::Offer
+    );
+    assert_eq!(update_action(AutoUpdate::Off, false), UpdateAction::Offer);
+}
+
+#[test]
+fn test_update_action_silent_waits_for_idle() {
+    assert_eq!(
+        update_action(AutoUpdate::Silent, false),
+        UpdateAction::Install
+    );
+    assert_eq!(update_action(AutoUpdate::Silent, true), UpdateAction::Defer);
+}
+
+#[test]
+fn test_take_installed_update_reads_once() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("installed_update");
+    fs::write(&path, "1.2.0\n").unwrap();
+
+    assert_eq!(take_installed_update(&path), Some("1.2.0".to_string()));
+    assert!(!path.exists());
+    assert_eq!(take_installed_update(&path), None);
+}
+
+#[test]
+fn test_take_installed_update_ignores_empty_record() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("installed_update");
+    fs::write(&path, "").unwrap();
+
+    assert_eq!(take_installed_update(&path), None);
+}
@@ -1,4 +1,5 @@
 import { useState, useEffect, useCallback } from "react";
+import { useTranslation } from "react-i18next";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
@@ -14,6 +15,8 @@ import { Footer } from "./components/Footer";
 import { SettingsPage } from "./components/SettingsPage";
 import { AboutPage } from "./components/AboutPage";
 import { Layout } from "./components/Layout";
+import { ConfirmDialog } from "./components/ConfirmDialog";
+import { useConfirmDialog } from "./hooks/useConfirmDialog";
 import { useFontSize } from "./hooks/useFontSize";
 import { useScanEvents } from "./hooks/useScanEvents";
 import { useDependencyStore } from "./stores/dependencyStore";
@@ -21,14 +24,20 @@ import { useUpdateStore } from "./stores/updateStore";
 import { useLicenseStore } from "./stores/licenseStore";
 import { appLogger } from "./utilities/logger";
 import { AppRoute } from "./types/appRoute";
+import type { DependencyCategory } from "./types/dependencyCategory";
 
 function App(): React.ReactElement {
+  const { t } = useTranslation();
   const [currentRoute, setCurrentRoute] = useState<AppRoute>(AppRoute.SCAN);
   const [isInitialized, setIsInitialized] = useState(false);
   const [hideSupportBanner, setHideSupportBanner] = useState(false);
   const loadSettings = useDependencyStore((state) => state.loadSettings);
   const startScan = useDependencyStore((state) => state.startScan);
   const updateTrayIcon = useDependencyStore((state) => state.updateTrayIcon);
+  const selectCategory = useDependencyStore((state) => state.selectCategory);
+  const selectPaths = useDependencyStore((state) => state.selectPaths);
+  const fullDiskAccessDialog = useConfirmDialog();
+  const showFullDiskAccessDialog = fullDiskAccessDialog.showConfirmDialog;
   const checkForUpdates = useUpdateStore((state) => state.checkForUpdates);
   const downloadAndInstall = useUpdateStore(
     (state) => state.downloadAndInstall
@@ -57,7 +66,11 @@ function App(): React.ReactElement {
         useDependencyStore.persist.clearStorage();
         await invoke("reset_settings");
         try {
-          await invoke("set_tray_icon", { totalSize: 0, threshold: 1 });
+          await invoke("set_tray_icon", {
+            totalSize: 0,
+            threshold: 1,
+            exceededCategories: [],
+          });
         } catch (error) {
           appLogger.error("Failed to clear tray icon:", error);
         }
@@ -148,11 +161,53 @@ function App(): React.ReactElement {
         downloadAndInstall();
       });
 
+      const unlistenCategory = await listen<DependencyCategory>(
+        "tray-category-requested",
+        (event) => {
+          setCurrentRoute(AppRoute.SCAN);
+          selectCategory(event.payload);
+        }
+      );
+
+      const unlistenClean = await listen<string>("deep-link-clean-requested", (event) => {
+        setCurrentRoute(AppRoute.SCAN);
+        selectPaths([event.payload]);
+      });
+
+      const unlistenSecondInstance = await listen<string[]>(
+        "second-instance-launched",
+        (event) => {
+          appLogger.log(`Second instance launched with: ${event.payload.join(" ")}`);
+          setCurrentRoute(AppRoute.SCAN);
+        }
+      );
+
+      const unlistenFullDiskAccess = await listen("full_disk_access_missing", async () => {
+        const confirmed = await showFullDiskAccessDialog({
+          title: t("dialogs.fullDiskAccessTitle"),
+          message: t("dialogs.fullDiskAccessMessage"),
+          confirmLabel: t("dialogs.openSettings"),
+          cancelLabel: t("dialogs.cancel"),
+        });
+        if (!confirmed) {
+          return;
+        }
+        try {
+          await invoke("request_full_disk_access");
+        } catch (error) {
+          appLogger.error("Failed to open Full Disk Access settings:", error);
+        }
+      });
+
       const unlistenAll = (): void => {
         unlistenScan();
         unlistenSettings();
         unlistenAbout();
         unlistenUpdate();
+        unlistenCategory();
+        unlistenClean();
+        unlistenSecondInstance();
+        unlistenFullDiskAccess();
       };
 
       // Guard against unmount during async setup
@@ -170,7 +225,7 @@ function App(): React.ReactElement {
       isMounted = false;
       cleanup?.();
     };
-  }, [startScan, downloadAndInstall]);
+  }, [startScan, downloadAndInstall, selectCategory, selectPaths, showFullDiskAccessDialog, t]);
 
   useEffect(() => {
     const REVALIDATION_INTERVAL_MS = 24 * 60 * 60 * 1000;
@@ -261,6 +316,16 @@ function App(): React.ReactElement {
         hideProgress={currentRoute !== AppRoute.SCAN}
       />
       {renderPageContent()}
+      {fullDiskAccessDialog.isOpen && (
+        <ConfirmDialog
+          title={fullDiskAccessDialog.title}
+          message={fullDiskAccessDialog.message}
+          confirmLabel={fullDiskAccessDialog.confirmLabel}
+          cancelLabel={fullDiskAccessDialog.cancelLabel}
+          onConfirm={fullDiskAccessDialog.handleConfirm}
+          onCancel={fullDiskAccessDialog.handleCancel}
+        />
+      )}
     </Layout>
   );
 }
@@ -13,6 +13,7 @@ import { SearchBox } from "./SearchBox";
 import { ScanStatus } from "../types/scanStatus";
 import type { DirectoryEntry } from "../types/interfaces";
 import { DEPENDENCY_CATEGORY_SHORT_LABELS } from "../types/dependencyCategory";
+import { CLOUD_ONLY_ERROR_CODE } from "../utilities/commandError";
 import { sortDirectories } from "../utilities/sortDirectories";
 import { filterRecentlyCheckedPaths } from "../utilities/pathFilters";
 import { addToSet, removeFromSet } from "../utilities/setUtils";
@@ -147,7 +148,19 @@ export function DirectoryList(): React.ReactElement {
 
       setSingleDeletingPaths((previous) => addToSet(previous, path));
       try {
-        await deleteDirectory(path);
+        const result = await deleteDirectory(path);
+        if (result?.errorCode === CLOUD_ONLY_ERROR_CODE) {
+          const confirmed = await confirmDialog.showConfirmDialog({
+            title: t("dialogs.icloudTitle"),
+            message: t("dialogs.icloudMessage"),
+            confirmLabel: t("dialogs.delete"),
+            cancelLabel: t("dialogs.cancel"),
+            directories: [{ path: entry.path, sizeBytes: entry.sizeBytes }],
+          });
+          if (confirmed) {
+            await deleteDirectory(path, true);
+          }
+        }
       } finally {
         setSingleDeletingPaths((previous) => removeFromSet(previous, path));
       }
@@ -176,7 +189,7 @@ export function DirectoryList(): React.ReactElement {
 
   const handleOpenFinder = useCallback(async (path: string): Promise<void> => {
     try {
-      await invoke("open_in_finder", { path });
+      await invoke("reveal_in_file_manager", { path });
     } catch (error) {
       componentLogger.error("Failed to open in Finder:", error);
     }
@@ -56,7 +56,7 @@ export function ExploreFilesDialog({
   const handleOpenFileLocation = useCallback(async (filePath: string): Promise<void> => {
     try {
       const parentDir = getParentDirectory(filePath);
-      await invoke("open_in_finder", { path: parentDir });
+      await invoke("reveal_in_file_manager", { path: parentDir });
     } catch (openError) {
       componentLogger.error("Failed to open file location in Finder:", openError);
     }
@@ -42,9 +42,12 @@ const CATEGORY_COLORS: Record<DependencyCategory, CheckboxColor> = {
   [DependencyCategory.BUNDLER]: "bundler",
   [DependencyCategory.PODS]: "pods",
   [DependencyCategory.PYTHON_VENV]: "venv",
+  [DependencyCategory.CONDA_ENV]: "venv",
   [DependencyCategory.ELIXIR_DEPS]: "elixir",
   [DependencyCategory.DART_TOOL]: "dart",
   [DependencyCategory.GO_MOD]: "go",
+  [DependencyCategory.DOT_NET]: "composer",
+  [DependencyCategory.BUILD_ARTIFACTS]: "node",
 };
 
 interface SettingsPageProps {
@@ -468,8 +471,8 @@ export function SettingsPage({
           onChange={handleExcludePathsChange}
           onBlur={handleExcludePathsBlur}
           onKeyDown={handleExcludePathsKeyDown}
-          placeholder="*/active-*, */keep/*"
-          aria-label="Comma-separated wildcard patterns to exclude"
+          placeholder="active-*, ~/work/keep/**"
+          aria-label="Comma-separated glob patterns to exclude"
         />
       </FormField>
 
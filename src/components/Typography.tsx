@@ -172,12 +172,18 @@ const categoryPillVariants = cva(
           "bg-category-pods/15 text-category-pods dark:bg-category-pods/20 dark:text-category-pods",
         [DependencyCategory.PYTHON_VENV]:
           "bg-category-venv/15 text-category-venv dark:bg-category-venv/20 dark:text-category-venv",
+        [DependencyCategory.CONDA_ENV]:
+          "bg-category-venv/15 text-category-venv dark:bg-category-venv/20 dark:text-category-venv",
         [DependencyCategory.ELIXIR_DEPS]:
           "bg-category-elixir/15 text-category-elixir dark:bg-category-elixir/20 dark:text-category-elixir",
         [DependencyCategory.DART_TOOL]:
           "bg-category-dart/15 text-category-dart dark:bg-category-dart/20 dark:text-category-dart",
         [DependencyCategory.GO_MOD]:
           "bg-category-go/15 text-category-go dark:bg-category-go/20 dark:text-category-go",
+        [DependencyCategory.DOT_NET]:
+          "bg-category-composer/15 text-category-composer dark:bg-category-composer/20 dark:text-category-composer",
+        [DependencyCategory.BUILD_ARTIFACTS]:
+          "bg-category-node/15 text-category-node dark:bg-category-node/20 dark:text-category-node",
       },
     },
   }
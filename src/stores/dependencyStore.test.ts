@@ -507,6 +507,24 @@ describe("dependencyStore", () => {
       expect(state.error).toBe("String error");
     });
 
+    it("uses the message of a structured command error", async () => {
+      // First call is for updateTrayIcon (set_tray_icon), second is for start_scan
+      mockInvoke.mockResolvedValueOnce(undefined);
+      mockInvoke.mockRejectedValueOnce({
+        code: "INVALID_PATH",
+        message: "Root directory does not exist",
+        context: "/missing",
+      });
+
+      await act(async () => {
+        await useDependencyStore.getState().startScan();
+      });
+
+      const state = useDependencyStore.getState();
+      expect(state.scanStatus).toBe(ScanStatus.ERROR);
+      expect(state.error).toBe("Root directory does not exist");
+    });
+
     it("resets totalSize to zero and clears tray when starting new scan", async () => {
       act(() => {
         useDependencyStore.setState({
@@ -587,7 +605,10 @@ describe("dependencyStore", () => {
       });
 
       expect(result).toEqual(deleteResult);
-      expect(mockInvoke).toHaveBeenCalledWith("delete_to_trash", { path: entry.path });
+      expect(mockInvoke).toHaveBeenCalledWith("delete_to_trash", {
+        path: entry.path,
+        confirmCloudEviction: false,
+      });
       const state = useDependencyStore.getState();
       expect(state.directories).toEqual([]);
       expect(state.totalSize).toBe(0);
@@ -648,6 +669,39 @@ describe("dependencyStore", () => {
         sizeFreed: 0,
       });
     });
+
+    it("passes cloud eviction confirmation and reports the error code", async () => {
+      const entry = createMockDirectoryEntry({ path: "/icloud/project/node_modules", sizeBytes: 1000 });
+
+      act(() => {
+        useDependencyStore.setState({
+          directories: [entry],
+          totalSize: 1000,
+        });
+      });
+
+      mockInvoke.mockRejectedValueOnce({
+        code: "DELETE_CLOUD_ONLY",
+        message: "Some files are only in the cloud",
+        context: null,
+      });
+
+      let result: DeleteResult | null = null;
+      await act(async () => {
+        result = await useDependencyStore.getState().deleteDirectory(entry.path, true);
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith("delete_to_trash", {
+        path: entry.path,
+        confirmCloudEviction: true,
+      });
+      expect(result).toEqual({
+        success: false,
+        path: entry.path,
+        sizeFreed: 0,
+        errorCode: "DELETE_CLOUD_ONLY",
+      });
+    });
   });
 
   describe("rescanDirectory", () => {
@@ -1181,6 +1235,48 @@ describe("dependencyStore", () => {
       });
     });
 
+    describe("selectCategory", () => {
+      it("selects only directories in the category", () => {
+        const nodeModules = createMockDirectoryEntry({ path: "/project1/node_modules" });
+        const vendor = createMockDirectoryEntry({
+          path: "/project2/vendor",
+          category: DependencyCategory.COMPOSER,
+        });
+
+        act(() => {
+          useDependencyStore.setState({
+            directories: [nodeModules, vendor],
+            selectedPaths: new Set([nodeModules.path]),
+          });
+        });
+
+        act(() => {
+          useDependencyStore.getState().selectCategory(DependencyCategory.COMPOSER);
+        });
+
+        expect(useDependencyStore.getState().selectedPaths).toEqual(new Set([vendor.path]));
+      });
+    });
+
+    describe("selectPaths", () => {
+      it("selects requested paths that are in the results", () => {
+        const entry1 = createMockDirectoryEntry({ path: "/project1/node_modules" });
+        const entry2 = createMockDirectoryEntry({ path: "/project2/node_modules" });
+
+        act(() => {
+          useDependencyStore.setState({
+            directories: [entry1, entry2],
+          });
+        });
+
+        act(() => {
+          useDependencyStore.getState().selectPaths([entry2.path, "/missing/node_modules"]);
+        });
+
+        expect(useDependencyStore.getState().selectedPaths).toEqual(new Set([entry2.path]));
+      });
+    });
+
     describe("deleteSelectedDirectories", () => {
       it("deletes all selected directories successfully", async () => {
         const entry1 = createMockDirectoryEntry({ path: "/project1/node_modules", sizeBytes: 1000 });
@@ -13,6 +13,7 @@ import { SortOrder } from "../types/sortOrder";
 import { DependencyCategory, ALL_DEPENDENCY_CATEGORIES } from "../types/dependencyCategory";
 import { FontSize } from "../types/fontSize";
 import { RescanInterval, RESCAN_INTERVAL_MS } from "../types/rescanInterval";
+import { getErrorMessage, isCommandError } from "../utilities/commandError";
 import { storeLogger } from "../utilities/logger";
 import { containsDependencyDirectory } from "../utilities/pathParser";
 import { notifyThresholdExceeded } from "../utilities/notifications";
@@ -55,7 +56,7 @@ interface DependencyActions {
   setScanComplete: (result: ScanResult) => void;
   setScanCancelled: () => void;
   setScanError: (error: string) => void;
-  deleteDirectory: (path: string) => Promise<DeleteResult | null>;
+  deleteDirectory: (path: string, confirmCloudEviction?: boolean) => Promise<DeleteResult | null>;
   deleteSelectedDirectories: () => Promise<void>;
   rescanDirectory: (path: string) => Promise<RescanResult | null>;
   setThreshold: (bytes: number) => Promise<void>;
@@ -77,6 +78,8 @@ interface DependencyActions {
   toggleSelection: (path: string) => void;
   clearSelection: () => void;
   selectAll: () => void;
+  selectCategory: (category: DependencyCategory) => void;
+  selectPaths: (paths: string[]) => void;
 }
 
 type DependencyStore = DependencyState & DependencyActions;
@@ -213,7 +216,7 @@ export const useDependencyStore = create<DependencyStore>()(
       storeLogger.error("start_scan error:", error);
       set({
         scanStatus: ScanStatus.ERROR,
-        error: error instanceof Error ? error.message : String(error),
+        error: getErrorMessage(error),
       });
     }
   },
@@ -316,7 +319,7 @@ export const useDependencyStore = create<DependencyStore>()(
     });
   },
 
-  deleteDirectory: async (path: string): Promise<DeleteResult | null> => {
+  deleteDirectory: async (path: string, confirmCloudEviction = false): Promise<DeleteResult | null> => {
     storeLogger.log(`deleteDirectory: ${path}`);
     const startTime = performance.now();
     const state = get();
@@ -328,7 +331,7 @@ export const useDependencyStore = create<DependencyStore>()(
     }
 
     try {
-      const result = await invoke<DeleteResult>("delete_to_trash", { path });
+      const result = await invoke<DeleteResult>("delete_to_trash", { path, confirmCloudEviction });
       storeLogger.log(`deleteDirectory completed in ${(performance.now() - startTime).toFixed(2)}ms, success: ${result.success}`);
 
       if (result.success) {
@@ -355,6 +358,7 @@ export const useDependencyStore = create<DependencyStore>()(
         success: false,
         path,
         sizeFreed: 0,
+        errorCode: isCommandError(error) ? error.code : undefined,
       };
     }
   },
@@ -637,6 +641,27 @@ export const useDependencyStore = create<DependencyStore>()(
     }));
   },
 
+  selectCategory: (category: DependencyCategory): void => {
+    set((state) => ({
+      selectedPaths: new Set(
+        state.directories
+          .filter((directory) => directory.category === category)
+          .map((directory) => directory.path)
+      ),
+    }));
+  },
+
+  selectPaths: (paths: string[]): void => {
+    const requested = new Set(paths);
+    set((state) => ({
+      selectedPaths: new Set(
+        state.directories
+          .map((directory) => directory.path)
+          .filter((path) => requested.has(path))
+      ),
+    }));
+  },
+
   deleteSelectedDirectories: async (): Promise<void> => {
     const state = get();
     const pathsToDelete = Array.from(state.selectedPaths);
@@ -177,6 +177,22 @@ describe("licenseStore", () => {
       expect(state.activationError).toBe("Invalid license key");
     });
 
+    it("uses the message of a structured command error", async () => {
+      mockInvoke.mockRejectedValue({
+        code: "LICENSE_DEVICE_LIMIT",
+        message: "This license is already activated on 3 devices",
+        context: "3",
+      });
+
+      await act(async () => {
+        await useLicenseStore.getState().activateLicense("TEST-KEY");
+      });
+
+      expect(useLicenseStore.getState().activationError).toBe(
+        "This license is already activated on 3 devices"
+      );
+    });
+
     it("clears activation error on new activation attempt", async () => {
       act(() => {
         useLicenseStore.setState({ activationError: "Previous error" });
@@ -284,6 +300,26 @@ describe("licenseStore", () => {
       expect(state.activationError).toBe("Network error");
       expect(state.lastEnteredKey).toBeNull();
     });
+
+    it("handles structured revalidation errors", async () => {
+      act(() => {
+        useLicenseStore.setState({ isLicensed: true });
+      });
+
+      mockInvoke.mockRejectedValue({
+        code: "LICENSE_REJECTED",
+        message: "License has been refunded",
+        context: null,
+      });
+
+      await act(async () => {
+        await useLicenseStore.getState().revalidateLicense();
+      });
+
+      const state = useLicenseStore.getState();
+      expect(state.isLicensed).toBe(false);
+      expect(state.activationError).toBe("License has been refunded");
+    });
   });
 
   describe("deactivateLicense", () => {
@@ -1,6 +1,7 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 import type { LicenseInfo, LicenseStore } from "../types/license";
+import { getErrorMessage } from "../utilities/commandError";
 import { storeLogger } from "../utilities/logger";
 
 const initialState = {
@@ -70,8 +71,7 @@ export const useLicenseStore = create<LicenseStore>()((set, get) => ({
 
       return true;
     } catch (error) {
-      const errorMessage =
-        error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       storeLogger.error("Failed to activate license:", errorMessage);
 
       set({
@@ -105,8 +105,7 @@ export const useLicenseStore = create<LicenseStore>()((set, get) => ({
         isCached: licenseInfo.isCached,
       });
     } catch (error) {
-      const errorString =
-        error instanceof Error ? error.message : String(error);
+      const errorString = getErrorMessage(error);
       const parts = errorString.split("|");
       const errorMessage = parts[0] ?? errorString;
       const fullKey = parts[1] ?? null;
@@ -57,12 +57,12 @@ describe("DEPENDENCY_CATEGORY_LABELS", () => {
 
 describe("ALL_DEPENDENCY_CATEGORIES", () => {
   it("contains all eight categories", () => {
-    expect(ALL_DEPENDENCY_CATEGORIES).toHaveLength(8);
+    expect(ALL_DEPENDENCY_CATEGORIES).toHaveLength(11);
   });
 
   it("contains each category exactly once", () => {
     const uniqueCategories = new Set(ALL_DEPENDENCY_CATEGORIES);
-    expect(uniqueCategories.size).toBe(8);
+    expect(uniqueCategories.size).toBe(11);
   });
 
   it("includes all expected categories", () => {
@@ -74,5 +74,7 @@ describe("ALL_DEPENDENCY_CATEGORIES", () => {
     expect(ALL_DEPENDENCY_CATEGORIES).toContain(DependencyCategory.ELIXIR_DEPS);
     expect(ALL_DEPENDENCY_CATEGORIES).toContain(DependencyCategory.DART_TOOL);
     expect(ALL_DEPENDENCY_CATEGORIES).toContain(DependencyCategory.GO_MOD);
+    expect(ALL_DEPENDENCY_CATEGORIES).toContain(DependencyCategory.DOT_NET);
+    expect(ALL_DEPENDENCY_CATEGORIES).toContain(DependencyCategory.BUILD_ARTIFACTS);
   });
 });
@@ -4,9 +4,12 @@ export const DependencyCategory = {
   BUNDLER: "BUNDLER",
   PODS: "PODS",
   PYTHON_VENV: "PYTHON_VENV",
+  CONDA_ENV: "CONDA_ENV",
   ELIXIR_DEPS: "ELIXIR_DEPS",
   DART_TOOL: "DART_TOOL",
   GO_MOD: "GO_MOD",
+  DOT_NET: "DOT_NET",
+  BUILD_ARTIFACTS: "BUILD_ARTIFACTS",
 } as const;
 
 export type DependencyCategory = (typeof DependencyCategory)[keyof typeof DependencyCategory];
@@ -17,9 +20,12 @@ export const DEPENDENCY_CATEGORY_LABELS: Record<DependencyCategory, string> = {
   [DependencyCategory.BUNDLER]: "Ruby (bundler)",
   [DependencyCategory.PODS]: "iOS (pods)",
   [DependencyCategory.PYTHON_VENV]: "Python (venv)",
+  [DependencyCategory.CONDA_ENV]: "Python (conda)",
   [DependencyCategory.ELIXIR_DEPS]: "Elixir (deps)",
   [DependencyCategory.DART_TOOL]: "Dart (dart_tool)",
   [DependencyCategory.GO_MOD]: "Go (pkg/mod)",
+  [DependencyCategory.DOT_NET]: ".NET (bin/obj)",
+  [DependencyCategory.BUILD_ARTIFACTS]: "Build output (dist)",
 };
 
 export const DEPENDENCY_CATEGORY_SHORT_LABELS: Record<DependencyCategory, string> = {
@@ -28,9 +34,12 @@ export const DEPENDENCY_CATEGORY_SHORT_LABELS: Record<DependencyCategory, string
   [DependencyCategory.BUNDLER]: "ruby",
   [DependencyCategory.PODS]: "ios",
   [DependencyCategory.PYTHON_VENV]: "python",
+  [DependencyCategory.CONDA_ENV]: "conda",
   [DependencyCategory.ELIXIR_DEPS]: "elixir",
   [DependencyCategory.DART_TOOL]: "dart",
   [DependencyCategory.GO_MOD]: "go",
+  [DependencyCategory.DOT_NET]: "dotnet",
+  [DependencyCategory.BUILD_ARTIFACTS]: "build",
 };
 
 export const ALL_DEPENDENCY_CATEGORIES: DependencyCategory[] = [
@@ -39,7 +48,10 @@ export const ALL_DEPENDENCY_CATEGORIES: DependencyCategory[] = [
   DependencyCategory.BUNDLER,
   DependencyCategory.PODS,
   DependencyCategory.PYTHON_VENV,
+  DependencyCategory.CONDA_ENV,
   DependencyCategory.ELIXIR_DEPS,
   DependencyCategory.DART_TOOL,
   DependencyCategory.GO_MOD,
+  DependencyCategory.DOT_NET,
+  DependencyCategory.BUILD_ARTIFACTS,
 ];
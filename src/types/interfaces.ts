@@ -41,6 +41,7 @@ export interface DeleteResult {
   success: boolean;
   path: string;
   sizeFreed: number;
+  errorCode?: string;
 }
 
 export interface RescanResult {
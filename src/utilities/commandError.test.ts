@@ -0,0 +1,33 @@
+import { describe, it, expect } from "vitest";
+import { getErrorMessage, isCommandError } from "./commandError";
+
+describe("commandError", () => {
+  describe("isCommandError", () => {
+    it("should accept a structured command error", () => {
+      expect(
+        isCommandError({ code: "DELETE_FAILED", message: "Failed to move to trash", context: "/a" })
+      ).toBe(true);
+    });
+
+    it("should reject strings, errors and null", () => {
+      expect(isCommandError("Failed")).toBe(false);
+      expect(isCommandError(new Error("Failed"))).toBe(false);
+      expect(isCommandError(null)).toBe(false);
+    });
+  });
+
+  describe("getErrorMessage", () => {
+    it("should return the message of a structured command error", () => {
+      const error = { code: "LICENSE_DEVICE_LIMIT", message: "Too many devices", context: "3" };
+      expect(getErrorMessage(error)).toBe("Too many devices");
+    });
+
+    it("should return the message of an Error", () => {
+      expect(getErrorMessage(new Error("Network error"))).toBe("Network error");
+    });
+
+    it("should stringify anything else", () => {
+      expect(getErrorMessage("Plain failure")).toBe("Plain failure");
+    });
+  });
+});
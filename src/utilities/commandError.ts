@@ -0,0 +1,22 @@
+export const CLOUD_ONLY_ERROR_CODE = "DELETE_CLOUD_ONLY";
+
+export interface CommandError {
+  code: string;
+  message: string;
+  context: string | null;
+}
+
+export function isCommandError(error: unknown): error is CommandError {
+  if (typeof error !== "object" || error === null) {
+    return false;
+  }
+  const candidate = error as Partial<CommandError>;
+  return typeof candidate.code === "string" && typeof candidate.message === "string";
+}
+
+export function getErrorMessage(error: unknown): string {
+  if (isCommandError(error) || error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}